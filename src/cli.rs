@@ -1,6 +1,9 @@
 use anyhow::Result;
 use itertools::Itertools;
-use std::{io::Write, path::PathBuf};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
 
 use crate::{
     config::{cli::Command, Config},
@@ -9,7 +12,10 @@ use crate::{
         commands::{volume::Bound, IdleEvent},
         mpd_client::{Filter, MpdClient, Tag},
     },
-    shared::{lrc::LrcIndex, macros::status_error},
+    shared::{
+        lrc::LrcIndex,
+        macros::{status_error, status_warn},
+    },
     WorkRequest,
 };
 use anyhow::bail;
@@ -61,7 +67,10 @@ impl Command {
             Command::Unpause => client.unpause()?,
             Command::Stop => client.stop()?,
             Command::Volume { value: Some(value) } => client.volume(value.parse()?)?,
-            Command::Volume { value: None } => println!("{}", client.get_status()?.volume.value()),
+            Command::Volume { value: None } => match client.get_status()?.volume {
+                Some(volume) => println!("{}", volume.value()),
+                None => bail!("No mixer available"),
+            },
             Command::Next => client.next()?,
             Command::Prev => client.prev()?,
             Command::Repeat { value } => client.repeat((value).into())?,
@@ -70,7 +79,7 @@ impl Command {
             Command::Consume { value } => client.consume((value).into())?,
             Command::Seek { value } => client.seek_current(value.parse()?)?,
             Command::Clear => client.clear()?,
-            Command::Add { file } => client.add(&file)?,
+            Command::Add { file } => client.add(&file, None)?,
             Command::AddYt { url } => {
                 request_work(WorkRequest::DownloadYoutube { url }, client);
             }
@@ -121,7 +130,7 @@ impl Command {
                     std::process::exit(3);
                 };
 
-                let album_art = client.find_album_art(&song.file)?;
+                let album_art = client.find_album_art(&song.file, config.album_art.source)?;
 
                 let Some(album_art) = album_art else {
                     std::process::exit(2);
@@ -221,3 +230,27 @@ pub fn create_env<'a>(
 
     Ok(result)
 }
+
+/// Opens the directory containing the given song in the system file manager. The song is resolved
+/// against `music_directory` and the file manager is spawned on a separate thread.
+pub fn open_song_location(context: &AppContext, file: &str) {
+    let Some(music_directory) = context.config.music_directory else {
+        status_warn!("Cannot open the song location, music_directory is not configured");
+        return;
+    };
+    if file.contains("://") {
+        status_warn!("Cannot open the song location, '{}' is not a local file", file);
+        return;
+    }
+    let Some(directory) = Path::new(music_directory).join(file).parent().map(Path::to_path_buf) else {
+        return;
+    };
+
+    let opener = if cfg!(target_os = "macos") { "open" } else { "xdg-open" };
+    std::thread::spawn(move || {
+        let directory = directory.to_string_lossy();
+        if let Err(err) = run_external_blocking(&[opener, &directory], std::iter::empty::<(&str, &str)>()) {
+            status_error!("Failed to open the song location: {}", err);
+        }
+    });
+}
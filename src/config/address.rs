@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 use crate::shared::env::ENV;
 
 use super::utils::tilde_expand;
@@ -28,6 +30,35 @@ impl From<String> for MpdPassword<'static> {
     }
 }
 
+/// Password as written in the config file. Either the password itself or `(env: "NAME")` to read it from the
+/// environment variable of the given name, so the password does not have to be stored in plaintext.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MpdPasswordFile {
+    Plain(String),
+    Env { env: String },
+}
+
+impl MpdPasswordFile {
+    pub fn resolve(self) -> anyhow::Result<String> {
+        match self {
+            MpdPasswordFile::Plain(password) => Ok(password),
+            MpdPasswordFile::Env { env } => ENV
+                .var(&env)
+                .map_err(|err| anyhow::anyhow!("Failed to read MPD password from environment variable '{env}': {err}")),
+        }
+    }
+}
+
+impl std::fmt::Debug for MpdPasswordFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MpdPasswordFile::Plain(_) => write!(f, "*****"),
+            MpdPasswordFile::Env { env } => write!(f, "Env({env:?})"),
+        }
+    }
+}
+
 impl<'a> Default for MpdAddress<'a> {
     fn default() -> Self {
         Self::IpAndPort("127.0.0.1:6600")
@@ -35,38 +66,40 @@ impl<'a> Default for MpdAddress<'a> {
 }
 
 impl MpdAddress<'static> {
+    /// The password from the config is only resolved when neither the CLI nor `MPD_HOST` override it, so a missing
+    /// password environment variable does not matter then.
     pub fn resolve(
         addr_from_cli: Option<String>,
         pw_from_cli: Option<String>,
         addr_from_config: String,
-        pw_from_config: Option<String>,
-    ) -> (MpdAddress<'static>, Option<MpdPassword<'static>>) {
+        pw_from_config: Option<MpdPasswordFile>,
+    ) -> anyhow::Result<(MpdAddress<'static>, Option<MpdPassword<'static>>)> {
         let (cli_addr, cli_pw) = Self::resolve_cli(addr_from_cli, pw_from_cli);
-        let (cfg_addr, cfg_pw) = Self::resolve_config(addr_from_config, pw_from_config);
-        let env = Self::resolve_env();
 
         if let Some(cli_addr) = cli_addr {
-            return (cli_addr, cli_pw);
+            return Ok((cli_addr, cli_pw));
         }
 
-        if let Some(env) = env {
-            return env;
+        if let Some(env) = Self::resolve_env() {
+            return Ok(env);
         }
 
-        (cfg_addr, cfg_pw)
+        let cfg_addr = Self::resolve_config(addr_from_config);
+        if cli_pw.is_some() {
+            return Ok((cfg_addr, cli_pw));
+        }
+        let cfg_pw = pw_from_config.map(MpdPasswordFile::resolve).transpose()?;
+
+        Ok((cfg_addr, cfg_pw.map(MpdPassword::from)))
     }
 
-    fn resolve_config(addr: String, pw: Option<String>) -> (MpdAddress<'static>, Option<MpdPassword<'static>>) {
+    fn resolve_config(addr: String) -> MpdAddress<'static> {
         let expanded = tilde_expand(&addr);
-        let addr = if expanded.starts_with('/') {
+        if expanded.starts_with('/') {
             MpdAddress::SocketPath(expanded.into_owned().leak())
         } else {
             MpdAddress::IpAndPort(addr.leak())
-        };
-
-        let pw: Option<MpdPassword<'_>> = pw.map(|pw| pw.into());
-
-        (addr, pw)
+        }
     }
 
     fn resolve_cli(
@@ -135,7 +168,7 @@ mod tests {
 
     use test_case::test_case;
     use crate::shared::env::ENV;
-    use super::{MpdAddress, MpdPassword};
+    use super::{MpdAddress, MpdPassword, MpdPasswordFile};
 
     static TEST_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
 
@@ -157,6 +190,7 @@ mod tests {
     #[test_case(                  None,           None, "127.0.0.1:7600", None,       Some("secret@/tmp/socket"), Some("6601"), MpdAddress::SocketPath("/tmp/socket"),        Some("secret".into()) ; "ENV with socket path and password")]
     #[test_case(                  None,           None, "/tmp/cfg_sock",  Some("secret"),                   None,         None, MpdAddress::SocketPath("/tmp/cfg_sock"),      Some("secret".into()) ; "socket path from config with password")]
     #[test_case(                  None,           None, "127.0.0.1:7600", Some("secret"),                   None,         None, MpdAddress::IpAndPort("127.0.0.1:7600"),      Some("secret".into()) ; "ip and port from config with password")]
+    #[test_case(                  None,  Some("other"), "127.0.0.1:7600", Some("secret"),                   None,         None, MpdAddress::IpAndPort("127.0.0.1:7600"),       Some("other".into()) ; "CLI password overrides config password")]
     fn resolves(
         cli_addr: Option<&str>,
         cli_pw: Option<&str>,
@@ -178,7 +212,7 @@ mod tests {
             ENV.set("MPD_PORT".to_string(), port.to_string());
         }
 
-        let result = MpdAddress::resolve(cli_addr.map(|v| v.to_string()), cli_pw.map(|v| v.to_string()), config_addr.to_string(), config_pw.map(|v| v.to_string()));
+        let result = MpdAddress::resolve(cli_addr.map(|v| v.to_string()), cli_pw.map(|v| v.to_string()), config_addr.to_string(), config_pw.map(|v| MpdPasswordFile::Plain(v.to_string()))).unwrap();
 
         assert_eq!(result.0, expected_addr);
         assert_eq!(result.1, expected_pw);
@@ -190,4 +224,55 @@ mod tests {
 
         assert_eq!(format!("{pw:?}"), "*****");
     }
+
+    #[test]
+    fn parses_plain_password_from_config() {
+        let password: Option<MpdPasswordFile> = ron::de::from_str(r#"Some("secret")"#).unwrap();
+
+        assert_eq!(password.unwrap().resolve().unwrap(), "secret");
+    }
+
+    #[test]
+    fn reads_password_from_env_var() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        ENV.set("RMPC_TEST_MPD_PASSWORD".to_string(), "secret".to_string());
+        let password: Option<MpdPasswordFile> = ron::de::from_str(r#"Some((env: "RMPC_TEST_MPD_PASSWORD"))"#).unwrap();
+
+        assert_eq!(password.unwrap().resolve().unwrap(), "secret");
+        ENV.remove("RMPC_TEST_MPD_PASSWORD");
+    }
+
+    #[test]
+    fn fails_when_password_env_var_is_missing() {
+        let password = MpdPasswordFile::Env {
+            env: "RMPC_TEST_MISSING_MPD_PASSWORD".to_owned(),
+        };
+
+        assert!(password.resolve().is_err());
+    }
+
+    #[test_case(Some("127.0.0.1:6600"), Some("secret"),                   None ; "CLI address and password")]
+    #[test_case(                  None, Some("secret"),                   None ; "CLI password")]
+    #[test_case(                  None,           None, Some("secret@/tmp/socket") ; "MPD_HOST with password")]
+    fn does_not_resolve_config_password_when_overridden(cli_addr: Option<&str>, cli_pw: Option<&str>, host: Option<&str>) {
+        let _guard = TEST_LOCK.lock().unwrap();
+        ENV.clear();
+        if let Some(host) = host {
+            ENV.set("MPD_HOST".to_string(), host.to_string());
+        }
+        let config_pw = MpdPasswordFile::Env { env: "RMPC_TEST_MISSING_MPD_PASSWORD".to_owned() };
+
+        let result = MpdAddress::resolve(cli_addr.map(|v| v.to_string()), cli_pw.map(|v| v.to_string()), "127.0.0.1:7600".to_string(), Some(config_pw)).unwrap();
+
+        assert_eq!(result.1, Some("secret".into()));
+    }
+
+    #[test]
+    fn fails_when_config_password_env_var_is_missing_without_override() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        ENV.clear();
+        let config_pw = MpdPasswordFile::Env { env: "RMPC_TEST_MISSING_MPD_PASSWORD".to_owned() };
+
+        assert!(MpdAddress::resolve(None, None, "127.0.0.1:7600".to_string(), Some(config_pw)).is_err());
+    }
 }
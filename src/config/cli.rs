@@ -13,6 +13,9 @@ pub struct Args {
     #[arg(short, long)]
     /// Override the MPD password
     pub password: Option<String>,
+    #[arg(long)]
+    /// Run in a minimal single line mode showing only the playback state, progress and current song.
+    pub compact: bool,
 }
 
 #[derive(Subcommand, Clone, Debug, PartialEq)]
@@ -15,6 +15,10 @@ pub fn default_volume_step() -> u8 {
     5
 }
 
+pub fn default_volume_step_fine() -> u8 {
+    1
+}
+
 pub fn default_scrolloff() -> usize {
     0
 }
@@ -24,6 +28,27 @@ pub fn default_progress_update_interval_ms() -> Option<u64> {
     Some(1000)
 }
 
+pub fn default_key_sequence_timeout_ms() -> u64 {
+    500
+}
+
+pub fn default_input_poll_interval_ms() -> u64 {
+    250
+}
+
+#[allow(clippy::unnecessary_wraps)]
+pub fn default_idle_max_retries() -> Option<u64> {
+    Some(5)
+}
+
+pub fn default_log_timestamp_format() -> String {
+    "%H:%M:%S%.3f".to_string()
+}
+
+pub fn default_tag_separator() -> String {
+    " | ".to_string()
+}
+
 pub fn mpd_address() -> String {
     "127.0.0.1:6600".to_string()
 }
@@ -36,6 +61,10 @@ pub fn mpd_port() -> String {
     "6600".to_string()
 }
 
+pub fn default_album_art_cache_size() -> usize {
+    16
+}
+
 pub fn disabled_album_art_protos() -> Vec<String> {
     ["http://", "https://"].into_iter().map(|p| p.to_owned()).collect()
 }
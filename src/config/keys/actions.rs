@@ -14,18 +14,45 @@ pub enum GlobalAction {
     ShowCurrentSongInfo,
     ShowOutputs,
     ShowDecoders,
+    ShowCurrentSongPlaylists,
     NextTrack,
     PreviousTrack,
     Stop,
+    StopAfterCurrent,
     ToggleRepeat,
     ToggleSingle,
     ToggleRandom,
     ToggleConsume,
+    CycleReplayGain,
+    CrossfadeUp,
+    CrossfadeDown,
     TogglePause,
+    TogglePlayback,
     VolumeUp,
     VolumeDown,
+    VolumeUpFine,
+    VolumeDownFine,
+    ToggleMute,
     SeekForward,
     SeekBack,
+    AddCurrentAlbum {
+        skip_queued: bool,
+    },
+    AddSimilar {
+        count: usize,
+    },
+    RequeueCurrent,
+    AddFirst {
+        count: usize,
+        sort: Option<&'static str>,
+    },
+    Random,
+    ClearError,
+    OpenSongLocation,
+    Reconnect,
+    SetStatusUpdateInterval {
+        interval_ms: Option<u64>,
+    },
     CommandMode,
     NextTab,
     PreviousTab,
@@ -47,18 +74,45 @@ pub enum GlobalActionFile {
     ShowCurrentSongInfo,
     ShowOutputs,
     ShowDecoders,
+    ShowCurrentSongPlaylists,
     NextTrack,
     PreviousTrack,
     Stop,
+    StopAfterCurrent,
     ToggleRepeat,
     ToggleSingle,
     ToggleRandom,
     ToggleConsume,
+    CycleReplayGain,
+    CrossfadeUp,
+    CrossfadeDown,
     TogglePause,
+    TogglePlayback,
     VolumeUp,
     VolumeDown,
+    VolumeUpFine,
+    VolumeDownFine,
+    ToggleMute,
     SeekForward,
     SeekBack,
+    AddCurrentAlbum {
+        skip_queued: bool,
+    },
+    AddSimilar {
+        count: usize,
+    },
+    RequeueCurrent,
+    AddFirst {
+        count: usize,
+        sort: Option<String>,
+    },
+    Random,
+    ClearError,
+    OpenSongLocation,
+    Reconnect,
+    SetStatusUpdateInterval {
+        interval_ms: Option<u64>,
+    },
     NextTab,
     PreviousTab,
     SwitchToTab(String),
@@ -85,6 +139,7 @@ impl From<GlobalActionFile> for GlobalAction {
             GlobalActionFile::Quit => GlobalAction::Quit,
             GlobalActionFile::ShowOutputs => GlobalAction::ShowOutputs,
             GlobalActionFile::ShowDecoders => GlobalAction::ShowDecoders,
+            GlobalActionFile::ShowCurrentSongPlaylists => GlobalAction::ShowCurrentSongPlaylists,
             GlobalActionFile::ShowCurrentSongInfo => GlobalAction::ShowCurrentSongInfo,
             GlobalActionFile::CommandMode => GlobalAction::CommandMode,
             GlobalActionFile::Command { command, description } => GlobalAction::Command {
@@ -95,14 +150,36 @@ impl From<GlobalActionFile> for GlobalAction {
             GlobalActionFile::NextTrack => GlobalAction::NextTrack,
             GlobalActionFile::PreviousTrack => GlobalAction::PreviousTrack,
             GlobalActionFile::Stop => GlobalAction::Stop,
+            GlobalActionFile::StopAfterCurrent => GlobalAction::StopAfterCurrent,
             GlobalActionFile::ToggleRepeat => GlobalAction::ToggleRepeat,
             GlobalActionFile::ToggleRandom => GlobalAction::ToggleRandom,
             GlobalActionFile::ToggleSingle => GlobalAction::ToggleSingle,
+            GlobalActionFile::CycleReplayGain => GlobalAction::CycleReplayGain,
+            GlobalActionFile::CrossfadeUp => GlobalAction::CrossfadeUp,
+            GlobalActionFile::CrossfadeDown => GlobalAction::CrossfadeDown,
             GlobalActionFile::TogglePause => GlobalAction::TogglePause,
+            GlobalActionFile::TogglePlayback => GlobalAction::TogglePlayback,
             GlobalActionFile::SeekForward => GlobalAction::SeekForward,
             GlobalActionFile::SeekBack => GlobalAction::SeekBack,
+            GlobalActionFile::AddCurrentAlbum { skip_queued } => GlobalAction::AddCurrentAlbum { skip_queued },
+            GlobalActionFile::AddSimilar { count } => GlobalAction::AddSimilar { count },
+            GlobalActionFile::RequeueCurrent => GlobalAction::RequeueCurrent,
+            GlobalActionFile::AddFirst { count, sort } => GlobalAction::AddFirst {
+                count,
+                sort: sort.map(|s| s.leak() as &'static str),
+            },
+            GlobalActionFile::Random => GlobalAction::Random,
+            GlobalActionFile::ClearError => GlobalAction::ClearError,
+            GlobalActionFile::OpenSongLocation => GlobalAction::OpenSongLocation,
+            GlobalActionFile::Reconnect => GlobalAction::Reconnect,
+            GlobalActionFile::SetStatusUpdateInterval { interval_ms } => {
+                GlobalAction::SetStatusUpdateInterval { interval_ms }
+            }
             GlobalActionFile::VolumeDown => GlobalAction::VolumeDown,
             GlobalActionFile::VolumeUp => GlobalAction::VolumeUp,
+            GlobalActionFile::VolumeDownFine => GlobalAction::VolumeDownFine,
+            GlobalActionFile::VolumeUpFine => GlobalAction::VolumeUpFine,
+            GlobalActionFile::ToggleMute => GlobalAction::ToggleMute,
             GlobalActionFile::PreviousTab => GlobalAction::PreviousTab,
             GlobalActionFile::NextTab => GlobalAction::NextTab,
             GlobalActionFile::ToggleConsume => GlobalAction::ToggleConsume,
@@ -131,6 +208,9 @@ impl ToDescription for GlobalAction {
             GlobalAction::Quit => "Exit rmpc",
             GlobalAction::ShowOutputs => "Show MPD outputs config",
             GlobalAction::ShowDecoders => "Show MPD decoder plugins",
+            GlobalAction::ShowCurrentSongPlaylists => {
+                "Show which stored playlists contain the currently playing song and toggle whether they do"
+            }
             GlobalAction::ShowCurrentSongInfo => "Show metadata of the currently playing song in a modal popup",
             GlobalAction::ToggleRepeat => "Toggle repeat",
             GlobalAction::ToggleSingle => {
@@ -138,14 +218,45 @@ impl ToDescription for GlobalAction {
             }
             GlobalAction::ToggleRandom => "Toggles random playback",
             GlobalAction::ToggleConsume => "Remove song from the queue after playing",
+            GlobalAction::CycleReplayGain => "Cycle the ReplayGain mode between off, track, album and auto",
+            GlobalAction::CrossfadeUp => "Increase the crossfade between songs by one second",
+            GlobalAction::CrossfadeDown => "Decrease the crossfade between songs by one second",
             GlobalAction::TogglePause => "Pause/Unpause playback",
+            GlobalAction::TogglePlayback => "Pause/Unpause playback or start it when stopped",
             GlobalAction::Stop => "Stop playback",
+            GlobalAction::StopAfterCurrent => "Stop playback once the current song ends, press again to cancel",
             GlobalAction::VolumeUp => "Raise volume",
             GlobalAction::VolumeDown => "Lower volume",
+            GlobalAction::VolumeUpFine => "Raise volume by the fine volume step",
+            GlobalAction::VolumeDownFine => "Lower volume by the fine volume step",
+            GlobalAction::ToggleMute => "Mute the volume or restore it to the level before muting",
             GlobalAction::NextTrack => "Play next track in the queue",
             GlobalAction::PreviousTrack => "Play previous track in the queue",
             GlobalAction::SeekForward => "Seek currently playing track forwards",
             GlobalAction::SeekBack => "Seek currently playing track backwards",
+            GlobalAction::AddCurrentAlbum { skip_queued: false } => {
+                "Add the album of the currently playing song to the queue"
+            }
+            GlobalAction::AddCurrentAlbum { skip_queued: true } => {
+                "Add the songs from the album of the currently playing song which are not queued yet"
+            }
+            GlobalAction::AddSimilar { .. } => {
+                "Add random songs sharing genre or artist with the currently playing song"
+            }
+            GlobalAction::RequeueCurrent => "Add another copy of the currently playing song right after it",
+            GlobalAction::AddFirst { .. } => "Add only the first songs of the item under cursor to the queue",
+            GlobalAction::Random => "Jump to a random item in browser panes, elsewhere add a random song to the queue",
+            GlobalAction::ClearError => "Clear the error reported by MPD, for example after a song failed to decode",
+            GlobalAction::OpenSongLocation => {
+                "Open the directory of the currently playing song in the file manager, in the queue the selected one"
+            }
+            GlobalAction::Reconnect => "Reconnect to MPD and reload the whole state, ie. after MPD was restarted",
+            GlobalAction::SetStatusUpdateInterval { interval_ms: Some(_) } => {
+                "Change how often the status is refreshed while playing"
+            }
+            GlobalAction::SetStatusUpdateInterval { interval_ms: None } => {
+                "Stop refreshing the status periodically while playing"
+            }
             GlobalAction::NextTab => "Switch to next tab",
             GlobalAction::PreviousTab => "Switch to previous tab",
             GlobalAction::SwitchToTab(TabName("Queue")) => "Switch directly to Queue tab",
@@ -278,6 +389,10 @@ pub enum QueueActionsFile {
     AddToPlaylist,
     ShowInfo,
     JumpToCurrent,
+    Crop,
+    RemovePlayed,
+    ToggleQueueTime,
+    ToggleFollowPlaying,
 }
 
 #[derive(Debug, Display, PartialEq, Eq, Hash, Clone, Copy)]
@@ -289,6 +404,10 @@ pub enum QueueActions {
     AddToPlaylist,
     ShowInfo,
     JumpToCurrent,
+    Crop,
+    RemovePlayed,
+    ToggleQueueTime,
+    ToggleFollowPlaying,
 }
 
 impl From<QueueActionsFile> for QueueActions {
@@ -301,6 +420,10 @@ impl From<QueueActionsFile> for QueueActions {
             QueueActionsFile::AddToPlaylist => QueueActions::AddToPlaylist,
             QueueActionsFile::ShowInfo => QueueActions::ShowInfo,
             QueueActionsFile::JumpToCurrent => QueueActions::JumpToCurrent,
+            QueueActionsFile::Crop => QueueActions::Crop,
+            QueueActionsFile::RemovePlayed => QueueActions::RemovePlayed,
+            QueueActionsFile::ToggleQueueTime => QueueActions::ToggleQueueTime,
+            QueueActionsFile::ToggleFollowPlaying => QueueActions::ToggleFollowPlaying,
         }
     }
 }
@@ -312,9 +435,13 @@ impl ToDescription for QueueActions {
             QueueActions::DeleteAll => "Clear current queue",
             QueueActions::Play => "Play song under cursor",
             QueueActions::Save => "Save current queue as a new playlist",
-            QueueActions::AddToPlaylist => "Add song under cursor to an existing playlist",
+            QueueActions::AddToPlaylist => "Add marked songs or song under cursor to an existing playlist",
             QueueActions::ShowInfo => "Show metadata of the song under cursor in a modal popup",
             QueueActions::JumpToCurrent => "Moves the cursor in Queue table to the currently playing song",
+            QueueActions::Crop => "Remove all songs from the queue except the currently playing one",
+            QueueActions::RemovePlayed => "Remove all songs before the currently playing one from the queue",
+            QueueActions::ToggleQueueTime => "Cycle the duration column between duration, time left and hidden",
+            QueueActions::ToggleFollowPlaying => "Toggle keeping the currently playing song selected in the queue",
         }
     }
 }
@@ -349,6 +476,9 @@ pub enum CommonActionFile {
     Confirm,
     FocusInput,
     AddAll,
+    ToggleMatchMode,
+    RateUp,
+    RateDown,
 }
 
 #[derive(Debug, Display, PartialEq, Eq, Hash, Clone, Copy)]
@@ -379,6 +509,9 @@ pub enum CommonAction {
     Confirm,
     FocusInput,
     AddAll,
+    ToggleMatchMode,
+    RateUp,
+    RateDown,
 }
 
 impl ToDescription for CommonAction {
@@ -390,8 +523,8 @@ impl ToDescription for CommonAction {
             CommonAction::DownHalf => "Jump by half a screen down",
             CommonAction::MoveUp => "Move current item up, for example song in a queue",
             CommonAction::MoveDown => "Move current item down, for example song in a queue",
-            CommonAction::Right => "Go right",
-            CommonAction::Left => "Go left",
+            CommonAction::Right => "Go right, or seek forward in tabs without a focusable pane",
+            CommonAction::Left => "Go left, or seek back in tabs without a focusable pane",
             CommonAction::Top => "Jump all the way to the top",
             CommonAction::Bottom => "Jump all the way to the bottom",
             CommonAction::EnterSearch => "Enter search mode",
@@ -401,8 +534,11 @@ impl ToDescription for CommonAction {
             CommonAction::InvertSelection => "Inverts the current selected items",
             CommonAction::Add => "Add item to queue",
             CommonAction::AddAll => "Add all items to queue",
+            CommonAction::ToggleMatchMode => "Switch between exact and case-insensitive substring matching of tags in the Artists, Album Artists and Albums browsers",
             CommonAction::Delete => "Delete. For example a playlist, song from a playlist or wipe the current queue",
             CommonAction::Rename => "Rename. Currently only for playlists",
+            CommonAction::RateUp => "Raise the rating of the song under cursor by one star",
+            CommonAction::RateDown => "Lower the rating of the song under cursor by one star",
             CommonAction::Close => "Close/Stop whatever action is currently going on. Cancel filter, close a modal, etc.",
             CommonAction::Confirm => "Confirm whatever action is currently going on. In browser panes it either enters a directory or adds and plays a song under cursor",
             CommonAction::FocusInput => "Focuses textbox if any is on the screen and is not focused",
@@ -439,6 +575,9 @@ impl From<CommonActionFile> for CommonAction {
             CommonActionFile::Confirm => CommonAction::Confirm,
             CommonActionFile::FocusInput => CommonAction::FocusInput,
             CommonActionFile::AddAll => CommonAction::AddAll,
+            CommonActionFile::ToggleMatchMode => CommonAction::ToggleMatchMode,
+            CommonActionFile::RateUp => CommonAction::RateUp,
+            CommonActionFile::RateDown => CommonAction::RateDown,
             CommonActionFile::PaneUp => CommonAction::PaneUp,
             CommonActionFile::PaneDown => CommonAction::PaneDown,
             CommonActionFile::PaneLeft => CommonAction::PaneLeft,
@@ -467,20 +606,30 @@ impl From<PlaylistsActionsFile> for PlaylistsActions {
     }
 }
 
+// Search actions
+
 #[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash, Clone)]
-pub enum SearchActionsFile {}
+pub enum SearchActionsFile {
+    PlayNow,
+}
 
 #[derive(Debug, Display, PartialEq, Eq, Hash, Clone, Copy)]
-pub enum SearchActions {}
+pub enum SearchActions {
+    PlayNow,
+}
 
 impl ToDescription for SearchActions {
     fn to_description(&self) -> &str {
-        ""
+        match self {
+            SearchActions::PlayNow => "Replace the queue with the selected or marked songs and start playing them",
+        }
     }
 }
 
 impl From<SearchActionsFile> for SearchActions {
-    fn from(_value: SearchActionsFile) -> Self {
-        unreachable!()
+    fn from(value: SearchActionsFile) -> Self {
+        match value {
+            SearchActionsFile::PlayNow => SearchActions::PlayNow,
+        }
     }
 }
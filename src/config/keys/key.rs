@@ -177,6 +177,69 @@ impl FromStr for Key {
         Ok(Self { key, modifiers })
     }
 }
+
+/// One or more keys which have to be pressed after each other to trigger a keybind, for example `gg`
+#[derive(Debug, SerializeDisplay, DeserializeFromStr, PartialEq, Eq, Hash, Clone)]
+pub struct KeySequence(pub Vec<Key>);
+
+impl KeySequence {
+    /// Whether this sequence starts with all of the `keys` and is longer than them
+    pub fn continues(&self, keys: &[Key]) -> bool {
+        self.0.len() > keys.len() && self.0.starts_with(keys)
+    }
+}
+
+impl From<Key> for KeySequence {
+    fn from(value: Key) -> Self {
+        Self(vec![value])
+    }
+}
+
+impl Display for KeySequence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.iter().try_for_each(|key| write!(f, "{key}"))
+    }
+}
+
+impl FromStr for KeySequence {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars = s.chars().collect_vec();
+        if chars.len() <= 1 || (s.starts_with('<') && s.ends_with('>')) {
+            if let Ok(key) = s.parse::<Key>() {
+                return Ok(key.into());
+            }
+        }
+
+        let mut keys = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            // The shortest `<...>` group which is a valid key, otherwise a single character
+            let key = (i + 2..chars.len())
+                .filter(|_| chars[i] == '<')
+                .filter(|end| chars[*end] == '>')
+                .find_map(|end| {
+                    chars[i..=end]
+                        .iter()
+                        .collect::<String>()
+                        .parse::<Key>()
+                        .ok()
+                        .filter(|key| key.key != KeyCode::Null)
+                        .map(|key| (key, end + 1))
+                });
+            let (key, next) = match key {
+                Some(key) => key,
+                None => (chars[i].to_string().parse::<Key>()?, i + 1),
+            };
+            keys.push(key);
+            i = next;
+        }
+
+        Ok(Self(keys))
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -249,4 +312,25 @@ mod tests {
         let deserialized: Key = input.parse().unwrap();
         assert_eq!(deserialized, expected);
     }
+
+    #[rstest]
+    #[case("gg",          vec![Key { key: KeyCode::Char('g'), modifiers: KeyModifiers::NONE },
+                               Key { key: KeyCode::Char('g'), modifiers: KeyModifiers::NONE }])]
+    #[case("<C-w>j",      vec![Key { key: KeyCode::Char('w'), modifiers: KeyModifiers::CONTROL },
+                               Key { key: KeyCode::Char('j'), modifiers: KeyModifiers::NONE }])]
+    #[case("<Space>a<CR>", vec![Key { key: KeyCode::Char(' '), modifiers: KeyModifiers::NONE },
+                               Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::NONE },
+                               Key { key: KeyCode::Enter,     modifiers: KeyModifiers::NONE }])]
+    #[case("<g",          vec![Key { key: KeyCode::Char('<'), modifiers: KeyModifiers::NONE },
+                               Key { key: KeyCode::Char('g'), modifiers: KeyModifiers::NONE }])]
+    #[case("<C-S-<>",     vec![Key { key: KeyCode::Char('<'), modifiers: KeyModifiers::CONTROL | KeyModifiers::SHIFT }])]
+    #[case("G",           vec![Key { key: KeyCode::Char('G'), modifiers: KeyModifiers::SHIFT }])]
+    fn sequence_serialization_round_trip(#[case] expected_str: &str, #[case] input: Vec<Key>) {
+        let input = KeySequence(input);
+        let serialized = input.to_string();
+        assert_eq!(expected_str, serialized);
+
+        let deserialized: KeySequence = serialized.parse().unwrap();
+        assert_eq!(deserialized, input);
+    }
 }
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use actions::{
     AlbumsActionsFile, ArtistsActionsFile, CommonActionFile, DirectoriesActionsFile, GlobalActionFile,
-    PlaylistsActionsFile, QueueActionsFile,
+    PlaylistsActionsFile, QueueActionsFile, SearchActionsFile,
 };
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use serde::{Deserialize, Serialize};
@@ -16,41 +16,42 @@ pub use actions::{
     AlbumsActions, ArtistsActions, CommonAction, DirectoriesActions, GlobalAction, PlaylistsActions, QueueActions,
     SearchActions,
 };
-pub use key::Key;
+pub use key::{Key, KeySequence};
 
 mod actions;
 mod key;
 
 #[derive(Debug, PartialEq, Default, Clone)]
 pub struct KeyConfig {
-    pub global: HashMap<Key, GlobalAction>,
-    pub navigation: HashMap<Key, CommonAction>,
-    pub albums: HashMap<Key, AlbumsActions>,
-    pub artists: HashMap<Key, ArtistsActions>,
-    pub directories: HashMap<Key, DirectoriesActions>,
-    pub playlists: HashMap<Key, PlaylistsActions>,
-    pub search: HashMap<Key, SearchActions>,
+    pub global: HashMap<KeySequence, GlobalAction>,
+    pub navigation: HashMap<KeySequence, CommonAction>,
+    pub albums: HashMap<KeySequence, AlbumsActions>,
+    pub artists: HashMap<KeySequence, ArtistsActions>,
+    pub directories: HashMap<KeySequence, DirectoriesActions>,
+    pub playlists: HashMap<KeySequence, PlaylistsActions>,
+    pub search: HashMap<KeySequence, SearchActions>,
     #[cfg(debug_assertions)]
-    pub logs: HashMap<Key, LogsActions>,
-    pub queue: HashMap<Key, QueueActions>,
+    pub logs: HashMap<KeySequence, LogsActions>,
+    pub queue: HashMap<KeySequence, QueueActions>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct KeyConfigFile {
     #[serde(default)]
-    pub global: HashMap<Key, GlobalActionFile>,
+    pub global: HashMap<KeySequence, GlobalActionFile>,
     #[serde(default)]
-    pub navigation: HashMap<Key, CommonActionFile>,
+    pub navigation: HashMap<KeySequence, CommonActionFile>,
     // pub albums: HashMap<AlbumsActions, Vec<Key>>,
     // pub artists: HashMap<ArtistsActions, Vec<Key>>,
     // pub directories: HashMap<DirectoriesActions, Vec<Key>>,
     // pub playlists: HashMap<PlaylistsActions, Vec<Key>>,
-    // pub search: HashMap<SearchActions, Vec<Key>>,
+    #[serde(default)]
+    pub search: HashMap<KeySequence, SearchActionsFile>,
     #[cfg(debug_assertions)]
     #[serde(default)]
-    pub logs: HashMap<Key, LogsActionsFile>,
+    pub logs: HashMap<KeySequence, LogsActionsFile>,
     #[serde(default)]
-    pub queue: HashMap<Key, QueueActionsFile>,
+    pub queue: HashMap<KeySequence, QueueActionsFile>,
 }
 
 impl Default for KeyConfigFile {
@@ -68,68 +69,73 @@ impl Default for KeyConfigFile {
         #[cfg(debug_assertions)]
         use LogsActionsFile as L;
         use QueueActionsFile as Q;
+        use SearchActionsFile as S;
         Self {
             global: HashMap::from([
-                (Key { key: K::Char('q'), modifiers: M::NONE  }, G::Quit),
-                (Key { key: K::Char(':'), modifiers: M::NONE  }, G::CommandMode),
-                (Key { key: K::Char('~'), modifiers: M::NONE  }, G::ShowHelp),
-                (Key { key: K::Char('I'), modifiers: M::SHIFT }, G::ShowCurrentSongInfo),
-                (Key { key: K::Char('O'), modifiers: M::SHIFT }, G::ShowOutputs),
-                (Key { key: K::Char('P'), modifiers: M::SHIFT }, G::ShowDecoders),
-                (Key { key: K::Char('>'), modifiers: M::NONE  }, G::NextTrack),
-                (Key { key: K::Char('<'), modifiers: M::NONE  }, G::PreviousTrack),
-                (Key { key: K::Char('s'), modifiers: M::NONE  }, G::Stop),
-                (Key { key: K::Char('z'), modifiers: M::NONE  }, G::ToggleRepeat),
-                (Key { key: K::Char('x'), modifiers: M::NONE  }, G::ToggleRandom),
-                (Key { key: K::Char('c'), modifiers: M::NONE  }, G::ToggleConsume),
-                (Key { key: K::Char('v'), modifiers: M::NONE  }, G::ToggleSingle),
-                (Key { key: K::Char('p'), modifiers: M::NONE  }, G::TogglePause),
-                (Key { key: K::Char('f'), modifiers: M::NONE  }, G::SeekForward),
-                (Key { key: K::Char('b'), modifiers: M::NONE  }, G::SeekBack),
-                (Key { key: K::Char(','), modifiers: M::NONE  }, G::VolumeDown),
-                (Key { key: K::Char('.'), modifiers: M::NONE  }, G::VolumeUp),
-                (Key { key: K::BackTab,   modifiers: M::SHIFT }, G::PreviousTab),
-                (Key { key: K::Tab,       modifiers: M::NONE  }, G::NextTab),
-                (Key { key: K::Char('1'), modifiers: M::NONE  }, G::SwitchToTab("Queue".to_string())),
-                (Key { key: K::Char('2'), modifiers: M::NONE  }, G::SwitchToTab("Directories".to_string())),
-                (Key { key: K::Char('3'), modifiers: M::NONE  }, G::SwitchToTab("Artists".to_string())),
-                (Key { key: K::Char('4'), modifiers: M::NONE  }, G::SwitchToTab("Album Artists".to_string())),
-                (Key { key: K::Char('5'), modifiers: M::NONE  }, G::SwitchToTab("Albums".to_string())),
-                (Key { key: K::Char('6'), modifiers: M::NONE  }, G::SwitchToTab("Playlists".to_string())),
-                (Key { key: K::Char('7'), modifiers: M::NONE  }, G::SwitchToTab("Search".to_string())),
+                (Key { key: K::Char('q'), modifiers: M::NONE  }.into(), G::Quit),
+                (Key { key: K::Char(':'), modifiers: M::NONE  }.into(), G::CommandMode),
+                (Key { key: K::Char('~'), modifiers: M::NONE  }.into(), G::ShowHelp),
+                (Key { key: K::Char('I'), modifiers: M::SHIFT }.into(), G::ShowCurrentSongInfo),
+                (Key { key: K::Char('O'), modifiers: M::SHIFT }.into(), G::ShowOutputs),
+                (Key { key: K::Char('P'), modifiers: M::SHIFT }.into(), G::ShowDecoders),
+                (Key { key: K::Char('>'), modifiers: M::NONE  }.into(), G::NextTrack),
+                (Key { key: K::Char('<'), modifiers: M::NONE  }.into(), G::PreviousTrack),
+                (Key { key: K::Char('s'), modifiers: M::NONE  }.into(), G::Stop),
+                (Key { key: K::Char('z'), modifiers: M::NONE  }.into(), G::ToggleRepeat),
+                (Key { key: K::Char('x'), modifiers: M::NONE  }.into(), G::ToggleRandom),
+                (Key { key: K::Char('c'), modifiers: M::NONE  }.into(), G::ToggleConsume),
+                (Key { key: K::Char('v'), modifiers: M::NONE  }.into(), G::ToggleSingle),
+                (Key { key: K::Char('p'), modifiers: M::NONE  }.into(), G::TogglePause),
+                (Key { key: K::Char('f'), modifiers: M::NONE  }.into(), G::SeekForward),
+                (Key { key: K::Char('b'), modifiers: M::NONE  }.into(), G::SeekBack),
+                (Key { key: K::Char(','), modifiers: M::NONE  }.into(), G::VolumeDown),
+                (Key { key: K::Char('.'), modifiers: M::NONE  }.into(), G::VolumeUp),
+                (Key { key: K::Char(','), modifiers: M::CONTROL }.into(), G::VolumeDownFine),
+                (Key { key: K::Char('.'), modifiers: M::CONTROL }.into(), G::VolumeUpFine),
+                (Key { key: K::Char('m'), modifiers: M::NONE  }.into(), G::ToggleMute),
+                (Key { key: K::BackTab,   modifiers: M::SHIFT }.into(), G::PreviousTab),
+                (Key { key: K::Tab,       modifiers: M::NONE  }.into(), G::NextTab),
+                (Key { key: K::Char('1'), modifiers: M::NONE  }.into(), G::SwitchToTab("Queue".to_string())),
+                (Key { key: K::Char('2'), modifiers: M::NONE  }.into(), G::SwitchToTab("Directories".to_string())),
+                (Key { key: K::Char('3'), modifiers: M::NONE  }.into(), G::SwitchToTab("Artists".to_string())),
+                (Key { key: K::Char('4'), modifiers: M::NONE  }.into(), G::SwitchToTab("Album Artists".to_string())),
+                (Key { key: K::Char('5'), modifiers: M::NONE  }.into(), G::SwitchToTab("Albums".to_string())),
+                (Key { key: K::Char('6'), modifiers: M::NONE  }.into(), G::SwitchToTab("Playlists".to_string())),
+                (Key { key: K::Char('7'), modifiers: M::NONE  }.into(), G::SwitchToTab("Search".to_string())),
             ]),
             navigation: HashMap::from([
-                (Key { key: K::Char('k'), modifiers: M::NONE    }, C::Up),
-                (Key { key: K::Char('j'), modifiers: M::NONE    }, C::Down),
-                (Key { key: K::Char('l'), modifiers: M::NONE    }, C::Right),
-                (Key { key: K::Left,      modifiers: M::NONE    }, C::Left),
-                (Key { key: K::Up,        modifiers: M::NONE    }, C::Up),
-                (Key { key: K::Down,      modifiers: M::NONE    }, C::Down),
-                (Key { key: K::Right,     modifiers: M::NONE    }, C::Right),
-                (Key { key: K::Char('h'), modifiers: M::NONE    }, C::Left),
-                (Key { key: K::Char('k'), modifiers: M::CONTROL }, C::PaneUp),
-                (Key { key: K::Char('j'), modifiers: M::CONTROL }, C::PaneDown),
-                (Key { key: K::Char('l'), modifiers: M::CONTROL }, C::PaneRight),
-                (Key { key: K::Char('h'), modifiers: M::CONTROL }, C::PaneLeft),
-                (Key { key: K::Char('K'), modifiers: M::SHIFT   }, C::MoveUp),
-                (Key { key: K::Char('J'), modifiers: M::SHIFT   }, C::MoveDown),
-                (Key { key: K::Char('d'), modifiers: M::CONTROL }, C::DownHalf),
-                (Key { key: K::Char('u'), modifiers: M::CONTROL }, C::UpHalf),
-                (Key { key: K::Char('G'), modifiers: M::SHIFT   }, C::Bottom),
-                (Key { key: K::Char('g'), modifiers: M::NONE    }, C::Top),
-                (Key { key: K::Char('/'), modifiers: M::NONE    }, C::EnterSearch),
-                (Key { key: K::Char('n'), modifiers: M::NONE    }, C::NextResult),
-                (Key { key: K::Char('N'), modifiers: M::SHIFT   }, C::PreviousResult),
-                (Key { key: K::Char(' '), modifiers: M::NONE    }, C::Select),
-                (Key { key: K::Char(' '), modifiers: M::CONTROL }, C::InvertSelection),
-                (Key { key: K::Char('a'), modifiers: M::NONE    }, C::Add),
-                (Key { key: K::Char('A'), modifiers: M::SHIFT   }, C::AddAll),
-                (Key { key: K::Char('D'), modifiers: M::SHIFT   }, C::Delete),
-                (Key { key: K::Char('r'), modifiers: M::NONE    }, C::Rename),
-                (Key { key: K::Char('c'), modifiers: M::CONTROL }, C::Close),
-                (Key { key: K::Esc,       modifiers: M::NONE    }, C::Close),
-                (Key { key: K::Enter,     modifiers: M::NONE    }, C::Confirm),
-                (Key { key: K::Char('i'), modifiers: M::NONE    }, C::FocusInput),
+                (Key { key: K::Char('k'), modifiers: M::NONE    }.into(), C::Up),
+                (Key { key: K::Char('j'), modifiers: M::NONE    }.into(), C::Down),
+                (Key { key: K::Char('l'), modifiers: M::NONE    }.into(), C::Right),
+                (Key { key: K::Left,      modifiers: M::NONE    }.into(), C::Left),
+                (Key { key: K::Up,        modifiers: M::NONE    }.into(), C::Up),
+                (Key { key: K::Down,      modifiers: M::NONE    }.into(), C::Down),
+                (Key { key: K::Right,     modifiers: M::NONE    }.into(), C::Right),
+                (Key { key: K::Char('h'), modifiers: M::NONE    }.into(), C::Left),
+                (Key { key: K::Char('k'), modifiers: M::CONTROL }.into(), C::PaneUp),
+                (Key { key: K::Char('j'), modifiers: M::CONTROL }.into(), C::PaneDown),
+                (Key { key: K::Char('l'), modifiers: M::CONTROL }.into(), C::PaneRight),
+                (Key { key: K::Char('h'), modifiers: M::CONTROL }.into(), C::PaneLeft),
+                (Key { key: K::Char('K'), modifiers: M::SHIFT   }.into(), C::MoveUp),
+                (Key { key: K::Char('J'), modifiers: M::SHIFT   }.into(), C::MoveDown),
+                (Key { key: K::Char('d'), modifiers: M::CONTROL }.into(), C::DownHalf),
+                (Key { key: K::Char('u'), modifiers: M::CONTROL }.into(), C::UpHalf),
+                (Key { key: K::Char('G'), modifiers: M::SHIFT   }.into(), C::Bottom),
+                (Key { key: K::Char('g'), modifiers: M::NONE    }.into(), C::Top),
+                (Key { key: K::Char('/'), modifiers: M::NONE    }.into(), C::EnterSearch),
+                (Key { key: K::Char('n'), modifiers: M::NONE    }.into(), C::NextResult),
+                (Key { key: K::Char('N'), modifiers: M::SHIFT   }.into(), C::PreviousResult),
+                (Key { key: K::Char(' '), modifiers: M::NONE    }.into(), C::Select),
+                (Key { key: K::Char(' '), modifiers: M::CONTROL }.into(), C::InvertSelection),
+                (Key { key: K::Char('a'), modifiers: M::NONE    }.into(), C::Add),
+                (Key { key: K::Char('A'), modifiers: M::SHIFT   }.into(), C::AddAll),
+                (Key { key: K::Char('M'), modifiers: M::SHIFT   }.into(), C::ToggleMatchMode),
+                (Key { key: K::Char('D'), modifiers: M::SHIFT   }.into(), C::Delete),
+                (Key { key: K::Char('r'), modifiers: M::NONE    }.into(), C::Rename),
+                (Key { key: K::Char('c'), modifiers: M::CONTROL }.into(), C::Close),
+                (Key { key: K::Esc,       modifiers: M::NONE    }.into(), C::Close),
+                (Key { key: K::Enter,     modifiers: M::NONE    }.into(), C::Confirm),
+                (Key { key: K::Char('i'), modifiers: M::NONE    }.into(), C::FocusInput),
             ]),
             // albums: HashMap::from([
             // ]),
@@ -139,19 +145,25 @@ impl Default for KeyConfigFile {
             // ]),
             // playlists: HashMap::from([
             // ]),
+            search: HashMap::from([
+                (Key { key: K::Char('o'), modifiers: M::NONE    }.into(), S::PlayNow),
+            ]),
             #[cfg(debug_assertions)]
             logs: HashMap::from([
-                (Key { key: K::Char('D'), modifiers: M::SHIFT   }, L::Clear),
-                (Key { key: K::Char('S'), modifiers: M::SHIFT   }, L::ToggleScroll),
+                (Key { key: K::Char('D'), modifiers: M::SHIFT   }.into(), L::Clear),
+                (Key { key: K::Char('S'), modifiers: M::SHIFT   }.into(), L::ToggleScroll),
             ]),
             queue: HashMap::from([
-                (Key { key: K::Char('d'), modifiers: M::NONE    }, Q::Delete),
-                (Key { key: K::Char('D'), modifiers: M::SHIFT   }, Q::DeleteAll),
-                (Key { key: K::Enter,     modifiers: M::NONE    }, Q::Play),
-                (Key { key: K::Char('s'), modifiers: M::CONTROL }, Q::Save),
-                (Key { key: K::Char('a'), modifiers: M::NONE    }, Q::AddToPlaylist),
-                (Key { key: K::Char('i'), modifiers: M::NONE    }, Q::ShowInfo),
-                (Key { key: K::Char('C'), modifiers: M::SHIFT   }, Q::JumpToCurrent),
+                (Key { key: K::Char('d'), modifiers: M::NONE    }.into(), Q::Delete),
+                (Key { key: K::Char('D'), modifiers: M::SHIFT   }.into(), Q::DeleteAll),
+                (Key { key: K::Enter,     modifiers: M::NONE    }.into(), Q::Play),
+                (Key { key: K::Char('s'), modifiers: M::CONTROL }.into(), Q::Save),
+                (Key { key: K::Char('a'), modifiers: M::NONE    }.into(), Q::AddToPlaylist),
+                (Key { key: K::Char('i'), modifiers: M::NONE    }.into(), Q::ShowInfo),
+                (Key { key: K::Char('C'), modifiers: M::SHIFT   }.into(), Q::JumpToCurrent),
+                (Key { key: K::Char('X'), modifiers: M::SHIFT   }.into(), Q::Crop),
+                (Key { key: K::Char('R'), modifiers: M::SHIFT   }.into(), Q::RemovePlayed),
+                (Key { key: K::Char('T'), modifiers: M::SHIFT   }.into(), Q::ToggleQueueTime),
             ]),
         }
     }
@@ -170,7 +182,7 @@ impl From<KeyConfigFile> for KeyConfig {
             artists: HashMap::new(),
             directories: HashMap::new(),
             playlists: HashMap::new(),
-            search: HashMap::new(),
+            search: value.search.into_iter().map(|(k, v)| (k, v.into())).collect(),
             #[cfg(debug_assertions)]
             logs: value.logs.into_iter().map(|(k, v)| (k, v.into())).collect(),
             queue: value.queue.into_iter().map(|(k, v)| (k, v.into())).collect(),
@@ -178,6 +190,34 @@ impl From<KeyConfigFile> for KeyConfig {
     }
 }
 
+impl KeyConfig {
+    fn sequences(&self) -> impl Iterator<Item = &KeySequence> {
+        let sequences = self
+            .global
+            .keys()
+            .chain(self.navigation.keys())
+            .chain(self.albums.keys())
+            .chain(self.artists.keys())
+            .chain(self.directories.keys())
+            .chain(self.playlists.keys())
+            .chain(self.search.keys())
+            .chain(self.queue.keys());
+        #[cfg(debug_assertions)]
+        let sequences = sequences.chain(self.logs.keys());
+        sequences
+    }
+
+    /// Whether any keybind is a longer sequence starting with the `keys`
+    pub fn is_sequence_prefix(&self, keys: &[Key]) -> bool {
+        self.sequences().any(|sequence| sequence.continues(keys))
+    }
+
+    /// Whether the `keys` are bound to an action as a whole
+    pub fn is_bound(&self, keys: &[Key]) -> bool {
+        self.sequences().any(|sequence| sequence.0 == keys)
+    }
+}
+
 impl From<KeyEvent> for Key {
     fn from(value: KeyEvent) -> Self {
         Self {
@@ -202,8 +242,8 @@ mod tests {
     #[cfg(debug_assertions)]
     use crate::config::keys::LogsActionsFile;
     use crate::config::keys::{
-        actions::{CommonActionFile, GlobalActionFile, QueueActionsFile},
-        CommonAction, GlobalAction, QueueActions,
+        actions::{CommonActionFile, GlobalActionFile, QueueActionsFile, SearchActionsFile},
+        CommonAction, GlobalAction, QueueActions, SearchActions,
     };
 
     use super::{Key, KeyConfig, KeyConfigFile};
@@ -212,34 +252,35 @@ mod tests {
     #[rustfmt::skip]
     fn converts() {
         let input = KeyConfigFile {
-            global: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, GlobalActionFile::Quit)]),
+            global: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }.into(), GlobalActionFile::Quit)]),
 
             #[cfg(debug_assertions)]
-            logs: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, LogsActionsFile::Clear)]),
-            queue: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, QueueActionsFile::Play),
-                                  (Key { key: KeyCode::Char('b'), modifiers: KeyModifiers::SHIFT, }, QueueActionsFile::Save)]),
+            logs: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }.into(), LogsActionsFile::Clear)]),
+            queue: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }.into(), QueueActionsFile::Play),
+                                  (Key { key: KeyCode::Char('b'), modifiers: KeyModifiers::SHIFT, }.into(), QueueActionsFile::Save)]),
             // albums: HashMap::from([]),
             // artists: HashMap::from([]),
             // directories: HashMap::from([]),
             // playlists: HashMap::from([]),
+            search: HashMap::from([(Key { key: KeyCode::Char('o'), modifiers: KeyModifiers::NONE, }.into(), SearchActionsFile::PlayNow)]),
             navigation: HashMap::from([
-                (Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, CommonActionFile::Up),
-                (Key { key: KeyCode::Char('b'), modifiers: KeyModifiers::SHIFT }, CommonActionFile::Up)
+                (Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }.into(), CommonActionFile::Up),
+                (Key { key: KeyCode::Char('b'), modifiers: KeyModifiers::SHIFT }.into(), CommonActionFile::Up)
             ])
         };
         let expected = KeyConfig {
-            global: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, GlobalAction::Quit)]),
+            global: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }.into(), GlobalAction::Quit)]),
             #[cfg(debug_assertions)]
-            logs: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, LogsActions::Clear)]),
-            queue: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, QueueActions::Play),
-                                  (Key { key: KeyCode::Char('b'), modifiers: KeyModifiers::SHIFT, }, QueueActions::Save)]),
+            logs: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }.into(), LogsActions::Clear)]),
+            queue: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }.into(), QueueActions::Play),
+                                  (Key { key: KeyCode::Char('b'), modifiers: KeyModifiers::SHIFT, }.into(), QueueActions::Save)]),
             albums: HashMap::from([]),
             artists: HashMap::from([]),
             directories: HashMap::from([]),
             playlists: HashMap::from([]),
-            search: HashMap::from([]),
-            navigation: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL }, CommonAction::Up),
-                                       (Key { key: KeyCode::Char('b'), modifiers: KeyModifiers::SHIFT }, CommonAction::Up)]),
+            search: HashMap::from([(Key { key: KeyCode::Char('o'), modifiers: KeyModifiers::NONE, }.into(), SearchActions::PlayNow)]),
+            navigation: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL }.into(), CommonAction::Up),
+                                       (Key { key: KeyCode::Char('b'), modifiers: KeyModifiers::SHIFT }.into(), CommonAction::Up)]),
         };
 
         let result: KeyConfig = input.into();
@@ -1,7 +1,7 @@
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-use address::MpdPassword;
+use address::{MpdPassword, MpdPasswordFile};
 use anyhow::Context;
 use anyhow::Result;
 use clap::Parser;
@@ -22,6 +22,7 @@ mod search;
 pub mod tabs;
 pub mod theme;
 
+use crate::mpd::mpd_client::AlbumArtSource;
 use crate::shared::image;
 use crate::shared::image::ImageProtocol;
 use crate::shared::macros::status_warn;
@@ -59,6 +60,65 @@ pub enum ImageMethod {
     Unsupported,
 }
 
+#[derive(Default, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AddPosition {
+    Start,
+    #[default]
+    End,
+    AfterCurrent,
+}
+
+#[derive(Default, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum OnDuplicateAdd {
+    #[default]
+    Allow,
+    Skip,
+    Warn,
+}
+
+#[derive(Default, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DeleteSelectionBehavior {
+    #[default]
+    KeepIndex,
+    MoveUp,
+}
+
+#[derive(Default, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RightOnSong {
+    #[default]
+    Add,
+    AddAndPlay,
+}
+
+#[derive(Default, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum FilterBehavior {
+    #[default]
+    Jump,
+    Reduce,
+}
+
+#[derive(Default, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Name,
+    Date,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct BrowserSort {
+    #[serde(default)]
+    pub albums: SortOrder,
+    #[serde(default)]
+    pub artists: SortOrder,
+}
+
+#[derive(Default, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ElapsedSource {
+    #[default]
+    Poll,
+    Interpolate,
+}
+
 #[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Eq)]
 pub struct Size {
     pub width: u16,
@@ -75,18 +135,45 @@ impl Default for Size {
 }
 
 #[derive(Debug, Default, Clone)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Config {
     pub address: MpdAddress<'static>,
     pub password: Option<MpdPassword<'static>>,
     pub cache_dir: Option<&'static str>,
     pub lyrics_dir: Option<&'static str>,
+    pub music_directory: Option<&'static str>,
+    pub status_output_path: Option<&'static str>,
     pub volume_step: u8,
+    pub volume_step_fine: u8,
     pub scrolloff: usize,
     pub wrap_navigation: bool,
     pub keybinds: KeyConfig,
     pub enable_mouse: bool,
+    pub compact: bool,
+    pub tab_number_keys: bool,
+    pub confirm_quit: bool,
+    pub keybind_hints: bool,
     pub status_update_interval_ms: Option<u64>,
+    pub elapsed_source: ElapsedSource,
+    pub key_sequence_timeout_ms: u64,
+    pub input_poll_interval_ms: u64,
+    pub idle_max_retries: Option<u64>,
+    pub log_timestamp_format: &'static str,
     pub select_current_song_on_change: bool,
+    pub follow_playing: bool,
+    /// Number of album arts kept in memory so they are not fetched again when switching back
+    pub album_art_cache_size: usize,
+    pub pause_on_error: bool,
+    pub auto_clear_error: bool,
+    pub add_position: AddPosition,
+    pub on_duplicate_add: OnDuplicateAdd,
+    pub delete_selection_behavior: DeleteSelectionBehavior,
+    pub filter_behavior: FilterBehavior,
+    pub right_on_song: RightOnSong,
+    pub retain_filter_on_back: bool,
+    pub advance_after_add: bool,
+    pub artist_split_separator: Option<&'static str>,
+    pub browser_sort: BrowserSort,
     pub theme: UiConfig,
     pub album_art: AlbumArtConfig,
     pub on_song_change: Option<&'static [&'static str]>,
@@ -95,29 +182,80 @@ pub struct Config {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ConfigFile {
     #[serde(default = "defaults::mpd_address")]
     pub address: String,
     #[serde(default)]
-    password: Option<String>,
+    password: Option<MpdPasswordFile>,
     #[serde(default)]
     cache_dir: Option<String>,
     #[serde(default)]
     lyrics_dir: Option<String>,
     #[serde(default)]
+    music_directory: Option<String>,
+    #[serde(default)]
+    status_output_path: Option<String>,
+    #[serde(default)]
     pub theme: Option<String>,
     #[serde(default = "defaults::default_volume_step")]
     volume_step: u8,
+    #[serde(default = "defaults::default_volume_step_fine")]
+    volume_step_fine: u8,
     #[serde(default = "defaults::default_scrolloff")]
     scrolloff: usize,
     #[serde(default = "defaults::default_false")]
     wrap_navigation: bool,
     #[serde(default = "defaults::default_progress_update_interval_ms")]
     status_update_interval_ms: Option<u64>,
+    #[serde(default)]
+    elapsed_source: ElapsedSource,
+    #[serde(default = "defaults::default_key_sequence_timeout_ms")]
+    key_sequence_timeout_ms: u64,
+    #[serde(default = "defaults::default_input_poll_interval_ms")]
+    input_poll_interval_ms: u64,
+    #[serde(default = "defaults::default_idle_max_retries")]
+    idle_max_retries: Option<u64>,
+    #[serde(default = "defaults::default_log_timestamp_format")]
+    log_timestamp_format: String,
     #[serde(default = "defaults::default_false")]
     select_current_song_on_change: bool,
+    #[serde(default = "defaults::default_false")]
+    follow_playing: bool,
+    #[serde(default = "defaults::default_album_art_cache_size")]
+    album_art_cache_size: usize,
+    #[serde(default = "defaults::default_false")]
+    pause_on_error: bool,
+    #[serde(default = "defaults::default_false")]
+    auto_clear_error: bool,
+    #[serde(default)]
+    add_position: AddPosition,
+    #[serde(default)]
+    on_duplicate_add: OnDuplicateAdd,
+    #[serde(default)]
+    delete_selection_behavior: DeleteSelectionBehavior,
+    #[serde(default)]
+    filter_behavior: FilterBehavior,
+    #[serde(default)]
+    right_on_song: RightOnSong,
+    #[serde(default = "defaults::default_false")]
+    retain_filter_on_back: bool,
+    #[serde(default = "defaults::default_false")]
+    advance_after_add: bool,
+    #[serde(default)]
+    artist_split_separator: Option<String>,
+    #[serde(default)]
+    browser_sort: BrowserSort,
     #[serde(default = "defaults::default_true")]
     enable_mouse: bool,
+    #[serde(default = "defaults::default_false")]
+    compact: bool,
+    #[serde(default = "defaults::default_true")]
+    tab_number_keys: bool,
+    #[serde(default = "defaults::default_false")]
+    confirm_quit: bool,
+    #[serde(default = "defaults::default_false")]
+    keybind_hints: bool,
     #[serde(default)]
     keybinds: KeyConfigFile,
     #[serde(default)]
@@ -134,7 +272,7 @@ pub struct ConfigFile {
     tabs: TabsFile,
 }
 
-#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
 pub struct AlbumArtConfigFile {
     #[serde(default)]
     pub method: ImageMethodFile,
@@ -142,6 +280,42 @@ pub struct AlbumArtConfigFile {
     pub max_size_px: Size,
     #[serde(default = "defaults::disabled_album_art_protos")]
     pub disabled_protocols: Vec<String>,
+    #[serde(default)]
+    pub output_path: Option<String>,
+    #[serde(default)]
+    pub source: AlbumArtSourceFile,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AlbumArtSourceFile {
+    AlbumArt,
+    ReadPicture,
+    AlbumArtThenReadPicture,
+    #[default]
+    ReadPictureThenAlbumArt,
+}
+
+impl From<AlbumArtSourceFile> for AlbumArtSource {
+    fn from(value: AlbumArtSourceFile) -> Self {
+        match value {
+            AlbumArtSourceFile::AlbumArt => AlbumArtSource::AlbumArt,
+            AlbumArtSourceFile::ReadPicture => AlbumArtSource::ReadPicture,
+            AlbumArtSourceFile::AlbumArtThenReadPicture => AlbumArtSource::AlbumArtThenReadPicture,
+            AlbumArtSourceFile::ReadPictureThenAlbumArt => AlbumArtSource::ReadPictureThenAlbumArt,
+        }
+    }
+}
+
+impl Default for AlbumArtConfigFile {
+    fn default() -> Self {
+        Self {
+            method: ImageMethodFile::default(),
+            max_size_px: Size::default(),
+            disabled_protocols: defaults::disabled_album_art_protos(),
+            output_path: None,
+            source: AlbumArtSourceFile::default(),
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -149,6 +323,8 @@ pub struct AlbumArtConfig {
     pub method: ImageMethod,
     pub max_size_px: Size,
     pub disabled_protocols: Vec<&'static str>,
+    pub output_path: Option<&'static str>,
+    pub source: AlbumArtSource,
 }
 
 impl Default for ConfigFile {
@@ -157,22 +333,44 @@ impl Default for ConfigFile {
             address: String::from("127.0.0.1:6600"),
             keybinds: KeyConfigFile::default(),
             volume_step: 5,
+            volume_step_fine: 1,
             scrolloff: 0,
             status_update_interval_ms: Some(1000),
+            elapsed_source: ElapsedSource::Poll,
+            key_sequence_timeout_ms: 500,
+            input_poll_interval_ms: 250,
+            idle_max_retries: Some(5),
+            log_timestamp_format: defaults::default_log_timestamp_format(),
             theme: None,
             cache_dir: None,
             lyrics_dir: None,
+            music_directory: None,
+            status_output_path: None,
             image_method: None,
             select_current_song_on_change: false,
+            follow_playing: false,
+            album_art_cache_size: defaults::default_album_art_cache_size(),
+            pause_on_error: false,
+            auto_clear_error: false,
+            add_position: AddPosition::End,
+            on_duplicate_add: OnDuplicateAdd::Allow,
+            delete_selection_behavior: DeleteSelectionBehavior::KeepIndex,
+            filter_behavior: FilterBehavior::Jump,
+            right_on_song: RightOnSong::Add,
+            retain_filter_on_back: false,
+            advance_after_add: false,
+            artist_split_separator: None,
+            browser_sort: BrowserSort::default(),
             album_art_max_size_px: Size::default(),
-            album_art: AlbumArtConfigFile {
-                disabled_protocols: defaults::disabled_album_art_protos(),
-                ..Default::default()
-            },
+            album_art: AlbumArtConfigFile::default(),
             on_song_change: None,
             search: SearchFile::default(),
             tabs: TabsFile::default(),
             enable_mouse: true,
+            compact: false,
+            tab_number_keys: true,
+            confirm_quit: false,
+            keybind_hints: false,
             wrap_navigation: false,
             password: None,
         }
@@ -222,8 +420,14 @@ impl ConfigFile {
             .unwrap_or_default()
             .try_into()?;
 
+        if chrono::format::StrftimeItems::new(&self.log_timestamp_format)
+            .any(|item| item == chrono::format::Item::Error)
+        {
+            anyhow::bail!("Invalid log_timestamp_format '{}'", self.log_timestamp_format);
+        }
+
         let size = self.album_art.max_size_px;
-        let (address, password) = MpdAddress::resolve(address_cli, password_cli, self.address, self.password);
+        let (address, password) = MpdAddress::resolve(address_cli, password_cli, self.address, self.password)?;
         let mut config = Config {
             theme,
             cache_dir: self
@@ -238,15 +442,47 @@ impl ConfigFile {
                 }
                 .leak() as &'static _
             }),
+            music_directory: self
+                .music_directory
+                .map(|v| tilde_expand(&v).into_owned().leak() as &'static _),
+            status_output_path: self
+                .status_output_path
+                .map(|v| tilde_expand(&v).into_owned().leak() as &'static _),
             address,
             password,
             volume_step: self.volume_step,
+            volume_step_fine: self.volume_step_fine,
             scrolloff: self.scrolloff,
             wrap_navigation: self.wrap_navigation,
             status_update_interval_ms: self.status_update_interval_ms.map(|v| v.max(100)),
+            elapsed_source: self.elapsed_source,
+            key_sequence_timeout_ms: self.key_sequence_timeout_ms,
+            input_poll_interval_ms: self.input_poll_interval_ms,
+            idle_max_retries: self.idle_max_retries,
+            log_timestamp_format: self.log_timestamp_format.leak(),
             enable_mouse: self.enable_mouse,
+            compact: self.compact,
+            tab_number_keys: self.tab_number_keys,
+            confirm_quit: self.confirm_quit,
+            keybind_hints: self.keybind_hints,
             keybinds: self.keybinds.into(),
             select_current_song_on_change: self.select_current_song_on_change,
+            follow_playing: self.follow_playing,
+            album_art_cache_size: self.album_art_cache_size,
+            pause_on_error: self.pause_on_error,
+            auto_clear_error: self.auto_clear_error,
+            add_position: self.add_position,
+            on_duplicate_add: self.on_duplicate_add,
+            delete_selection_behavior: self.delete_selection_behavior,
+            filter_behavior: self.filter_behavior,
+            right_on_song: self.right_on_song,
+            retain_filter_on_back: self.retain_filter_on_back,
+            advance_after_add: self.advance_after_add,
+            artist_split_separator: self
+                .artist_split_separator
+                .filter(|v| !v.is_empty())
+                .map(|v| v.leak() as &'static _),
+            browser_sort: self.browser_sort,
             search: self.search.into(),
             tabs: self.tabs.try_into()?,
             album_art: AlbumArtConfig {
@@ -261,6 +497,11 @@ impl ConfigFile {
                     .into_iter()
                     .map(|proto| proto.leak() as &'static _)
                     .collect(),
+                output_path: self
+                    .album_art
+                    .output_path
+                    .map(|v| tilde_expand(&v).into_owned().leak() as &'static _),
+                source: self.album_art.source.into(),
             },
             on_song_change: self.on_song_change.map(|arr| {
                 arr.into_iter()
@@ -21,7 +21,7 @@ mod style;
 pub use self::queue_table::{PercentOrLength, SongTableColumn};
 pub use style::{ConfigColor, StyleFile};
 
-use super::defaults;
+use super::{defaults, tabs::PaneType};
 
 const DEFAULT_ART: &[u8; 58599] = include_bytes!("../../../assets/default.jpg");
 
@@ -35,9 +35,13 @@ pub struct UiConfig {
     pub borders_style: Style,
     pub highlighted_item_style: Style,
     pub current_item_style: Style,
+    pub playing_item_style: Style,
+    pub played_item_style: Style,
     pub highlight_border_style: Style,
     pub column_widths: [u16; 3],
+    pub pane_column_widths: PaneColumnWidths,
     pub browser_song_format: SongFormat,
+    pub format_tag_separator: &'static str,
     pub symbols: SymbolsConfig,
     pub progress_bar: ProgressBarConfig,
     pub tab_bar: TabBar,
@@ -46,11 +50,12 @@ pub struct UiConfig {
     pub song_table_format: &'static [SongTableColumn],
     pub header: HeaderConfig,
     pub default_album_art: &'static [u8],
+    pub album_art_accent: bool,
 }
 
 impl std::fmt::Debug for UiConfig {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "UiConfig {{ draw_borders: {}, background_color: {:?}, header_background_color: {:?}, background_color_modal: {:?}, borders_style: {:?}, highlighted_item_style: {:?}, current_item_style: {:?}, highlight_border_style: {:?}, tab_bar: {:?}, column_widths: {:?}, symbols: {:?}, progress_bar: {:?}, scrollbar: {:?}, show_song_table_header: {}, song_table_format: {:?}, header: {:?}, default_album_art: [u8; {}] }}", self.draw_borders, self.background_color, self.header_background_color, self.modal_background_color, self.borders_style, self.highlighted_item_style, self.current_item_style, self.highlight_border_style, self.tab_bar, self.column_widths, self.symbols, self.progress_bar, self.scrollbar, self.show_song_table_header, self.song_table_format, self.header, self.default_album_art.len())
+        write!(f, "UiConfig {{ draw_borders: {}, background_color: {:?}, header_background_color: {:?}, background_color_modal: {:?}, borders_style: {:?}, highlighted_item_style: {:?}, current_item_style: {:?}, playing_item_style: {:?}, played_item_style: {:?}, highlight_border_style: {:?}, tab_bar: {:?}, column_widths: {:?}, symbols: {:?}, progress_bar: {:?}, scrollbar: {:?}, show_song_table_header: {}, song_table_format: {:?}, format_tag_separator: {:?}, header: {:?}, default_album_art: [u8; {}], album_art_accent: {} }}", self.draw_borders, self.background_color, self.header_background_color, self.modal_background_color, self.borders_style, self.highlighted_item_style, self.current_item_style, self.playing_item_style, self.played_item_style, self.highlight_border_style, self.tab_bar, self.column_widths, self.symbols, self.progress_bar, self.scrollbar, self.show_song_table_header, self.song_table_format, self.format_tag_separator, self.header, self.default_album_art.len(), self.album_art_accent)
     }
 }
 
@@ -65,7 +70,11 @@ pub struct UiConfigFile {
     #[serde(default = "defaults::default_column_widths")]
     pub(super) browser_column_widths: Vec<u16>,
     #[serde(default)]
+    pub(super) pane_column_widths: PaneColumnWidthsFile,
+    #[serde(default)]
     pub(super) browser_song_format: SongFormatFile,
+    #[serde(default = "defaults::default_tag_separator")]
+    pub(super) format_tag_separator: String,
     pub(super) background_color: Option<String>,
     pub(super) text_color: Option<String>,
     pub(super) header_background_color: Option<String>,
@@ -73,17 +82,22 @@ pub struct UiConfigFile {
     pub(super) borders_style: Option<StyleFile>,
     pub(super) highlighted_item_style: Option<StyleFile>,
     pub(super) current_item_style: Option<StyleFile>,
+    pub(super) playing_item_style: Option<StyleFile>,
+    pub(super) played_item_style: Option<StyleFile>,
     pub(super) highlight_border_style: Option<StyleFile>,
     pub(super) show_song_table_header: bool,
     pub(super) song_table_format: QueueTableColumnsFile,
     pub(super) header: HeaderConfigFile,
     pub(super) default_album_art_path: Option<String>,
+    #[serde(default)]
+    pub(super) album_art_accent: bool,
 }
 
 impl Default for UiConfigFile {
     fn default() -> Self {
         Self {
             default_album_art_path: None,
+            album_art_accent: false,
             draw_borders: true,
             background_color: None,
             text_color: None,
@@ -106,6 +120,12 @@ impl Default for UiConfigFile {
                 bg: Some("blue".to_string()),
                 modifiers: Some(Modifiers::Bold),
             }),
+            playing_item_style: None,
+            played_item_style: Some(StyleFile {
+                fg: Some("dark_gray".to_string()),
+                bg: None,
+                modifiers: None,
+            }),
             highlight_border_style: Some(StyleFile {
                 fg: Some("blue".to_string()),
                 bg: None,
@@ -125,6 +145,7 @@ impl Default for UiConfigFile {
                 }),
             },
             browser_column_widths: vec![20, 38, 42],
+            pane_column_widths: PaneColumnWidthsFile::default(),
             progress_bar: ProgressBarConfigFile::default(),
             scrollbar: ScrollbarConfigFile::default(),
             symbols: SymbolsFile {
@@ -135,10 +156,78 @@ impl Default for UiConfigFile {
             },
             song_table_format: QueueTableColumnsFile::default(),
             browser_song_format: SongFormatFile::default(),
+            format_tag_separator: " | ".to_owned(),
         }
     }
 }
 
+impl UiConfig {
+    /// Column widths of the given browser pane, falling back to `browser_column_widths`
+    pub fn browser_column_widths(&self, pane: PaneType) -> [u16; 3] {
+        let widths = match pane {
+            PaneType::Directories => self.pane_column_widths.directories,
+            PaneType::Artists => self.pane_column_widths.artists,
+            PaneType::AlbumArtists => self.pane_column_widths.album_artists,
+            PaneType::Albums => self.pane_column_widths.albums,
+            PaneType::Playlists => self.pane_column_widths.playlists,
+            PaneType::Search => self.pane_column_widths.search,
+            _ => None,
+        };
+        widths.unwrap_or(self.column_widths)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PaneColumnWidthsFile {
+    #[serde(default)]
+    pub(super) directories: Option<Vec<u16>>,
+    #[serde(default)]
+    pub(super) artists: Option<Vec<u16>>,
+    #[serde(default)]
+    pub(super) album_artists: Option<Vec<u16>>,
+    #[serde(default)]
+    pub(super) albums: Option<Vec<u16>>,
+    #[serde(default)]
+    pub(super) playlists: Option<Vec<u16>>,
+    #[serde(default)]
+    pub(super) search: Option<Vec<u16>>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PaneColumnWidths {
+    pub directories: Option<[u16; 3]>,
+    pub artists: Option<[u16; 3]>,
+    pub album_artists: Option<[u16; 3]>,
+    pub albums: Option<[u16; 3]>,
+    pub playlists: Option<[u16; 3]>,
+    pub search: Option<[u16; 3]>,
+}
+
+impl TryFrom<PaneColumnWidthsFile> for PaneColumnWidths {
+    type Error = anyhow::Error;
+
+    fn try_from(value: PaneColumnWidthsFile) -> Result<Self, Self::Error> {
+        let convert = |pane: &str, widths: Option<Vec<u16>>| -> Result<Option<[u16; 3]>> {
+            widths
+                .map(|widths| {
+                    <[u16; 3]>::try_from(widths).map_err(|widths| {
+                        anyhow::anyhow!("Column widths of {pane} must have exactly three values, got {widths:?}")
+                    })
+                })
+                .transpose()
+        };
+
+        Ok(Self {
+            directories: convert("directories", value.directories)?,
+            artists: convert("artists", value.artists)?,
+            album_artists: convert("album_artists", value.album_artists)?,
+            albums: convert("albums", value.albums)?,
+            playlists: convert("playlists", value.playlists)?,
+            search: convert("search", value.search)?,
+        })
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct TabBarFile {
     pub(super) enabled: Option<bool>,
@@ -188,6 +277,7 @@ impl TryFrom<UiConfigFile> for UiConfig {
         let bg_color = StringColor(value.background_color).to_color()?;
         let header_bg_color = StringColor(value.header_background_color).to_color()?.or(bg_color);
         let fallback_border_fg = Color::White;
+        let highlighted_item_style = value.highlighted_item_style.to_config_or(Some(Color::Blue), None)?;
 
         Ok(Self {
             background_color: bg_color,
@@ -196,7 +286,7 @@ impl TryFrom<UiConfigFile> for UiConfig {
             text_color: StringColor(value.text_color).to_color()?,
             header_background_color: header_bg_color,
             borders_style: value.borders_style.to_config_or(Some(fallback_border_fg), None)?,
-            highlighted_item_style: value.highlighted_item_style.to_config_or(Some(Color::Blue), None)?,
+            highlighted_item_style,
             highlight_border_style: value.highlight_border_style.to_config_or(Some(Color::Blue), None)?,
             symbols: value.symbols.into(),
             show_song_table_header: value.show_song_table_header,
@@ -211,6 +301,7 @@ impl TryFrom<UiConfigFile> for UiConfig {
                 value.browser_column_widths[1],
                 value.browser_column_widths[2],
             ],
+            pane_column_widths: value.pane_column_widths.try_into()?,
             tab_bar: TabBar {
                 enabled: value.tab_bar.enabled.unwrap_or(true),
                 active_style: value
@@ -222,12 +313,53 @@ impl TryFrom<UiConfigFile> for UiConfig {
             current_item_style: value
                 .current_item_style
                 .to_config_or(Some(Color::Black), Some(Color::Blue))?,
+            playing_item_style: value
+                .playing_item_style
+                .map_or(Ok(highlighted_item_style), |style| style.to_config_or(None, None))?,
+            played_item_style: value.played_item_style.to_config_or(Some(Color::DarkGray), None)?,
             default_album_art: value
                 .default_album_art_path
                 .map_or(Ok(DEFAULT_ART as &'static [u8]), |path| -> Result<_> {
                     Ok(std::fs::read(path)?.leak())
                 })?,
             browser_song_format: TryInto::<SongFormat>::try_into(value.browser_song_format)?,
+            format_tag_separator: value.format_tag_separator.leak(),
+            album_art_accent: value.album_art_accent,
         })
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::config::tabs::PaneType;
+
+    use super::{PaneColumnWidths, PaneColumnWidthsFile, UiConfig};
+
+    #[test]
+    fn pane_column_widths_fall_back_to_browser_column_widths() {
+        let config = UiConfig {
+            column_widths: [20, 38, 42],
+            pane_column_widths: PaneColumnWidthsFile {
+                albums: Some(vec![33, 33, 34]),
+                ..Default::default()
+            }
+            .try_into()
+            .unwrap(),
+            ..Default::default()
+        };
+
+        assert_eq!(config.browser_column_widths(PaneType::Albums), [33, 33, 34]);
+        assert_eq!(config.browser_column_widths(PaneType::Artists), [20, 38, 42]);
+    }
+
+    #[test]
+    fn pane_column_widths_require_three_values() {
+        let input = PaneColumnWidthsFile {
+            search: Some(vec![50, 50]),
+            ..Default::default()
+        };
+
+        assert!(PaneColumnWidths::try_from(input).is_err());
+    }
+}
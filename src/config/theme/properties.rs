@@ -18,6 +18,7 @@ pub enum SongPropertyFile {
     Album,
     Duration,
     Track,
+    Rating,
     Other(String),
 }
 
@@ -30,6 +31,7 @@ pub enum SongProperty {
     Album,
     Duration,
     Track,
+    Rating,
     Other(&'static str),
 }
 
@@ -45,6 +47,7 @@ pub enum StatusPropertyFile {
     Duration,
     Crossfade,
     Bitrate,
+    ReplayGain,
 }
 
 #[derive(Debug, Clone, Display)]
@@ -59,6 +62,7 @@ pub enum StatusProperty {
     Duration,
     Crossfade,
     Bitrate,
+    ReplayGain,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -141,6 +145,7 @@ impl TryFrom<SongPropertyFile> for SongProperty {
             SongPropertyFile::Album => SongProperty::Album,
             SongPropertyFile::Duration => SongProperty::Duration,
             SongPropertyFile::Track => SongProperty::Track,
+            SongPropertyFile::Rating => SongProperty::Rating,
             SongPropertyFile::Other(name) => SongProperty::Other(name.leak()),
         })
     }
@@ -171,6 +176,7 @@ impl TryFrom<StatusPropertyFile> for StatusProperty {
             StatusPropertyFile::Single => StatusProperty::Single,
             StatusPropertyFile::Bitrate => StatusProperty::Bitrate,
             StatusPropertyFile::Crossfade => StatusProperty::Crossfade,
+            StatusPropertyFile::ReplayGain => StatusProperty::ReplayGain,
         })
     }
 }
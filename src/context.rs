@@ -1,19 +1,26 @@
-use std::{cell::Cell, collections::HashSet, path::PathBuf, sync::mpsc::Sender};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    sync::{mpsc::Sender, Arc},
+};
 
 use crate::{
-    config::{Config, ImageMethod, Leak},
+    config::{AddPosition, Config, ImageMethod, Leak},
     mpd::{
         client::Client,
-        commands::{Song, State, Status},
-        mpd_client::MpdClient,
+        commands::{sticker::RATING_STICKER, volume::MuteState, Ratings, Song, State, Status},
+        errors::MpdError,
+        mpd_client::{MpdClient, QueueMoveTarget},
     },
     shared::{
-        lrc::{Lrc, LrcIndex},
+        album_art_cache::AlbumArtCache,
+        lrc::{find_lrc_path, Lrc, LrcIndex},
         macros::status_warn,
     },
     AppEvent, WorkRequest,
 };
-use anyhow::{bail, Result};
+use anyhow::Result;
+use ratatui::style::{Color, Style};
 
 pub struct AppContext {
     pub config: &'static Config,
@@ -24,6 +31,30 @@ pub struct AppContext {
     pub work_sender: Sender<WorkRequest>,
     pub needs_render: Cell<bool>,
     pub lrc_index: LrcIndex,
+    pub mute: MuteState,
+    /// Average color of the current song's album art, only computed when enabled in the theme
+    pub album_art_accent: Option<Color>,
+    /// Song uris of the stored playlists by their name. Fetched when first needed and dropped
+    /// whenever the stored playlists change.
+    pub stored_playlists: Option<HashMap<String, Vec<String>>>,
+    /// Id of the song after which playback is stopped, set by the `StopAfterCurrent` action
+    pub stop_after_current: Option<u32>,
+    /// Song ratings read from the rating sticker, empty when MPD has no sticker database. Shared with the browser
+    /// widgets which render them.
+    pub ratings: Arc<Ratings>,
+    pub connection: ConnectionState,
+    /// Recently fetched album art, shared by everything that shows or exports it
+    pub album_art_cache: RefCell<AlbumArtCache>,
+}
+
+/// State of the connection to MPD, shown in the header while not connected
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    #[default]
+    Connected,
+    /// The connection was lost or a reconnect was requested and new connections are being established
+    Reconnecting,
+    Disconnected,
 }
 
 impl AppContext {
@@ -33,9 +64,12 @@ impl AppContext {
         app_event_sender: Sender<AppEvent>,
         work_sender: Sender<WorkRequest>,
     ) -> Result<Self> {
-        let status = client.get_status()?;
+        let mut status = client.get_status()?;
         let queue = client.playlist_info()?.unwrap_or_default();
         let supported_commands: HashSet<String> = client.commands()?.0.into_iter().collect();
+        if supported_commands.contains("replay_gain_status") {
+            status.replay_gain_mode = Some(client.replay_gain_status()?);
+        }
 
         log::info!(supported_commands:? = supported_commands; "Supported commands by server");
 
@@ -45,9 +79,17 @@ impl AppContext {
         }
 
         log::info!(config:? = config; "Resolved config");
+        let ratings = fetch_ratings(client, &supported_commands);
 
         Ok(Self {
             lrc_index: LrcIndex::default(),
+            mute: MuteState::default(),
+            album_art_accent: None,
+            stored_playlists: None,
+            stop_after_current: None,
+            ratings: Arc::new(ratings),
+            connection: ConnectionState::default(),
+            album_art_cache: RefCell::new(AlbumArtCache::new(config.album_art_cache_size)),
             config: config.leak(),
             status,
             queue,
@@ -58,6 +100,46 @@ impl AppContext {
         })
     }
 
+    /// Reloads the state kept from MPD, ie. after reconnecting
+    pub fn reload(&mut self, client: &mut impl MpdClient) -> Result<()> {
+        self.status = client.get_status()?;
+        self.queue = client.playlist_info()?.unwrap_or_default();
+        self.supported_commands = client.commands()?.0.into_iter().collect();
+        if self.supported_commands.contains("replay_gain_status") {
+            self.status.replay_gain_mode = Some(client.replay_gain_status()?);
+        }
+        self.stored_playlists = None;
+        self.stop_after_current = None;
+        self.refresh_ratings(client);
+        Ok(())
+    }
+
+    pub fn refresh_ratings(&mut self, client: &mut impl MpdClient) {
+        self.ratings = Arc::new(fetch_ratings(client, &self.supported_commands));
+    }
+
+    /// Replaces the status with a newly fetched one. The replay gain mode is not part of MPD's status response so the
+    /// last known one is kept.
+    pub fn set_status(&mut self, status: Status) {
+        let replay_gain_mode = self.status.replay_gain_mode;
+        self.status = Status {
+            replay_gain_mode,
+            ..status
+        };
+    }
+
+    /// Album art of the song file, only asks MPD when it is not cached
+    pub fn find_album_art(&self, client: &mut impl MpdClient, file: &str) -> Result<Option<Vec<u8>>, MpdError> {
+        if let Some(data) = self.album_art_cache.borrow_mut().get(file) {
+            log::debug!(file; "Reusing cached album art");
+            return Ok(data);
+        }
+
+        let data = client.find_album_art(file, self.config.album_art.source)?;
+        self.album_art_cache.borrow_mut().insert(file.to_owned(), data.clone());
+        Ok(data)
+    }
+
     pub fn render(&self) -> Result<(), std::sync::mpsc::SendError<AppEvent>> {
         if self.needs_render.get() {
             return Ok(());
@@ -67,6 +149,31 @@ impl AppContext {
         self.app_event_sender.send(AppEvent::RequestRender(false))
     }
 
+    /// Style of the item under cursor with the album art accent applied
+    pub fn current_item_style(&self) -> Style {
+        self.with_accent(self.config.theme.current_item_style)
+    }
+
+    /// Style of the active tab with the album art accent applied
+    pub fn active_tab_style(&self) -> Style {
+        self.with_accent(self.config.theme.tab_bar.active_style)
+    }
+
+    fn with_accent(&self, style: Style) -> Style {
+        let Some(accent @ Color::Rgb(r, g, b)) = self.album_art_accent else {
+            return style;
+        };
+
+        // Keep the text readable on both light and dark covers
+        let luminance = 299 * u32::from(r) + 587 * u32::from(g) + 114 * u32::from(b);
+        let fg = if luminance > 128_000 {
+            Color::Black
+        } else {
+            Color::White
+        };
+        style.bg(accent).fg(fg)
+    }
+
     pub fn finish_frame(&self) {
         self.needs_render.replace(false);
     }
@@ -81,6 +188,18 @@ impl AppContext {
             .and_then(|id| self.queue.iter().enumerate().find(|(_, song)| song.id == id))
     }
 
+    /// Position in the queue where added songs should be inserted according to
+    /// the `add_position` config. `None` means the end of the queue.
+    pub fn add_position(&self) -> Option<QueueMoveTarget> {
+        match self.config.add_position {
+            AddPosition::Start => Some(QueueMoveTarget::Absolute(0)),
+            AddPosition::End => None,
+            // Relative positions are rejected by MPD when there is no current song
+            AddPosition::AfterCurrent if self.status.song.is_some() => Some(QueueMoveTarget::RelativeAdd(0)),
+            AddPosition::AfterCurrent => None,
+        }
+    }
+
     /// Gets the owned version of current song by either cloning it from queue
     /// or by querying MPD if not found
     pub fn get_current_song(&self, client: &mut impl MpdClient) -> Result<Option<Song>> {
@@ -96,26 +215,13 @@ impl AppContext {
             return Ok(None);
         };
 
-        let Some(lyrics_dir) = self.config.lyrics_dir else {
-            return Ok(None);
-        };
-
-        let mut path: PathBuf = PathBuf::from(lyrics_dir);
-        path.push(&song.file);
-        let Some(stem) = path.file_stem().map(|stem| format!("{}.lrc", stem.to_string_lossy())) else {
-            bail!("No file stem for lyrics path: {path:?}");
-        };
-
-        path.pop();
-        path.push(stem);
-        log::debug!(path:?; "getting lrc at path");
-        match std::fs::read_to_string(&path) {
-            Ok(lrc) => return Ok(Some(lrc.parse()?)),
-            Err(err) if matches!(err.kind(), std::io::ErrorKind::NotFound) => {
-                log::trace!(path:?; "Lyrics not found");
-            }
-            Err(err) => {
-                log::error!(err:?; "Encountered error when searching for sidecar lyrics");
+        if let Some(path) = find_lrc_path(song, self.config.lyrics_dir, self.config.music_directory) {
+            log::debug!(path:?; "getting lrc at path");
+            match std::fs::read_to_string(&path) {
+                Ok(lrc) => return Ok(Some(lrc.parse()?)),
+                Err(err) => {
+                    log::error!(err:?; "Encountered error when reading sidecar lyrics");
+                }
             }
         }
 
@@ -126,3 +232,52 @@ impl AppContext {
         Ok(None)
     }
 }
+
+/// Reads the ratings of all songs. Ratings are not essential so failing to read them only gets logged.
+fn fetch_ratings(client: &mut impl MpdClient, supported_commands: &HashSet<String>) -> Ratings {
+    if !supported_commands.contains("sticker") {
+        return Ratings::default();
+    }
+
+    match client.sticker_find("", RATING_STICKER) {
+        Ok(found) => found.into_iter().collect(),
+        Err(err) => {
+            log::warn!(error:? = err; "Failed to read song ratings");
+            Ratings::default()
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::HashMap;
+
+    use rstest::rstest;
+
+    use super::AppContext;
+    use crate::{
+        mpd::{commands::State, mpd_client::MpdClient},
+        tests::fixtures::{
+            app_context,
+            mpd_client::{client, TestMpdClient},
+        },
+    };
+
+    #[rstest]
+    fn reload_replaces_the_state_kept_from_mpd(mut app_context: AppContext, mut client: TestMpdClient) {
+        client.queue = vec![3, 4];
+        client.status.state = State::Play;
+        client.status.songid = Some(client.songs[3].id);
+        app_context.stop_after_current = Some(1);
+        app_context.stored_playlists = Some(HashMap::new());
+
+        app_context.reload(&mut client).unwrap();
+
+        assert_eq!(app_context.status.state, State::Play);
+        assert_eq!(app_context.queue, client.playlist_info().unwrap().unwrap());
+        assert!(app_context.supported_commands.contains("playlistinfo"));
+        assert_eq!(app_context.stop_after_current, None);
+        assert_eq!(app_context.stored_playlists, None);
+    }
+}
@@ -18,7 +18,7 @@ use std::{
     io::{Read, Write},
     ops::Sub,
     path::PathBuf,
-    sync::mpsc::TryRecvError,
+    sync::mpsc::RecvTimeoutError,
     time::Duration,
 };
 
@@ -26,35 +26,42 @@ use anyhow::{bail, Context, Result};
 use clap::Parser;
 use cli::run_external;
 use config::{
+    address::MpdPassword,
     cli::{Args, Command},
-    ConfigFile,
+    ConfigFile, ElapsedSource, ImageMethod, MpdAddress,
 };
 use crossterm::event::{Event, KeyEvent};
 use itertools::Itertools;
-use log::{error, info, trace, warn};
+use log::{debug, error, info, trace, warn};
 use mpd::{
-    client::Client,
+    client::{reconnect_with_backoff, Client, ReconnectMode, ShutdownHandle},
     commands::{idle::IdleEvent, State},
+    errors::MpdError,
 };
-use ratatui::{prelude::Backend, Terminal};
+use ratatui::{prelude::Backend, style::Color, Terminal};
 use rustix::path::Arg;
 use shared::{
-    dependencies::{DEPENDENCIES, FFMPEG, FFPROBE, PYTHON3, PYTHON3MUTAGEN, UEBERZUGPP, YTDLP},
-    lrc::LrcIndex,
-};
-use shared::{
+    album_art_output::AlbumArtOutput,
     env::ENV,
     ext::{duration::DurationExt, error::ErrorExt},
-    logging,
+    key_event::{self, PendingKeys},
+    logging::{self, LogEntry},
     macros::{status_error, status_info, try_cont, try_skip},
     mouse_event::{MouseEvent, MouseEventTracker},
+    status_output::StatusOutput,
     tmux,
     ytdlp::YtDlp,
 };
+use shared::{
+    dependencies::{DEPENDENCIES, FFMPEG, FFPROBE, PYTHON3, PYTHON3MUTAGEN, UEBERZUGPP, YTDLP},
+    image::average_color,
+    lrc::LrcIndex,
+};
 use ui::{Level, UiAppEvent, UiEvent};
 
 use crate::{
     config::Config,
+    context::ConnectionState,
     mpd::mpd_client::MpdClient,
     shared::macros::{status_warn, try_ret},
     ui::Ui,
@@ -76,12 +83,14 @@ mod ui;
 pub enum WorkRequest {
     DownloadYoutube { url: String },
     IndexLyrics { lyrics_dir: &'static str },
+    AlbumArtAccent { song_id: u32, data: Vec<u8> },
 }
 
 #[derive(Debug)]
 pub enum WorkDone {
     YoutubeDowloaded { file_path: String },
     LyricsIndexed { index: LrcIndex },
+    AlbumArtAccent { song_id: u32, color: Color },
 }
 
 #[derive(Debug)]
@@ -89,13 +98,17 @@ pub enum AppEvent {
     UserKeyInput(KeyEvent),
     UserMouseInput(MouseEvent),
     Status(String, Level),
-    Log(Vec<u8>),
+    Log(LogEntry),
     IdleEvent(IdleEvent),
     RequestStatusUpdate,
     RequestRender(bool),
     Resized { columns: u16, rows: u16 },
     WorkDone(Result<WorkDone>),
     UiAppEvent(UiAppEvent),
+    Reconnect,
+    Reconnected(Box<Result<(Client<'static>, Client<'static>), MpdError>>),
+    Connection(ConnectionState),
+    SetStatusUpdateInterval(Option<Duration>),
 }
 
 fn main() -> Result<()> {
@@ -199,16 +212,16 @@ fn main() -> Result<()> {
                     true,
                 )?,
             }));
-            let mut client = Client::init(config.address, config.password, "", true)?;
+            let mut client = Client::init(config.address, config.password, "", ReconnectMode::Backoff)?;
             cmd.execute(&mut client, config, |work_request, c| {
                 match handle_work_request(work_request, config) {
-                    Ok(WorkDone::YoutubeDowloaded { file_path }) => match c.add(&file_path) {
+                    Ok(WorkDone::YoutubeDowloaded { file_path }) => match c.add(&file_path, None) {
                         Ok(()) => {}
                         Err(err) => {
                             log::error!(path = file_path.as_str(), err = err.to_string().as_str(); "Failed to add already downloaded youtube video to queue");
                         }
                     },
-                    Ok(WorkDone::LyricsIndexed { .. }) => {}, // lrc indexing does not make sense in cli mode
+                    Ok(WorkDone::LyricsIndexed { .. } | WorkDone::AlbumArtAccent { .. }) => {}, // not used in cli mode
                     Err(err) => {
                         log::error!(err = err.to_string().as_str(); "Failed to handle work request");
                     }
@@ -223,7 +236,7 @@ fn main() -> Result<()> {
 
             let (worker_tx, worker_rx) = std::sync::mpsc::channel::<WorkRequest>();
 
-            let config = match ConfigFile::read(&args.config) {
+            let mut config = match ConfigFile::read(&args.config) {
                 Ok(val) => val.into_config(
                     Some(&args.config),
                     std::mem::take(&mut args.address),
@@ -241,6 +254,14 @@ fn main() -> Result<()> {
                 }
             };
 
+            if args.compact {
+                config.compact = true;
+            }
+            if config.compact {
+                // There is no pane to show the album art in
+                config.album_art.method = ImageMethod::None;
+            }
+
             if let Some(lyrics_dir) = config.lyrics_dir {
                 try_ret!(
                     worker_tx.send(WorkRequest::IndexLyrics { lyrics_dir }),
@@ -250,7 +271,7 @@ fn main() -> Result<()> {
             try_ret!(tx.send(AppEvent::RequestRender(false)), "Failed to render first frame");
 
             let mut client = try_ret!(
-                Client::init(config.address, config.password, "command", true),
+                Client::init(config.address, config.password, "command", ReconnectMode::Once),
                 "Failed to connect to MPD"
             );
 
@@ -276,21 +297,28 @@ fn main() -> Result<()> {
 
             std::thread::Builder::new()
                 .name("input poll".to_owned())
-                .spawn(|| input_poll_task(tx_clone))?;
+                .spawn(|| input_poll_task(tx_clone, context.config.input_poll_interval_ms))?;
 
             let mut idle_client = try_ret!(
-                Client::init(context.config.address, context.config.password, "idle", true),
+                Client::init(
+                    context.config.address,
+                    context.config.password,
+                    "idle",
+                    ReconnectMode::Backoff
+                ),
                 "Failed to connect to MPD with idle client"
             );
+            let idle_max_retries = context.config.idle_max_retries;
+            let idle_shutdown = idle_client.shutdown_handle()?;
 
             let main_task = std::thread::Builder::new().name("main task".to_owned()).spawn(|| {
-                main_task(context, rx, client, render_loop, terminal);
+                main_task(context, rx, client, idle_shutdown, render_loop, terminal);
             })?;
 
             idle_client.set_read_timeout(None)?;
             std::thread::Builder::new()
                 .name("idle task".to_owned())
-                .spawn(|| idle_task(idle_client, tx))?;
+                .spawn(move || idle_task(idle_client, tx, idle_max_retries))?;
 
             let original_hook = std::panic::take_hook();
             std::panic::set_hook(Box::new(move |panic| {
@@ -336,6 +364,10 @@ fn handle_work_request(request: WorkRequest, config: &Config) -> Result<WorkDone
             log::info!(found_count = index.len(), elapsed:? = start.elapsed(); "Indexed lrc files");
             Ok(WorkDone::LyricsIndexed { index })
         }
+        WorkRequest::AlbumArtAccent { song_id, data } => Ok(WorkDone::AlbumArtAccent {
+            song_id,
+            color: average_color(&data)?,
+        }),
     }
 }
 
@@ -366,7 +398,8 @@ fn worker_task(
 fn main_task<B: Backend + std::io::Write>(
     mut context: context::AppContext,
     event_receiver: std::sync::mpsc::Receiver<AppEvent>,
-    mut client: Client<'_>,
+    mut client: Client<'static>,
+    mut idle_shutdown: ShutdownHandle,
     mut render_loop: RenderLoop,
     mut terminal: Terminal<B>,
 ) {
@@ -378,41 +411,65 @@ fn main_task<B: Backend + std::io::Write>(
     let min_frame_duration = Duration::from_secs_f64(1f64 / max_fps);
     let mut last_render = std::time::Instant::now().sub(Duration::from_secs(10));
     let mut additional_evs = HashSet::new();
+    let mut last_status_update = std::time::Instant::now();
+    let mut reconnecting = false;
+    let mut pending_keys = PendingKeys::default();
+    let mut status_output = context.config.status_output_path.map(StatusOutput::new);
+    let mut album_art_output = context.config.album_art.output_path.map(AlbumArtOutput::new);
     ui.before_show(&mut context, &mut client)
         .expect("Initial render init to succeed");
+    handle_album_art(&mut context, &mut client, album_art_output.as_mut());
 
     loop {
         let now = std::time::Instant::now();
+        let key_sequence_timeout = Duration::from_millis(context.config.key_sequence_timeout_ms);
 
-        let event = if render_wanted {
-            match event_receiver.recv_timeout(
-                min_frame_duration
-                    .checked_sub(now - last_render)
-                    .unwrap_or(Duration::ZERO),
-            ) {
+        // Wake up for the next frame and for the keys of an unfinished keybind sequence to expire
+        let frame_wait = render_wanted.then(|| {
+            min_frame_duration
+                .checked_sub(now - last_render)
+                .unwrap_or(Duration::ZERO)
+        });
+        let event = match frame_wait
+            .into_iter()
+            .chain(pending_keys.remaining(now, key_sequence_timeout))
+            .min()
+        {
+            Some(wait) => match event_receiver.recv_timeout(wait) {
                 Ok(v) => Some(v),
                 Err(std::sync::mpsc::RecvTimeoutError::Timeout) => None,
                 Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => None,
-            }
-        } else {
-            event_receiver.recv().ok()
+            },
+            None => event_receiver.recv().ok(),
         };
 
+        let expired_keys = pending_keys.expire(
+            std::time::Instant::now(),
+            key_sequence_timeout,
+            &context.config.keybinds,
+        );
+        if let ui::KeyHandleResult::Quit =
+            handle_keys(expired_keys, &mut ui, &mut context, &mut client, &mut render_wanted)
+        {
+            break;
+        }
+
         if let Some(event) = event {
             match event {
-                AppEvent::UserKeyInput(key) => match ui.handle_key(&mut key.into(), &mut context, &mut client) {
-                    Ok(ui::KeyHandleResult::None) => continue,
-                    Ok(ui::KeyHandleResult::Quit) => {
-                        if let Err(err) = ui.on_event(UiEvent::Exit, &mut context, &mut client) {
-                            error!(error:? = err, event:?; "UI failed to handle quit event");
-                        }
+                AppEvent::UserKeyInput(key) => {
+                    let keys = if ui.accepts_key_sequences() {
+                        pending_keys.push(key, std::time::Instant::now(), &context.config.keybinds)
+                    } else {
+                        let mut keys = pending_keys.flush(&context.config.keybinds);
+                        keys.push(key.into());
+                        keys
+                    };
+                    if let ui::KeyHandleResult::Quit =
+                        handle_keys(keys, &mut ui, &mut context, &mut client, &mut render_wanted)
+                    {
                         break;
                     }
-                    Err(err) => {
-                        status_error!(err:?; "Error: {}", err.to_status());
-                        render_wanted = true;
-                    }
-                },
+                }
                 AppEvent::UserMouseInput(ev) => match ui.handle_mouse_event(ev, &mut client, &mut context) {
                     Ok(()) => {}
                     Err(err) => {
@@ -430,7 +487,17 @@ fn main_task<B: Backend + std::io::Write>(
                     }
                 }
                 AppEvent::IdleEvent(event) => {
-                    match handle_idle_event(event, &mut context, &mut client, &mut render_loop, &mut additional_evs) {
+                    if matches!(event, IdleEvent::Player | IdleEvent::Options | IdleEvent::Mixer) {
+                        last_status_update = std::time::Instant::now();
+                    }
+                    match handle_idle_event(
+                        event,
+                        &mut context,
+                        &mut client,
+                        &mut render_loop,
+                        &mut additional_evs,
+                        album_art_output.as_mut(),
+                    ) {
                         Ok(()) => {
                             for ev in additional_evs.drain() {
                                 if let Err(err) = ui.on_event(ev, &mut context, &mut client) {
@@ -442,15 +509,34 @@ fn main_task<B: Backend + std::io::Write>(
                             status_error!(error:? = err, event:?; "Failed handle idle event, event: '{:?}', error: '{}'", event, err.to_status());
                         }
                     }
+                    if let Some(output) = status_output.as_mut() {
+                        if matches!(event, IdleEvent::Player | IdleEvent::Options) {
+                            output.write(&context);
+                        }
+                    }
                     render_wanted = true;
                 }
                 AppEvent::RequestStatusUpdate => {
-                    match client.get_status() {
-                        Ok(status) => context.status = status,
-                        Err(err) => {
-                            error!(err:?; "Unable to update status requested by render loop");
+                    match context.config.elapsed_source {
+                        ElapsedSource::Poll => match client.get_status() {
+                            Ok(status) => context.set_status(status),
+                            Err(err) => {
+                                error!(err:?; "Unable to update status requested by render loop");
+                            }
+                        },
+                        ElapsedSource::Interpolate => {
+                            let now = std::time::Instant::now();
+                            context.status.elapsed += now - last_status_update;
+                            // Streams have no known duration
+                            if context.status.duration != Duration::ZERO {
+                                context.status.elapsed = context.status.elapsed.min(context.status.duration);
+                            }
+                            last_status_update = now;
                         }
                     };
+                    if let Some(output) = status_output.as_mut() {
+                        output.write(&context);
+                    }
                     render_wanted = true;
                 }
                 AppEvent::RequestRender(wanted) => {
@@ -459,7 +545,7 @@ fn main_task<B: Backend + std::io::Write>(
                 }
                 AppEvent::WorkDone(Ok(result)) => match result {
                     WorkDone::YoutubeDowloaded { file_path } => {
-                        match client.add(&file_path) {
+                        match client.add(&file_path, None) {
                             Ok(()) => {
                                 status_info!("File '{file_path}' added to the queue");
                             }
@@ -474,6 +560,16 @@ fn main_task<B: Backend + std::io::Write>(
                             error!(error:? = err; "UI failed to resize event");
                         }
                     }
+                    WorkDone::AlbumArtAccent { song_id, color } => {
+                        // The song might have changed while the color was being computed
+                        if context
+                            .find_current_song_in_queue()
+                            .is_some_and(|(_, song)| song.id == song_id)
+                        {
+                            context.album_art_accent = Some(color);
+                            render_wanted = true;
+                        }
+                    }
                 },
                 AppEvent::WorkDone(Err(err)) => {
                     status_error!("{}", err);
@@ -485,8 +581,77 @@ fn main_task<B: Backend + std::io::Write>(
                     full_rerender_wanted = true;
                     render_wanted = true;
                 }
+                AppEvent::Connection(state) => {
+                    context.connection = state;
+                    render_wanted = true;
+                }
+                AppEvent::Reconnect => {
+                    if reconnecting {
+                        status_info!("Already reconnecting to MPD");
+                    } else {
+                        let (address, password) = (context.config.address, context.config.password);
+                        let sender = context.app_event_sender.clone();
+                        match std::thread::Builder::new()
+                            .name("reconnect task".to_owned())
+                            .spawn(move || reconnect_task(address, password, &sender, std::thread::sleep))
+                        {
+                            Ok(_) => {
+                                reconnecting = true;
+                                context.connection = ConnectionState::Reconnecting;
+                            }
+                            Err(err) => status_error!(err:?; "Failed to start reconnecting to MPD: {}", err),
+                        }
+                    }
+                    render_wanted = true;
+                }
+                AppEvent::Reconnected(result) => {
+                    reconnecting = false;
+                    match (*result)
+                        .map_err(anyhow::Error::from)
+                        .and_then(|(new_client, idle_client)| {
+                            swap_clients(&mut context, &mut idle_shutdown, new_client, idle_client)
+                        }) {
+                        Ok(new_client) => {
+                            client = new_client;
+                            context.connection = ConnectionState::Connected;
+                            match context.status.state {
+                                State::Play => try_skip!(render_loop.start(), "Failed to start render loop"),
+                                State::Pause | State::Stop => {
+                                    try_skip!(render_loop.stop(), "Failed to stop render loop");
+                                }
+                            }
+                            for ev in [UiEvent::Database, UiEvent::StoredPlaylist, UiEvent::Player] {
+                                if let Err(err) = ui.on_event(ev, &mut context, &mut client) {
+                                    error!(error:? = err; "UI failed to handle event after reconnecting");
+                                }
+                            }
+                            handle_album_art(&mut context, &mut client, album_art_output.as_mut());
+                            if let Err(err) = ui.on_event(UiEvent::SongChanged, &mut context, &mut client) {
+                                error!(error:? = err; "UI failed to handle event after reconnecting");
+                            }
+                            status_info!("Reconnected to MPD");
+                        }
+                        Err(err) => {
+                            context.connection = ConnectionState::Disconnected;
+                            status_error!(err:?; "Failed to reconnect to MPD: {}", err.to_status());
+                        }
+                    }
+                    render_wanted = true;
+                }
+                AppEvent::SetStatusUpdateInterval(interval) => {
+                    try_skip!(
+                        render_loop.set_interval(interval),
+                        "Failed to change status update interval"
+                    );
+                }
                 AppEvent::UiAppEvent(event) => match ui.on_ui_app_event(event, &mut context, &mut client) {
-                    Ok(()) => {}
+                    Ok(ui::KeyHandleResult::None) => {}
+                    Ok(ui::KeyHandleResult::Quit) => {
+                        if let Err(err) = ui.on_event(UiEvent::Exit, &mut context, &mut client) {
+                            error!(error:? = err; "UI failed to handle quit event");
+                        }
+                        break;
+                    }
                     Err(err) => {
                         status_error!(err:?; "Error: {}", err.to_status());
                         render_wanted = true;
@@ -525,26 +690,196 @@ fn main_task<B: Backend + std::io::Write>(
     ui::restore_terminal(&mut terminal, context.config.enable_mouse).expect("Terminal restore to succeed");
 }
 
+/// Passes the key events to the UI in order. Stops at the first one which quits the application.
+fn handle_keys(
+    keys: Vec<key_event::KeyEvent>,
+    ui: &mut Ui<'_>,
+    context: &mut context::AppContext,
+    client: &mut Client<'static>,
+    render_wanted: &mut bool,
+) -> ui::KeyHandleResult {
+    for mut key in keys {
+        match ui.handle_key(&mut key, context, client) {
+            Ok(ui::KeyHandleResult::None) => {}
+            Ok(ui::KeyHandleResult::Quit) => {
+                if let Err(err) = ui.on_event(UiEvent::Exit, context, client) {
+                    error!(error:? = err; "UI failed to handle quit event");
+                }
+                return ui::KeyHandleResult::Quit;
+            }
+            Err(err) => {
+                status_error!(err:?; "Error: {}", err.to_status());
+                *render_wanted = true;
+            }
+        }
+    }
+    ui::KeyHandleResult::None
+}
+
+/// Connects new command and idle clients, retrying with backoff, and hands them over to the main task with
+/// [`AppEvent::Reconnected`]. Runs on its own thread so the UI keeps rendering the progress in the meantime.
+fn reconnect_task(
+    address: MpdAddress<'static>,
+    password: Option<MpdPassword<'static>>,
+    sender: &std::sync::mpsc::Sender<AppEvent>,
+    mut sleep: impl FnMut(Duration),
+) {
+    let result = reconnect_with_backoff(
+        || Client::init(address, password, "command", ReconnectMode::Once),
+        &mut sleep,
+    )
+    .and_then(|client| {
+        let idle_client = reconnect_with_backoff(
+            || Client::init(address, password, "idle", ReconnectMode::Backoff),
+            &mut sleep,
+        )?;
+        Ok((client, idle_client))
+    });
+    if let Err(err) = sender.send(AppEvent::Reconnected(Box::new(result))) {
+        error!(error:? = err; "Failed to hand over the new MPD connections");
+    }
+}
+
+/// Reloads the state from MPD with the new command client and replaces the idle connection. The previous idle
+/// connection is shut down which stops its task. Nothing is replaced when reloading fails.
+fn swap_clients(
+    context: &mut context::AppContext,
+    idle_shutdown: &mut ShutdownHandle,
+    mut client: Client<'static>,
+    mut idle_client: Client<'static>,
+) -> Result<Client<'static>> {
+    context.reload(&mut client)?;
+
+    idle_client.set_read_timeout(None)?;
+    std::mem::replace(idle_shutdown, idle_client.shutdown_handle()?).shutdown();
+    let sender = context.app_event_sender.clone();
+    let idle_max_retries = context.config.idle_max_retries;
+    std::thread::Builder::new()
+        .name("idle task".to_owned())
+        .spawn(move || idle_task(idle_client, sender, idle_max_retries))?;
+
+    Ok(client)
+}
+
+/// Fetches the current song's album art once for the accent color, if enabled in the theme, and for the album art
+/// output, if configured
+fn handle_album_art(
+    context: &mut context::AppContext,
+    client: &mut impl MpdClient,
+    album_art_output: Option<&mut AlbumArtOutput>,
+) {
+    let accent = context.config.theme.album_art_accent;
+    if !accent && album_art_output.is_none() {
+        return;
+    }
+    context.album_art_accent = None;
+
+    let Some((_, song)) = context.find_current_song_in_queue() else {
+        return;
+    };
+    let disabled_protos = &context.config.album_art.disabled_protocols;
+    let data = if disabled_protos.iter().any(|proto| song.file.starts_with(proto)) {
+        None
+    } else {
+        match context.find_album_art(client, &song.file) {
+            Ok(data) => data,
+            Err(err) => {
+                warn!(error:? = err; "Failed to fetch album art");
+                return;
+            }
+        }
+    };
+
+    if let Some(output) = album_art_output {
+        output.write(data.as_deref());
+    }
+    if let Some(data) = data.filter(|_| accent) {
+        try_skip!(
+            context
+                .work_sender
+                .send(WorkRequest::AlbumArtAccent { song_id: song.id, data }),
+            "Failed to request album art accent"
+        );
+    }
+}
+
 fn handle_idle_event(
     event: IdleEvent,
     context: &mut context::AppContext,
-    client: &mut Client<'_>,
+    client: &mut impl MpdClient,
     render_loop: &mut RenderLoop,
     result_ui_evs: &mut HashSet<UiEvent>,
+    album_art_output: Option<&mut AlbumArtOutput>,
 ) -> Result<()> {
     match event {
         IdleEvent::Mixer => {
-            if context.supported_commands.contains("getvol") {
-                context.status.volume = try_ret!(client.get_volume(), "Failed to get volume");
+            let old_volume = context.status.volume;
+            let volume = if context.supported_commands.contains("getvol") {
+                client
+                    .get_volume()
+                    .inspect_err(|err| log::warn!(error:? = err; "Failed to get volume, falling back to status"))
+                    .ok()
             } else {
-                context.status = try_ret!(client.get_status(), "Failed to get status");
+                None
+            };
+            match volume {
+                Some(volume) => context.status.volume = volume,
+                None => context.set_status(try_ret!(client.get_status(), "Failed to get status")),
+            }
+            if let (Some(old_volume), Some(volume)) = (old_volume, context.status.volume) {
+                context.mute.on_volume_changed(old_volume, volume);
+            }
+        }
+        IdleEvent::Options => {
+            context.set_status(try_ret!(client.get_status(), "Failed to get status"));
+            if context.supported_commands.contains("replay_gain_status") {
+                context.status.replay_gain_mode = Some(try_ret!(
+                    client.replay_gain_status(),
+                    "Failed to get replay gain status"
+                ));
             }
         }
-        IdleEvent::Options => context.status = try_ret!(client.get_status(), "Failed to get status"),
         IdleEvent::Player => {
             let current_song_id = context.find_current_song_in_queue().map(|(_, song)| song.id);
+            let previous_state = context.status.state;
+            let previous_error = context.status.error.take();
+            let previous_next_song_id = context.status.nextsongid;
+
+            context.set_status(try_ret!(client.get_status(), "Failed get status"));
 
-            context.status = try_ret!(client.get_status(), "Failed get status");
+            if context
+                .stop_after_current
+                .is_some_and(|id| context.status.state == State::Stop || context.status.songid != Some(id))
+            {
+                context.stop_after_current = None;
+                // A song which finished on its own is followed by the one MPD reported as next, anything else means
+                // the track was changed manually
+                if context.status.state == State::Play && context.status.songid == previous_next_song_id {
+                    try_ret!(client.stop(), "Failed to stop playback after the current song");
+                    context.set_status(try_ret!(client.get_status(), "Failed get status"));
+                    status_info!("Playback stopped after the current song");
+                } else if context.status.state != State::Stop {
+                    status_info!("Track changed, playback will continue after the current song");
+                }
+            }
+
+            if let Some(error) = context
+                .status
+                .error
+                .clone()
+                .filter(|err| previous_error.as_ref() != Some(err))
+            {
+                if context.config.pause_on_error && context.status.state == State::Play {
+                    try_ret!(client.pause(), "Failed to pause playback after MPD error");
+                    context.status.state = State::Pause;
+                    status_error!("MPD reported an error, playback paused: {}", error);
+                } else {
+                    status_error!("MPD reported an error: {}", error);
+                }
+                if context.config.auto_clear_error {
+                    try_skip!(client.clear_error(), "Failed to clear MPD error");
+                }
+            }
 
             match context.status.state {
                 State::Play => {
@@ -554,11 +889,16 @@ fn handle_idle_event(
                     try_skip!(render_loop.stop(), "Failed to stop render loop");
                 }
                 State::Stop => {
-                    result_ui_evs.insert(UiEvent::SongChanged);
+                    // Other player events while stopped, ie. seeking, do not change what is shown
+                    if previous_state != State::Stop {
+                        result_ui_evs.insert(UiEvent::SongChanged);
+                        context.album_art_accent = None;
+                    }
                     try_skip!(render_loop.stop(), "Failed to stop render loop");
                 }
             }
 
+            // Events which only reflect progress or state changes of the same song keep its album art and metadata
             if context
                 .find_current_song_in_queue()
                 .map(|(_, song)| song.id)
@@ -571,7 +911,7 @@ fn handle_idle_event(
                             .into_iter()
                             .map(|(mut k, v)| {
                                 k.make_ascii_uppercase();
-                                (k, v)
+                                (k, v.join(context.config.theme.format_tag_separator).into_owned())
                             })
                             .chain(std::iter::once(("FILE".to_owned(), song.file)))
                             .chain(std::iter::once((
@@ -592,6 +932,7 @@ fn handle_idle_event(
                     run_external(command, env);
                 };
 
+                handle_album_art(context, client, album_art_output);
                 result_ui_evs.insert(UiEvent::SongChanged);
             }
         }
@@ -599,12 +940,14 @@ fn handle_idle_event(
             let queue = client.playlist_info()?;
             context.queue = queue.unwrap_or_default();
         }
-        IdleEvent::StoredPlaylist => {}
+        IdleEvent::StoredPlaylist => {
+            context.stored_playlists = None;
+        }
         IdleEvent::Database => {}
         IdleEvent::Update => {}
-        IdleEvent::Output
-        | IdleEvent::Partition
-        | IdleEvent::Sticker
+        IdleEvent::Output => {}
+        IdleEvent::Sticker => context.refresh_ratings(client),
+        IdleEvent::Partition
         | IdleEvent::Subscription
         | IdleEvent::Message
         | IdleEvent::Neighbor
@@ -619,38 +962,71 @@ fn handle_idle_event(
     Ok(())
 }
 
-fn idle_task(mut idle_client: Client<'_>, sender: std::sync::mpsc::Sender<AppEvent>) {
+const MAX_IDLE_RETRY_DELAY_SECS: u64 = 30;
+
+fn idle_task(mut idle_client: Client<'_>, sender: std::sync::mpsc::Sender<AppEvent>, max_retries: Option<u64>) {
     let mut error_count = 0;
     let sender = sender;
+    let send = |event: AppEvent| {
+        if let Err(err) = sender.send(event) {
+            error!(error:? = err; "Failed to send app event");
+        }
+    };
     loop {
         let events = match idle_client.idle(None) {
-            Ok(val) => val,
+            Ok(val) => {
+                if error_count > 0 {
+                    info!(error_count; "Recovered from errors when receiving idle events");
+                    send(AppEvent::Status("Connection to MPD recovered".to_owned(), Level::Info));
+                    send(AppEvent::Connection(ConnectionState::Connected));
+                    error_count = 0;
+                }
+                val
+            }
+            Err(_) if idle_client.is_shut_down() => {
+                debug!("Idle client was shut down, stopping idle task");
+                break;
+            }
             Err(err) => {
-                if error_count > 5 {
+                if max_retries.is_some_and(|max| error_count >= max) {
                     error!(err:?; "Unexpected error when receiving idle events");
+                    send(AppEvent::Connection(ConnectionState::Disconnected));
                     break;
                 }
                 warn!(err:?; "Unexpected error when receiving idle events");
+                if error_count == 0 {
+                    send(AppEvent::Connection(ConnectionState::Reconnecting));
+                }
                 error_count += 1;
-                std::thread::sleep(Duration::from_secs(error_count));
+                std::thread::sleep(Duration::from_secs(error_count.min(MAX_IDLE_RETRY_DELAY_SECS)));
                 continue;
             }
         };
 
         for event in events {
             trace!(idle_event:? = event; "Received idle event");
-            if let Err(err) = sender.send(AppEvent::IdleEvent(event)) {
-                error!(error:? = err; "Failed to send app event");
-            }
+            send(AppEvent::IdleEvent(event));
         }
     }
 }
 
-fn input_poll_task(user_input_tx: std::sync::mpsc::Sender<AppEvent>) {
+/// The input poll timeout grows up to this multiple of the configured interval while there is no input
+const MAX_IDLE_INPUT_POLL_MULTIPLIER: u32 = 8;
+
+fn input_poll_task(user_input_tx: std::sync::mpsc::Sender<AppEvent>, poll_interval_ms: u64) {
     let user_input_tx = user_input_tx;
     let mut mouse_event_tracker = MouseEventTracker::default();
+    let poll_interval = Duration::from_millis(poll_interval_ms.max(1));
+    let max_poll_interval = poll_interval * MAX_IDLE_INPUT_POLL_MULTIPLIER;
+    let mut timeout = poll_interval;
     loop {
-        match crossterm::event::poll(Duration::from_millis(250)) {
+        let polled = crossterm::event::poll(timeout);
+        // Poll returns as soon as there is input so a longer timeout only means fewer wakeups when idle
+        timeout = match polled {
+            Ok(false) => (timeout * 2).min(max_poll_interval),
+            Ok(true) | Err(_) => poll_interval,
+        };
+        match polled {
             Ok(true) => match crossterm::event::read() {
                 Ok(Event::Mouse(mouse)) => {
                     if let Some(ev) = mouse_event_tracker.track_and_get(mouse) {
@@ -686,6 +1062,7 @@ fn input_poll_task(user_input_tx: std::sync::mpsc::Sender<AppEvent>) {
 enum LoopEvent {
     Start,
     Stop,
+    SetInterval(Option<Duration>),
 }
 
 #[derive(Debug)]
@@ -696,38 +1073,56 @@ struct RenderLoop {
 impl RenderLoop {
     fn new(render_sender: std::sync::mpsc::Sender<AppEvent>, config: &Config) -> Self {
         let (tx, rx) = std::sync::mpsc::channel::<LoopEvent>();
+        let mut update_interval = config
+            .status_update_interval_ms
+            .filter(|ms| *ms > 0)
+            .map(Duration::from_millis);
 
-        // send stop event at the start to not start the loop immedietally
-        if let Err(err) = tx.send(LoopEvent::Stop) {
-            error!(error:? = err; "Failed to properly initialize status update loop");
-        }
-
-        let Some(update_interval) = config.status_update_interval_ms.map(Duration::from_millis) else {
-            return Self { event_tx: None };
-        };
         std::thread::spawn(move || {
+            // The loop does not run until started
+            let mut running = false;
             loop {
-                match rx.try_recv() {
-                    Ok(LoopEvent::Stop) => loop {
-                        if let Ok(LoopEvent::Start) = rx.recv() {
-                            break;
+                let event = match update_interval.filter(|_| running) {
+                    Some(interval) => match rx.recv_timeout(interval) {
+                        Ok(event) => event,
+                        Err(RecvTimeoutError::Timeout) => {
+                            if let Err(err) = render_sender.send(AppEvent::RequestStatusUpdate) {
+                                error!(error:? = err; "Failed to send status update request");
+                            }
+                            continue;
                         }
+                        Err(RecvTimeoutError::Disconnected) => break,
                     },
-                    Err(TryRecvError::Disconnected) => {
-                        error!("Render loop channel is disconnected");
-                    }
-                    Ok(LoopEvent::Start) | Err(TryRecvError::Empty) => {} // continue with the update loop
-                }
+                    None => match rx.recv() {
+                        Ok(event) => event,
+                        Err(_) => break,
+                    },
+                };
 
-                std::thread::sleep(update_interval);
-                if let Err(err) = render_sender.send(AppEvent::RequestStatusUpdate) {
-                    error!(error:? = err; "Failed to send status update request");
+                match event {
+                    LoopEvent::Start => running = true,
+                    LoopEvent::Stop => running = false,
+                    LoopEvent::SetInterval(interval) => update_interval = interval,
                 }
             }
+            log::debug!("Render loop channel is disconnected, stopping the status update loop");
         });
         Self { event_tx: Some(tx) }
     }
 
+    /// Changes how often the status is refreshed, `None` or zero stops the periodic refresh. Like in the config the
+    /// interval is at least 100ms.
+    fn set_interval(&mut self, interval: Option<Duration>) -> Result<()> {
+        if let Some(tx) = &self.event_tx {
+            let interval = interval
+                .filter(|interval| !interval.is_zero())
+                .map(|interval| interval.max(Duration::from_millis(100)));
+            Ok(tx.send(LoopEvent::SetInterval(interval))?)
+        } else {
+            Ok(())
+        }
+    }
+
     fn start(&mut self) -> Result<()> {
         if let Some(tx) = &self.event_tx {
             Ok(tx.send(LoopEvent::Start)?)
@@ -744,3 +1139,306 @@ impl RenderLoop {
         }
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod idle_event_tests {
+    use std::collections::HashSet;
+
+    use rstest::rstest;
+
+    use crate::{
+        config::{Config, Leak},
+        context::AppContext,
+        mpd::commands::{idle::IdleEvent, Song, State},
+        tests::fixtures::{app_context, mpd_client::client, mpd_client::TestMpdClient},
+        ui::UiEvent,
+        RenderLoop,
+    };
+
+    use super::handle_idle_event;
+
+    fn player_event(app_context: &mut AppContext, client: &mut TestMpdClient) -> HashSet<UiEvent> {
+        let mut ui_events = HashSet::new();
+        handle_idle_event(
+            IdleEvent::Player,
+            app_context,
+            client,
+            &mut RenderLoop { event_tx: None },
+            &mut ui_events,
+            None,
+        )
+        .unwrap();
+        ui_events
+    }
+
+    fn with_album_art_accent(mut app_context: AppContext) -> AppContext {
+        let mut config = Config::default();
+        config.theme.album_art_accent = true;
+        app_context.config = config.leak();
+        app_context.queue = (1..=2)
+            .map(|id| Song {
+                id,
+                ..Default::default()
+            })
+            .collect();
+        app_context
+    }
+
+    #[rstest]
+    fn does_not_fetch_album_art_when_the_song_did_not_change(app_context: AppContext, mut client: TestMpdClient) {
+        let mut app_context = with_album_art_accent(app_context);
+        app_context.status.state = State::Play;
+        app_context.status.songid = Some(1);
+        client.status = app_context.status.clone();
+        client.status.elapsed = std::time::Duration::from_secs(10);
+
+        let ui_events = player_event(&mut app_context, &mut client);
+
+        assert_eq!(client.calls.get("find_album_art"), None);
+        assert!(!ui_events.contains(&UiEvent::SongChanged));
+    }
+
+    #[rstest]
+    fn fetches_album_art_when_the_song_changed(app_context: AppContext, mut client: TestMpdClient) {
+        let mut app_context = with_album_art_accent(app_context);
+        app_context.status.state = State::Play;
+        app_context.status.songid = Some(1);
+        client.status = app_context.status.clone();
+        client.status.songid = Some(2);
+
+        let ui_events = player_event(&mut app_context, &mut client);
+
+        assert_eq!(client.calls.get("find_album_art"), Some(&1));
+        assert!(ui_events.contains(&UiEvent::SongChanged));
+    }
+
+    #[rstest]
+    fn does_not_report_song_change_while_staying_stopped(app_context: AppContext, mut client: TestMpdClient) {
+        let mut app_context = with_album_art_accent(app_context);
+        app_context.status.state = State::Stop;
+        app_context.status.songid = Some(1);
+        client.status = app_context.status.clone();
+
+        let ui_events = player_event(&mut app_context, &mut client);
+
+        assert_eq!(client.calls.get("find_album_art"), None);
+        assert!(!ui_events.contains(&UiEvent::SongChanged));
+    }
+
+    fn playing_with_stop_after_current(app_context: &mut AppContext, client: &mut TestMpdClient) {
+        app_context.status.state = State::Play;
+        app_context.status.songid = Some(1);
+        app_context.status.nextsongid = Some(2);
+        app_context.stop_after_current = Some(1);
+        client.status = app_context.status.clone();
+    }
+
+    #[rstest]
+    fn stops_when_the_current_song_finished(mut app_context: AppContext, mut client: TestMpdClient) {
+        playing_with_stop_after_current(&mut app_context, &mut client);
+        client.status.songid = Some(2);
+        client.status.nextsongid = Some(3);
+
+        player_event(&mut app_context, &mut client);
+
+        assert_eq!(client.status.state, State::Stop);
+        assert_eq!(app_context.status.state, State::Stop);
+        assert_eq!(app_context.stop_after_current, None);
+    }
+
+    #[rstest]
+    #[case(Some(3), State::Play)]
+    #[case(Some(1), State::Stop)]
+    fn clears_stop_after_current_when_the_song_changed_otherwise(
+        #[case] songid: Option<u32>,
+        #[case] state: State,
+        mut app_context: AppContext,
+        mut client: TestMpdClient,
+    ) {
+        playing_with_stop_after_current(&mut app_context, &mut client);
+        client.status.songid = songid;
+        client.status.state = state;
+
+        player_event(&mut app_context, &mut client);
+
+        assert_eq!(client.status.state, state);
+        assert_eq!(app_context.stop_after_current, None);
+    }
+
+    #[rstest]
+    fn keeps_stop_after_current_while_the_song_plays(mut app_context: AppContext, mut client: TestMpdClient) {
+        playing_with_stop_after_current(&mut app_context, &mut client);
+        client.status.elapsed = std::time::Duration::from_secs(10);
+
+        player_event(&mut app_context, &mut client);
+
+        assert_eq!(client.status.state, State::Play);
+        assert_eq!(app_context.stop_after_current, Some(1));
+    }
+
+    #[rstest]
+    #[case(true, Some(&1))]
+    #[case(false, None)]
+    fn clears_new_mpd_error_only_when_enabled(
+        #[case] auto_clear_error: bool,
+        #[case] expected_calls: Option<&u32>,
+        mut app_context: AppContext,
+        mut client: TestMpdClient,
+    ) {
+        app_context.config = Config {
+            auto_clear_error,
+            ..app_context.config.clone()
+        }
+        .leak();
+        client.status.error = Some("Failed to decode".to_owned());
+
+        player_event(&mut app_context, &mut client);
+
+        assert_eq!(client.calls.get("clear_error"), expected_calls);
+    }
+
+    #[rstest]
+    fn does_not_clear_an_already_reported_error_again(mut app_context: AppContext, mut client: TestMpdClient) {
+        app_context.config = Config {
+            auto_clear_error: true,
+            ..app_context.config.clone()
+        }
+        .leak();
+        app_context.status.error = Some("Failed to decode".to_owned());
+        client.status.error = Some("Failed to decode".to_owned());
+
+        player_event(&mut app_context, &mut client);
+
+        assert_eq!(client.calls.get("clear_error"), None);
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod render_loop_tests {
+    use std::{
+        sync::mpsc::{channel, Receiver},
+        time::Duration,
+    };
+
+    use crate::{
+        config::{Config, Leak},
+        AppEvent, RenderLoop,
+    };
+
+    fn render_loop(interval_ms: Option<u64>) -> (RenderLoop, Receiver<AppEvent>) {
+        let (tx, rx) = channel();
+        let config = Config {
+            status_update_interval_ms: interval_ms,
+            ..Config::default()
+        }
+        .leak();
+        (RenderLoop::new(tx, config), rx)
+    }
+
+    fn receives_update(rx: &Receiver<AppEvent>, timeout: Duration) -> bool {
+        matches!(rx.recv_timeout(timeout), Ok(AppEvent::RequestStatusUpdate))
+    }
+
+    #[test]
+    fn requests_updates_only_after_start() {
+        let (mut render_loop, rx) = render_loop(Some(10));
+
+        assert!(!receives_update(&rx, Duration::from_millis(100)));
+
+        render_loop.start().unwrap();
+        assert!(receives_update(&rx, Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn stops_requesting_updates_when_interval_is_unset() {
+        let (mut render_loop, rx) = render_loop(Some(10));
+        render_loop.start().unwrap();
+        assert!(receives_update(&rx, Duration::from_secs(1)));
+
+        render_loop.set_interval(None).unwrap();
+        while receives_update(&rx, Duration::from_millis(100)) {}
+
+        assert!(!receives_update(&rx, Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn starts_requesting_updates_when_interval_is_set_at_runtime() {
+        let (mut render_loop, rx) = render_loop(None);
+        render_loop.start().unwrap();
+        assert!(!receives_update(&rx, Duration::from_millis(100)));
+
+        render_loop.set_interval(Some(Duration::from_millis(10))).unwrap();
+
+        assert!(receives_update(&rx, Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn clamps_interval_set_at_runtime_to_minimum() {
+        let (mut render_loop, rx) = render_loop(None);
+        render_loop.start().unwrap();
+
+        render_loop.set_interval(Some(Duration::from_millis(1))).unwrap();
+
+        assert!(!receives_update(&rx, Duration::from_millis(50)));
+        assert!(receives_update(&rx, Duration::from_secs(1)));
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod reconnect_task_tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        os::unix::net::UnixListener,
+        path::PathBuf,
+        sync::mpsc::channel,
+    };
+
+    use crate::{config::MpdAddress, AppEvent};
+
+    use super::reconnect_task;
+
+    fn socket_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("rmpc_{name}_{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn hands_over_both_clients_once_connected() {
+        let path = socket_path("reconnect_task");
+        let listener = UnixListener::bind(&path).unwrap();
+        let server = std::thread::spawn(move || {
+            for _ in 0..2 {
+                let (mut stream, _) = listener.accept().unwrap();
+                stream.write_all(b"OK MPD 0.24.0\n").unwrap();
+                let mut binary_limit = String::new();
+                BufReader::new(&stream).read_line(&mut binary_limit).unwrap();
+                stream.write_all(b"OK\n").unwrap();
+            }
+        });
+        let (tx, rx) = channel();
+        let address = MpdAddress::SocketPath(path.to_string_lossy().into_owned().leak());
+
+        reconnect_task(address, None, &tx, |_| {});
+        server.join().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(rx.try_recv().unwrap(), AppEvent::Reconnected(result) if result.is_ok()));
+    }
+
+    #[test]
+    fn hands_over_the_error_after_backing_off() {
+        let path = socket_path("reconnect_task_missing");
+        let (tx, rx) = channel();
+        let address = MpdAddress::SocketPath(path.to_string_lossy().into_owned().leak());
+        let mut sleeps = 0;
+
+        reconnect_task(address, None, &tx, |_| sleeps += 1);
+
+        assert!(sleeps > 0);
+        assert!(matches!(rx.try_recv().unwrap(), AppEvent::Reconnected(result) if result.is_err()));
+    }
+}
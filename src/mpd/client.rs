@@ -1,17 +1,22 @@
 use std::{
     io::{BufRead, BufReader, Write},
-    net::TcpStream,
+    net::{Shutdown, TcpStream},
     os::unix::net::UnixStream,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
 };
 
 use crate::{
     config::{address::MpdPassword, MpdAddress},
     mpd::mpd_client::MpdClient,
-    shared::macros::status_warn,
+    shared::macros::{status_error, status_info, status_warn},
 };
 
 use super::{
-    errors::MpdError,
+    errors::{ErrorCode, MpdError, MpdFailureResponse},
     proto_client::{ProtoClient, SocketClient},
     version::Version,
 };
@@ -26,21 +31,67 @@ const MIN_SUPPORTED_VERSION: Version = Version {
     patch: 5,
 };
 
+const RECONNECT_MAX_ATTEMPTS: u32 = 6;
+const RECONNECT_INITIAL_DELAY: Duration = Duration::from_millis(100);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(1);
+
+/// Calls `connect` until it succeeds or runs out of attempts. The delay between the attempts doubles after each
+/// failure up to [`RECONNECT_MAX_DELAY`]. A closed client is not retried. The status bar only shows the progress
+/// once the first attempt fails.
+pub fn reconnect_with_backoff<T>(
+    mut connect: impl FnMut() -> MpdResult<T>,
+    mut sleep: impl FnMut(Duration),
+) -> MpdResult<T> {
+    let mut delay = RECONNECT_INITIAL_DELAY;
+    let mut attempt = 1;
+    loop {
+        match connect() {
+            Ok(result) => {
+                if attempt > 1 {
+                    status_info!("Reconnected to MPD");
+                }
+                return Ok(result);
+            }
+            Err(err @ MpdError::ClientClosed) => return Err(err),
+            Err(err) if attempt >= RECONNECT_MAX_ATTEMPTS => return Err(err),
+            Err(err) => {
+                if attempt == 1 {
+                    status_warn!("Reconnecting to MPD…");
+                }
+                debug!(err:?, attempt, delay:?; "Failed to reconnect to MPD, retrying");
+                sleep(delay);
+                delay = (delay * 2).min(RECONNECT_MAX_DELAY);
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// How a client gets a lost connection back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReconnectMode {
+    /// Retries with [`reconnect_with_backoff`], which blocks the calling thread in between the attempts
+    Backoff,
+    /// Tries only once so the thread using the client, ie. the UI, never waits for MPD to come back
+    Once,
+}
+
 pub struct Client<'name> {
     name: &'name str,
     rx: BufReader<TcpOrUnixStream>,
     stream: TcpOrUnixStream,
-    reconnect: bool,
+    reconnect: ReconnectMode,
     addr: MpdAddress<'name>,
     password: Option<MpdPassword<'name>>,
     pub version: Version,
+    shut_down: Arc<AtomicBool>,
 }
 
 impl std::fmt::Debug for Client<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Client {{ name: {:?}, recconect: {}, addr: {:?} }}",
+            "Client {{ name: {:?}, recconect: {:?}, addr: {:?} }}",
             self.name, self.reconnect, self.addr
         )
     }
@@ -76,6 +127,13 @@ impl TcpOrUnixStream {
         Ok(())
     }
 
+    fn shutdown(&self) -> std::io::Result<()> {
+        match self {
+            TcpOrUnixStream::Unix(s) => s.shutdown(Shutdown::Both),
+            TcpOrUnixStream::Tcp(s) => s.shutdown(Shutdown::Both),
+        }
+    }
+
     fn try_clone(&self) -> std::io::Result<Self> {
         Ok(match self {
             TcpOrUnixStream::Unix(s) => TcpOrUnixStream::Unix(s.try_clone()?),
@@ -115,7 +173,7 @@ impl<'name> Client<'name> {
         addr: MpdAddress<'name>,
         password: Option<MpdPassword<'name>>,
         name: &'name str,
-        reconnect: bool,
+        reconnect: ReconnectMode,
     ) -> MpdResult<Client<'name>> {
         let mut stream = match addr {
             MpdAddress::IpAndPort(addr) => TcpOrUnixStream::Tcp(TcpStream::connect(addr)?),
@@ -152,11 +210,23 @@ impl<'name> Client<'name> {
             addr,
             password,
             version,
+            shut_down: Arc::new(AtomicBool::new(false)),
         };
 
         if let Some(MpdPassword(password)) = password {
             debug!("Used password auth to MPD");
-            client.password(password)?;
+            match client.password(password) {
+                Ok(()) => {}
+                Err(MpdError::Mpd(MpdFailureResponse {
+                    code: ErrorCode::Password,
+                    ..
+                })) => {
+                    return Err(MpdError::Generic(
+                        "Authentication to MPD failed, the configured password is incorrect".to_owned(),
+                    ));
+                }
+                Err(err) => return Err(err),
+            }
         }
 
         client.binary_limit(1024 * 1024 * 5)?;
@@ -165,6 +235,29 @@ impl<'name> Client<'name> {
     }
 
     fn reconnect(&mut self) -> MpdResult<&Client> {
+        // Connections closed on purpose, ie. the old idle connection after a manual reconnect, stay closed silently
+        if self.is_shut_down() {
+            return Err(MpdError::ClientClosed);
+        }
+
+        let result = match self.reconnect {
+            ReconnectMode::Backoff => reconnect_with_backoff(|| self.connect_again(), std::thread::sleep),
+            ReconnectMode::Once => self.connect_again(),
+        };
+        match result {
+            Ok(()) => Ok(self),
+            Err(err) => {
+                status_error!(err:?; "Failed to reconnect to MPD");
+                Err(err)
+            }
+        }
+    }
+
+    /// Replaces the connection with a new one to the same address
+    fn connect_again(&mut self) -> MpdResult<()> {
+        if self.is_shut_down() {
+            return Err(MpdError::ClientClosed);
+        }
         let mut stream = match self.addr {
             MpdAddress::IpAndPort(addr) => TcpOrUnixStream::Tcp(TcpStream::connect(addr)?),
             MpdAddress::SocketPath(addr) => TcpOrUnixStream::Unix(UnixStream::connect(addr)?),
@@ -198,7 +291,20 @@ impl<'name> Client<'name> {
 
         self.binary_limit(1024 * 1024 * 5)?;
 
-        Ok(self)
+        Ok(())
+    }
+
+    /// Handle which closes the connection from another thread, ie. to stop a blocking idle
+    pub fn shutdown_handle(&self) -> std::io::Result<ShutdownHandle> {
+        Ok(ShutdownHandle {
+            stream: self.stream.try_clone()?,
+            shut_down: Arc::clone(&self.shut_down),
+        })
+    }
+
+    /// Whether the connection was closed by its `ShutdownHandle`, such client does not reconnect
+    pub fn is_shut_down(&self) -> bool {
+        self.shut_down.load(Ordering::Relaxed)
     }
 
     pub fn set_read_timeout(&mut self, timeout: Option<std::time::Duration>) -> std::io::Result<()> {
@@ -220,6 +326,26 @@ impl<'name> Client<'name> {
     }
 }
 
+pub struct ShutdownHandle {
+    stream: TcpOrUnixStream,
+    shut_down: Arc<AtomicBool>,
+}
+
+impl std::fmt::Debug for ShutdownHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ShutdownHandle {{ shut_down: {:?} }}", self.shut_down)
+    }
+}
+
+impl ShutdownHandle {
+    pub fn shutdown(&self) {
+        self.shut_down.store(true, Ordering::Relaxed);
+        if let Err(err) = self.stream.shutdown() {
+            log::warn!(error:? = err; "Failed to shut down MPD connection");
+        }
+    }
+}
+
 impl<'name> SocketClient for Client<'name> {
     fn reconnect(&mut self) -> MpdResult<&impl SocketClient> {
         self.reconnect()
@@ -237,3 +363,104 @@ impl<'name> SocketClient for Client<'name> {
         self.clear_read_buf()
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        os::unix::net::UnixListener,
+        time::{Duration, Instant},
+    };
+
+    use crate::{
+        config::MpdAddress,
+        mpd::{errors::MpdError, mpd_client::MpdClient},
+    };
+
+    use super::{reconnect_with_backoff, Client, ReconnectMode, RECONNECT_INITIAL_DELAY};
+
+    #[test]
+    fn retries_with_increasing_delays() {
+        let mut attempts = 0;
+        let mut delays = Vec::new();
+
+        let result = reconnect_with_backoff(
+            || {
+                attempts += 1;
+                if attempts < 4 {
+                    Err(MpdError::Generic("connection refused".to_owned()))
+                } else {
+                    Ok(attempts)
+                }
+            },
+            |delay| delays.push(delay),
+        );
+
+        assert_eq!(result.unwrap(), 4);
+        assert_eq!(
+            delays,
+            vec![
+                Duration::from_millis(100),
+                Duration::from_millis(200),
+                Duration::from_millis(400)
+            ]
+        );
+    }
+
+    #[test]
+    fn caps_the_delay_and_gives_up() {
+        let mut delays = Vec::new();
+
+        let result: Result<(), _> = reconnect_with_backoff(
+            || Err(MpdError::Generic("connection refused".to_owned())),
+            |delay| delays.push(delay),
+        );
+
+        assert!(result.is_err());
+        assert_eq!(
+            delays,
+            vec![
+                Duration::from_millis(100),
+                Duration::from_millis(200),
+                Duration::from_millis(400),
+                Duration::from_millis(800),
+                Duration::from_secs(1)
+            ]
+        );
+    }
+
+    #[test]
+    fn does_not_retry_closed_client() {
+        let mut delays = Vec::new();
+
+        let result: Result<(), _> = reconnect_with_backoff(|| Err(MpdError::ClientClosed), |delay| delays.push(delay));
+
+        assert_eq!(result, Err(MpdError::ClientClosed));
+        assert!(delays.is_empty());
+    }
+
+    #[test]
+    fn client_reconnecting_once_does_not_wait_for_mpd() {
+        let path = std::env::temp_dir().join(format!("rmpc_reconnect_once_{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            stream.write_all(b"OK MPD 0.24.0\n").unwrap();
+            let mut binary_limit = String::new();
+            BufReader::new(&stream).read_line(&mut binary_limit).unwrap();
+            stream.write_all(b"OK\n").unwrap();
+        });
+        let address = MpdAddress::SocketPath(path.to_string_lossy().into_owned().leak());
+        let mut client = Client::init(address, None, "command", ReconnectMode::Once).unwrap();
+        server.join().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let start = Instant::now();
+        let result = client.get_status();
+
+        assert!(result.is_err());
+        assert!(start.elapsed() < RECONNECT_INITIAL_DELAY);
+    }
+}
@@ -0,0 +1,17 @@
+use crate::mpd::errors::MpdError;
+use crate::mpd::{FromMpd, LineHandled};
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct AddId {
+    pub id: u32,
+}
+
+impl FromMpd for AddId {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        match key {
+            "id" => self.id = value.parse()?,
+            _ => return Ok(LineHandled::No { value }),
+        }
+        Ok(LineHandled::Yes)
+    }
+}
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+
+use derive_more::{AsMut, AsRef, Into, IntoIterator};
+use serde::Serialize;
+
+use crate::mpd::{errors::MpdError, FromMpd, LineHandled};
+
+/// Tags which can contain the lyrics of a song, in order of preference
+const LYRICS_COMMENTS: [&str; 3] = ["lyrics", "unsyncedlyrics", "uslt"];
+
+/// Response of `readcomments`, all comments of the song keyed by their lowercased names
+#[derive(Debug, Serialize, Default, IntoIterator, AsRef, AsMut, Into)]
+pub struct Comments(pub HashMap<String, String>);
+
+impl Comments {
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+
+    /// Lyrics embedded in the song's tags, these can be either synced lrc or plain text
+    pub fn lyrics(&self) -> Option<&str> {
+        LYRICS_COMMENTS
+            .iter()
+            .find_map(|key| self.get(key))
+            .filter(|lyrics| !lyrics.trim().is_empty())
+    }
+}
+
+impl FromMpd for Comments {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        self.0.insert(key.to_owned(), value);
+        Ok(LineHandled::Yes)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::mpd::FromMpd;
+
+    use super::Comments;
+
+    #[test]
+    fn parses_comments() {
+        let mut comments = Comments::default();
+
+        comments.next("TITLE: some: title".to_owned()).unwrap();
+        comments.next("LYRICS: [00:01.00]line".to_owned()).unwrap();
+
+        assert_eq!(comments.get("title"), Some("some: title"));
+        assert_eq!(comments.lyrics(), Some("[00:01.00]line"));
+    }
+
+    #[test]
+    fn has_no_lyrics_without_lyrics_comment() {
+        let mut comments = Comments::default();
+
+        comments.next("COMMENT: not lyrics".to_owned()).unwrap();
+        comments.next("USLT: ".to_owned()).unwrap();
+
+        assert_eq!(comments.lyrics(), None);
+    }
+}
@@ -1,4 +1,8 @@
-use std::{collections::HashMap, time::Duration};
+use std::{
+    borrow::Cow,
+    collections::{hash_map::Entry, HashMap},
+    time::Duration,
+};
 
 use serde::Serialize;
 
@@ -9,7 +13,48 @@ pub struct Song {
     pub id: u32,
     pub file: String,
     pub duration: Option<Duration>,
-    pub metadata: HashMap<String, String>,
+    pub metadata: HashMap<String, MetadataTag>,
+}
+
+/// Value of a single tag. MPD sends a separate line for each value of a multi-valued tag.
+#[derive(Debug, Serialize, PartialEq, Eq, Clone)]
+#[serde(untagged)]
+pub enum MetadataTag {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl MetadataTag {
+    pub fn first(&self) -> &str {
+        self.iter().next().map_or("", String::as_str)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, String> {
+        match self {
+            MetadataTag::Single(value) => std::slice::from_ref(value).iter(),
+            MetadataTag::Multiple(values) => values.iter(),
+        }
+    }
+
+    pub fn join(&self, separator: &str) -> Cow<'_, str> {
+        match self {
+            MetadataTag::Single(value) => Cow::Borrowed(value),
+            MetadataTag::Multiple(values) => Cow::Owned(values.join(separator)),
+        }
+    }
+
+    fn push(&mut self, value: String) {
+        match self {
+            MetadataTag::Single(current) => *self = MetadataTag::Multiple(vec![std::mem::take(current), value]),
+            MetadataTag::Multiple(values) => values.push(value),
+        }
+    }
+}
+
+impl From<String> for MetadataTag {
+    fn from(value: String) -> Self {
+        MetadataTag::Single(value)
+    }
 }
 
 impl std::fmt::Debug for Song {
@@ -27,15 +72,15 @@ impl std::fmt::Debug for Song {
 }
 
 impl Song {
-    pub fn title(&self) -> Option<&String> {
+    pub fn title(&self) -> Option<&MetadataTag> {
         self.metadata.get("title")
     }
 
-    pub fn artist(&self) -> Option<&String> {
+    pub fn artist(&self) -> Option<&MetadataTag> {
         self.metadata.get("artist")
     }
 
-    pub fn album(&self) -> Option<&String> {
+    pub fn album(&self) -> Option<&MetadataTag> {
         self.metadata.get("album")
     }
 }
@@ -48,11 +93,82 @@ impl FromMpd for Song {
             "duration" => {
                 self.duration = Some(Duration::from_secs_f64(value.parse().logerr(key, &value)?));
             }
-            "time" | "format" => {} // deprecated or ignored
-            key => {
-                self.metadata.insert(key.to_owned(), value);
+            // deprecated integer version of duration, used only if the precise one is missing
+            "time" if self.duration.is_none() => {
+                self.duration = Some(Duration::from_secs(value.parse().logerr(key, &value)?));
             }
+            "time" | "format" => {} // deprecated or ignored
+            key => match self.metadata.entry(key.to_owned()) {
+                Entry::Occupied(mut tag) => tag.get_mut().push(value),
+                Entry::Vacant(tag) => {
+                    tag.insert(MetadataTag::Single(value));
+                }
+            },
         }
         Ok(LineHandled::Yes)
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::mpd::FromMpd;
+
+    use super::{MetadataTag, Song};
+
+    fn parse(lines: &[&str]) -> Song {
+        let mut song = Song::default();
+        for line in lines {
+            song.next((*line).to_owned()).unwrap();
+        }
+        song
+    }
+
+    #[test]
+    fn duration_from_time_only() {
+        let song = parse(&["file: a.flac", "Time: 213"]);
+
+        assert_eq!(song.duration, Some(Duration::from_secs(213)));
+    }
+
+    #[test]
+    fn duration_from_duration_only() {
+        let song = parse(&["file: a.flac", "duration: 212.506"]);
+
+        assert_eq!(song.duration, Some(Duration::from_secs_f64(212.506)));
+    }
+
+    #[test]
+    fn duration_prefers_precise_value() {
+        let song = parse(&["file: a.flac", "Time: 213", "duration: 212.506"]);
+        assert_eq!(song.duration, Some(Duration::from_secs_f64(212.506)));
+
+        let song = parse(&["file: a.flac", "duration: 212.506", "Time: 213"]);
+        assert_eq!(song.duration, Some(Duration::from_secs_f64(212.506)));
+    }
+
+    #[test]
+    fn repeated_tag_lines_are_all_kept() {
+        let song = parse(&[
+            "file: a.flac",
+            "Artist: first",
+            "Title: song",
+            "Artist: second",
+            "Artist: third",
+        ]);
+
+        assert_eq!(
+            song.artist(),
+            Some(&MetadataTag::Multiple(vec![
+                "first".to_owned(),
+                "second".to_owned(),
+                "third".to_owned()
+            ]))
+        );
+        assert_eq!(song.title(), Some(&MetadataTag::Single("song".to_owned())));
+        assert_eq!(song.artist().unwrap().join(", "), "first, second, third");
+        assert_eq!(song.artist().unwrap().first(), "first");
+    }
+}
@@ -15,19 +15,9 @@ pub struct Decoder {
     pub mime_types: Vec<String>,
 }
 
-impl Decoder {
-    fn sort(&mut self) {
-        self.suffixes.sort();
-        self.mime_types.sort();
-    }
-}
-
 impl FromMpd for Decoders {
     fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
         if key == "plugin" {
-            if let Some(last_decoder) = self.0.last_mut() {
-                last_decoder.sort();
-            }
             self.0.push(Decoder::default());
         }
 
@@ -46,10 +36,52 @@ impl FromMpd for Decoder {
     fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
         match key {
             "plugin" => self.name = value,
-            "suffix" => self.suffixes.push(value),
-            "mime_type" => self.mime_types.push(value),
+            "suffix" => insert_sorted(&mut self.suffixes, value),
+            "mime_type" => insert_sorted(&mut self.mime_types, value),
             _ => return Ok(LineHandled::No { value }),
         }
         Ok(LineHandled::Yes)
     }
 }
+
+/// Keeps the values sorted as they are parsed so that even the last plugin does not need a separate pass
+fn insert_sorted(values: &mut Vec<String>, value: String) {
+    let idx = values.partition_point(|v| *v < value);
+    values.insert(idx, value);
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::Decoders;
+    use crate::mpd::FromMpd;
+
+    #[test]
+    fn parses_plugins_with_sorted_suffixes_and_mime_types() {
+        let mut result = Decoders::default();
+
+        for line in [
+            "plugin: mad",
+            "suffix: mp3",
+            "suffix: mp2",
+            "mime_type: audio/mpeg",
+            "plugin: flac",
+            "suffix: oga",
+            "suffix: flac",
+            "mime_type: audio/x-flac",
+            "mime_type: audio/flac",
+        ] {
+            result.next(line.to_owned()).unwrap();
+        }
+
+        let [mad, flac] = result.0.as_slice() else {
+            panic!("Expected two plugins");
+        };
+        assert_eq!(mad.name, "mad");
+        assert_eq!(mad.suffixes, ["mp2", "mp3"]);
+        assert_eq!(mad.mime_types, ["audio/mpeg"]);
+        assert_eq!(flac.name, "flac");
+        assert_eq!(flac.suffixes, ["flac", "oga"]);
+        assert_eq!(flac.mime_types, ["audio/flac", "audio/x-flac"]);
+    }
+}
@@ -17,3 +17,90 @@ impl FromMpd for MpdList {
         Ok(LineHandled::Yes)
     }
 }
+
+/// Response of `list` with `group` clauses. Keys are kept because the values of the groups are
+/// interleaved with the listed values.
+#[derive(Debug, Default)]
+pub struct MpdGroupedList(pub Vec<(String, String)>);
+
+impl MpdGroupedList {
+    /// Pairs every listed value with the values of the groups it belongs to, in the order of
+    /// `group_keys`. MPD only repeats a group line when its value changes so the last seen value
+    /// of each group applies.
+    pub fn into_grouped(self, group_keys: &[&str]) -> Vec<(Vec<String>, String)> {
+        let mut groups = vec![String::new(); group_keys.len()];
+        let mut result = Vec::new();
+        for (key, value) in self.0 {
+            if let Some(idx) = group_keys.iter().position(|group| key.eq_ignore_ascii_case(group)) {
+                groups[idx] = value;
+            } else {
+                result.push((groups.clone(), value));
+            }
+        }
+        result
+    }
+}
+
+impl FromMpd for MpdGroupedList {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        self.0.push((key.to_owned(), value));
+        Ok(LineHandled::Yes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MpdGroupedList;
+
+    #[test]
+    fn pairs_values_with_their_group() {
+        let list = MpdGroupedList(vec![
+            ("date".to_owned(), "1999".to_owned()),
+            ("album".to_owned(), "a".to_owned()),
+            ("album".to_owned(), "b".to_owned()),
+            ("date".to_owned(), "2001".to_owned()),
+            ("album".to_owned(), "c".to_owned()),
+        ]);
+
+        assert_eq!(
+            list.into_grouped(&["Date"]),
+            vec![
+                (vec!["1999".to_owned()], "a".to_owned()),
+                (vec!["1999".to_owned()], "b".to_owned()),
+                (vec!["2001".to_owned()], "c".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_values_of_all_groups() {
+        let list = MpdGroupedList(vec![
+            ("albumartist".to_owned(), "x".to_owned()),
+            ("date".to_owned(), "1999".to_owned()),
+            ("album".to_owned(), "a".to_owned()),
+            ("date".to_owned(), "2001".to_owned()),
+            ("album".to_owned(), "b".to_owned()),
+            ("albumartist".to_owned(), "y".to_owned()),
+            ("album".to_owned(), "c".to_owned()),
+        ]);
+
+        assert_eq!(
+            list.into_grouped(&["Date", "AlbumArtist"]),
+            vec![
+                (vec!["1999".to_owned(), "x".to_owned()], "a".to_owned()),
+                (vec!["2001".to_owned(), "x".to_owned()], "b".to_owned()),
+                (vec!["2001".to_owned(), "y".to_owned()], "c".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn values_before_any_group_have_empty_group() {
+        let list = MpdGroupedList(vec![("album".to_owned(), "a".to_owned())]);
+
+        assert_eq!(
+            list.into_grouped(&["Date"]),
+            vec![(vec![String::new()], "a".to_owned())]
+        );
+    }
+}
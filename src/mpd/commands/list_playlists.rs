@@ -3,7 +3,7 @@ use anyhow::Context;
 
 use crate::mpd::{errors::MpdError, FromMpd, LineHandled};
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, PartialEq, Eq)]
 pub struct Playlist {
     pub name: String,
     pub last_modified: String,
@@ -37,3 +37,35 @@ impl FromMpd for Vec<Playlist> {
             .next_internal(key, value)
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::mpd::FromMpd;
+
+    use super::Playlist;
+
+    #[test]
+    fn parses_all_playlists() {
+        let mut playlists: Vec<Playlist> = Vec::new();
+        for line in [
+            "playlist: Favourites",
+            "Last-Modified: 2024-10-01T12:00:00Z",
+            "playlist: Road trip",
+            "Last-Modified: 2024-11-15T08:30:00Z",
+        ] {
+            playlists.next(line.to_owned()).unwrap();
+        }
+
+        assert_eq!(
+            playlists
+                .iter()
+                .map(|playlist| (playlist.name.as_str(), playlist.last_modified.as_str()))
+                .collect::<Vec<_>>(),
+            [
+                ("Favourites", "2024-10-01T12:00:00Z"),
+                ("Road trip", "2024-11-15T08:30:00Z")
+            ]
+        );
+    }
+}
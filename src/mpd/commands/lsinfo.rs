@@ -1,6 +1,6 @@
 use crate::mpd::{errors::MpdError, FromMpd, LineHandled};
 
-use super::Song;
+use super::{Playlist, Song};
 use anyhow::anyhow;
 use anyhow::Context;
 use derive_more::{AsMut, AsRef, Into, IntoIterator};
@@ -12,6 +12,8 @@ pub struct LsInfo(pub Vec<FileOrDir>);
 pub enum FileOrDir {
     Dir(Dir),
     File(Song),
+    /// Playlist file in the music directory, ie. an m3u file
+    Playlist(Playlist),
 }
 
 #[derive(Debug, Default, PartialEq, Eq)]
@@ -35,7 +37,6 @@ impl FromMpd for Dir {
                 self.full_path = value;
             }
             "last-modified" => self.last_modified = value,
-            "playlist" => {} // ignore, deprecated
             _ => return Ok(LineHandled::No { value }),
         }
         Ok(LineHandled::Yes)
@@ -50,6 +51,9 @@ impl FromMpd for LsInfo {
         if key == "directory" {
             self.0.push(FileOrDir::Dir(Dir::default()));
         }
+        if key == "playlist" {
+            self.0.push(FileOrDir::Playlist(Playlist::default()));
+        }
 
         match self.0.last_mut().context(anyhow!(
             "No element in accumulator while parsing LsInfo. Key '{}' Value :'{}'",
@@ -58,6 +62,66 @@ impl FromMpd for LsInfo {
         ))? {
             FileOrDir::Dir(dir) => dir.next_internal(key, value),
             FileOrDir::File(song) => song.next_internal(key, value),
+            FileOrDir::Playlist(playlist) => playlist.next_internal(key, value),
         }
     }
 }
+
+#[cfg(test)]
+impl LsInfo {
+    /// Parses the lines of an `lsinfo` response without the trailing `OK`
+    pub fn from_lines(lines: &[&str]) -> Result<Self, MpdError> {
+        let mut result = Self::default();
+        for line in lines {
+            result.next((*line).to_owned())?;
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{FileOrDir, LsInfo};
+
+    #[test]
+    fn parses_directories_and_files() {
+        let result = LsInfo::from_lines(&[
+            "directory: music/rock",
+            "Last-Modified: 2024-01-01T00:00:00Z",
+            "file: music/song.flac",
+            "Title: Song",
+        ])
+        .unwrap();
+
+        let [FileOrDir::Dir(dir), FileOrDir::File(song)] = result.0.as_slice() else {
+            panic!("Expected a directory and a file, got {result:?}");
+        };
+        assert_eq!(dir.path, "rock");
+        assert_eq!(dir.full_path, "music/rock");
+        assert_eq!(song.file, "music/song.flac");
+    }
+
+    #[test]
+    fn parses_playlist_files_separately() {
+        let result = LsInfo::from_lines(&[
+            "playlist: music/favourites.m3u",
+            "Last-Modified: 2024-01-01T00:00:00Z",
+            "file: music/song.flac",
+            "Last-Modified: 2024-02-02T00:00:00Z",
+            "playlist: music/other.m3u",
+            "Last-Modified: 2024-03-03T00:00:00Z",
+        ])
+        .unwrap();
+
+        let [FileOrDir::Playlist(first), FileOrDir::File(song), FileOrDir::Playlist(second)] = result.0.as_slice()
+        else {
+            panic!("Expected a song between two playlists, got {result:?}");
+        };
+        assert_eq!(first.name, "music/favourites.m3u");
+        assert_eq!(song.file, "music/song.flac");
+        assert!(!song.metadata.contains_key("playlist"));
+        assert_eq!(second.name, "music/other.m3u");
+        assert_eq!(second.last_modified, "2024-03-03T00:00:00Z");
+    }
+}
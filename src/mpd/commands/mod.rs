@@ -1,3 +1,5 @@
+pub mod add_id;
+pub mod comments;
 pub mod current_song;
 pub mod decoders;
 pub mod idle;
@@ -9,10 +11,15 @@ pub mod list_playlists;
 pub mod lsinfo;
 pub mod outputs;
 pub mod playlist_info;
+pub mod queue_positions;
+pub mod replay_gain;
 pub mod status;
+pub mod sticker;
 pub mod update;
 pub mod volume;
 
+pub use self::add_id::AddId;
+pub use self::comments::Comments;
 pub use self::current_song::Song;
 pub use self::decoders::Decoder;
 pub use self::idle::IdleEvent;
@@ -21,7 +28,13 @@ pub use self::list_mounts::Mounts;
 pub use self::list_playlists::Playlist;
 pub use self::lsinfo::LsInfo;
 pub use self::outputs::Output;
+pub use self::queue_positions::QueuePositions;
+pub use self::replay_gain::ReplayGainMode;
 pub use self::status::State;
 pub use self::status::Status;
+pub use self::sticker::FoundStickers;
+pub use self::sticker::Ratings;
+pub use self::sticker::Sticker;
+pub use self::sticker::Stickers;
 pub use self::update::Update;
 pub use self::volume::Volume;
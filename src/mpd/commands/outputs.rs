@@ -8,7 +8,7 @@ use crate::mpd::{errors::MpdError, FromMpd, LineHandled, ParseErrorExt};
 #[derive(Debug, Serialize, Default, IntoIterator, AsRef, AsMut, Into)]
 pub struct Outputs(pub Vec<Output>);
 
-#[derive(Debug, Default, Serialize)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
 pub struct Output {
     pub id: u32,
     pub name: String,
@@ -47,3 +47,42 @@ impl FromMpd for Output {
         Ok(LineHandled::Yes)
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::mpd::FromMpd;
+
+    use super::{Output, Outputs};
+
+    #[test]
+    fn parses_all_outputs() {
+        let mut outputs = Outputs::default();
+        for line in [
+            "outputid: 0",
+            "outputname: Pipewire",
+            "outputenabled: 1",
+            "outputid: 1",
+            "outputname: FIFO visualizer",
+            "outputenabled: 0",
+        ] {
+            outputs.next(line.to_owned()).unwrap();
+        }
+
+        assert_eq!(
+            outputs.0,
+            [
+                Output {
+                    id: 0,
+                    name: "Pipewire".to_owned(),
+                    enabled: true,
+                },
+                Output {
+                    id: 1,
+                    name: "FIFO visualizer".to_owned(),
+                    enabled: false,
+                },
+            ]
+        );
+    }
+}
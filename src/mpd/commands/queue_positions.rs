@@ -0,0 +1,43 @@
+use derive_more::{AsRef, Into, IntoIterator};
+
+use crate::mpd::{errors::MpdError, FromMpd, LineHandled};
+
+/// Positions in the queue of the songs returned by `playlistfind` and `playlistsearch`. Other
+/// fields of the songs are skipped.
+#[derive(Debug, Default, PartialEq, Eq, IntoIterator, AsRef, Into)]
+pub struct QueuePositions(pub Vec<usize>);
+
+impl FromMpd for QueuePositions {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        if key == "pos" {
+            self.0.push(value.parse()?);
+        }
+        Ok(LineHandled::Yes)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::QueuePositions;
+    use crate::mpd::FromMpd;
+
+    #[test]
+    fn collects_positions_of_songs() {
+        let mut result = QueuePositions::default();
+
+        for line in [
+            "file: a.flac",
+            "Title: A",
+            "Pos: 3",
+            "Id: 10",
+            "file: b.flac",
+            "Pos: 7",
+            "Id: 11",
+        ] {
+            result.next(line.to_owned()).unwrap();
+        }
+
+        assert_eq!(result, QueuePositions(vec![3, 7]));
+    }
+}
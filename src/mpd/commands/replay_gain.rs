@@ -0,0 +1,106 @@
+use anyhow::anyhow;
+use serde::Serialize;
+
+use crate::mpd::{errors::MpdError, FromMpd, LineHandled, ParseErrorExt};
+
+#[derive(Debug, Serialize, Default, Clone, Copy, PartialEq, Eq, strum::AsRefStr)]
+pub enum ReplayGainMode {
+    #[default]
+    #[strum(serialize = "Off")]
+    Off,
+    #[strum(serialize = "Track")]
+    Track,
+    #[strum(serialize = "Album")]
+    Album,
+    #[strum(serialize = "Auto")]
+    Auto,
+}
+
+impl ReplayGainMode {
+    pub fn cycle(self) -> Self {
+        match self {
+            ReplayGainMode::Off => ReplayGainMode::Track,
+            ReplayGainMode::Track => ReplayGainMode::Album,
+            ReplayGainMode::Album => ReplayGainMode::Auto,
+            ReplayGainMode::Auto => ReplayGainMode::Off,
+        }
+    }
+
+    pub fn to_mpd_value(self) -> &'static str {
+        match self {
+            ReplayGainMode::Off => "off",
+            ReplayGainMode::Track => "track",
+            ReplayGainMode::Album => "album",
+            ReplayGainMode::Auto => "auto",
+        }
+    }
+}
+
+impl std::str::FromStr for ReplayGainMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(ReplayGainMode::Off),
+            "track" => Ok(ReplayGainMode::Track),
+            "album" => Ok(ReplayGainMode::Album),
+            "auto" => Ok(ReplayGainMode::Auto),
+            _ => Err(anyhow!("Invalid replay gain mode: '{}'", s)),
+        }
+    }
+}
+
+/// Response of the `replay_gain_status` command
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ReplayGainStatus {
+    pub mode: ReplayGainMode,
+}
+
+impl FromMpd for ReplayGainStatus {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        match key {
+            "replay_gain_mode" => self.mode = value.parse().logerr(key, &value)?,
+            _ => return Ok(LineHandled::No { value }),
+        }
+        Ok(LineHandled::Yes)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use test_case::test_case;
+
+    use crate::mpd::FromMpd;
+
+    use super::{ReplayGainMode, ReplayGainStatus};
+
+    #[test_case("off", ReplayGainMode::Off)]
+    #[test_case("track", ReplayGainMode::Track)]
+    #[test_case("album", ReplayGainMode::Album)]
+    #[test_case("auto", ReplayGainMode::Auto)]
+    fn parses_replay_gain_mode(input: &str, expected: ReplayGainMode) {
+        let mut status = ReplayGainStatus::default();
+
+        status.next(format!("replay_gain_mode: {input}")).unwrap();
+
+        assert_eq!(status.mode, expected);
+    }
+
+    #[test]
+    fn rejects_unknown_replay_gain_mode() {
+        let mut status = ReplayGainStatus::default();
+
+        assert!(status.next("replay_gain_mode: loud".to_owned()).is_err());
+    }
+
+    #[test]
+    fn cycles_through_all_modes() {
+        let modes = std::iter::successors(Some(ReplayGainMode::Off), |mode| Some(mode.cycle()))
+            .take(5)
+            .map(ReplayGainMode::to_mpd_value)
+            .collect::<Vec<_>>();
+
+        assert_eq!(modes, ["off", "track", "album", "auto", "off"]);
+    }
+}
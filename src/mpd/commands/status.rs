@@ -5,43 +5,42 @@ use serde::Serialize;
 
 use crate::mpd::{errors::MpdError, FromMpd, LineHandled, ParseErrorExt};
 
-use super::Volume;
+use super::{ReplayGainMode, Volume};
 
 #[derive(Debug, Serialize, Default, Clone)]
 pub struct Status {
-    pub partition: String, // the name of the current partition (see Partition commands)
-    pub volume: Volume,    // 0-100 (deprecated: -1 if the volume cannot be determined)
+    pub partition: Option<String>, // the name of the current partition (see Partition commands)
+    pub volume: Option<Volume>,    // 0-100, None when MPD has no mixer and reports -1
     pub repeat: bool,
     pub random: bool,
     pub single: OnOffOneshot,
     pub consume: OnOffOneshot,
-    pub playlist: Option<u32>,        // 31-bit unsigned integer, the playlist version number
-    pub playlistlength: u32,          // integer, the length of the playlist
-    pub state: State,                 // play, stop, or pause
-    pub song: Option<u32>,            // playlist song number of the current song stopped on or playing
-    pub songid: Option<u32>,          // playlist songid of the current song stopped on or playing
-    pub nextsong: Option<u32>,        // playlist song number of the next song to be played
-    pub nextsongid: Option<u32>,      // playlist songid of the next song to be played
+    pub playlist: Option<u32>,          // 31-bit unsigned integer, the playlist version number
+    pub playlistlength: u32,            // integer, the length of the playlist
+    pub state: State,                   // play, stop, or pause
+    pub song: Option<u32>,              // playlist song number of the current song stopped on or playing
+    pub songid: Option<u32>,            // playlist songid of the current song stopped on or playing
+    pub nextsong: Option<u32>,          // playlist song number of the next song to be played
+    pub nextsongid: Option<u32>,        // playlist songid of the next song to be played
     pub elapsed: Duration, // Total time elapsed within the current song in seconds, but with higher resolution.
     pub duration: Duration, // Duration of the current song in seconds.
     pub bitrate: Option<u32>, // instantaneous bitrate in kbps
     pub xfade: Option<u32>, // crossfade in seconds (see Cross-Fading)
-    pub mixrampdb: Option<String>, // mixramp threshold in dB
-    pub mixrampdelay: Option<String>, // mixrampdelay in seconds
+    pub mixrampdb: Option<f32>, // mixramp threshold in dB
+    pub mixrampdelay: Option<Duration>, // mixrampdelay in seconds, None when disabled
     pub audio: Option<String>, // The format emitted by the decoder plugin during playback, format: samplerate:bits:channels. See Global Audio Format for a detailed explanation.
     pub updating_db: Option<u32>, // job id
     pub error: Option<String>, // if there is an error, returns message here
+    /// Not part of MPD's status response, fetched separately with `replay_gain_status`
+    pub replay_gain_mode: Option<ReplayGainMode>,
 }
 
 impl FromMpd for Status {
     fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
         match key {
-            "partition" => self.partition = value,
-            "volume" if value == "-1" => {
-                log::warn!(command = "status", key, value = value.as_str(); "Received unsupported value");
-                self.volume = Volume::new(0);
-            }
-            "volume" => self.volume = Volume::new(value.parse().logerr(key, &value)?),
+            "partition" => self.partition = Some(value),
+            "volume" if value == "-1" => self.volume = None,
+            "volume" => self.volume = Some(Volume::new(value.parse().logerr(key, &value)?)),
             "repeat" => self.repeat = value != "0",
             "random" => self.random = value != "0",
             "single" => self.single = value.parse().logerr(key, &value)?,
@@ -53,17 +52,35 @@ impl FromMpd for Status {
             "songid" => self.songid = Some(value.parse().logerr(key, &value)?),
             "nextsong" => self.nextsong = Some(value.parse().logerr(key, &value)?),
             "nextsongid" => self.nextsongid = Some(value.parse().logerr(key, &value)?),
-            "elapsed" => self.elapsed = Duration::from_secs_f32(value.parse().logerr(key, &value)?),
-            "duration" => self.duration = Duration::from_secs_f32(value.parse().logerr(key, &value)?),
+            "elapsed" => self.elapsed = Duration::from_secs_f64(value.parse().logerr(key, &value)?),
+            "duration" => self.duration = Duration::from_secs_f64(value.parse().logerr(key, &value)?),
             "bitrate" if value != "0" => self.bitrate = Some(value.parse().logerr(key, &value)?),
             "xfade" => self.xfade = Some(value.parse().logerr(key, &value)?),
-            "mixrampdb" => self.mixrampdb = Some(value),
-            "mixrampdelay" => self.mixrampdelay = Some(value),
+            "mixrampdb" => {
+                let db: f32 = value.parse().logerr(key, &value)?;
+                self.mixrampdb = (!db.is_nan()).then_some(db);
+            }
+            // older MPD versions send "nan" when mixramp is disabled
+            "mixrampdelay" => {
+                let delay: f64 = value.parse().logerr(key, &value)?;
+                self.mixrampdelay = (delay.is_finite() && delay >= 0.0).then(|| Duration::from_secs_f64(delay));
+            }
             "audio" => self.audio = Some(value),
             "updating_db" => self.updating_db = Some(value.parse().logerr(key, &value)?),
             "error" => self.error = Some(value),
             "bitrate" => self.bitrate = None,
-            "time" => {} // deprecated
+            // deprecated "elapsed:duration" in whole seconds, used only if the precise values are missing
+            "time" => {
+                let (elapsed, duration) = value
+                    .split_once(':')
+                    .ok_or_else(|| MpdError::Generic(format!("Invalid time value: '{value}'")))?;
+                if self.elapsed.is_zero() {
+                    self.elapsed = Duration::from_secs(elapsed.parse().logerr(key, &value)?);
+                }
+                if self.duration.is_zero() {
+                    self.duration = Duration::from_secs(duration.parse().logerr(key, &value)?);
+                }
+            }
             _ => return Ok(LineHandled::No { value }),
         }
         Ok(LineHandled::Yes)
@@ -95,9 +112,9 @@ pub enum OnOffOneshot {
 impl OnOffOneshot {
     pub fn cycle(self) -> Self {
         match self {
-            OnOffOneshot::On => OnOffOneshot::Off,
-            OnOffOneshot::Off => OnOffOneshot::Oneshot,
-            OnOffOneshot::Oneshot => OnOffOneshot::On,
+            OnOffOneshot::Off => OnOffOneshot::On,
+            OnOffOneshot::On => OnOffOneshot::Oneshot,
+            OnOffOneshot::Oneshot => OnOffOneshot::Off,
         }
     }
 
@@ -109,6 +126,15 @@ impl OnOffOneshot {
         }
     }
 
+    /// Human readable name of the mode for status messages
+    pub fn label(self) -> &'static str {
+        match self {
+            OnOffOneshot::On => "on",
+            OnOffOneshot::Off => "off",
+            OnOffOneshot::Oneshot => "oneshot",
+        }
+    }
+
     pub fn to_mpd_value(self) -> &'static str {
         match self {
             OnOffOneshot::On => "1",
@@ -171,3 +197,101 @@ impl std::str::FromStr for State {
         }
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::mpd::FromMpd;
+
+    use super::{OnOffOneshot, Status, Volume};
+
+    fn parse(lines: &[&str]) -> Status {
+        let mut status = Status::default();
+        for line in lines {
+            status.next((*line).to_owned()).unwrap();
+        }
+        status
+    }
+
+    #[test]
+    fn duration_from_time_only() {
+        let status = parse(&["time: 12:213"]);
+
+        assert_eq!(status.elapsed, Duration::from_secs(12));
+        assert_eq!(status.duration, Duration::from_secs(213));
+    }
+
+    #[test]
+    fn duration_from_duration_only() {
+        let status = parse(&["elapsed: 12.345", "duration: 212.506"]);
+
+        assert_eq!(status.elapsed, Duration::from_secs_f64(12.345));
+        assert_eq!(status.duration, Duration::from_secs_f64(212.506));
+    }
+
+    #[test]
+    fn parses_optional_fields() {
+        let status = parse(&[
+            "partition: default",
+            "xfade: 5",
+            "mixrampdb: -17.000000",
+            "mixrampdelay: 2.500000",
+        ]);
+
+        assert_eq!(status.partition.as_deref(), Some("default"));
+        assert_eq!(status.xfade, Some(5));
+        assert_eq!(status.mixrampdb, Some(-17.0));
+        assert_eq!(status.mixrampdelay, Some(Duration::from_millis(2500)));
+    }
+
+    #[test]
+    fn optional_fields_are_none_when_omitted() {
+        let status = parse(&["volume: 50", "state: play"]);
+
+        assert_eq!(status.partition, None);
+        assert_eq!(status.xfade, None);
+        assert_eq!(status.mixrampdb, None);
+        assert_eq!(status.mixrampdelay, None);
+    }
+
+    #[test]
+    fn volume_is_none_without_mixer() {
+        assert_eq!(parse(&["volume: -1"]).volume, None);
+        assert_eq!(parse(&["volume: 40"]).volume, Some(Volume::new(40)));
+    }
+
+    #[test]
+    fn mixrampdelay_is_none_when_disabled() {
+        let status = parse(&["mixrampdb: 0.000000", "mixrampdelay: nan"]);
+
+        assert_eq!(status.mixrampdb, Some(0.0));
+        assert_eq!(status.mixrampdelay, None);
+    }
+
+    #[test]
+    fn duration_prefers_precise_value() {
+        let status = parse(&["time: 12:213", "elapsed: 12.345", "duration: 212.506"]);
+
+        assert_eq!(status.elapsed, Duration::from_secs_f64(12.345));
+        assert_eq!(status.duration, Duration::from_secs_f64(212.506));
+    }
+
+    #[test]
+    fn cycles_off_on_oneshot() {
+        let modes = std::iter::successors(Some(OnOffOneshot::Off), |mode| Some(mode.cycle()))
+            .take(4)
+            .map(OnOffOneshot::label)
+            .collect::<Vec<_>>();
+
+        assert_eq!(modes, ["off", "on", "oneshot", "off"]);
+    }
+
+    #[test]
+    fn cycles_without_oneshot_before_mpd_24() {
+        assert_eq!(OnOffOneshot::Off.cycle_pre_mpd_24().label(), "on");
+        assert_eq!(OnOffOneshot::On.cycle_pre_mpd_24().label(), "off");
+        assert_eq!(OnOffOneshot::Oneshot.cycle_pre_mpd_24().label(), "off");
+    }
+}
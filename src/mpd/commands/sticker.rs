@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context};
+use derive_more::{AsMut, AsRef, Into, IntoIterator};
+use serde::Serialize;
+
+use crate::mpd::{errors::MpdError, FromMpd, LineHandled};
+
+#[derive(Debug, Serialize, Default, IntoIterator, AsRef, AsMut, Into)]
+pub struct Stickers(pub Vec<Sticker>);
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct Sticker {
+    pub name: String,
+    pub value: String,
+}
+
+impl FromMpd for Stickers {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        if key != "sticker" {
+            return Ok(LineHandled::No { value });
+        }
+
+        let mut sticker = Sticker::default();
+        sticker.next_internal(key, value)?;
+        self.0.push(sticker);
+        Ok(LineHandled::Yes)
+    }
+}
+
+impl FromMpd for Sticker {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        if key != "sticker" {
+            return Ok(LineHandled::No { value });
+        }
+
+        // Sticker names cannot contain '=' but values can, so only the first one separates them
+        let (name, value) = value
+            .split_once('=')
+            .ok_or_else(|| anyhow!("Invalid sticker, expected 'name=value' but got '{}'", value))?;
+        name.clone_into(&mut self.name);
+        value.clone_into(&mut self.value);
+        Ok(LineHandled::Yes)
+    }
+}
+
+/// Response of `sticker find`, every song which has the sticker together with its value
+#[derive(Debug, Default, IntoIterator, AsRef, AsMut, Into)]
+pub struct FoundStickers(pub Vec<FoundSticker>);
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FoundSticker {
+    pub file: String,
+    pub sticker: Sticker,
+}
+
+impl FromMpd for FoundStickers {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        if key == "file" {
+            self.0.push(FoundSticker::default());
+        }
+
+        self.0
+            .last_mut()
+            .context(anyhow!(
+                "No element in accumulator while parsing FoundStickers. Key '{}' Value :'{}'",
+                key,
+                value
+            ))?
+            .next_internal(key, value)
+    }
+}
+
+impl FromMpd for FoundSticker {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        match key {
+            "file" => self.file = value,
+            "sticker" => return self.sticker.next_internal(key, value),
+            _ => return Ok(LineHandled::No { value }),
+        }
+        Ok(LineHandled::Yes)
+    }
+}
+
+/// Name of the sticker holding the rating of a song, a convention shared by several MPD clients
+pub const RATING_STICKER: &str = "rating";
+pub const MAX_RATING: u8 = 5;
+
+/// Ratings of songs by their file, read from the rating sticker. Ratings above [`MAX_RATING`], for example from
+/// clients using a scale to ten, are capped and values which are not a number are ignored.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Ratings(HashMap<String, u8>);
+
+impl Ratings {
+    /// Returns the rating of the song, zero when it has none
+    pub fn get(&self, file: &str) -> u8 {
+        self.0.get(file).copied().unwrap_or_default()
+    }
+
+    pub fn parse(value: &str) -> Option<u8> {
+        value.trim().parse::<u8>().ok().map(|rating| rating.min(MAX_RATING))
+    }
+
+    pub fn stars(rating: u8) -> String {
+        let rating = rating.min(MAX_RATING);
+        format!(
+            "{}{}",
+            "★".repeat(rating.into()),
+            "☆".repeat((MAX_RATING - rating).into())
+        )
+    }
+}
+
+impl FromIterator<FoundSticker> for Ratings {
+    fn from_iter<I: IntoIterator<Item = FoundSticker>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .filter_map(|found| Some((found.file, Self::parse(&found.sticker.value)?)))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::mpd::FromMpd;
+
+    use super::{FoundStickers, Ratings, Sticker, Stickers};
+
+    #[test]
+    fn parses_sticker() {
+        let mut sticker = Sticker::default();
+
+        sticker.next("sticker: rating=5".to_owned()).unwrap();
+
+        assert_eq!(
+            sticker,
+            Sticker {
+                name: "rating".to_owned(),
+                value: "5".to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn splits_only_on_the_first_equals_sign() {
+        let mut sticker = Sticker::default();
+
+        sticker.next("sticker: note=a=b==".to_owned()).unwrap();
+
+        assert_eq!(sticker.name, "note");
+        assert_eq!(sticker.value, "a=b==");
+    }
+
+    #[test]
+    fn parses_empty_value() {
+        let mut sticker = Sticker::default();
+
+        sticker.next("sticker: note=".to_owned()).unwrap();
+
+        assert_eq!(sticker.name, "note");
+        assert_eq!(sticker.value, "");
+    }
+
+    #[test]
+    fn rejects_sticker_without_equals_sign() {
+        let mut sticker = Sticker::default();
+
+        assert!(sticker.next("sticker: rating".to_owned()).is_err());
+    }
+
+    #[test]
+    fn parses_all_stickers() {
+        let mut stickers = Stickers::default();
+        for line in [
+            "sticker: rating=5",
+            "sticker: playcount=12",
+            "sticker: url=http://a?b=c",
+        ] {
+            stickers.next(line.to_owned()).unwrap();
+        }
+
+        let stickers = stickers
+            .0
+            .into_iter()
+            .map(|sticker| (sticker.name, sticker.value))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            stickers,
+            [
+                ("rating".to_owned(), "5".to_owned()),
+                ("playcount".to_owned(), "12".to_owned()),
+                ("url".to_owned(), "http://a?b=c".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_found_stickers() {
+        let mut found = FoundStickers::default();
+        for line in [
+            "file: artist/album/01.flac",
+            "sticker: rating=4",
+            "file: artist/album/02.flac",
+            "sticker: rating=2",
+        ] {
+            found.next(line.to_owned()).unwrap();
+        }
+
+        let found = found
+            .0
+            .into_iter()
+            .map(|found| (found.file, found.sticker.value))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            [
+                ("artist/album/01.flac".to_owned(), "4".to_owned()),
+                ("artist/album/02.flac".to_owned(), "2".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_sticker_before_file() {
+        let mut found = FoundStickers::default();
+
+        assert!(found.next("sticker: rating=4".to_owned()).is_err());
+    }
+
+    #[test]
+    fn collects_ratings_of_found_stickers() {
+        let mut found = FoundStickers::default();
+        for line in [
+            "file: a.flac",
+            "sticker: rating=3",
+            "file: b.flac",
+            "sticker: rating=10",
+            "file: c.flac",
+            "sticker: rating=great",
+        ] {
+            found.next(line.to_owned()).unwrap();
+        }
+
+        let ratings: Ratings = found.into_iter().collect();
+
+        assert_eq!(ratings.get("a.flac"), 3);
+        assert_eq!(ratings.get("b.flac"), 5);
+        assert_eq!(ratings.get("c.flac"), 0);
+        assert_eq!(ratings.get("unrated.flac"), 0);
+    }
+
+    #[test]
+    fn shows_rating_as_stars() {
+        assert_eq!(Ratings::stars(0), "☆☆☆☆☆");
+        assert_eq!(Ratings::stars(3), "★★★☆☆");
+        assert_eq!(Ratings::stars(7), "★★★★★");
+    }
+}
@@ -45,6 +45,35 @@ impl Volume {
     }
 }
 
+/// Remembers the volume from before the output was muted. MPD has no notion of mute so muting
+/// is simply setting the volume to zero, which other clients can do or undo at any time.
+#[derive(Debug, Default, PartialEq)]
+pub struct MuteState {
+    volume_before_mute: Option<Volume>,
+}
+
+impl MuteState {
+    /// Returns the volume to set in order to toggle mute, `None` if there is nothing to unmute to.
+    pub fn toggle(&mut self, current: Volume) -> Option<Volume> {
+        if current.0 == 0 {
+            self.volume_before_mute.take()
+        } else {
+            self.volume_before_mute = Some(current);
+            Some(Volume(0))
+        }
+    }
+
+    /// Keeps the state in sync with volume changes reported by MPD, including the ones made by
+    /// other clients. A change to zero is treated as mute and any other change as unmute.
+    pub fn on_volume_changed(&mut self, old: Volume, new: Volume) {
+        if new.0 != 0 {
+            self.volume_before_mute = None;
+        } else if old.0 != 0 && self.volume_before_mute.is_none() {
+            self.volume_before_mute = Some(old);
+        }
+    }
+}
+
 #[allow(dead_code)]
 pub trait Bound<T> {
     fn value(&self) -> &u8;
@@ -65,3 +94,59 @@ impl FromMpd for Volume {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{MuteState, Volume};
+
+    #[test]
+    fn unmute_restores_volume_before_mute() {
+        let mut state = MuteState::default();
+
+        assert_eq!(state.toggle(Volume::new(60)), Some(Volume::new(0)));
+        state.on_volume_changed(Volume::new(60), Volume::new(0));
+
+        assert_eq!(state.toggle(Volume::new(0)), Some(Volume::new(60)));
+    }
+
+    #[test]
+    fn external_change_while_muted_replaces_previous_volume() {
+        let mut state = MuteState::default();
+        state.toggle(Volume::new(60));
+        state.on_volume_changed(Volume::new(60), Volume::new(0));
+
+        // another client raises the volume and then mutes it again
+        state.on_volume_changed(Volume::new(0), Volume::new(40));
+        state.on_volume_changed(Volume::new(40), Volume::new(0));
+
+        assert_eq!(state.toggle(Volume::new(0)), Some(Volume::new(40)));
+    }
+
+    #[test]
+    fn external_change_while_muted_unmutes() {
+        let mut state = MuteState::default();
+        state.toggle(Volume::new(60));
+        state.on_volume_changed(Volume::new(60), Volume::new(0));
+
+        state.on_volume_changed(Volume::new(0), Volume::new(40));
+
+        assert_eq!(state.toggle(Volume::new(40)), Some(Volume::new(0)));
+        assert_eq!(state.toggle(Volume::new(0)), Some(Volume::new(40)));
+    }
+
+    #[test]
+    fn external_set_to_zero_is_treated_as_mute() {
+        let mut state = MuteState::default();
+
+        state.on_volume_changed(Volume::new(75), Volume::new(0));
+
+        assert_eq!(state.toggle(Volume::new(0)), Some(Volume::new(75)));
+    }
+
+    #[test]
+    fn nothing_to_unmute_to() {
+        let mut state = MuteState::default();
+
+        assert_eq!(state.toggle(Volume::new(0)), None);
+    }
+}
@@ -1,10 +1,12 @@
 use std::{
+    borrow::Cow,
     ops::{Range, RangeInclusive},
     str::FromStr,
 };
 
 use anyhow::Result;
 use derive_more::Deref;
+use itertools::Itertools;
 use strum::AsRefStr;
 
 use crate::shared::{ext::error::ErrorExt, macros::status_error};
@@ -12,8 +14,15 @@ use crate::shared::{ext::error::ErrorExt, macros::status_error};
 use super::{
     client::Client,
     commands::{
-        decoders::Decoders, list::MpdList, list_playlist::FileList, outputs::Outputs, status::OnOffOneshot,
-        volume::Bound, IdleEvent, ListFiles, LsInfo, Mounts, Playlist, Song, Status, Update, Volume,
+        decoders::Decoders,
+        list::{MpdGroupedList, MpdList},
+        list_playlist::FileList,
+        outputs::Outputs,
+        replay_gain::ReplayGainStatus,
+        status::OnOffOneshot,
+        volume::Bound,
+        AddId, Comments, FoundStickers, IdleEvent, ListFiles, LsInfo, Mounts, Playlist, QueuePositions, ReplayGainMode,
+        Song, Status, Sticker, Stickers, Update, Volume,
     },
     errors::{ErrorCode, MpdError, MpdFailureResponse},
     proto_client::ProtoClient,
@@ -22,6 +31,54 @@ use super::{
 
 type MpdResult<T> = Result<T, MpdError>;
 
+/// Order of the MPD commands used to find album art. `albumart` reads a cover file from the directory of the song
+/// while `readpicture` reads a picture embedded in the song itself.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AlbumArtSource {
+    AlbumArt,
+    ReadPicture,
+    AlbumArtThenReadPicture,
+    #[default]
+    ReadPictureThenAlbumArt,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AlbumArtCommand {
+    AlbumArt,
+    ReadPicture,
+}
+
+impl AlbumArtSource {
+    fn commands(self) -> &'static [AlbumArtCommand] {
+        match self {
+            AlbumArtSource::AlbumArt => &[AlbumArtCommand::AlbumArt],
+            AlbumArtSource::ReadPicture => &[AlbumArtCommand::ReadPicture],
+            AlbumArtSource::AlbumArtThenReadPicture => &[AlbumArtCommand::AlbumArt, AlbumArtCommand::ReadPicture],
+            AlbumArtSource::ReadPictureThenAlbumArt => &[AlbumArtCommand::ReadPicture, AlbumArtCommand::AlbumArt],
+        }
+    }
+}
+
+/// Runs the commands of the source in order until one of them finds album art. Art which does not exist is not an
+/// error and falls through to the next command, any other error stops the search.
+fn find_first_album_art(
+    source: AlbumArtSource,
+    mut fetch: impl FnMut(AlbumArtCommand) -> MpdResult<Option<Vec<u8>>>,
+) -> MpdResult<Option<Vec<u8>>> {
+    for command in source.commands() {
+        match fetch(*command) {
+            Ok(Some(data)) => return Ok(Some(data)),
+            Ok(None)
+            | Err(MpdError::Mpd(MpdFailureResponse {
+                code: ErrorCode::NoExist,
+                ..
+            })) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(None)
+}
+
 #[derive(AsRefStr, Debug)]
 #[allow(dead_code)]
 pub enum SaveMode {
@@ -33,6 +90,7 @@ pub enum SaveMode {
     Replace,
 }
 
+#[derive(Debug)]
 pub enum ValueChange {
     Increase(u32),
     Decrease(u32),
@@ -71,12 +129,15 @@ pub trait MpdClient {
     fn rescan(&mut self, path: Option<&str>) -> MpdResult<Update>;
     fn idle(&mut self, subsystem: Option<IdleEvent>) -> MpdResult<Vec<IdleEvent>>;
     fn noidle(&mut self) -> MpdResult<()>;
-    fn get_volume(&mut self) -> MpdResult<Volume>;
+    /// Current volume, `None` when MPD has no mixer
+    fn get_volume(&mut self) -> MpdResult<Option<Volume>>;
     fn set_volume(&mut self, volume: Volume) -> MpdResult<()>;
     /// Set playback volume relative to current
     fn volume(&mut self, change: ValueChange) -> MpdResult<()>;
     fn get_current_song(&mut self) -> MpdResult<Option<Song>>;
     fn get_status(&mut self) -> MpdResult<Status>;
+    /// Clears the error MPD keeps in its status, for example after a song failed to decode
+    fn clear_error(&mut self) -> MpdResult<()>;
     // Playback control
     fn pause_toggle(&mut self) -> MpdResult<()>;
     fn pause(&mut self) -> MpdResult<()>;
@@ -92,45 +153,87 @@ pub trait MpdClient {
     fn random(&mut self, enabled: bool) -> MpdResult<()>;
     fn single(&mut self, single: OnOffOneshot) -> MpdResult<()>;
     fn consume(&mut self, consume: OnOffOneshot) -> MpdResult<()>;
+    fn replay_gain_status(&mut self) -> MpdResult<ReplayGainMode>;
+    fn set_replay_gain_mode(&mut self, mode: ReplayGainMode) -> MpdResult<()>;
+    /// Sets the crossfade between songs in seconds, zero disables it
+    fn set_crossfade(&mut self, secs: u32) -> MpdResult<()>;
+    /// Sets the threshold at which songs are overlapped when using `MixRamp`, in decibels
+    fn set_mixramp_db(&mut self, db: f32) -> MpdResult<()>;
     // Mounts
     fn mount(&mut self, name: &str, path: &str) -> MpdResult<()>;
     fn unmount(&mut self, name: &str) -> MpdResult<()>;
     fn list_mounts(&mut self) -> MpdResult<Mounts>;
     // Current queue
-    fn add(&mut self, path: &str) -> MpdResult<()>;
+    fn add(&mut self, path: &str, position: Option<QueueMoveTarget>) -> MpdResult<()>;
+    fn add_id(&mut self, path: &str, position: Option<usize>) -> MpdResult<AddId>;
+    /// Adds all given paths in a single command list, in the given order
+    fn add_multiple(&mut self, paths: &[&str], position: Option<QueueMoveTarget>) -> MpdResult<()>;
     fn clear(&mut self) -> MpdResult<()>;
     fn delete_id(&mut self, id: u32) -> MpdResult<()>;
     fn delete_from_queue(&mut self, songs: SingleOrRange) -> MpdResult<()>;
+    /// Deletes all given ranges in a single command list. Ranges are deleted in the given
+    /// order so they should be sorted from the end of the queue to keep the indices valid.
+    fn delete_ranges_from_queue(&mut self, ranges: &[SingleOrRange]) -> MpdResult<()>;
     fn playlist_info(&mut self) -> MpdResult<Option<Vec<Song>>>;
     fn find(&mut self, filter: &[Filter<'_, '_>]) -> MpdResult<Vec<Song>>;
     fn search(&mut self, filter: &[Filter<'_, '_>]) -> MpdResult<Vec<Song>>;
+    fn queue_find(&mut self, filter: &[Filter<'_, '_>]) -> MpdResult<QueuePositions>;
+    fn queue_search(&mut self, filter: &[Filter<'_, '_>]) -> MpdResult<QueuePositions>;
     fn move_in_queue(&mut self, from: SingleOrRange, to: QueueMoveTarget) -> MpdResult<()>;
     fn move_id(&mut self, id: u32, to: QueueMoveTarget) -> MpdResult<()>;
     fn find_one(&mut self, filter: &[Filter<'_, '_>]) -> MpdResult<Option<Song>>;
-    fn find_add(&mut self, filter: &[Filter<'_, '_>]) -> MpdResult<()>;
-    fn search_add(&mut self, filter: &[Filter<'_, '_>]) -> MpdResult<()>;
+    fn find_add(&mut self, filter: &[Filter<'_, '_>], position: Option<QueueMoveTarget>) -> MpdResult<()>;
+    fn find_with(&mut self, filter: &[Filter<'_, '_>], options: FindOptions<'_>) -> MpdResult<Vec<Song>>;
+    fn find_add_with(
+        &mut self,
+        filter: &[Filter<'_, '_>],
+        options: FindOptions<'_>,
+        position: Option<QueueMoveTarget>,
+    ) -> MpdResult<()>;
+    fn search_add(&mut self, filter: &[Filter<'_, '_>], position: Option<QueueMoveTarget>) -> MpdResult<()>;
+    fn find_expression(&mut self, expression: &FilterExpression<'_, '_>) -> MpdResult<Vec<Song>>;
+    fn search_expression(&mut self, expression: &FilterExpression<'_, '_>) -> MpdResult<Vec<Song>>;
+    fn find_add_expression(
+        &mut self,
+        expression: &FilterExpression<'_, '_>,
+        position: Option<QueueMoveTarget>,
+    ) -> MpdResult<()>;
+    fn search_add_expression(
+        &mut self,
+        expression: &FilterExpression<'_, '_>,
+        position: Option<QueueMoveTarget>,
+    ) -> MpdResult<()>;
     fn list_tag(&mut self, tag: Tag, filter: Option<&[Filter<'_, '_>]>) -> MpdResult<MpdList>;
+    fn list_tag_grouped(
+        &mut self,
+        tag: Tag,
+        groups: &[Tag],
+        filter: Option<&[Filter<'_, '_>]>,
+    ) -> MpdResult<Vec<(Vec<String>, String)>>;
     // Database
     fn lsinfo(&mut self, path: Option<&str>) -> MpdResult<LsInfo>;
     fn list_files(&mut self, path: Option<&str>) -> MpdResult<ListFiles>;
     fn read_picture(&mut self, path: &str) -> MpdResult<Option<Vec<u8>>>;
     fn albumart(&mut self, path: &str) -> MpdResult<Option<Vec<u8>>>;
+    /// Reads all tags of the song including those which MPD does not support, e.g. embedded lyrics
+    fn read_comments(&mut self, path: &str) -> MpdResult<Comments>;
     // Stored playlists
     fn list_playlists(&mut self) -> MpdResult<Vec<Playlist>>;
     fn list_playlist(&mut self, name: &str) -> MpdResult<FileList>;
     fn list_playlist_info(&mut self, playlist: &str, range: Option<SingleOrRange>) -> MpdResult<Vec<Song>>;
-    fn load_playlist(&mut self, name: &str) -> MpdResult<()>;
+    fn load_playlist(&mut self, name: &str, position: Option<QueueMoveTarget>) -> MpdResult<()>;
     fn rename_playlist(&mut self, name: &str, new_name: &str) -> MpdResult<()>;
     fn delete_playlist(&mut self, name: &str) -> MpdResult<()>;
     fn delete_from_playlist(&mut self, playlist_name: &str, songs: &SingleOrRange) -> MpdResult<()>;
     fn move_in_playlist(&mut self, playlist_name: &str, range: &SingleOrRange, target_position: usize)
         -> MpdResult<()>;
     fn add_to_playlist(&mut self, playlist_name: &str, uri: &str, target_position: Option<usize>) -> MpdResult<()>;
+    /// Appends all given songs to the end of the playlist in a single command list
+    fn add_songs_to_playlist(&mut self, playlist_name: &str, uris: &[String]) -> MpdResult<()>;
     fn save_queue_as_playlist(&mut self, name: &str, mode: Option<SaveMode>) -> MpdResult<()>;
-    /// This function first invokes [`Self::albumart`].
-    /// If no album art is fonud it invokes [`Self::read_picture`].
-    /// If no art is still found, but no errors were encountered, None is returned.
-    fn find_album_art(&mut self, path: &str) -> MpdResult<Option<Vec<u8>>>;
+    /// This function invokes [`Self::albumart`] and [`Self::read_picture`] in the order given by `source`.
+    /// If no art is found by any of them, but no errors were encountered, None is returned.
+    fn find_album_art(&mut self, path: &str, source: AlbumArtSource) -> MpdResult<Option<Vec<u8>>>;
     // Outputs
     fn outputs(&mut self) -> MpdResult<Outputs>;
     fn toggle_output(&mut self, id: u32) -> MpdResult<()>;
@@ -138,6 +241,14 @@ pub trait MpdClient {
     fn disable_output(&mut self, id: u32) -> MpdResult<()>;
     // Decoders
     fn decoders(&mut self) -> MpdResult<Decoders>;
+    // Stickers
+    /// Returns the sticker of the song, `None` when the song has no sticker with the given name
+    fn sticker_get(&mut self, uri: &str, name: &str) -> MpdResult<Option<Sticker>>;
+    fn sticker_set(&mut self, uri: &str, name: &str, value: &str) -> MpdResult<()>;
+    fn sticker_delete(&mut self, uri: &str, name: &str) -> MpdResult<()>;
+    fn sticker_list(&mut self, uri: &str) -> MpdResult<Stickers>;
+    /// Finds all songs in the directory which have the sticker, an empty uri searches the whole database
+    fn sticker_find(&mut self, uri: &str, name: &str) -> MpdResult<FoundStickers>;
 }
 
 impl MpdClient for Client<'_> {
@@ -192,11 +303,12 @@ impl MpdClient for Client<'_> {
         self.send("noidle").and_then(ProtoClient::read_ok)
     }
 
-    fn get_volume(&mut self) -> MpdResult<Volume> {
+    fn get_volume(&mut self) -> MpdResult<Option<Volume>> {
         if self.version < Version::new(0, 23, 0) {
             Err(MpdError::UnsupportedMpdVersion("getvol can be used since MPD 0.23.0"))
         } else {
-            self.send("getvol").and_then(ProtoClient::read_response)
+            // The response is empty when there is no mixer
+            self.send("getvol").and_then(ProtoClient::read_opt_response)
         }
     }
 
@@ -222,6 +334,10 @@ impl MpdClient for Client<'_> {
         self.send("status").and_then(ProtoClient::read_response)
     }
 
+    fn clear_error(&mut self) -> MpdResult<()> {
+        self.send("clearerror").and_then(ProtoClient::read_ok)
+    }
+
     // Playback control
     fn pause_toggle(&mut self) -> MpdResult<()> {
         self.send("pause").and_then(ProtoClient::read_ok)
@@ -290,6 +406,25 @@ impl MpdClient for Client<'_> {
         }
     }
 
+    fn replay_gain_status(&mut self) -> MpdResult<ReplayGainMode> {
+        self.send("replay_gain_status")
+            .and_then(ProtoClient::read_response::<ReplayGainStatus>)
+            .map(|status| status.mode)
+    }
+
+    fn set_replay_gain_mode(&mut self, mode: ReplayGainMode) -> MpdResult<()> {
+        self.send(&format!("replay_gain_mode {}", mode.to_mpd_value()))
+            .and_then(ProtoClient::read_ok)
+    }
+
+    fn set_crossfade(&mut self, secs: u32) -> MpdResult<()> {
+        self.send(&format!("crossfade {secs}")).and_then(ProtoClient::read_ok)
+    }
+
+    fn set_mixramp_db(&mut self, db: f32) -> MpdResult<()> {
+        self.send(&format!("mixrampdb {db}")).and_then(ProtoClient::read_ok)
+    }
+
     // Mounts
     fn mount(&mut self, name: &str, path: &str) -> MpdResult<()> {
         self.send(&format!("mount \"{name}\" \"{path}\""))
@@ -305,8 +440,37 @@ impl MpdClient for Client<'_> {
     }
 
     // Current queue
-    fn add(&mut self, path: &str) -> MpdResult<()> {
-        self.send(&format!("add \"{path}\"")).and_then(ProtoClient::read_ok)
+    fn add(&mut self, path: &str, position: Option<QueueMoveTarget>) -> MpdResult<()> {
+        if let Some(position) = position {
+            self.send(&format!("add \"{path}\" {}", position.as_mpd_str()))
+                .and_then(ProtoClient::read_ok)
+        } else {
+            self.send(&format!("add \"{path}\"")).and_then(ProtoClient::read_ok)
+        }
+    }
+
+    fn add_multiple(&mut self, paths: &[&str], position: Option<QueueMoveTarget>) -> MpdResult<()> {
+        if paths.is_empty() {
+            return Ok(());
+        }
+        let position = position.map(|position| format!(" {}", position.as_mpd_str()));
+        let commands = paths
+            .iter()
+            .map(|path| format!("add \"{path}\"{}", position.as_deref().unwrap_or_default()))
+            .join("\n");
+        self.send(&format!("command_list_ok_begin\n{commands}\ncommand_list_end"))
+            .and_then(ProtoClient::read_list_response::<MpdList>)
+            .map(|_| ())
+    }
+
+    fn add_id(&mut self, path: &str, position: Option<usize>) -> MpdResult<AddId> {
+        if let Some(position) = position {
+            self.send(&format!("addid \"{path}\" {position}"))
+                .and_then(ProtoClient::read_response)
+        } else {
+            self.send(&format!("addid \"{path}\""))
+                .and_then(ProtoClient::read_response)
+        }
     }
 
     fn clear(&mut self) -> MpdResult<()> {
@@ -322,6 +486,16 @@ impl MpdClient for Client<'_> {
             .and_then(ProtoClient::read_ok)
     }
 
+    fn delete_ranges_from_queue(&mut self, ranges: &[SingleOrRange]) -> MpdResult<()> {
+        let commands = ranges
+            .iter()
+            .map(|range| format!("delete {}", range.as_mpd_range()))
+            .collect::<Vec<_>>()
+            .join("\n");
+        self.send(&format!("command_list_begin\n{commands}\ncommand_list_end"))
+            .and_then(ProtoClient::read_ok)
+    }
+
     fn move_id(&mut self, id: u32, to: QueueMoveTarget) -> MpdResult<()> {
         self.send(&format!("moveid \"{id}\" \"{}\"", to.as_mpd_str()))
             .and_then(ProtoClient::read_ok)
@@ -338,51 +512,168 @@ impl MpdClient for Client<'_> {
 
     /// Search the database for songs matching FILTER
     fn find(&mut self, filter: &[Filter<'_, '_>]) -> MpdResult<Vec<Song>> {
-        self.send(&format!("find \"({})\"", filter.to_query_str()))
+        self.send(&format!("find {}", filter.to_query_str(self.version)))
             .and_then(ProtoClient::read_response)
     }
 
     /// Search the database for songs matching FILTER (see Filters).
     /// Parameters have the same meaning as for find, except that search is not case sensitive.
     fn search(&mut self, filter: &[Filter<'_, '_>]) -> MpdResult<Vec<Song>> {
-        let query = filter.to_query_str();
+        let query = filter.to_query_str(self.version);
         let query = query.as_str();
         log::debug!(query; "Searching for songs");
-        self.send(&format!("search \"({query})\""))
+        self.send(&format!("search {query}"))
+            .and_then(ProtoClient::read_response)
+    }
+
+    /// Positions of the songs in the queue matching FILTER
+    fn queue_find(&mut self, filter: &[Filter<'_, '_>]) -> MpdResult<QueuePositions> {
+        self.send(&format!("playlistfind {}", filter.to_query_str(self.version)))
+            .and_then(ProtoClient::read_response)
+    }
+
+    /// Same as `queue_find` except that the matching is not case sensitive
+    fn queue_search(&mut self, filter: &[Filter<'_, '_>]) -> MpdResult<QueuePositions> {
+        self.send(&format!("playlistsearch {}", filter.to_query_str(self.version)))
             .and_then(ProtoClient::read_response)
     }
 
     /// Search the database for songs matching FILTER (see Filters) AND add them to queue.
     /// Parameters have the same meaning as for find, except that search is not case sensitive.
-    fn search_add(&mut self, filter: &[Filter<'_, '_>]) -> MpdResult<()> {
-        let query = filter.to_query_str();
+    fn search_add(&mut self, filter: &[Filter<'_, '_>], position: Option<QueueMoveTarget>) -> MpdResult<()> {
+        let query = filter.to_query_str(self.version);
         let query = query.as_str();
         log::debug!(query; "Searching for songs and adding them");
-        self.send(&format!("searchadd \"({query})\""))
-            .and_then(ProtoClient::read_ok)
+        if let Some(position) = position {
+            self.send(&format!("searchadd {query} position {}", position.as_mpd_str()))
+                .and_then(ProtoClient::read_ok)
+        } else {
+            self.send(&format!("searchadd {query}")).and_then(ProtoClient::read_ok)
+        }
     }
 
     fn find_one(&mut self, filter: &[Filter<'_, '_>]) -> MpdResult<Option<Song>> {
         Ok(self
-            .send(&format!("find \"({})\"", filter.to_query_str()))
+            .send(&format!("find {}", filter.to_query_str(self.version)))
             .and_then(ProtoClient::read_response::<Vec<Song>>)?
             .pop())
     }
 
-    fn find_add(&mut self, filter: &[Filter<'_, '_>]) -> MpdResult<()> {
-        self.send(&format!("findadd \"({})\"", filter.to_query_str()))
+    fn find_add(&mut self, filter: &[Filter<'_, '_>], position: Option<QueueMoveTarget>) -> MpdResult<()> {
+        if let Some(position) = position {
+            self.send(&format!(
+                "findadd {} position {}",
+                filter.to_query_str(self.version),
+                position.as_mpd_str()
+            ))
+            .and_then(ProtoClient::read_ok)
+        } else {
+            self.send(&format!("findadd {}", filter.to_query_str(self.version)))
+                .and_then(ProtoClient::read_ok)
+        }
+    }
+
+    /// Same as find but the results can be sorted and limited to a window
+    fn find_with(&mut self, filter: &[Filter<'_, '_>], options: FindOptions<'_>) -> MpdResult<Vec<Song>> {
+        self.send(&format!(
+            "find {}{}",
+            filter.to_query_str(self.version),
+            options.to_mpd_str()
+        ))
+        .and_then(ProtoClient::read_response)
+    }
+
+    /// Same as `find_add` but the added songs can be sorted and limited to a window
+    fn find_add_with(
+        &mut self,
+        filter: &[Filter<'_, '_>],
+        options: FindOptions<'_>,
+        position: Option<QueueMoveTarget>,
+    ) -> MpdResult<()> {
+        let position = position.map_or_else(String::new, |position| format!(" position {}", position.as_mpd_str()));
+        self.send(&format!(
+            "findadd {}{}{position}",
+            filter.to_query_str(self.version),
+            options.to_mpd_str()
+        ))
+        .and_then(ProtoClient::read_ok)
+    }
+
+    /// Same as find but with a filter expression which can be negated and combined with OR
+    fn find_expression(&mut self, expression: &FilterExpression<'_, '_>) -> MpdResult<Vec<Song>> {
+        self.send(&format!("find {}", expression.to_query_str(self.version)?))
+            .and_then(ProtoClient::read_response)
+    }
+
+    /// Same as search but with a filter expression which can be negated and combined with OR
+    fn search_expression(&mut self, expression: &FilterExpression<'_, '_>) -> MpdResult<Vec<Song>> {
+        let query = expression.to_query_str(self.version)?;
+        log::debug!(query:?; "Searching for songs");
+        self.send(&format!("search {query}"))
+            .and_then(ProtoClient::read_response)
+    }
+
+    /// Same as `find_add` but with a filter expression which can be negated and combined with OR
+    fn find_add_expression(
+        &mut self,
+        expression: &FilterExpression<'_, '_>,
+        position: Option<QueueMoveTarget>,
+    ) -> MpdResult<()> {
+        let position = position.map_or_else(String::new, |position| format!(" position {}", position.as_mpd_str()));
+        self.send(&format!("findadd {}{position}", expression.to_query_str(self.version)?))
+            .and_then(ProtoClient::read_ok)
+    }
+
+    /// Same as `search_add` but with a filter expression which can be negated and combined with OR
+    fn search_add_expression(
+        &mut self,
+        expression: &FilterExpression<'_, '_>,
+        position: Option<QueueMoveTarget>,
+    ) -> MpdResult<()> {
+        let query = expression.to_query_str(self.version)?;
+        log::debug!(query:?; "Searching for songs and adding them");
+        let position = position.map_or_else(String::new, |position| format!(" position {}", position.as_mpd_str()));
+        self.send(&format!("searchadd {query}{position}"))
             .and_then(ProtoClient::read_ok)
     }
 
     fn list_tag(&mut self, tag: Tag, filter: Option<&[Filter<'_, '_>]>) -> MpdResult<MpdList> {
         self.send(&if let Some(filter) = filter {
-            format!("list {} \"({})\"", tag.as_str(), filter.to_query_str())
+            format!("list {} {}", tag.as_str(), filter.to_query_str(self.version))
         } else {
             format!("list {}", tag.as_str())
         })
         .and_then(ProtoClient::read_response)
     }
 
+    /// Lists values of the tag paired with the values of the group tags of the songs they come
+    /// from, in the order of `groups`
+    fn list_tag_grouped(
+        &mut self,
+        tag: Tag,
+        groups: &[Tag],
+        filter: Option<&[Filter<'_, '_>]>,
+    ) -> MpdResult<Vec<(Vec<String>, String)>> {
+        let group_keys: Vec<_> = groups.iter().map(Tag::as_str).collect();
+        let group_clause = group_keys.iter().fold(String::new(), |mut acc, group| {
+            acc.push_str(" group ");
+            acc.push_str(group);
+            acc
+        });
+        let list: MpdGroupedList = self
+            .send(&if let Some(filter) = filter {
+                format!(
+                    "list {} {}{group_clause}",
+                    tag.as_str(),
+                    filter.to_query_str(self.version)
+                )
+            } else {
+                format!("list {}{group_clause}", tag.as_str())
+            })
+            .and_then(ProtoClient::read_response)?;
+        Ok(list.into_grouped(&group_keys))
+    }
+
     // Database
     fn lsinfo(&mut self, path: Option<&str>) -> MpdResult<LsInfo> {
         Ok(if let Some(path) = path {
@@ -431,8 +722,14 @@ impl MpdClient for Client<'_> {
                 .and_then(ProtoClient::read_response)
         }
     }
-    fn load_playlist(&mut self, name: &str) -> MpdResult<()> {
-        self.send(&format!("load \"{name}\"")).and_then(ProtoClient::read_ok)
+    fn load_playlist(&mut self, name: &str, position: Option<QueueMoveTarget>) -> MpdResult<()> {
+        if let Some(position) = position {
+            // Position can only be given together with a range, "0:" is the whole playlist
+            self.send(&format!("load \"{name}\" 0: {}", position.as_mpd_str()))
+                .and_then(ProtoClient::read_ok)
+        } else {
+            self.send(&format!("load \"{name}\"")).and_then(ProtoClient::read_ok)
+        }
     }
     fn delete_playlist(&mut self, name: &str) -> MpdResult<()> {
         self.send(&format!("rm \"{name}\"")).and_then(ProtoClient::read_ok)
@@ -465,6 +762,16 @@ impl MpdClient for Client<'_> {
         }
     }
 
+    fn add_songs_to_playlist(&mut self, playlist_name: &str, uris: &[String]) -> MpdResult<()> {
+        let commands = uris
+            .iter()
+            .map(|uri| format!(r#"playlistadd "{playlist_name}" "{uri}""#))
+            .collect::<Vec<_>>()
+            .join("\n");
+        self.send(&format!("command_list_begin\n{commands}\ncommand_list_end"))
+            .and_then(ProtoClient::read_ok)
+    }
+
     fn rename_playlist(&mut self, name: &str, new_name: &str) -> MpdResult<()> {
         self.send(&format!("rename \"{name}\" \"{new_name}\""))
             .and_then(ProtoClient::read_ok)
@@ -494,31 +801,22 @@ impl MpdClient for Client<'_> {
             .and_then(ProtoClient::read_bin)
     }
 
-    fn find_album_art(&mut self, path: &str) -> MpdResult<Option<Vec<u8>>> {
-        match self.albumart(path) {
-            Ok(Some(v)) => Ok(Some(v)),
-            Ok(None)
-            | Err(MpdError::Mpd(MpdFailureResponse {
-                code: ErrorCode::NoExist,
-                ..
-            })) => match self.read_picture(path) {
-                Ok(Some(p)) => Ok(Some(p)),
-                Ok(None) => {
-                    log::debug!("No album art found, falling back to placeholder image");
-                    Ok(None)
-                }
-                Err(MpdError::Mpd(MpdFailureResponse {
-                    code: ErrorCode::NoExist,
-                    ..
-                })) => {
-                    log::debug!("No album art found, falling back to placeholder image");
-                    Ok(None)
-                }
-                Err(e) => {
-                    status_error!(error:? = e; "Failed to read picture. {}", e.to_status());
-                    Ok(None)
-                }
-            },
+    fn read_comments(&mut self, path: &str) -> MpdResult<Comments> {
+        self.send(&format!("readcomments \"{path}\""))
+            .and_then(ProtoClient::read_response)
+    }
+
+    fn find_album_art(&mut self, path: &str, source: AlbumArtSource) -> MpdResult<Option<Vec<u8>>> {
+        let result = find_first_album_art(source, |command| match command {
+            AlbumArtCommand::AlbumArt => self.albumart(path),
+            AlbumArtCommand::ReadPicture => self.read_picture(path),
+        });
+        match result {
+            Ok(Some(data)) => Ok(Some(data)),
+            Ok(None) => {
+                log::debug!("No album art found, falling back to placeholder image");
+                Ok(None)
+            }
             Err(e) => {
                 status_error!(error:? = e; "Failed to read picture. {}", e.to_status());
                 Ok(None)
@@ -547,9 +845,43 @@ impl MpdClient for Client<'_> {
     fn decoders(&mut self) -> MpdResult<Decoders> {
         self.send("decoders").and_then(ProtoClient::read_response)
     }
+
+    // Stickers
+    fn sticker_get(&mut self, uri: &str, name: &str) -> MpdResult<Option<Sticker>> {
+        match self
+            .send(&format!("sticker get song \"{uri}\" \"{name}\""))
+            .and_then(ProtoClient::read_opt_response)
+        {
+            Err(MpdError::Mpd(MpdFailureResponse {
+                code: ErrorCode::NoExist,
+                ..
+            })) => Ok(None),
+            result => result,
+        }
+    }
+
+    fn sticker_set(&mut self, uri: &str, name: &str, value: &str) -> MpdResult<()> {
+        self.send(&format!("sticker set song \"{uri}\" \"{name}\" \"{value}\""))
+            .and_then(ProtoClient::read_ok)
+    }
+
+    fn sticker_delete(&mut self, uri: &str, name: &str) -> MpdResult<()> {
+        self.send(&format!("sticker delete song \"{uri}\" \"{name}\""))
+            .and_then(ProtoClient::read_ok)
+    }
+
+    fn sticker_list(&mut self, uri: &str) -> MpdResult<Stickers> {
+        self.send(&format!("sticker list song \"{uri}\""))
+            .and_then(ProtoClient::read_response)
+    }
+
+    fn sticker_find(&mut self, uri: &str, name: &str) -> MpdResult<FoundStickers> {
+        self.send(&format!("sticker find song \"{uri}\" \"{name}\""))
+            .and_then(ProtoClient::read_response)
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 #[allow(dead_code)]
 pub enum QueueMoveTarget {
     /// relative to the currently playing song; e.g. +0 moves to right after the current song
@@ -607,6 +939,26 @@ impl SingleOrRange {
     }
 }
 
+/// Sort and window modifiers of the find family of commands
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct FindOptions<'a> {
+    /// Tag to sort the results by, prefixed with `-` for descending order
+    pub sort: Option<&'a str>,
+    /// Only the results in this range are returned
+    pub window: Option<SingleOrRange>,
+}
+
+impl FindOptions<'_> {
+    fn to_mpd_str(self) -> String {
+        let sort = self.sort.map(|sort| format!(" sort {sort}")).unwrap_or_default();
+        let window = self
+            .window
+            .map(|window| format!(" window {}", window.as_mpd_range()))
+            .unwrap_or_default();
+        format!("{sort}{window}")
+    }
+}
+
 trait StrExt {
     fn escape(self) -> String;
 }
@@ -630,6 +982,7 @@ pub enum Tag<'custom> {
     Title,
     File,
     Genre,
+    Date,
     Custom(&'custom str),
 }
 
@@ -643,6 +996,7 @@ impl Tag<'_> {
             Tag::Title => "Title",
             Tag::File => "File",
             Tag::Genre => "Genre",
+            Tag::Date => "Date",
             Tag::Custom(v) => v,
         }
     }
@@ -661,7 +1015,7 @@ pub enum FilterKind {
 #[derive(Debug)]
 pub struct Filter<'tag, 'value> {
     pub tag: Tag<'tag>,
-    pub value: &'value str,
+    pub value: Cow<'value, str>,
     pub kind: FilterKind,
 }
 
@@ -673,18 +1027,18 @@ impl<'tag> From<&'tag str> for Tag<'tag> {
 
 #[allow(dead_code)]
 impl<'tag, 'value> Filter<'tag, 'value> {
-    pub fn new<T: Into<Tag<'tag>>>(tag: T, value: &'value str) -> Self {
+    pub fn new<T: Into<Tag<'tag>>>(tag: T, value: impl Into<Cow<'value, str>>) -> Self {
         Self {
             tag: tag.into(),
-            value,
+            value: value.into(),
             kind: FilterKind::Exact,
         }
     }
 
-    pub fn new_with_kind<T: Into<Tag<'tag>>>(tag: T, value: &'value str, kind: FilterKind) -> Self {
+    pub fn new_with_kind<T: Into<Tag<'tag>>>(tag: T, value: impl Into<Cow<'value, str>>, kind: FilterKind) -> Self {
         Self {
             tag: tag.into(),
-            value,
+            value: value.into(),
             kind,
         }
     }
@@ -696,27 +1050,131 @@ impl<'tag, 'value> Filter<'tag, 'value> {
 
     fn to_query_str(&self) -> String {
         match self.kind {
-            FilterKind::Exact => format!("{} == '{}'", self.tag.as_str(), self.value.escape()),
-            FilterKind::StartsWith => format!("{} =~ '^{}'", self.tag.as_str(), self.value.escape()),
-            FilterKind::Contains => format!("{} =~ '.*{}.*'", self.tag.as_str(), self.value.escape()),
-            FilterKind::Regex => format!("{} =~ '{}'", self.tag.as_str(), self.value.escape()),
+            FilterKind::Exact => format!("{} == '{}'", self.tag.as_str(), self.value.as_ref().escape()),
+            FilterKind::StartsWith => format!("{} =~ '^{}'", self.tag.as_str(), self.value.as_ref().escape()),
+            FilterKind::Contains => format!("{} =~ '.*{}.*'", self.tag.as_str(), self.value.as_ref().escape()),
+            FilterKind::Regex => format!("{} =~ '{}'", self.tag.as_str(), self.value.as_ref().escape()),
         }
     }
+
+    /// Classic `TAG "VALUE"` form understood by MPD older than 0.21. It has no notion of the
+    /// filter kind, the matching is decided by the command instead.
+    fn to_classic_query_str(&self) -> String {
+        if !matches!(self.kind, FilterKind::Exact) {
+            log::warn!(tag = self.tag.as_str(), kind:? = self.kind; "Filter kind is not supported by MPD older than 0.21, matching by value only");
+        }
+        format!(
+            "{} \"{}\"",
+            self.tag.as_str(),
+            self.value.replace('\\', "\\\\").replace('"', "\\\"")
+        )
+    }
 }
 
 trait FilterExt {
-    fn to_query_str(&self) -> String;
+    /// Serializes the filters as command arguments. Filter expressions are used when the server
+    /// supports them (MPD 0.21+), the classic form otherwise.
+    fn to_query_str(&self, version: Version) -> String;
 }
 impl FilterExt for &[Filter<'_, '_>] {
-    fn to_query_str(&self) -> String {
-        self.iter().enumerate().fold(String::new(), |mut acc, (idx, filter)| {
+    fn to_query_str(&self, version: Version) -> String {
+        if version < Version::new(0, 21, 0) {
+            return self.iter().map(Filter::to_classic_query_str).join(" ");
+        }
+
+        let expression = self.iter().enumerate().fold(String::new(), |mut acc, (idx, filter)| {
             if idx > 0 {
                 acc.push_str(&format!(" AND ({})", filter.to_query_str()));
             } else {
                 acc.push_str(&format!("({})", filter.to_query_str()));
             }
             acc
-        })
+        });
+        format!("\"({expression})\"")
+    }
+}
+
+/// Filters combined with AND, OR and NOT. MPD has no OR operator so `A OR B` is sent as
+/// `!(!A AND !B)`. Servers older than 0.21 only understand a plain AND of filters.
+#[derive(Debug)]
+pub enum FilterExpression<'tag, 'value> {
+    Filter(Filter<'tag, 'value>),
+    Not(Box<FilterExpression<'tag, 'value>>),
+    And(Vec<FilterExpression<'tag, 'value>>),
+    Or(Vec<FilterExpression<'tag, 'value>>),
+}
+
+impl<'tag, 'value> From<Filter<'tag, 'value>> for FilterExpression<'tag, 'value> {
+    fn from(filter: Filter<'tag, 'value>) -> Self {
+        Self::Filter(filter)
+    }
+}
+
+impl FilterExpression<'_, '_> {
+    pub fn not(expression: impl Into<Self>) -> Self {
+        Self::Not(Box::new(expression.into()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match self {
+            FilterExpression::Filter(_) => false,
+            FilterExpression::Not(expression) => expression.is_empty(),
+            FilterExpression::And(expressions) | FilterExpression::Or(expressions) => {
+                expressions.iter().all(FilterExpression::is_empty)
+            }
+        }
+    }
+
+    /// Serializes the expression as a command argument
+    pub fn to_query_str(&self, version: Version) -> MpdResult<String> {
+        if version < Version::new(0, 21, 0) {
+            return self.to_classic_query_str().ok_or(MpdError::UnsupportedMpdVersion(
+                "negated and OR filters can be used since MPD 0.21.0",
+            ));
+        }
+
+        Ok(format!("\"{}\"", self.to_expression_str()))
+    }
+
+    fn to_expression_str(&self) -> String {
+        match self {
+            FilterExpression::Filter(filter) => format!("({})", filter.to_query_str()),
+            FilterExpression::Not(expression) => format!("(!{})", expression.to_expression_str()),
+            FilterExpression::And(expressions) => match Self::non_empty(expressions)[..] {
+                [expression] => expression.to_expression_str(),
+                ref expressions => format!("({})", expressions.iter().map(|e| e.to_expression_str()).join(" AND ")),
+            },
+            FilterExpression::Or(expressions) => match Self::non_empty(expressions)[..] {
+                [expression] => expression.to_expression_str(),
+                ref expressions => format!(
+                    "(!({}))",
+                    expressions
+                        .iter()
+                        .map(|e| format!("(!{})", e.to_expression_str()))
+                        .join(" AND ")
+                ),
+            },
+        }
+    }
+
+    fn non_empty(expressions: &[Self]) -> Vec<&Self> {
+        expressions.iter().filter(|e| !e.is_empty()).collect_vec()
+    }
+
+    fn to_classic_query_str(&self) -> Option<String> {
+        match self {
+            FilterExpression::Filter(filter) => Some(filter.to_classic_query_str()),
+            FilterExpression::Not(_) => None,
+            FilterExpression::And(expressions) => Self::non_empty(expressions)
+                .into_iter()
+                .map(FilterExpression::to_classic_query_str)
+                .collect::<Option<Vec<_>>>()
+                .map(|parts| parts.join(" ")),
+            FilterExpression::Or(expressions) => match Self::non_empty(expressions)[..] {
+                [expression] => expression.to_classic_query_str(),
+                _ => None,
+            },
+        }
     }
 }
 
@@ -732,45 +1190,103 @@ mod strext_tests {
     }
 }
 
+#[cfg(test)]
+mod find_options_tests {
+    use super::{FindOptions, SingleOrRange};
+
+    #[test]
+    fn empty_by_default() {
+        assert_eq!(FindOptions::default().to_mpd_str(), "");
+    }
+
+    #[test]
+    fn sort_only() {
+        let options = FindOptions {
+            sort: Some("-Date"),
+            window: None,
+        };
+
+        assert_eq!(options.to_mpd_str(), " sort -Date");
+    }
+
+    #[test]
+    fn window_only() {
+        let options = FindOptions {
+            sort: None,
+            window: Some(SingleOrRange::range(0, 50)),
+        };
+
+        assert_eq!(options.to_mpd_str(), " window \"0:50\"");
+    }
+
+    #[test]
+    fn sort_and_window() {
+        let options = FindOptions {
+            sort: Some("Title"),
+            window: Some(SingleOrRange::range(10, 20)),
+        };
+
+        assert_eq!(options.to_mpd_str(), " sort Title window \"10:20\"");
+    }
+}
+
 #[cfg(test)]
 mod filter_tests {
-    use crate::mpd::mpd_client::{FilterExt, FilterKind, Tag};
+    use crate::mpd::{
+        mpd_client::{FilterExt, FilterKind, Tag},
+        version::Version,
+    };
 
     use super::Filter;
     use test_case::test_case;
 
+    const EXPRESSIONS: Version = Version {
+        major: 0,
+        minor: 21,
+        patch: 0,
+    };
+    const CLASSIC: Version = Version {
+        major: 0,
+        minor: 20,
+        patch: 0,
+    };
+
     #[test_case(Tag::Artist, "Artist")]
     #[test_case(Tag::Album, "Album")]
     #[test_case(Tag::AlbumArtist, "AlbumArtist")]
     #[test_case(Tag::Title, "Title")]
     #[test_case(Tag::File, "File")]
     #[test_case(Tag::Genre, "Genre")]
+    #[test_case(Tag::Date, "Date")]
     #[test_case(Tag::Custom("customtag"), "customtag")]
     fn single_value(tag: Tag, expected: &str) {
         let input: &[Filter<'_, '_>] = &[Filter::new(tag, "mrs singer")];
 
-        assert_eq!(input.to_query_str(), format!("({expected} == 'mrs singer')"));
+        assert_eq!(
+            input.to_query_str(EXPRESSIONS),
+            format!("\"(({expected} == 'mrs singer'))\"")
+        );
     }
 
     #[test]
     fn starts_with() {
         let input: &[Filter<'_, '_>] = &[Filter::new_with_kind(Tag::Artist, "mrs singer", FilterKind::StartsWith)];
 
-        assert_eq!(input.to_query_str(), "(Artist =~ '^mrs singer')");
+        assert_eq!(input.to_query_str(EXPRESSIONS), "\"((Artist =~ '^mrs singer'))\"");
     }
 
     #[test]
     fn exact() {
         let input: &[Filter<'_, '_>] = &[Filter::new_with_kind(Tag::Album, "the greatest", FilterKind::Exact)];
 
-        assert_eq!(input.to_query_str(), "(Album == 'the greatest')");
+        assert_eq!(input.to_query_str(EXPRESSIONS), "\"((Album == 'the greatest'))\"");
     }
 
     #[test]
     fn contains() {
         let input: &[Filter<'_, '_>] = &[Filter::new_with_kind(Tag::Album, "the greatest", FilterKind::Contains)];
 
-        assert_eq!(input.to_query_str(), "(Album =~ '.*the greatest.*')");
+        assert_eq!(input.to_query_str(EXPRESSIONS), "\"((Album =~ '.*the greatest.*'))\"");
     }
 
     #[test]
@@ -781,7 +1297,10 @@ mod filter_tests {
             FilterKind::Regex,
         )];
 
-        assert_eq!(input.to_query_str(), r"(Album =~ 'the greatest.*\\\\s+[A-Za-z]+$')");
+        assert_eq!(
+            input.to_query_str(EXPRESSIONS),
+            r#""((Album =~ 'the greatest.*\\\\s+[A-Za-z]+$'))""#
+        );
     }
 
     #[test]
@@ -792,8 +1311,246 @@ mod filter_tests {
         ];
 
         assert_eq!(
-            input.to_query_str(),
-            "(Album == 'the greatest') AND (Artist == 'mrs singer')"
+            input.to_query_str(EXPRESSIONS),
+            "\"((Album == 'the greatest') AND (Artist == 'mrs singer'))\""
         );
     }
+
+    #[test]
+    fn classic_single_value() {
+        let input: &[Filter<'_, '_>] = &[Filter::new(Tag::Artist, "mrs singer")];
+
+        assert_eq!(input.to_query_str(CLASSIC), "Artist \"mrs singer\"");
+    }
+
+    #[test]
+    fn classic_multiple_values() {
+        let input: &[Filter<'_, '_>] = &[
+            Filter::new(Tag::Album, "the greatest"),
+            Filter::new(Tag::Artist, "mrs singer"),
+        ];
+
+        assert_eq!(
+            input.to_query_str(CLASSIC),
+            "Album \"the greatest\" Artist \"mrs singer\""
+        );
+    }
+
+    #[test]
+    fn classic_escapes_quotes_and_backslashes() {
+        let input: &[Filter<'_, '_>] = &[Filter::new(Tag::Title, r#"say "hi" \o/"#)];
+
+        assert_eq!(input.to_query_str(CLASSIC), r#"Title "say \"hi\" \\o/""#);
+    }
+
+    #[test]
+    fn classic_ignores_filter_kind() {
+        let input: &[Filter<'_, '_>] = &[Filter::new_with_kind(Tag::Artist, "mrs", FilterKind::StartsWith)];
+
+        assert_eq!(input.to_query_str(CLASSIC), "Artist \"mrs\"");
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod filter_expression_tests {
+    use crate::mpd::{
+        errors::MpdError,
+        mpd_client::{Filter, FilterExpression, FilterKind, Tag},
+        version::Version,
+    };
+
+    const EXPRESSIONS: Version = Version {
+        major: 0,
+        minor: 21,
+        patch: 0,
+    };
+    const CLASSIC: Version = Version {
+        major: 0,
+        minor: 20,
+        patch: 0,
+    };
+
+    fn artist(value: &str) -> FilterExpression<'static, '_> {
+        Filter::new(Tag::Artist, value).into()
+    }
+
+    #[test]
+    fn single_filter() {
+        let input = artist("mrs singer");
+
+        assert_eq!(input.to_query_str(EXPRESSIONS).unwrap(), "\"(Artist == 'mrs singer')\"");
+    }
+
+    #[test]
+    fn and() {
+        let input = FilterExpression::And(vec![
+            artist("mrs singer"),
+            Filter::new_with_kind(Tag::Album, "greatest", FilterKind::Contains).into(),
+        ]);
+
+        assert_eq!(
+            input.to_query_str(EXPRESSIONS).unwrap(),
+            "\"((Artist == 'mrs singer') AND (Album =~ '.*greatest.*'))\""
+        );
+    }
+
+    #[test]
+    fn not() {
+        let input = FilterExpression::not(Filter::new(Tag::Genre, "metal"));
+
+        assert_eq!(input.to_query_str(EXPRESSIONS).unwrap(), "\"(!(Genre == 'metal'))\"");
+    }
+
+    #[test]
+    fn or_is_sent_as_negated_and() {
+        let input = FilterExpression::Or(vec![artist("a"), artist("b")]);
+
+        assert_eq!(
+            input.to_query_str(EXPRESSIONS).unwrap(),
+            "\"(!((!(Artist == 'a')) AND (!(Artist == 'b'))))\""
+        );
+    }
+
+    #[test]
+    fn nested() {
+        let input = FilterExpression::And(vec![
+            FilterExpression::Or(vec![artist("a"), artist("b")]),
+            FilterExpression::not(FilterExpression::Or(vec![
+                Filter::new(Tag::Genre, "metal").into(),
+                Filter::new(Tag::Genre, "rock").into(),
+            ])),
+        ]);
+
+        assert_eq!(
+            input.to_query_str(EXPRESSIONS).unwrap(),
+            "\"((!((!(Artist == 'a')) AND (!(Artist == 'b')))) AND (!(!((!(Genre == 'metal')) AND (!(Genre == 'rock'))))))\""
+        );
+    }
+
+    #[test]
+    fn single_child_groups_are_unwrapped() {
+        let input = FilterExpression::And(vec![FilterExpression::Or(vec![artist("a")])]);
+
+        assert_eq!(input.to_query_str(EXPRESSIONS).unwrap(), "\"(Artist == 'a')\"");
+    }
+
+    #[test]
+    fn empty_groups_are_skipped() {
+        let input = FilterExpression::And(vec![
+            FilterExpression::Or(Vec::new()),
+            artist("a"),
+            FilterExpression::not(FilterExpression::And(Vec::new())),
+        ]);
+
+        assert!(!input.is_empty());
+        assert_eq!(input.to_query_str(EXPRESSIONS).unwrap(), "\"(Artist == 'a')\"");
+    }
+
+    #[test]
+    fn empty() {
+        let input = FilterExpression::And(vec![FilterExpression::Or(Vec::new())]);
+
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn classic_and() {
+        let input = FilterExpression::And(vec![
+            artist("mrs singer"),
+            FilterExpression::Or(vec![Filter::new(Tag::Album, "greatest").into()]),
+        ]);
+
+        assert_eq!(
+            input.to_query_str(CLASSIC).unwrap(),
+            "Artist \"mrs singer\" Album \"greatest\""
+        );
+    }
+
+    #[test]
+    fn classic_rejects_not() {
+        let input = FilterExpression::And(vec![artist("a"), FilterExpression::not(artist("b"))]);
+
+        assert!(matches!(
+            input.to_query_str(CLASSIC),
+            Err(MpdError::UnsupportedMpdVersion(_))
+        ));
+    }
+
+    #[test]
+    fn classic_rejects_or() {
+        let input = FilterExpression::Or(vec![artist("a"), artist("b")]);
+
+        assert!(matches!(
+            input.to_query_str(CLASSIC),
+            Err(MpdError::UnsupportedMpdVersion(_))
+        ));
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod album_art_source_tests {
+    use rstest::rstest;
+
+    use super::{find_first_album_art, AlbumArtCommand, AlbumArtSource};
+    use crate::mpd::errors::{ErrorCode, MpdError, MpdFailureResponse};
+
+    fn not_found() -> MpdError {
+        MpdError::Mpd(MpdFailureResponse {
+            code: ErrorCode::NoExist,
+            command_list_index: 0,
+            command: "albumart".to_string(),
+            message: "No file exists".to_string(),
+        })
+    }
+
+    #[rstest]
+    #[case(AlbumArtSource::ReadPictureThenAlbumArt, vec![AlbumArtCommand::ReadPicture, AlbumArtCommand::AlbumArt])]
+    #[case(AlbumArtSource::AlbumArtThenReadPicture, vec![AlbumArtCommand::AlbumArt, AlbumArtCommand::ReadPicture])]
+    #[case(AlbumArtSource::ReadPicture, vec![AlbumArtCommand::ReadPicture])]
+    #[case(AlbumArtSource::AlbumArt, vec![AlbumArtCommand::AlbumArt])]
+    fn tries_commands_in_order_of_source(#[case] source: AlbumArtSource, #[case] expected: Vec<AlbumArtCommand>) {
+        let mut called = Vec::new();
+
+        let result = find_first_album_art(source, |command| {
+            called.push(command);
+            Ok(None)
+        });
+
+        assert_eq!(result.unwrap(), None);
+        assert_eq!(called, expected);
+    }
+
+    #[test]
+    fn falls_through_to_second_command_when_first_finds_nothing() {
+        let result = find_first_album_art(AlbumArtSource::ReadPictureThenAlbumArt, |command| match command {
+            AlbumArtCommand::ReadPicture => Err(not_found()),
+            AlbumArtCommand::AlbumArt => Ok(Some(vec![1, 2, 3])),
+        });
+
+        assert_eq!(result.unwrap(), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn stops_at_first_found_art() {
+        let mut called = Vec::new();
+
+        let result = find_first_album_art(AlbumArtSource::AlbumArtThenReadPicture, |command| {
+            called.push(command);
+            Ok(Some(vec![1]))
+        });
+
+        assert_eq!(result.unwrap(), Some(vec![1]));
+        assert_eq!(called, vec![AlbumArtCommand::AlbumArt]);
+    }
+
+    #[test]
+    fn returns_other_errors() {
+        let result = find_first_album_art(AlbumArtSource::AlbumArtThenReadPicture, |_| {
+            Err(MpdError::Generic("broken".to_string()))
+        });
+
+        assert_eq!(result, Err(MpdError::Generic("broken".to_string())));
+    }
 }
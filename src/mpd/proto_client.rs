@@ -27,6 +27,8 @@ struct BinaryMpdResponse {
 #[derive(Debug, PartialEq, Eq)]
 pub enum MpdLine {
     Ok,
+    /// Acknowledges a single command of a list started with `command_list_ok_begin`
+    ListOk,
     Value(String),
 }
 
@@ -68,7 +70,7 @@ impl<'cmd, 'client, C: SocketClient> ProtoClient<'cmd, 'client, C> {
     pub(super) fn read_ok(mut self) -> Result<(), MpdError> {
         trace!(command = self.command; "Reading command");
         match self.read_line() {
-            Ok(MpdLine::Ok) => Ok(()),
+            Ok(MpdLine::Ok | MpdLine::ListOk) => Ok(()),
             Ok(MpdLine::Value(val)) => Err(MpdError::Generic(format!("Expected 'OK' but got '{val}'"))),
             Err(MpdError::ClientClosed) => {
                 self.client.reconnect()?;
@@ -100,7 +102,7 @@ impl<'cmd, 'client, C: SocketClient> ProtoClient<'cmd, 'client, C> {
         let mut result = V::default();
         loop {
             match self.read_line() {
-                Ok(MpdLine::Ok) => return Ok(result),
+                Ok(MpdLine::Ok | MpdLine::ListOk) => return Ok(result),
                 Ok(MpdLine::Value(val)) => self.next(&mut result, val)?,
                 Err(MpdError::ClientClosed) => {
                     self.client.reconnect()?;
@@ -124,7 +126,7 @@ impl<'cmd, 'client, C: SocketClient> ProtoClient<'cmd, 'client, C> {
         let mut found_any = false;
         loop {
             match self.read_line() {
-                Ok(MpdLine::Ok) => return if found_any { Ok(Some(result)) } else { Ok(None) },
+                Ok(MpdLine::Ok | MpdLine::ListOk) => return if found_any { Ok(Some(result)) } else { Ok(None) },
                 Ok(MpdLine::Value(val)) => {
                     found_any = true;
                     self.next(&mut result, val)?;
@@ -142,6 +144,33 @@ impl<'cmd, 'client, C: SocketClient> ProtoClient<'cmd, 'client, C> {
         }
     }
 
+    /// Reads the response of a command list started with `command_list_ok_begin`. The response of every command is
+    /// terminated by `list_OK` and collected separately, in the order the commands were sent.
+    pub(super) fn read_list_response<V>(mut self) -> Result<Vec<V>, MpdError>
+    where
+        V: FromMpd + Default,
+    {
+        trace!(command = self.command; "Reading command list");
+        let mut results = Vec::new();
+        let mut result = V::default();
+        loop {
+            match self.read_line() {
+                Ok(MpdLine::Ok) => return Ok(results),
+                Ok(MpdLine::ListOk) => results.push(std::mem::take(&mut result)),
+                Ok(MpdLine::Value(val)) => self.next(&mut result, val)?,
+                Err(MpdError::ClientClosed) => {
+                    self.client.reconnect()?;
+                    self.execute(self.command)?;
+                    return self.read_list_response::<V>();
+                }
+                Err(e) => {
+                    self.client.clear_read_buf()?;
+                    return Err(e);
+                }
+            }
+        }
+    }
+
     pub(super) fn read_bin(mut self) -> MpdResult<Option<Vec<u8>>> {
         let mut buf = Vec::new();
         let _ = match self._read_bin(&mut buf) {
@@ -181,7 +210,7 @@ impl<'cmd, 'client, C: SocketClient> ProtoClient<'cmd, 'client, C> {
         {
             loop {
                 match self.read_line()? {
-                    MpdLine::Ok => {
+                    MpdLine::Ok | MpdLine::ListOk => {
                         log::warn!("Expected binary data but got 'OK'");
                         return Ok(None);
                     }
@@ -210,16 +239,16 @@ impl<'cmd, 'client, C: SocketClient> ProtoClient<'cmd, 'client, C> {
         let _ = handle.read_to_end(binary_buf)?;
         let _ = read.read_line(&mut String::new()); // MPD prints an empty new line at the end of binary response
         match self.read_line()? {
-            MpdLine::Ok => Ok(Some(result)),
+            MpdLine::Ok | MpdLine::ListOk => Ok(Some(result)),
             MpdLine::Value(val) => Err(MpdError::Generic(format!("Expected 'OK' but got '{val}'"))),
         }
     }
 
     fn read_line(&mut self) -> Result<MpdLine, MpdError> {
         let read = self.client.read();
-        let mut line = String::new();
+        let mut bytes = Vec::new();
 
-        let bytes_read = match read.read_line(&mut line) {
+        let bytes_read = match read.read_until(b'\n', &mut bytes) {
             Ok(v) => Ok(v),
             Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Err(MpdError::ClientClosed),
             _ => Err(MpdError::ClientClosed),
@@ -229,9 +258,19 @@ impl<'cmd, 'client, C: SocketClient> ProtoClient<'cmd, 'client, C> {
             return Err(MpdError::ClientClosed);
         }
 
-        if line.starts_with("OK") || line.starts_with("list_OK") {
+        if bytes.starts_with(b"OK") {
             return Ok(MpdLine::Ok);
         }
+        if bytes.starts_with(b"list_OK") {
+            return Ok(MpdLine::ListOk);
+        }
+        // Tags of some files are not valid UTF-8, invalid sequences are replaced instead of failing
+        // the whole response
+        let mut line = String::from_utf8(bytes).unwrap_or_else(|err| {
+            let line = String::from_utf8_lossy(err.as_bytes()).into_owned();
+            log::warn!(line = line.as_str(); "Received a line which is not valid UTF-8");
+            line
+        });
         if line.starts_with("ACK") {
             return Err(MpdError::Mpd(MpdFailureResponse::from_str(&line)?));
         }
@@ -316,12 +355,12 @@ mod tests {
         }
 
         #[rstest]
-        fn returns_ok_for_list_ok(mut client: TestMpdClient) {
+        fn returns_list_ok(mut client: TestMpdClient) {
             client.set_read_content(Box::new(Cursor::new(b"list_OK enenene")));
             let mut client = ProtoClient::new("", &mut client).unwrap();
             let result = client.read_line();
 
-            assert_eq!(Ok(MpdLine::Ok), result);
+            assert_eq!(Ok(MpdLine::ListOk), result);
         }
 
         #[rstest]
@@ -340,6 +379,24 @@ mod tests {
             assert_eq!(Err(MpdError::Mpd(err)), result);
         }
 
+        #[rstest]
+        fn replaces_invalid_utf8_in_values(mut client: TestMpdClient) {
+            client.set_read_content(Box::new(Cursor::new(b"Title: Caf\xe9 del Mar\n")));
+            let mut client = ProtoClient::new("", &mut client).unwrap();
+            let result = client.read_line();
+
+            assert_eq!(Ok(MpdLine::Value("Title: Caf\u{FFFD} del Mar".to_owned())), result);
+        }
+
+        #[rstest]
+        fn keeps_valid_utf8_in_values(mut client: TestMpdClient) {
+            client.set_read_content(Box::new(Cursor::new("Title: Café del Mar\n".as_bytes())));
+            let mut client = ProtoClient::new("", &mut client).unwrap();
+            let result = client.read_line();
+
+            assert_eq!(Ok(MpdLine::Value("Title: Café del Mar".to_owned())), result);
+        }
+
         #[rstest]
         fn returns_client_closed_on_broken_pipe(mut client: TestMpdClient) {
             struct Mock;
@@ -389,6 +446,23 @@ mod tests {
             );
         }
 
+        #[test]
+        fn parses_response_with_invalid_utf8_value() {
+            let buf: &[u8] = b"val_b: \xff\xfe\nval_a: 5\nOK\n";
+
+            let result = ProtoClient::new("", &mut TestClient::new(buf))
+                .unwrap()
+                .read_response::<TestMpdObject>();
+
+            assert_eq!(
+                result,
+                Ok(TestMpdObject {
+                    val_a: "5".to_owned(),
+                    val_b: "\u{FFFD}\u{FFFD}".to_owned()
+                })
+            );
+        }
+
         #[test]
         fn returns_parse_error() {
             let buf: &[u8] = b"fail: lol\nOK\n";
@@ -417,8 +491,70 @@ mod tests {
             assert_eq!(result, Err(MpdError::Mpd(err)));
         }
     }
+    mod list_response {
+        use crate::mpd::{
+            errors::{ErrorCode, MpdError, MpdFailureResponse},
+            proto_client::ProtoClient,
+        };
+
+        use super::*;
+
+        #[test]
+        fn collects_response_of_each_command() {
+            let buf: &[u8] = b"val_a: 1\nval_b: a\nlist_OK\nval_a: 2\nlist_OK\nlist_OK\nOK\n";
+
+            let result = ProtoClient::new("", &mut TestClient::new(buf))
+                .unwrap()
+                .read_list_response::<TestMpdObject>();
+
+            assert_eq!(
+                result,
+                Ok(vec![
+                    TestMpdObject {
+                        val_a: "1".to_owned(),
+                        val_b: "a".to_owned()
+                    },
+                    TestMpdObject {
+                        val_a: "2".to_owned(),
+                        val_b: String::new()
+                    },
+                    TestMpdObject::default(),
+                ])
+            );
+        }
+
+        #[test]
+        fn returns_empty_list_for_empty_command_list() {
+            let buf: &[u8] = b"OK\n";
+
+            let result = ProtoClient::new("", &mut TestClient::new(buf))
+                .unwrap()
+                .read_list_response::<TestMpdObject>();
+
+            assert_eq!(result, Ok(Vec::new()));
+        }
+
+        #[test]
+        fn returns_mpd_error_of_failed_command() {
+            let buf: &[u8] = b"val_a: 1\nlist_OK\nACK [50@1] {add} No such directory\n";
+            let err = MpdFailureResponse {
+                code: ErrorCode::NoExist,
+                command_list_index: 1,
+                command: "add".to_string(),
+                message: "No such directory".to_string(),
+            };
+
+            let result = ProtoClient::new("", &mut TestClient::new(buf))
+                .unwrap()
+                .read_list_response::<TestMpdObject>();
+
+            assert_eq!(result, Err(MpdError::Mpd(err)));
+        }
+    }
+
     mod response_opt {
         use crate::mpd::{
+            commands::Volume,
             errors::{ErrorCode, MpdError, MpdFailureResponse},
             proto_client::ProtoClient,
         };
@@ -442,6 +578,17 @@ mod tests {
             );
         }
 
+        #[test]
+        fn parses_getvol_response() {
+            let buf: &[u8] = b"volume: 42\nOK\n";
+
+            let result = ProtoClient::new("", &mut TestClient::new(buf))
+                .unwrap()
+                .read_opt_response::<Volume>();
+
+            assert_eq!(result, Ok(Some(Volume::new(42))));
+        }
+
         #[test]
         fn returns_none() {
             let buf: &[u8] = b"OK\n";
@@ -0,0 +1,69 @@
+use std::collections::VecDeque;
+
+/// Album art of recently played songs keyed by the song file, least recently used first. Songs without album art are
+/// remembered as well so that they are not searched for again.
+#[derive(Debug, Default)]
+pub struct AlbumArtCache {
+    capacity: usize,
+    entries: VecDeque<(String, Option<Vec<u8>>)>,
+}
+
+impl AlbumArtCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the cached result for the file and marks it as the most recently used. `None` means the file is not
+    /// cached while `Some(None)` means the song has no album art.
+    #[allow(clippy::option_option)]
+    pub fn get(&mut self, file: &str) -> Option<Option<Vec<u8>>> {
+        let idx = self.entries.iter().position(|(cached, _)| cached == file)?;
+        let entry = self.entries.remove(idx)?;
+        let data = entry.1.clone();
+        self.entries.push_back(entry);
+        Some(data)
+    }
+
+    pub fn insert(&mut self, file: String, data: Option<Vec<u8>>) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        self.entries.retain(|(cached, _)| *cached != file);
+        while self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((file, data));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AlbumArtCache;
+
+    #[test]
+    fn evicts_the_least_recently_used_entry() {
+        let mut cache = AlbumArtCache::new(2);
+        cache.insert("a".to_owned(), Some(vec![1]));
+        cache.insert("b".to_owned(), None);
+        assert_eq!(cache.get("a"), Some(Some(vec![1])));
+
+        cache.insert("c".to_owned(), Some(vec![3]));
+
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.get("a"), Some(Some(vec![1])));
+        assert_eq!(cache.get("c"), Some(Some(vec![3])));
+    }
+
+    #[test]
+    fn caches_nothing_without_capacity() {
+        let mut cache = AlbumArtCache::new(0);
+
+        cache.insert("a".to_owned(), Some(vec![1]));
+
+        assert_eq!(cache.get("a"), None);
+    }
+}
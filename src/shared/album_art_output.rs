@@ -0,0 +1,129 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// Writes the album art of the current song to the configured `album_art.output_path` for external tools. The
+/// extension of the detected image format is appended to the path and the file is replaced only when the art changes.
+#[derive(Debug)]
+pub struct AlbumArtOutput {
+    path: &'static str,
+    /// Path and hash of the last written album art
+    written: Option<(String, u64)>,
+}
+
+impl AlbumArtOutput {
+    pub fn new(path: &'static str) -> Self {
+        Self { path, written: None }
+    }
+
+    /// Writes the given album art or removes the previously written file when the current song has none
+    pub fn write(&mut self, data: Option<&[u8]>) {
+        let Some(data) = data else {
+            if let Some((path, _)) = self.written.take() {
+                if let Err(err) = std::fs::remove_file(&path) {
+                    log::warn!(error:? = err, path:?; "Failed to remove album art output");
+                }
+            }
+            return;
+        };
+
+        let mut hasher = DefaultHasher::new();
+        data.hash(&mut hasher);
+        let hash = hasher.finish();
+        let path = path_with_extension(self.path, data);
+        if self
+            .written
+            .as_ref()
+            .is_some_and(|(written_path, written_hash)| *written_path == path && *written_hash == hash)
+        {
+            return;
+        }
+
+        if let Err(err) = write_atomically(&path, data) {
+            log::warn!(error:? = err, path:?; "Failed to write album art output");
+            return;
+        }
+
+        let previous = self.written.replace((path.clone(), hash));
+        if let Some((old_path, _)) = previous.filter(|(old_path, _)| *old_path != path) {
+            if let Err(err) = std::fs::remove_file(&old_path) {
+                log::warn!(error:? = err, path:? = old_path; "Failed to remove previous album art output");
+            }
+        }
+    }
+}
+
+fn path_with_extension(path: &str, data: &[u8]) -> String {
+    image::guess_format(data)
+        .ok()
+        .and_then(|format| format.extensions_str().first())
+        .map_or_else(|| path.to_owned(), |ext| format!("{path}.{ext}"))
+}
+
+/// Writes to a temporary file next to the target first so that readers never see a partially written image
+fn write_atomically(path: &str, data: &[u8]) -> std::io::Result<()> {
+    let tmp_path = format!("{path}.tmp");
+    std::fs::write(&tmp_path, data)?;
+    std::fs::rename(&tmp_path, path)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::path::Path;
+
+    use super::AlbumArtOutput;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n";
+    const JPEG: &[u8] = b"\xFF\xD8\xFF\xE0";
+
+    fn output(name: &str) -> (AlbumArtOutput, String) {
+        let path = std::env::temp_dir()
+            .join(format!("rmpc-album-art-output-{name}-{}", std::process::id()))
+            .to_string_lossy()
+            .into_owned();
+        (AlbumArtOutput::new(path.clone().leak()), path)
+    }
+
+    #[test]
+    fn writes_with_extension_of_the_image_format() {
+        let (mut output, path) = output("extension");
+
+        output.write(Some(PNG));
+
+        assert_eq!(std::fs::read(format!("{path}.png")).unwrap(), PNG);
+        assert!(!Path::new(&format!("{path}.png.tmp")).exists());
+        std::fs::remove_file(format!("{path}.png")).unwrap();
+    }
+
+    #[test]
+    fn does_not_rewrite_unchanged_art() {
+        let (mut output, path) = output("unchanged");
+        output.write(Some(PNG));
+        std::fs::remove_file(format!("{path}.png")).unwrap();
+
+        output.write(Some(PNG));
+
+        assert!(!Path::new(&format!("{path}.png")).exists());
+    }
+
+    #[test]
+    fn removes_previous_file_when_format_changes() {
+        let (mut output, path) = output("format");
+        output.write(Some(PNG));
+
+        output.write(Some(JPEG));
+
+        assert!(!Path::new(&format!("{path}.png")).exists());
+        assert_eq!(std::fs::read(format!("{path}.jpg")).unwrap(), JPEG);
+        std::fs::remove_file(format!("{path}.jpg")).unwrap();
+    }
+
+    #[test]
+    fn removes_file_when_there_is_no_art() {
+        let (mut output, path) = output("none");
+        output.write(Some(PNG));
+
+        output.write(None);
+
+        assert!(!Path::new(&format!("{path}.png")).exists());
+    }
+}
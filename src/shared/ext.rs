@@ -127,21 +127,92 @@ pub mod iter {
 }
 
 pub mod mpd_client {
+    use std::collections::HashMap;
+
+    use itertools::Itertools;
+    use rand::seq::IteratorRandom;
+
     use crate::{
+        config::OnDuplicateAdd,
         context::AppContext,
         mpd::{
+            commands::Song,
             errors::{ErrorCode, MpdError, MpdFailureResponse},
-            mpd_client::MpdClient,
+            mpd_client::{Filter, MpdClient, QueueMoveTarget, SaveMode, SingleOrRange, Tag},
+            version::Version,
         },
+        shared::macros::status_warn,
     };
 
+    /// Outcome of [`MpdClientExt::add_song`]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SongAdd {
+        Added,
+        /// Added even though it was already in the queue, a warning was shown
+        AddedDuplicate,
+        /// Not added because it was already in the queue, a warning was shown
+        Skipped,
+    }
+
     pub trait MpdClientExt {
         fn play_last(&mut self, context: &AppContext) -> Result<(), MpdError>;
+        /// Adds a single song to the queue at the configured position. Songs already in the queue
+        /// are handled according to the `on_duplicate_add` config.
+        fn add_song(&mut self, file: &str, context: &AppContext) -> Result<SongAdd, MpdError>;
+        /// Adds the songs to the queue at the configured position while keeping their order.
+        fn add_songs(&mut self, songs: &[Song], context: &AppContext) -> Result<(), MpdError>;
+        /// Adds the whole album of the given song to the queue. Songs already in the queue are
+        /// skipped if `skip_queued` is set. Returns the number of songs added.
+        fn add_album_of(&mut self, song: &Song, skip_queued: bool, context: &AppContext) -> Result<usize, MpdError>;
+        /// Adds a song picked randomly from the whole library to the queue. Returns its file along
+        /// with the outcome or `None` if the library is empty.
+        fn add_random_song(&mut self, context: &AppContext) -> Result<Option<(String, SongAdd)>, MpdError>;
+        /// Adds up to `count` randomly picked songs sharing the genre of the given song, or its
+        /// artist if it has no genre. Songs already in the queue are skipped. Returns the number
+        /// of songs added.
+        fn add_similar(&mut self, song: &Song, count: usize, context: &AppContext) -> Result<usize, MpdError>;
+        /// Names of all stored playlists paired with whether they contain the song with the given
+        /// uri, ordered by name. Contents of the playlists are cached in the context.
+        fn playlists_containing(
+            &mut self,
+            uri: &str,
+            context: &mut AppContext,
+        ) -> Result<Vec<(String, bool)>, MpdError>;
+        /// Adds the song to the stored playlist, or removes all of its occurrences if the playlist
+        /// already contains it. Returns whether the playlist contains the song afterwards.
+        fn toggle_in_playlist(&mut self, playlist: &str, uri: &str, context: &mut AppContext)
+            -> Result<bool, MpdError>;
+        /// Saves the queue as the stored playlist with the given name, replacing its previous
+        /// contents. MPD before 0.24 has no replace mode so the playlist is removed first.
+        fn replace_playlist_with_queue(&mut self, name: &str) -> Result<(), MpdError>;
+    }
+
+    fn stored_playlists<'ctx>(
+        client: &mut impl MpdClient,
+        context: &'ctx mut AppContext,
+    ) -> Result<&'ctx mut HashMap<String, Vec<String>>, MpdError> {
+        let contents = match context.stored_playlists.take() {
+            Some(contents) => contents,
+            None => client
+                .list_playlists()?
+                .into_iter()
+                .map(|playlist| Ok((playlist.name.clone(), client.list_playlist(&playlist.name)?.0)))
+                .collect::<Result<_, MpdError>>()?,
+        };
+        Ok(context.stored_playlists.insert(contents))
     }
 
     impl<T: MpdClient> MpdClientExt for T {
         fn play_last(&mut self, context: &AppContext) -> Result<(), MpdError> {
-            match self.play_pos(context.queue.len()) {
+            let pos = match context.add_position() {
+                Some(QueueMoveTarget::Absolute(pos)) => pos,
+                Some(QueueMoveTarget::RelativeAdd(offset)) => context
+                    .status
+                    .song
+                    .map_or(context.queue.len(), |song| song as usize + 1 + offset),
+                Some(QueueMoveTarget::RelativeSub(_)) | None => context.queue.len(),
+            };
+            match self.play_pos(pos) {
                 Ok(()) => {}
                 Err(MpdError::Mpd(MpdFailureResponse {
                     code: ErrorCode::Argument,
@@ -156,6 +227,287 @@ pub mod mpd_client {
             };
             Ok(())
         }
+
+        fn add_song(&mut self, file: &str, context: &AppContext) -> Result<SongAdd, MpdError> {
+            let queued = context.queue.iter().any(|song| song.file == file);
+            match context.config.on_duplicate_add {
+                OnDuplicateAdd::Skip if queued => {
+                    status_warn!("'{file}' is already in the queue");
+                    Ok(SongAdd::Skipped)
+                }
+                OnDuplicateAdd::Warn if queued => {
+                    self.add(file, context.add_position())?;
+                    status_warn!("'{file}' added to queue, it was already queued");
+                    Ok(SongAdd::AddedDuplicate)
+                }
+                OnDuplicateAdd::Allow | OnDuplicateAdd::Skip | OnDuplicateAdd::Warn => {
+                    self.add(file, context.add_position())?;
+                    Ok(SongAdd::Added)
+                }
+            }
+        }
+
+        fn add_songs(&mut self, songs: &[Song], context: &AppContext) -> Result<(), MpdError> {
+            let position = context.add_position();
+            let mut files = songs.iter().map(|song| song.file.as_str()).collect_vec();
+            // Songs inserted at a fixed position end up in reverse order unless added back to front
+            if position.is_some() {
+                files.reverse();
+            }
+            self.add_multiple(&files, position)
+        }
+
+        fn add_album_of(&mut self, song: &Song, skip_queued: bool, context: &AppContext) -> Result<usize, MpdError> {
+            let Some(album) = song.album() else {
+                return Ok(0);
+            };
+            let mut filter = vec![Filter::new(Tag::Album, album.first())];
+            if let Some(album_artist) = song.metadata.get("albumartist") {
+                filter.push(Filter::new(Tag::AlbumArtist, album_artist.first()));
+            }
+
+            let songs = self.find(&filter)?;
+            if skip_queued {
+                let songs: Vec<_> = songs
+                    .into_iter()
+                    .filter(|song| !context.queue.iter().any(|queued| queued.file == song.file))
+                    .collect();
+                self.add_songs(&songs, context)?;
+                Ok(songs.len())
+            } else {
+                self.find_add(&filter, context.add_position())?;
+                Ok(songs.len())
+            }
+        }
+
+        fn add_random_song(&mut self, context: &AppContext) -> Result<Option<(String, SongAdd)>, MpdError> {
+            let Some(file) = self
+                .list_tag(Tag::File, None)?
+                .into_iter()
+                .choose(&mut rand::thread_rng())
+            else {
+                return Ok(None);
+            };
+
+            let added = self.add_song(&file, context)?;
+            Ok(Some((file, added)))
+        }
+
+        fn add_similar(&mut self, song: &Song, count: usize, context: &AppContext) -> Result<usize, MpdError> {
+            let filter = if let Some(genre) = song.metadata.get("genre") {
+                Filter::new(Tag::Genre, genre.first())
+            } else if let Some(artist) = song.artist() {
+                Filter::new(Tag::Artist, artist.first())
+            } else {
+                return Ok(0);
+            };
+
+            let songs = self
+                .search(&[filter])?
+                .into_iter()
+                .filter(|similar| similar.file != song.file)
+                .filter(|similar| !context.queue.iter().any(|queued| queued.file == similar.file))
+                .choose_multiple(&mut rand::thread_rng(), count);
+            self.add_songs(&songs, context)?;
+
+            Ok(songs.len())
+        }
+
+        fn playlists_containing(
+            &mut self,
+            uri: &str,
+            context: &mut AppContext,
+        ) -> Result<Vec<(String, bool)>, MpdError> {
+            Ok(stored_playlists(self, context)?
+                .iter()
+                .map(|(name, files)| (name.clone(), files.iter().any(|file| file == uri)))
+                .sorted()
+                .collect())
+        }
+
+        fn toggle_in_playlist(
+            &mut self,
+            playlist: &str,
+            uri: &str,
+            context: &mut AppContext,
+        ) -> Result<bool, MpdError> {
+            let Some(files) = stored_playlists(self, context)?.get_mut(playlist) else {
+                return Err(MpdError::Generic(format!("Playlist '{playlist}' does not exist")));
+            };
+
+            let positions = files.iter().positions(|file| file == uri).collect_vec();
+            if positions.is_empty() {
+                self.add_to_playlist(playlist, uri, None)?;
+                files.push(uri.to_owned());
+                return Ok(true);
+            }
+
+            // Removed back to front so that the remaining positions stay valid
+            for pos in positions.into_iter().rev() {
+                self.delete_from_playlist(playlist, &SingleOrRange::single(pos))?;
+                files.remove(pos);
+            }
+            Ok(false)
+        }
+
+        fn replace_playlist_with_queue(&mut self, name: &str) -> Result<(), MpdError> {
+            if self.version() < Version::new(0, 24, 0) {
+                self.delete_playlist(name)?;
+                self.save_queue_as_playlist(name, None)
+            } else {
+                self.save_queue_as_playlist(name, Some(SaveMode::Replace))
+            }
+        }
+    }
+
+    #[cfg(test)]
+    #[allow(clippy::unwrap_used)]
+    mod tests {
+        use rstest::rstest;
+
+        use super::{MpdClientExt, SongAdd};
+        use crate::{
+            config::{AddPosition, Config, Leak, OnDuplicateAdd},
+            context::AppContext,
+            mpd::{commands::Song, version::Version},
+            tests::fixtures::{
+                app_context,
+                mpd_client::{client, TestMpdClient},
+            },
+        };
+
+        #[rstest]
+        #[case(Version::new(0, 24, 0), &["save_queue_as_playlist mix Some(Replace)"])]
+        #[case(Version::new(0, 23, 5), &["delete_playlist mix", "save_queue_as_playlist mix None"])]
+        fn replaces_playlist_with_queue(
+            #[case] version: Version,
+            #[case] expected_calls: &[&str],
+            mut client: TestMpdClient,
+        ) {
+            client.version = version;
+
+            client.replace_playlist_with_queue("mix").unwrap();
+
+            assert_eq!(client.calls.len(), expected_calls.len());
+            for call in expected_calls {
+                assert_eq!(client.calls.get(*call), Some(&1));
+            }
+        }
+
+        #[rstest]
+        #[case(AddPosition::End, vec![0, 1, 2, 3])]
+        #[case(AddPosition::Start, vec![2, 3, 0, 1])]
+        #[case(AddPosition::AfterCurrent, vec![0, 2, 3, 1])]
+        fn adds_songs_in_order_at_the_configured_position(
+            #[case] add_position: AddPosition,
+            #[case] expected: Vec<usize>,
+            mut app_context: AppContext,
+            mut client: TestMpdClient,
+        ) {
+            client.queue = vec![0, 1];
+            client.status.song = Some(0);
+            app_context.status.song = Some(0);
+            app_context.config = Config {
+                add_position,
+                ..app_context.config.clone()
+            }
+            .leak();
+            let songs = [client.songs[2].clone(), client.songs[3].clone()];
+
+            client.add_songs(&songs, &app_context).unwrap();
+
+            assert_eq!(client.queue, expected);
+            assert_eq!(client.calls.get("add_multiple"), Some(&1));
+        }
+
+        #[rstest]
+        fn lists_playlists_containing_song(mut app_context: AppContext, mut client: TestMpdClient) {
+            let result = client
+                .playlists_containing("artist_1_album_2_file_0", &mut app_context)
+                .unwrap();
+
+            assert_eq!(
+                result,
+                vec![
+                    ("artist_1_album_1_2".to_owned(), true),
+                    ("playlist_2".to_owned(), true),
+                    ("playlist_3".to_owned(), false),
+                    ("playlist_4".to_owned(), false),
+                ]
+            );
+        }
+
+        #[rstest]
+        fn playlist_contents_are_cached(mut app_context: AppContext, mut client: TestMpdClient) {
+            client
+                .playlists_containing("artist_1_album_2_file_0", &mut app_context)
+                .unwrap();
+            client
+                .playlists_containing("artist_3_album_1_file_0", &mut app_context)
+                .unwrap();
+
+            assert_eq!(client.calls.get("list_playlist"), Some(&4));
+        }
+
+        #[rstest]
+        fn toggles_song_in_playlist(mut app_context: AppContext, mut client: TestMpdClient) {
+            let uri = "artist_1_album_2_file_0";
+
+            assert!(!client.toggle_in_playlist("playlist_2", uri, &mut app_context).unwrap());
+            assert!(!client.playlists[1].songs_indices.contains(&10));
+
+            assert!(client.toggle_in_playlist("playlist_2", uri, &mut app_context).unwrap());
+            assert!(client.playlists[1].songs_indices.contains(&10));
+            assert!(client
+                .playlists_containing(uri, &mut app_context)
+                .unwrap()
+                .contains(&("playlist_2".to_owned(), true)));
+        }
+
+        #[rstest]
+        fn adds_random_song_from_library(app_context: AppContext, mut client: TestMpdClient) {
+            let (file, added) = client.add_random_song(&app_context).unwrap().unwrap();
+
+            assert_eq!(added, SongAdd::Added);
+            assert!(client.songs.iter().any(|song| song.file == file));
+            assert_eq!(client.calls.get("add"), Some(&1));
+        }
+
+        #[rstest]
+        fn adds_nothing_when_library_is_empty(app_context: AppContext, mut client: TestMpdClient) {
+            client.songs.clear();
+
+            assert_eq!(client.add_random_song(&app_context).unwrap(), None);
+            assert_eq!(client.calls.get("add"), None);
+        }
+
+        #[rstest]
+        #[case(OnDuplicateAdd::Allow, "queued", SongAdd::Added, Some(&1))]
+        #[case(OnDuplicateAdd::Skip, "queued", SongAdd::Skipped, None)]
+        #[case(OnDuplicateAdd::Warn, "queued", SongAdd::AddedDuplicate, Some(&1))]
+        #[case(OnDuplicateAdd::Skip, "new", SongAdd::Added, Some(&1))]
+        #[case(OnDuplicateAdd::Warn, "new", SongAdd::Added, Some(&1))]
+        fn adds_song_according_to_on_duplicate_add(
+            #[case] on_duplicate_add: OnDuplicateAdd,
+            #[case] file: &str,
+            #[case] expected: SongAdd,
+            #[case] expected_add_calls: Option<&u32>,
+            mut app_context: AppContext,
+            mut client: TestMpdClient,
+        ) {
+            app_context.config = Config {
+                on_duplicate_add,
+                ..app_context.config.clone()
+            }
+            .leak();
+            app_context.queue.push(Song {
+                file: "queued".to_owned(),
+                ..Default::default()
+            });
+
+            assert_eq!(client.add_song(file, &app_context).unwrap(), expected);
+            assert_eq!(client.calls.get("add"), expected_add_calls);
+        }
     }
 }
 
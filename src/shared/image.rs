@@ -9,6 +9,7 @@ use image::codecs::jpeg::JpegEncoder;
 use image::AnimationDecoder;
 use image::DynamicImage;
 use image::ImageDecoder;
+use ratatui::style::Color;
 use rustix::path::Arg;
 
 use crate::config::Size;
@@ -27,7 +28,9 @@ pub enum ImageProtocol {
 }
 
 const ITERM2_TERMINAL_ENV_VARS: [&str; 3] = ["WEZTERM_EXECUTABLE", "TABBY_CONFIG_DIRECTORY", "VSCODE_INJECTION"];
-const ITERM2_TERM_PROGRAMS: [&str; 3] = ["WezTerm", "vscode", "Tabby"];
+const ITERM2_TERM_PROGRAMS: [&str; 4] = ["WezTerm", "vscode", "Tabby", "iTerm.app"];
+/// Set by iTerm2 itself, unlike `TERM_PROGRAM` it is kept inside tmux and forwarded over ssh
+const ITERM2_LC_TERMINALS: [&str; 1] = ["iTerm2"];
 
 pub fn determine_image_support(is_tmux: bool) -> Result<ImageProtocol> {
     if is_iterm2_supported(is_tmux) {
@@ -62,6 +65,13 @@ pub fn determine_image_support(is_tmux: bool) -> Result<ImageProtocol> {
 }
 
 pub fn is_iterm2_supported(is_tmux: bool) -> bool {
+    if ITERM2_LC_TERMINALS
+        .iter()
+        .any(|v| env::var_os("LC_TERMINAL").is_some_and(|var| var.as_str().unwrap_or_default() == *v))
+    {
+        return true;
+    }
+
     if is_tmux {
         if ITERM2_TERMINAL_ENV_VARS
             .iter()
@@ -117,12 +127,25 @@ pub fn query_device_attrs(is_tmux: bool) -> Result<ImageProtocol> {
 
     log::debug!(buf:?; "devattr response");
 
-    if buf.contains("_Gi=31;OK") {
-        return Ok(ImageProtocol::Kitty);
-    } else if buf.contains(";4;") || buf.contains(";4c") {
-        return Ok(ImageProtocol::Sixel);
+    Ok(parse_device_attrs(&buf))
+}
+
+/// Determines the image protocol from the response to the kitty graphics query followed by the primary device
+/// attributes (DA1) request. Sixel support is reported by the attribute `4` anywhere in the `ESC [ ? ... c` list.
+fn parse_device_attrs(response: &str) -> ImageProtocol {
+    if response.contains("_Gi=31;OK") {
+        return ImageProtocol::Kitty;
+    }
+
+    let supports_sixel = response
+        .rfind("[?")
+        .and_then(|start| response[start + 2..].split_once('c'))
+        .is_some_and(|(attrs, _)| attrs.split(';').any(|attr| attr == "4"));
+    if supports_sixel {
+        ImageProtocol::Sixel
+    } else {
+        ImageProtocol::None
     }
-    Ok(ImageProtocol::None)
 }
 
 pub fn is_ueberzug_wayland_supported() -> bool {
@@ -216,6 +239,32 @@ pub fn resize_image(image_data: &[u8], width_px: u16, hegiht_px: u16) -> Result<
         ))
 }
 
+/// Average color of the image. It is computed from a small thumbnail to stay cheap even for large covers.
+pub fn average_color(image_data: &[u8]) -> Result<Color> {
+    let thumbnail = image::ImageReader::new(Cursor::new(image_data))
+        .with_guessed_format()
+        .context("Unable to guess image format")?
+        .decode()
+        .context("Unable to decode image")?
+        .thumbnail(16, 16)
+        .to_rgb8();
+
+    let count = u64::from(thumbnail.width()) * u64::from(thumbnail.height());
+    if count == 0 {
+        anyhow::bail!("Image has no pixels");
+    }
+
+    let [r, g, b] = thumbnail.pixels().fold([0u64; 3], |mut acc, pixel| {
+        for (sum, channel) in acc.iter_mut().zip(pixel.0) {
+            *sum += u64::from(channel);
+        }
+        acc
+    });
+    let channel = |sum: u64| u8::try_from(sum / count).unwrap_or(u8::MAX);
+
+    Ok(Color::Rgb(channel(r), channel(g), channel(b)))
+}
+
 pub fn jpg_encode(img: &DynamicImage) -> Result<Vec<u8>> {
     let mut jpg = Vec::new();
     JpegEncoder::new(&mut jpg).encode_image(img)?;
@@ -263,9 +312,39 @@ mod test {
     use crossterm::terminal::WindowSize;
     use test_case::test_case;
 
+    use std::io::Cursor;
+
+    use image::{DynamicImage, ImageFormat, Rgb, RgbImage};
+    use ratatui::style::Color;
+
     use crate::config::Size;
 
-    use super::clamp_image_size;
+    use super::{average_color, clamp_image_size, parse_device_attrs, ImageProtocol};
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn average_color_of_solid_image() {
+        let image = DynamicImage::ImageRgb8(RgbImage::from_pixel(40, 30, Rgb([200, 100, 50])));
+        let mut data = Vec::new();
+        image.write_to(&mut Cursor::new(&mut data), ImageFormat::Png).unwrap();
+
+        assert_eq!(average_color(&data).unwrap(), Color::Rgb(200, 100, 50));
+    }
+
+    #[test_case("\x1b_Gi=31;OK\x1b\\\x1b[?62;4;22c", ImageProtocol::Kitty; "kitty")]
+    #[test_case("\x1b[?62;4;22c", ImageProtocol::Sixel; "sixel in the middle")]
+    #[test_case("\x1b[?63;1;2;3;4c", ImageProtocol::Sixel; "sixel at the end")]
+    #[test_case("\x1b[?4;6c", ImageProtocol::Sixel; "sixel at the start")]
+    #[test_case("\x1b[?62;22;44c", ImageProtocol::None; "no sixel")]
+    #[test_case("\x1b[?1;2c", ImageProtocol::None; "vt100")]
+    fn detects_protocol_from_device_attrs(response: &str, expected: ImageProtocol) {
+        assert_eq!(parse_device_attrs(response), expected);
+    }
+
+    #[test]
+    fn average_color_fails_for_invalid_data() {
+        assert!(average_color(b"definitely not an image").is_err());
+    }
 
     #[test_case(&WindowSize { width: 0, height: 0, columns: 10, rows: 10 }, 10, 10, Size { width: 500, height: 500 }, Size { width: 500, height: 500 }; "size not reported")]
     #[test_case(&WindowSize { width: 500, height: 500, columns: 10, rows: 10 }, 50, 10, Size { width: 500, height: 500 }, Size { width: 500, height: 500 }; "wider area")]
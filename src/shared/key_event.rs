@@ -1,7 +1,9 @@
+use std::time::{Duration, Instant};
+
 use crossterm::event::{KeyCode, KeyEvent as CKeyEvent};
 
 use crate::{
-    config::keys::{CommonAction, GlobalAction, QueueActions},
+    config::keys::{CommonAction, GlobalAction, Key, KeyConfig, KeySequence, QueueActions, SearchActions},
     context::AppContext,
 };
 
@@ -11,6 +13,8 @@ use crate::config::keys::LogsActions;
 #[derive(Debug, Clone)]
 pub struct KeyEvent {
     inner: CKeyEvent,
+    /// Keys looked up in the keybinds, ends with `inner` and has more keys when a multi-key keybind was completed
+    sequence: KeySequence,
     already_handled: bool,
 }
 
@@ -18,12 +22,23 @@ impl From<CKeyEvent> for KeyEvent {
     fn from(value: CKeyEvent) -> Self {
         Self {
             inner: value,
+            sequence: Key::from(value).into(),
             already_handled: false,
         }
     }
 }
 
 impl KeyEvent {
+    /// Event for a completed multi-key keybind, `keys` has to end with the key pressed last
+    fn completed_sequence(keys: &[CKeyEvent]) -> Option<Self> {
+        let last = keys.last()?;
+        Some(Self {
+            inner: *last,
+            sequence: KeySequence(keys.iter().map(|key| Key::from(*key)).collect()),
+            already_handled: false,
+        })
+    }
+
     pub fn code(&self) -> KeyCode {
         self.inner.code
     }
@@ -39,7 +54,7 @@ impl KeyEvent {
     pub fn as_common_action(&mut self, context: &AppContext) -> Option<CommonAction> {
         if self.already_handled {
             None
-        } else if let Some(action) = context.config.keybinds.navigation.get(&self.inner.into()) {
+        } else if let Some(action) = context.config.keybinds.navigation.get(&self.sequence) {
             self.already_handled = true;
             Some(*action)
         } else {
@@ -47,10 +62,19 @@ impl KeyEvent {
         }
     }
 
+    /// Returns the global action bound to this key without marking the event as handled
+    pub fn peek_global_action<'a>(&self, context: &'a AppContext) -> Option<&'a GlobalAction> {
+        if self.already_handled {
+            None
+        } else {
+            context.config.keybinds.global.get(&self.sequence)
+        }
+    }
+
     pub fn as_global_action(&mut self, context: &AppContext) -> Option<GlobalAction> {
         if self.already_handled {
             None
-        } else if let Some(action) = context.config.keybinds.global.get(&self.inner.into()) {
+        } else if let Some(action) = context.config.keybinds.global.get(&self.sequence) {
             self.already_handled = true;
             Some(*action)
         } else {
@@ -58,11 +82,24 @@ impl KeyEvent {
         }
     }
 
+    /// Zero based index of the tab to switch to when the key is one of the `1` to `9` tab number keys
+    pub fn as_tab_index(&mut self, context: &AppContext) -> Option<usize> {
+        if self.already_handled || !context.config.tab_number_keys || !self.inner.modifiers.is_empty() {
+            return None;
+        }
+        let KeyCode::Char(c @ '1'..='9') = self.inner.code else {
+            return None;
+        };
+
+        self.already_handled = true;
+        c.to_digit(10).and_then(|digit| usize::try_from(digit - 1).ok())
+    }
+
     #[cfg(debug_assertions)]
     pub fn as_logs_action(&mut self, context: &AppContext) -> Option<LogsActions> {
         if self.already_handled {
             None
-        } else if let Some(action) = context.config.keybinds.logs.get(&self.inner.into()) {
+        } else if let Some(action) = context.config.keybinds.logs.get(&self.sequence) {
             self.already_handled = true;
             Some(*action)
         } else {
@@ -73,7 +110,18 @@ impl KeyEvent {
     pub fn as_queue_action(&mut self, context: &AppContext) -> Option<QueueActions> {
         if self.already_handled {
             None
-        } else if let Some(action) = context.config.keybinds.queue.get(&self.inner.into()) {
+        } else if let Some(action) = context.config.keybinds.queue.get(&self.sequence) {
+            self.already_handled = true;
+            Some(*action)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_search_action(&mut self, context: &AppContext) -> Option<SearchActions> {
+        if self.already_handled {
+            None
+        } else if let Some(action) = context.config.keybinds.search.get(&self.sequence) {
             self.already_handled = true;
             Some(*action)
         } else {
@@ -81,3 +129,243 @@ impl KeyEvent {
         }
     }
 }
+
+/// Keys pressed so far which start a multi-key keybind, ie. the first `g` of `gg`
+#[derive(Debug, Default)]
+pub struct PendingKeys {
+    keys: Vec<CKeyEvent>,
+    last_press: Option<Instant>,
+}
+
+impl PendingKeys {
+    /// Adds the pressed key and returns the key events which should be handled now. Keys starting a longer keybind
+    /// are held back until the keybind is completed, a key which does not continue it is pressed or they expire.
+    pub fn push(&mut self, key: CKeyEvent, now: Instant, keybinds: &KeyConfig) -> Vec<KeyEvent> {
+        let mut keys = self.keys.clone();
+        keys.push(key);
+        let sequence = keys.iter().map(|key| Key::from(*key)).collect::<Vec<_>>();
+
+        if keybinds.is_sequence_prefix(&sequence) {
+            self.keys = keys;
+            self.last_press = Some(now);
+            return Vec::new();
+        }
+        if self.keys.is_empty() {
+            return vec![key.into()];
+        }
+        if keybinds.is_bound(&sequence) {
+            self.keys = keys;
+            return self.flush(keybinds);
+        }
+
+        let mut events = self.flush(keybinds);
+        events.extend(self.push(key, now, keybinds));
+        events
+    }
+
+    /// Returns the held back keys once `timeout` has passed since the last one was pressed
+    pub fn expire(&mut self, now: Instant, timeout: Duration, keybinds: &KeyConfig) -> Vec<KeyEvent> {
+        match self.last_press {
+            Some(last_press) if now.duration_since(last_press) >= timeout => self.flush(keybinds),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Time left until the held back keys expire
+    pub fn remaining(&self, now: Instant, timeout: Duration) -> Option<Duration> {
+        self.last_press
+            .map(|last_press| (last_press + timeout).saturating_duration_since(now))
+    }
+
+    /// Returns the held back keys as a single event when they are bound together, otherwise as individual events
+    pub fn flush(&mut self, keybinds: &KeyConfig) -> Vec<KeyEvent> {
+        self.last_press = None;
+        let keys = std::mem::take(&mut self.keys);
+        let sequence = keys.iter().map(|key| Key::from(*key)).collect::<Vec<_>>();
+
+        if keys.len() > 1 && keybinds.is_bound(&sequence) {
+            KeyEvent::completed_sequence(&keys).into_iter().collect()
+        } else {
+            keys.into_iter().map(KeyEvent::from).collect()
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crossterm::event::{KeyCode, KeyEvent as CKeyEvent, KeyModifiers};
+    use rstest::rstest;
+
+    use crate::{
+        config::{
+            keys::{CommonAction, KeyConfig},
+            Config, Leak,
+        },
+        context::AppContext,
+        tests::fixtures::app_context,
+    };
+
+    use super::{KeyEvent, PendingKeys};
+
+    const TIMEOUT: Duration = Duration::from_millis(500);
+
+    fn with_navigation(app_context: &mut AppContext, binds: &[(&str, CommonAction)]) {
+        let keybinds = KeyConfig {
+            navigation: binds
+                .iter()
+                .map(|(key, action)| (key.parse().unwrap(), *action))
+                .collect(),
+            ..Default::default()
+        };
+        app_context.config = Config {
+            keybinds,
+            ..Default::default()
+        }
+        .leak();
+    }
+
+    fn key(c: char) -> CKeyEvent {
+        CKeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    fn actions(keys: Vec<KeyEvent>, app_context: &AppContext) -> Vec<Option<CommonAction>> {
+        keys.into_iter()
+            .map(|mut key| key.as_common_action(app_context))
+            .collect()
+    }
+
+    #[rstest]
+    #[case(KeyCode::Char('1'), KeyModifiers::NONE, true, Some(0))]
+    #[case(KeyCode::Char('9'), KeyModifiers::NONE, true, Some(8))]
+    #[case(KeyCode::Char('0'), KeyModifiers::NONE, true, None)]
+    #[case(KeyCode::Char('a'), KeyModifiers::NONE, true, None)]
+    #[case(KeyCode::Char('3'), KeyModifiers::CONTROL, true, None)]
+    #[case(KeyCode::Char('3'), KeyModifiers::NONE, false, None)]
+    fn tab_index(
+        #[case] code: KeyCode,
+        #[case] modifiers: KeyModifiers,
+        #[case] enabled: bool,
+        #[case] expected: Option<usize>,
+        mut app_context: AppContext,
+    ) {
+        let config = Config {
+            tab_number_keys: enabled,
+            ..Default::default()
+        };
+        app_context.config = config.leak();
+        let mut key: KeyEvent = CKeyEvent::new(code, modifiers).into();
+
+        assert_eq!(key.as_tab_index(&app_context), expected);
+    }
+
+    #[rstest]
+    fn completes_sequence(mut app_context: AppContext) {
+        with_navigation(
+            &mut app_context,
+            &[("gg", CommonAction::Top), ("j", CommonAction::Down)],
+        );
+        let keybinds = &app_context.config.keybinds;
+        let mut pending = PendingKeys::default();
+        let now = Instant::now();
+
+        assert!(pending.push(key('g'), now, keybinds).is_empty());
+        let result = pending.push(key('g'), now, keybinds);
+
+        assert_eq!(actions(result, &app_context), vec![Some(CommonAction::Top)]);
+        assert_eq!(pending.remaining(now, TIMEOUT), None);
+    }
+
+    #[rstest]
+    fn passes_through_keys_which_do_not_start_a_sequence(mut app_context: AppContext) {
+        with_navigation(
+            &mut app_context,
+            &[("gg", CommonAction::Top), ("j", CommonAction::Down)],
+        );
+        let mut pending = PendingKeys::default();
+
+        let result = pending.push(key('j'), Instant::now(), &app_context.config.keybinds);
+
+        assert_eq!(actions(result, &app_context), vec![Some(CommonAction::Down)]);
+    }
+
+    #[rstest]
+    fn lone_prefix_expires_without_action(mut app_context: AppContext) {
+        with_navigation(&mut app_context, &[("gg", CommonAction::Top)]);
+        let keybinds = &app_context.config.keybinds;
+        let mut pending = PendingKeys::default();
+        let now = Instant::now();
+        pending.push(key('g'), now, keybinds);
+
+        assert_eq!(
+            pending.remaining(now + Duration::from_millis(200), TIMEOUT),
+            Some(Duration::from_millis(300))
+        );
+        assert!(pending
+            .expire(now + Duration::from_millis(499), TIMEOUT, keybinds)
+            .is_empty());
+        let result = pending.expire(now + TIMEOUT, TIMEOUT, keybinds);
+
+        assert_eq!(actions(result, &app_context), vec![None]);
+        assert_eq!(pending.remaining(now + TIMEOUT, TIMEOUT), None);
+    }
+
+    #[rstest]
+    fn expired_prefix_falls_back_to_its_own_keybind(mut app_context: AppContext) {
+        with_navigation(
+            &mut app_context,
+            &[("g", CommonAction::Top), ("gb", CommonAction::Bottom)],
+        );
+        let keybinds = &app_context.config.keybinds;
+        let mut pending = PendingKeys::default();
+        let now = Instant::now();
+
+        assert!(pending.push(key('g'), now, keybinds).is_empty());
+        let result = pending.expire(now + TIMEOUT, TIMEOUT, keybinds);
+
+        assert_eq!(actions(result, &app_context), vec![Some(CommonAction::Top)]);
+    }
+
+    #[rstest]
+    fn broken_sequence_falls_back_to_single_keys(mut app_context: AppContext) {
+        with_navigation(
+            &mut app_context,
+            &[
+                ("g", CommonAction::Top),
+                ("gb", CommonAction::Bottom),
+                ("j", CommonAction::Down),
+            ],
+        );
+        let keybinds = &app_context.config.keybinds;
+        let mut pending = PendingKeys::default();
+        let now = Instant::now();
+
+        pending.push(key('g'), now, keybinds);
+        let result = pending.push(key('j'), now, keybinds);
+
+        assert_eq!(
+            actions(result, &app_context),
+            vec![Some(CommonAction::Top), Some(CommonAction::Down)]
+        );
+    }
+
+    #[rstest]
+    fn key_breaking_a_sequence_can_start_a_new_one(mut app_context: AppContext) {
+        with_navigation(
+            &mut app_context,
+            &[("gg", CommonAction::Top), ("<C-w>j", CommonAction::PaneDown)],
+        );
+        let keybinds = &app_context.config.keybinds;
+        let mut pending = PendingKeys::default();
+        let now = Instant::now();
+
+        pending.push(key('g'), now, keybinds);
+        let result = pending.push(CKeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL), now, keybinds);
+        assert_eq!(actions(result, &app_context), vec![None]);
+        let result = pending.push(key('j'), now, keybinds);
+
+        assert_eq!(actions(result, &app_context), vec![Some(CommonAction::PaneDown)]);
+    }
+}
@@ -45,12 +45,40 @@ fn init_debug(tx: std::sync::mpsc::Sender<AppEvent>) -> Result<LoggerHandle, Fle
             Box::new(AppEventChannelWriter::new(tx.clone())),
         )
         .add_writer("status_bar", Box::new(StatusBarWriter::new(tx)))
-        .format_for_writer(structured_detailed_format)
         .format_for_files(structured_detailed_format)
         .set_palette("1;3;15;4;13".to_string())
         .start()
 }
 
+/// A single log entry captured for the logs pane
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LogEntry {
+    pub timestamp: chrono::DateTime<chrono::Local>,
+    pub level: log::Level,
+    pub target: String,
+    pub message: String,
+}
+
+impl LogEntry {
+    fn new(now: &mut flexi_logger::DeferredNow, record: &log::Record) -> Result<Self, log::kv::Error> {
+        let mut visitor = Visitor::new();
+        record.key_values().visit(&mut visitor)?;
+
+        let mut message = record.args().to_string();
+        if !visitor.values.is_empty() {
+            message.push(' ');
+            message.push_str(visitor.to_string().trim_end());
+        }
+
+        Ok(Self {
+            timestamp: *now.now(),
+            level: record.level(),
+            target: record.target().to_owned(),
+            message,
+        })
+    }
+}
+
 pub struct NullWriter;
 impl flexi_logger::writers::LogWriter for NullWriter {
     fn write(&self, _now: &mut flexi_logger::DeferredNow, _record: &log::Record) -> std::io::Result<()> {
@@ -74,7 +102,6 @@ impl StatusBarWriter {
 
 pub struct AppEventChannelWriter {
     tx: std::sync::mpsc::Sender<AppEvent>,
-    format_fn: Option<flexi_logger::FormatFunction>,
 }
 
 impl flexi_logger::writers::LogWriter for StatusBarWriter {
@@ -95,16 +122,15 @@ impl flexi_logger::writers::LogWriter for StatusBarWriter {
 
 impl AppEventChannelWriter {
     pub fn new(tx: std::sync::mpsc::Sender<AppEvent>) -> Self {
-        Self { tx, format_fn: None }
+        Self { tx }
     }
 }
 
 impl flexi_logger::writers::LogWriter for AppEventChannelWriter {
     fn write(&self, now: &mut flexi_logger::DeferredNow, record: &log::Record) -> std::io::Result<()> {
-        let mut buf = Vec::new();
-        (self.format_fn).map(|fun| fun(&mut buf, now, record));
+        let entry = LogEntry::new(now, record).map_err(std::io::Error::other)?;
 
-        match self.tx.send(AppEvent::Log(buf)) {
+        match self.tx.send(AppEvent::Log(entry)) {
             Ok(v) => Ok(v),
             Err(err) => Err(std::io::Error::new(std::io::ErrorKind::Other, err)),
         }
@@ -113,10 +139,6 @@ impl flexi_logger::writers::LogWriter for AppEventChannelWriter {
     fn flush(&self) -> std::io::Result<()> {
         Ok(())
     }
-
-    fn format(&mut self, format: flexi_logger::FormatFunction) {
-        self.format_fn = Some(format);
-    }
 }
 
 impl From<log::Level> for crate::ui::Level {
@@ -37,7 +37,9 @@ impl LrcIndex {
 
     pub fn find_lrc_for_song(&self, song: &Song) -> Result<Option<Lrc>> {
         match (song.artist(), song.title(), song.album(), song.duration) {
-            (Some(artist), Some(title), Some(album), length) => self.find_lrc(artist, title, album, length),
+            (Some(artist), Some(title), Some(album), length) => {
+                self.find_lrc(artist.first(), title.first(), album.first(), length)
+            }
             _ => None,
         }
         .map_or(Ok(None), |lrc| Ok(Some(std::fs::read_to_string(&lrc.path)?.parse()?)))
@@ -8,6 +8,9 @@ use super::parse_length;
 pub struct LrcLine {
     pub time: Duration,
     pub content: String,
+    /// Word fragments of enhanced lrc lines with their start times relative to [`LrcLine::time`], empty when the
+    /// line has no `<mm:ss.xx>` word timings
+    pub words: Vec<(Duration, String)>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -25,6 +28,14 @@ pub struct Lrc {
     pub length: Option<Duration>,
 }
 
+impl Lrc {
+    /// Index of the line being sung at `elapsed`, that is the last line which starts at or before it. None before
+    /// the first line starts.
+    pub fn active_line_idx(&self, elapsed: Duration) -> Option<usize> {
+        self.lines.iter().rposition(|line| line.time <= elapsed)
+    }
+}
+
 impl FromStr for Lrc {
     type Err = anyhow::Error;
 
@@ -52,29 +63,27 @@ impl FromStr for Lrc {
 
             match meta_or_time.chars().next() {
                 Some(c) if c.is_numeric() => {
-                    let (minutes, time_rest) = meta_or_time
-                        .split_once(':')
-                        .with_context(|| format!("Invalid lrc minutes format: '{meta_or_time}'"))?;
-                    let (seconds, hundreths) = time_rest
-                        .split_once('.')
-                        .or_else(|| time_rest.split_once(':'))
-                        .with_context(|| format!("Invalid lrc seconds and hundreths format: '{time_rest}'"))?;
-
-                    let mut milis = 0;
-                    milis += minutes.parse::<u64>()? * 60 * 1000;
-                    milis += seconds.parse::<u64>()? * 1000;
-                    milis += hundreths.parse::<u64>()? * 10;
-
-                    milis = match offset {
-                        Some(offset) if offset > 0 => milis.saturating_sub(offset.unsigned_abs()),
-                        Some(offset) if offset < 0 => milis.saturating_add(offset.unsigned_abs()),
-                        _ => milis,
-                    };
-
-                    result.lines.push(LrcLine {
-                        time: Duration::from_millis(milis),
-                        content: line.to_owned(),
-                    });
+                    // Repeated lines such as a chorus can have multiple timestamps, e.g. `[00:12.00][00:45.00]`
+                    let mut timestamps = vec![meta_or_time];
+                    let mut line = line;
+                    while let Some((time, rest)) = line
+                        .strip_prefix('[')
+                        .and_then(|s| s.split_once(']'))
+                        .filter(|(time, _)| time.starts_with(|c: char| c.is_numeric()))
+                    {
+                        timestamps.push(time);
+                        line = rest;
+                    }
+
+                    for timestamp in timestamps {
+                        let line_milis = parse_timestamp(timestamp)?;
+                        let (content, words) = parse_words(line, line_milis);
+                        result.lines.push(LrcLine {
+                            time: Duration::from_millis(line_milis),
+                            content,
+                            words,
+                        });
+                    }
                 }
                 Some(_) => {
                     let (key, value) = meta_or_time
@@ -96,10 +105,93 @@ impl FromStr for Lrc {
             }
         }
 
+        // Metadata can also come after the timed lines so the offset is applied only once everything is parsed
+        if let Some(offset) = offset {
+            let shift = Duration::from_millis(offset.unsigned_abs());
+            for line in &mut result.lines {
+                line.time = if offset > 0 {
+                    line.time.saturating_sub(shift)
+                } else {
+                    line.time.saturating_add(shift)
+                };
+            }
+        }
+        result.lines.sort_by_key(|line| line.time);
+
         Ok(result)
     }
 }
 
+/// Parses `mm:ss.xx`, `mm:ss:xx` or `mm:ss` into miliseconds. The fractional part can also have millisecond
+/// precision, e.g. `mm:ss.xxx`.
+fn parse_timestamp(input: &str) -> Result<u64> {
+    let (minutes, time_rest) = input
+        .split_once(':')
+        .with_context(|| format!("Invalid lrc minutes format: '{input}'"))?;
+    let (seconds, fraction) = time_rest
+        .split_once('.')
+        .or_else(|| time_rest.split_once(':'))
+        .unwrap_or((time_rest, "0"));
+
+    let mut milis = 0;
+    milis += minutes.parse::<u64>()? * 60 * 1000;
+    milis += seconds.parse::<u64>()? * 1000;
+    milis += match fraction.len() {
+        1 => fraction.parse::<u64>()? * 100,
+        2 => fraction.parse::<u64>()? * 10,
+        3 => fraction.parse::<u64>()?,
+        _ => bail!("Invalid lrc fractional seconds format: '{fraction}'"),
+    };
+    Ok(milis)
+}
+
+/// Splits the enhanced lrc `<mm:ss.xx>` word timings out of the line. Returns the line without them and the word
+/// fragments with their start times relative to the start of the line. Text before the first timing starts
+/// together with the line.
+fn parse_words(line: &str, line_milis: u64) -> (String, Vec<(Duration, String)>) {
+    if !line.contains('<') {
+        return (line.to_owned(), Vec::new());
+    }
+
+    let mut content = String::new();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut rest = line;
+    let mut start = Duration::ZERO;
+    let mut has_timings = false;
+    while let Some((text, timed)) = rest.split_once('<') {
+        let Some((time, after)) = timed.split_once('>') else {
+            break;
+        };
+        word.push_str(text);
+        // Anything in angle brackets which is not a timestamp, ie. `<Instrumental>`, is part of the text
+        let Ok(time) = parse_timestamp(time) else {
+            word.push('<');
+            word.push_str(time);
+            word.push('>');
+            rest = after;
+            continue;
+        };
+        if !word.is_empty() {
+            content.push_str(&word);
+            words.push((start, std::mem::take(&mut word)));
+        }
+        start = Duration::from_millis(time.saturating_sub(line_milis));
+        has_timings = true;
+        rest = after;
+    }
+    word.push_str(rest);
+    if !has_timings {
+        return (line.to_owned(), Vec::new());
+    }
+    if !word.is_empty() {
+        content.push_str(&word);
+        words.push((start, word));
+    }
+
+    (content, words)
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -134,19 +226,23 @@ mod tests {
                 lines: vec![
                     LrcLine {
                         time: Duration::from_millis(1860),
-                        content: "line with dot before hundredths".to_string()
+                        content: "line with dot before hundredths".to_string(),
+                        words: Vec::new()
                     },
                     LrcLine {
                         time: Duration::from_millis(4730),
-                        content: "line with colon before hundredths".to_string()
+                        content: "line with colon before hundredths".to_string(),
+                        words: Vec::new()
                     },
                     LrcLine {
                         time: Duration::from_millis(11240),
-                        content: String::new()
+                        content: String::new(),
+                        words: Vec::new()
                     },
                     LrcLine {
                         time: Duration::from_millis(676_910),
-                        content: "line with long time".to_string()
+                        content: "line with long time".to_string(),
+                        words: Vec::new()
                     },
                 ],
             }
@@ -175,11 +271,13 @@ mod tests {
                 lines: vec![
                     LrcLine {
                         time: Duration::from_millis(860),
-                        content: "line1".to_string()
+                        content: "line1".to_string(),
+                        words: Vec::new()
                     },
                     LrcLine {
                         time: Duration::from_millis(3730),
-                        content: "line2".to_string()
+                        content: "line2".to_string(),
+                        words: Vec::new()
                     },
                 ],
             }
@@ -208,14 +306,215 @@ mod tests {
                 lines: vec![
                     LrcLine {
                         time: Duration::from_millis(2860),
-                        content: "line1".to_string()
+                        content: "line1".to_string(),
+                        words: Vec::new()
                     },
                     LrcLine {
                         time: Duration::from_millis(5730),
-                        content: "line2".to_string()
+                        content: "line2".to_string(),
+                        words: Vec::new()
                     },
                 ],
             }
         );
     }
+
+    #[test]
+    fn lrc_enhanced_word_timings() {
+        let input = r"
+[offset: +500]
+
+[00:01.00]<00:01.00>first <00:01.50>timed <00:02.25>line
+[00:03.00]plain line
+[00:04.00]leading <00:04.40>words";
+
+        let result: Lrc = input.parse().unwrap();
+
+        assert_eq!(
+            result.lines,
+            vec![
+                LrcLine {
+                    time: Duration::from_millis(500),
+                    content: "first timed line".to_string(),
+                    words: vec![
+                        (Duration::ZERO, "first ".to_string()),
+                        (Duration::from_millis(500), "timed ".to_string()),
+                        (Duration::from_millis(1250), "line".to_string()),
+                    ],
+                },
+                LrcLine {
+                    time: Duration::from_millis(2500),
+                    content: "plain line".to_string(),
+                    words: Vec::new()
+                },
+                LrcLine {
+                    time: Duration::from_millis(3500),
+                    content: "leading words".to_string(),
+                    words: vec![
+                        (Duration::ZERO, "leading ".to_string()),
+                        (Duration::from_millis(400), "words".to_string()),
+                    ],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn lrc_multiple_timestamps_on_one_line() {
+        let input = r"
+[00:01.00]verse
+[00:02.00][00:04.00]chorus
+[00:03.00]bridge";
+
+        let result: Lrc = input.parse().unwrap();
+
+        assert_eq!(
+            result.lines,
+            vec![
+                LrcLine {
+                    time: Duration::from_secs(1),
+                    content: "verse".to_string(),
+                    words: Vec::new()
+                },
+                LrcLine {
+                    time: Duration::from_secs(2),
+                    content: "chorus".to_string(),
+                    words: Vec::new()
+                },
+                LrcLine {
+                    time: Duration::from_secs(3),
+                    content: "bridge".to_string(),
+                    words: Vec::new()
+                },
+                LrcLine {
+                    time: Duration::from_secs(4),
+                    content: "chorus".to_string(),
+                    words: Vec::new()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn lrc_timestamp_without_hundredths() {
+        let input = r"
+[01:02]text
+[01:03.50]next";
+
+        let result: Lrc = input.parse().unwrap();
+
+        assert_eq!(
+            result.lines,
+            vec![
+                LrcLine {
+                    time: Duration::from_secs(62),
+                    content: "text".to_string(),
+                    words: Vec::new()
+                },
+                LrcLine {
+                    time: Duration::from_millis(63_500),
+                    content: "next".to_string(),
+                    words: Vec::new()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn lrc_metadata_after_timed_lines() {
+        let input = r"
+[00:01.00]line1
+[00:02.00]line2
+[ti: late title]
+[offset: +500]";
+
+        let result: Lrc = input.parse().unwrap();
+
+        assert_eq!(
+            result,
+            Lrc {
+                title: Some("late title".to_string()),
+                artist: None,
+                album: None,
+                author: None,
+                length: None,
+                lines: vec![
+                    LrcLine {
+                        time: Duration::from_millis(500),
+                        content: "line1".to_string(),
+                        words: Vec::new()
+                    },
+                    LrcLine {
+                        time: Duration::from_millis(1500),
+                        content: "line2".to_string(),
+                        words: Vec::new()
+                    },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn lrc_timestamp_fraction_precision() {
+        let input = r"
+[00:01.86]hundredths
+[00:02.860]milliseconds
+[00:03.5]tenths";
+
+        let result: Lrc = input.parse().unwrap();
+
+        assert_eq!(
+            result.lines.iter().map(|line| line.time).collect::<Vec<_>>(),
+            vec![
+                Duration::from_millis(1860),
+                Duration::from_millis(2860),
+                Duration::from_millis(3500)
+            ]
+        );
+    }
+
+    #[test]
+    fn active_line_is_the_last_started_line() {
+        let input = r"
+[00:01.00]line1
+[00:02.00]line2
+[00:03.00]line3";
+        let lrc: Lrc = input.parse().unwrap();
+
+        assert_eq!(lrc.active_line_idx(Duration::from_millis(500)), None);
+        assert_eq!(lrc.active_line_idx(Duration::from_secs(1)), Some(0));
+        assert_eq!(lrc.active_line_idx(Duration::from_millis(2500)), Some(1));
+        assert_eq!(lrc.active_line_idx(Duration::from_secs(90)), Some(2));
+    }
+
+    #[test]
+    fn lrc_keeps_angle_brackets_which_are_not_timestamps() {
+        let input = r"
+[00:01.00]<Instrumental>
+[00:02.00]a <3 b>
+[00:03.00]<00:03.00>timed <Outro>";
+
+        let result: Lrc = input.parse().unwrap();
+
+        assert_eq!(
+            result.lines,
+            vec![
+                LrcLine {
+                    time: Duration::from_secs(1),
+                    content: "<Instrumental>".to_string(),
+                    words: Vec::new()
+                },
+                LrcLine {
+                    time: Duration::from_secs(2),
+                    content: "a <3 b>".to_string(),
+                    words: Vec::new()
+                },
+                LrcLine {
+                    time: Duration::from_secs(3),
+                    content: "timed <Outro>".to_string(),
+                    words: vec![(Duration::ZERO, "timed <Outro>".to_string())]
+                },
+            ]
+        );
+    }
 }
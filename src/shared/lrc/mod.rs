@@ -1,11 +1,13 @@
 mod index;
 mod lyrics;
+mod resolve;
 
 use std::time::Duration;
 
 use anyhow::Context;
 pub use index::LrcIndex;
 pub use lyrics::Lrc;
+pub use resolve::find_lrc_path;
 
 fn parse_length(input: &str) -> anyhow::Result<Duration> {
     let (minutes, seconds) = input.split_once(':').context("Invalid lrc length format")?;
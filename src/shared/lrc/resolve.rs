@@ -0,0 +1,123 @@
+use std::path::{Path, PathBuf};
+
+use crate::mpd::commands::Song;
+
+/// Finds the lrc file of the song. Tries the song's own path with the `.lrc` extension, the same relative path
+/// inside `lyrics_dir` and finally `{artist} - {title}.lrc` inside `lyrics_dir`. Returns the first existing file.
+pub fn find_lrc_path(song: &Song, lyrics_dir: Option<&str>, music_directory: Option<&str>) -> Option<PathBuf> {
+    candidates(song, lyrics_dir, music_directory).into_iter().find(|path| {
+        log::trace!(path:?; "Trying lrc path");
+        path.is_file()
+    })
+}
+
+fn candidates(song: &Song, lyrics_dir: Option<&str>, music_directory: Option<&str>) -> Vec<PathBuf> {
+    let relative = Path::new(&song.file).with_extension("lrc");
+    let mut result = Vec::new();
+
+    if let Some(music_directory) = music_directory {
+        result.push(Path::new(music_directory).join(&relative));
+    }
+
+    if let Some(lyrics_dir) = lyrics_dir {
+        result.push(Path::new(lyrics_dir).join(&relative));
+
+        if let (Some(artist), Some(title)) = (song.artist(), song.title()) {
+            // Slashes in tags such as "AC/DC" would otherwise be treated as directories
+            let name = format!("{} - {}.lrc", artist.first(), title.first()).replace('/', "_");
+            result.push(Path::new(lyrics_dir).join(name));
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::{collections::HashMap, path::PathBuf};
+
+    use crate::mpd::commands::Song;
+
+    use super::find_lrc_path;
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("rmpc_lrc_{}_{name}", std::process::id()));
+            std::fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+
+        fn create(&self, relative: &str) -> PathBuf {
+            let path = self.0.join(relative);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, "[00:01.00]line").unwrap();
+            path
+        }
+
+        fn path(&self, relative: &str) -> String {
+            self.0.join(relative).to_string_lossy().into_owned()
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn song() -> Song {
+        Song {
+            file: "artist/album/song.flac".to_owned(),
+            metadata: HashMap::from([
+                ("artist".to_owned(), "AC/DC".to_owned().into()),
+                ("title".to_owned(), "title".to_owned().into()),
+            ]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn prefers_lrc_next_to_the_song() {
+        let dir = TempDir::new("next_to_song");
+        let expected = dir.create("music/artist/album/song.lrc");
+        dir.create("lyrics/artist/album/song.lrc");
+        dir.create("lyrics/AC_DC - title.lrc");
+
+        let result = find_lrc_path(&song(), Some(&dir.path("lyrics")), Some(&dir.path("music")));
+
+        assert_eq!(result, Some(expected));
+    }
+
+    #[test]
+    fn falls_back_to_lyrics_dir_mirroring_the_library() {
+        let dir = TempDir::new("mirrored");
+        let expected = dir.create("lyrics/artist/album/song.lrc");
+        dir.create("lyrics/AC_DC - title.lrc");
+
+        let result = find_lrc_path(&song(), Some(&dir.path("lyrics")), Some(&dir.path("music")));
+
+        assert_eq!(result, Some(expected));
+    }
+
+    #[test]
+    fn falls_back_to_artist_and_title() {
+        let dir = TempDir::new("artist_title");
+        let expected = dir.create("lyrics/AC_DC - title.lrc");
+
+        let result = find_lrc_path(&song(), Some(&dir.path("lyrics")), Some(&dir.path("music")));
+
+        assert_eq!(result, Some(expected));
+    }
+
+    #[test]
+    fn returns_none_without_any_lrc() {
+        let dir = TempDir::new("none");
+
+        let result = find_lrc_path(&song(), Some(&dir.path("lyrics")), None);
+
+        assert_eq!(result, None);
+    }
+}
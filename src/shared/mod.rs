@@ -1,3 +1,5 @@
+pub mod album_art_cache;
+pub mod album_art_output;
 pub mod dependencies;
 pub mod env;
 pub mod ext;
@@ -10,5 +12,6 @@ pub mod lrc;
 pub mod macros;
 pub mod mouse_event;
 pub mod percent;
+pub mod status_output;
 pub mod tmux;
 pub mod ytdlp;
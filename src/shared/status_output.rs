@@ -0,0 +1,81 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    os::unix::fs::{FileTypeExt, OpenOptionsExt},
+};
+
+use serde::Serialize;
+
+use crate::{
+    context::AppContext,
+    mpd::commands::{Song, Status},
+};
+
+#[derive(Serialize)]
+struct StatusLine<'a> {
+    status: &'a Status,
+    song: Option<&'a Song>,
+}
+
+/// Writes the current status and song as a JSON line to the configured `status_output_path`. A FIFO receives every
+/// line while a regular file is overwritten so that it always contains only the latest one.
+#[derive(Debug)]
+pub struct StatusOutput {
+    path: &'static str,
+    fifo: Option<File>,
+}
+
+impl StatusOutput {
+    pub fn new(path: &'static str) -> Self {
+        Self { path, fifo: None }
+    }
+
+    pub fn write(&mut self, context: &AppContext) {
+        let line = StatusLine {
+            status: &context.status,
+            song: context.find_current_song_in_queue().map(|(_, song)| song),
+        };
+        let mut line = match serde_json::to_string(&line) {
+            Ok(line) => line,
+            Err(err) => {
+                log::error!(error:? = err; "Failed to serialize status output");
+                return;
+            }
+        };
+        line.push('\n');
+
+        if let Err(err) = self.write_line(line.as_bytes()) {
+            log::trace!(error:? = err, path = self.path; "Failed to write status output");
+        }
+    }
+
+    fn write_line(&mut self, line: &[u8]) -> std::io::Result<()> {
+        let is_fifo = std::fs::metadata(self.path).is_ok_and(|meta| meta.file_type().is_fifo());
+        if !is_fifo {
+            self.fifo = None;
+            return std::fs::write(self.path, line);
+        }
+
+        if self.fifo.is_none() {
+            // Opening the FIFO in non blocking mode fails right away when there is no reader instead of blocking the
+            // main thread until one shows up
+            #[allow(clippy::cast_possible_wrap)]
+            let nonblock = rustix::fs::OFlags::NONBLOCK.bits() as i32;
+            self.fifo = Some(OpenOptions::new().write(true).custom_flags(nonblock).open(self.path)?);
+        }
+        let Some(fifo) = self.fifo.as_mut() else {
+            return Ok(());
+        };
+
+        match fifo.write_all(line) {
+            Ok(()) => Ok(()),
+            // The reader is not keeping up and the pipe is full, drop the line
+            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => Ok(()),
+            Err(err) => {
+                // Most likely the reader went away, reopen the FIFO on the next write
+                self.fifo = None;
+                Err(err)
+            }
+        }
+    }
+}
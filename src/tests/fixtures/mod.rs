@@ -1,13 +1,17 @@
-use std::{cell::Cell, collections::HashSet, sync::mpsc::channel};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashSet,
+    sync::{mpsc::channel, Arc},
+};
 
 use ratatui::{backend::TestBackend, Terminal};
 use rstest::fixture;
 
 use crate::{
     config::{Config, ConfigFile, Leak},
-    context::AppContext,
-    mpd::commands::Status,
-    shared::lrc::LrcIndex,
+    context::{AppContext, ConnectionState},
+    mpd::commands::{volume::MuteState, Status},
+    shared::{album_art_cache::AlbumArtCache, lrc::LrcIndex},
 };
 
 pub mod mpd_client;
@@ -36,6 +40,13 @@ pub fn app_context() -> AppContext {
         supported_commands: HashSet::new(),
         needs_render: Cell::new(false),
         lrc_index: LrcIndex::default(),
+        album_art_accent: None,
+        stored_playlists: None,
+        stop_after_current: None,
+        ratings: Arc::default(),
+        connection: ConnectionState::default(),
+        album_art_cache: RefCell::new(AlbumArtCache::new(config.album_art_cache_size)),
+        mute: MuteState::default(),
     }
 }
 
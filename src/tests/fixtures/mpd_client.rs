@@ -10,12 +10,16 @@ use rstest::fixture;
 
 use crate::mpd::{
     commands::{
-        list::MpdList, list_playlist::FileList, status::OnOffOneshot, volume::Bound, IdleEvent, ListFiles, LsInfo,
-        Playlist, Song, Status, Update, Volume,
+        current_song::MetadataTag, list::MpdList, list_playlist::FileList, status::OnOffOneshot, volume::Bound, AddId,
+        IdleEvent, ListFiles, LsInfo, Playlist, QueuePositions, ReplayGainMode, Song, Status, Sticker, Update, Volume,
     },
     errors::MpdError,
-    mpd_client::{Filter, MpdClient, QueueMoveTarget, SaveMode, SingleOrRange, Tag, ValueChange},
+    mpd_client::{
+        AlbumArtSource, Filter, FilterExpression, FindOptions, MpdClient, QueueMoveTarget, SaveMode, SingleOrRange,
+        Tag, ValueChange,
+    },
     proto_client::SocketClient,
+    version::Version,
 };
 
 #[fixture]
@@ -35,9 +39,9 @@ pub fn client() -> TestMpdClient {
                 id: i,
                 file: format!("{}_{}_file_{i}", *artist, *album),
                 metadata: HashMap::from([
-                    ("artist".to_owned(), (*artist).to_string()),
-                    ("album".to_owned(), (*album).to_string()),
-                    ("title".to_owned(), format!("{}_{}_file_{i}", *artist, *album)),
+                    ("artist".to_owned(), (*artist).to_string().into()),
+                    ("album".to_owned(), (*album).to_string().into()),
+                    ("title".to_owned(), format!("{}_{}_file_{i}", *artist, *album).into()),
                 ]),
                 duration: Some(Duration::from_secs(i.into())),
             })
@@ -72,6 +76,9 @@ pub fn client() -> TestMpdClient {
         status: Status::default(),
         calls: HashMap::default(),
         rx: BufReader::new(Box::new(Cursor::new(String::new()))),
+        version: Version::new(0, 24, 0),
+        stickers: HashMap::default(),
+        comments: HashMap::default(),
     }
 }
 
@@ -89,6 +96,11 @@ pub struct TestMpdClient {
     pub status: Status,
     pub calls: HashMap<String, u32>,
     pub rx: BufReader<Box<dyn BufRead>>,
+    pub version: Version,
+    /// Stickers by song file and sticker name
+    pub stickers: HashMap<String, HashMap<String, String>>,
+    /// Comments by song file and comment name
+    pub comments: HashMap<String, HashMap<String, String>>,
 }
 
 impl TestMpdClient {
@@ -99,13 +111,41 @@ impl TestMpdClient {
     pub fn set_read(&mut self, read: BufReader<Box<dyn BufRead>>) {
         self.rx = read;
     }
+
+    /// Adds the song with the given file, or all songs in the given directory, to the queue like MPD would
+    fn add_to_queue(&mut self, path: &str, position: Option<QueueMoveTarget>) {
+        let dir = format!("{}/", path.trim_end_matches('/'));
+        let indices = self
+            .songs
+            .iter()
+            .positions(|song| song.file == path || song.file.starts_with(&dir))
+            .collect_vec();
+        let current = self.status.song.unwrap_or_default() as usize;
+        let position = match position {
+            Some(QueueMoveTarget::Absolute(pos)) => pos,
+            Some(QueueMoveTarget::RelativeAdd(offset)) => current + 1 + offset,
+            Some(QueueMoveTarget::RelativeSub(offset)) => current.saturating_sub(offset),
+            None => self.queue.len(),
+        }
+        .min(self.queue.len());
+        self.queue.splice(position..position, indices);
+    }
+
+    fn queue_positions_of(&self, songs: &[Song]) -> QueuePositions {
+        QueuePositions(
+            self.queue
+                .iter()
+                .positions(|idx| songs.iter().any(|song| song.file == self.songs[*idx].file))
+                .collect(),
+        )
+    }
 }
 
 type MpdResult<T> = Result<T, MpdError>;
 #[allow(clippy::cast_possible_truncation)]
 impl MpdClient for TestMpdClient {
     fn commands(&mut self) -> MpdResult<MpdList> {
-        todo!("Not yet implemented")
+        Ok(MpdList(["status", "playlistinfo"].map(str::to_owned).to_vec()))
     }
 
     fn idle(&mut self, _subsystem: Option<IdleEvent>) -> MpdResult<Vec<IdleEvent>> {
@@ -116,8 +156,8 @@ impl MpdClient for TestMpdClient {
         todo!()
     }
 
-    fn get_volume(&mut self) -> MpdResult<Volume> {
-        Ok(self.volume)
+    fn get_volume(&mut self) -> MpdResult<Option<Volume>> {
+        Ok(Some(self.volume))
     }
 
     fn set_volume(&mut self, volume: Volume) -> MpdResult<()> {
@@ -142,6 +182,12 @@ impl MpdClient for TestMpdClient {
         Ok(self.status.clone())
     }
 
+    fn clear_error(&mut self) -> MpdResult<()> {
+        self.calls.entry("clear_error".to_string()).or_default().add_assign(1);
+        self.status.error = None;
+        Ok(())
+    }
+
     fn pause_toggle(&mut self) -> MpdResult<()> {
         use crate::mpd::commands::State as S;
         self.status.state = match self.status.state {
@@ -207,8 +253,12 @@ impl MpdClient for TestMpdClient {
         Ok(())
     }
 
-    fn seek_current(&mut self, _value: ValueChange) -> MpdResult<()> {
-        todo!("Not yet implemented")
+    fn seek_current(&mut self, value: ValueChange) -> MpdResult<()> {
+        self.calls
+            .entry(format!("seek_current {value:?}"))
+            .or_default()
+            .add_assign(1);
+        Ok(())
     }
 
     fn repeat(&mut self, enabled: bool) -> MpdResult<()> {
@@ -231,7 +281,44 @@ impl MpdClient for TestMpdClient {
         Ok(())
     }
 
-    fn add(&mut self, _path: &str) -> MpdResult<()> {
+    fn replay_gain_status(&mut self) -> MpdResult<ReplayGainMode> {
+        Ok(self.status.replay_gain_mode.unwrap_or_default())
+    }
+
+    fn set_replay_gain_mode(&mut self, mode: ReplayGainMode) -> MpdResult<()> {
+        self.status.replay_gain_mode = Some(mode);
+        Ok(())
+    }
+
+    fn set_crossfade(&mut self, secs: u32) -> MpdResult<()> {
+        self.calls
+            .entry(format!("set_crossfade {secs}"))
+            .or_default()
+            .add_assign(1);
+        self.status.xfade = (secs > 0).then_some(secs);
+        Ok(())
+    }
+
+    fn set_mixramp_db(&mut self, db: f32) -> MpdResult<()> {
+        self.status.mixrampdb = Some(db);
+        Ok(())
+    }
+
+    fn add(&mut self, path: &str, position: Option<QueueMoveTarget>) -> MpdResult<()> {
+        self.calls.entry("add".to_string()).or_default().add_assign(1);
+        self.add_to_queue(path, position);
+        Ok(())
+    }
+
+    fn add_multiple(&mut self, paths: &[&str], position: Option<QueueMoveTarget>) -> MpdResult<()> {
+        self.calls.entry("add_multiple".to_string()).or_default().add_assign(1);
+        for path in paths {
+            self.add_to_queue(path, position);
+        }
+        Ok(())
+    }
+
+    fn add_id(&mut self, _path: &str, _position: Option<usize>) -> MpdResult<AddId> {
         todo!("Not yet implemented")
     }
 
@@ -242,8 +329,14 @@ impl MpdClient for TestMpdClient {
         Ok(())
     }
 
-    fn delete_id(&mut self, _id: u32) -> MpdResult<()> {
-        todo!("Not yet implemented")
+    fn delete_id(&mut self, id: u32) -> MpdResult<()> {
+        match self.queue.iter().position(|s| self.songs[*s].id == id) {
+            Some(idx) => {
+                self.queue.remove(idx);
+                Ok(())
+            }
+            None => Err(MpdError::Generic("Song id not found".to_string())),
+        }
     }
 
     fn playlist_info(&mut self) -> MpdResult<Option<Vec<Song>>> {
@@ -259,25 +352,30 @@ impl MpdClient for TestMpdClient {
             .iter()
             .filter(|s| {
                 let mut matches = true;
+                let file = MetadataTag::Single(s.file.clone());
                 let values = [
                     s.artist(),
                     s.metadata.get("albumartist"),
                     s.album(),
                     s.title(),
-                    Some(&s.file),
+                    Some(&file),
                     s.metadata.get("genre"),
                 ];
 
                 for filter in filter {
                     let value = match filter.tag {
-                        Tag::Any => values.iter().any(|a| a.is_some_and(|a| a.contains(filter.value))),
-                        Tag::Artist => values[0].is_some_and(|a| a.contains(filter.value)),
-                        Tag::AlbumArtist => values[1].is_some_and(|a| a.contains(filter.value)),
-                        Tag::Album => values[2].is_some_and(|a| a.contains(filter.value)),
-                        Tag::Title => values[3].is_some_and(|a| a.contains(filter.value)),
-                        Tag::File => values[4].is_some_and(|a| a.contains(filter.value)),
-                        Tag::Genre => values[5].is_some_and(|a| a.contains(filter.value)),
-                        Tag::Custom(_) => false,
+                        Tag::Any => values
+                            .iter()
+                            .any(|a| a.is_some_and(|a| a.iter().any(|v| v.contains(filter.value.as_ref())))),
+                        Tag::Artist => values[0].is_some_and(|a| a.iter().any(|v| v.contains(filter.value.as_ref()))),
+                        Tag::AlbumArtist => {
+                            values[1].is_some_and(|a| a.iter().any(|v| v.contains(filter.value.as_ref())))
+                        }
+                        Tag::Album => values[2].is_some_and(|a| a.iter().any(|v| v.contains(filter.value.as_ref()))),
+                        Tag::Title => values[3].is_some_and(|a| a.iter().any(|v| v.contains(filter.value.as_ref()))),
+                        Tag::File => values[4].is_some_and(|a| a.iter().any(|v| v.contains(filter.value.as_ref()))),
+                        Tag::Genre => values[5].is_some_and(|a| a.iter().any(|v| v.contains(filter.value.as_ref()))),
+                        Tag::Date | Tag::Custom(_) => false,
                     };
                     if !value {
                         matches = false;
@@ -296,37 +394,49 @@ impl MpdClient for TestMpdClient {
             .iter()
             .filter(|s| {
                 let mut matches = true;
+                let file = MetadataTag::Single(s.file.clone());
                 let values = [
                     s.artist(),
                     s.metadata.get("albumartist"),
                     s.album(),
                     s.title(),
-                    Some(&s.file),
+                    Some(&file),
                     s.metadata.get("genre"),
                 ];
 
                 for filter in filter {
                     let value = match filter.tag {
-                        Tag::Any => values
-                            .iter()
-                            .any(|a| a.is_some_and(|a| a.to_lowercase().contains(&filter.value.to_lowercase()))),
-                        Tag::Artist => {
-                            values[0].is_some_and(|a| a.to_lowercase().contains(&filter.value.to_lowercase()))
-                        }
-                        Tag::AlbumArtist => {
-                            values[1].is_some_and(|a| a.to_lowercase().contains(&filter.value.to_lowercase()))
-                        }
-                        Tag::Album => {
-                            values[2].is_some_and(|a| a.to_lowercase().contains(&filter.value.to_lowercase()))
-                        }
-                        Tag::Title => {
-                            values[3].is_some_and(|a| a.to_lowercase().contains(&filter.value.to_lowercase()))
-                        }
-                        Tag::File => values[4].is_some_and(|a| a.to_lowercase().contains(&filter.value.to_lowercase())),
-                        Tag::Genre => {
-                            values[5].is_some_and(|a| a.to_lowercase().contains(&filter.value.to_lowercase()))
-                        }
-                        Tag::Custom(_) => false,
+                        Tag::Any => values.iter().any(|a| {
+                            a.is_some_and(|a| {
+                                a.iter()
+                                    .any(|v| v.to_lowercase().contains(&filter.value.to_lowercase()))
+                            })
+                        }),
+                        Tag::Artist => values[0].is_some_and(|a| {
+                            a.iter()
+                                .any(|v| v.to_lowercase().contains(&filter.value.to_lowercase()))
+                        }),
+                        Tag::AlbumArtist => values[1].is_some_and(|a| {
+                            a.iter()
+                                .any(|v| v.to_lowercase().contains(&filter.value.to_lowercase()))
+                        }),
+                        Tag::Album => values[2].is_some_and(|a| {
+                            a.iter()
+                                .any(|v| v.to_lowercase().contains(&filter.value.to_lowercase()))
+                        }),
+                        Tag::Title => values[3].is_some_and(|a| {
+                            a.iter()
+                                .any(|v| v.to_lowercase().contains(&filter.value.to_lowercase()))
+                        }),
+                        Tag::File => values[4].is_some_and(|a| {
+                            a.iter()
+                                .any(|v| v.to_lowercase().contains(&filter.value.to_lowercase()))
+                        }),
+                        Tag::Genre => values[5].is_some_and(|a| {
+                            a.iter()
+                                .any(|v| v.to_lowercase().contains(&filter.value.to_lowercase()))
+                        }),
+                        Tag::Date | Tag::Custom(_) => false,
                     };
                     if !value {
                         matches = false;
@@ -339,8 +449,12 @@ impl MpdClient for TestMpdClient {
             .collect())
     }
 
-    fn move_id(&mut self, _id: u32, _to: QueueMoveTarget) -> MpdResult<()> {
-        todo!("Not yet implemented")
+    fn move_id(&mut self, id: u32, to: QueueMoveTarget) -> MpdResult<()> {
+        self.calls
+            .entry(format!("move_id {id} {to:?}"))
+            .or_default()
+            .add_assign(1);
+        Ok(())
     }
 
     fn find_one(&mut self, filter: &[Filter<'_, '_>]) -> MpdResult<Option<Song>> {
@@ -352,18 +466,44 @@ impl MpdClient for TestMpdClient {
         }
     }
 
-    fn find_add(&mut self, _filter: &[Filter<'_, '_>]) -> MpdResult<()> {
+    fn find_add(&mut self, _filter: &[Filter<'_, '_>], _position: Option<QueueMoveTarget>) -> MpdResult<()> {
         todo!("Not yet implemented")
     }
 
-    fn list_tag(&mut self, _tag: Tag, _filter: Option<&[Filter<'_, '_>]>) -> MpdResult<MpdList> {
+    fn find_with(&mut self, _filter: &[Filter<'_, '_>], _options: FindOptions<'_>) -> MpdResult<Vec<Song>> {
+        todo!("Not yet implemented")
+    }
+
+    fn find_add_with(
+        &mut self,
+        _filter: &[Filter<'_, '_>],
+        _options: FindOptions<'_>,
+        _position: Option<QueueMoveTarget>,
+    ) -> MpdResult<()> {
         todo!("Not yet implemented")
     }
 
-    fn lsinfo(&mut self, _path: Option<&str>) -> MpdResult<LsInfo> {
+    fn list_tag(&mut self, tag: Tag, _filter: Option<&[Filter<'_, '_>]>) -> MpdResult<MpdList> {
+        match tag {
+            Tag::File => Ok(self.songs.iter().map(|song| song.file.clone()).collect_vec().into()),
+            _ => todo!("Not yet implemented"),
+        }
+    }
+
+    fn list_tag_grouped(
+        &mut self,
+        _tag: Tag,
+        _groups: &[Tag],
+        _filter: Option<&[Filter<'_, '_>]>,
+    ) -> MpdResult<Vec<(Vec<String>, String)>> {
         todo!("Not yet implemented")
     }
 
+    fn lsinfo(&mut self, path: Option<&str>) -> MpdResult<LsInfo> {
+        self.calls.entry(format!("lsinfo {path:?}")).or_default().add_assign(1);
+        Ok(LsInfo::default())
+    }
+
     fn list_files(&mut self, _path: Option<&str>) -> MpdResult<ListFiles> {
         todo!("Not yet implemented")
     }
@@ -376,6 +516,15 @@ impl MpdClient for TestMpdClient {
         todo!("Not yet implemented")
     }
 
+    fn read_comments(&mut self, path: &str) -> MpdResult<crate::mpd::commands::Comments> {
+        if !self.songs.iter().any(|song| song.file == path) {
+            return Err(MpdError::Generic("Song not found".to_string()));
+        }
+        Ok(crate::mpd::commands::Comments(
+            self.comments.get(path).cloned().unwrap_or_default(),
+        ))
+    }
+
     fn list_playlists(&mut self) -> MpdResult<Vec<Playlist>> {
         self.playlists
             .iter()
@@ -389,6 +538,7 @@ impl MpdClient for TestMpdClient {
     }
 
     fn list_playlist(&mut self, name: &str) -> MpdResult<FileList> {
+        self.calls.entry("list_playlist".to_string()).or_default().add_assign(1);
         self.playlists.iter().find(|p| p.name == name).map_or_else(
             || Err(MpdError::Generic("Playlist not found".to_string())),
             |p| {
@@ -409,17 +559,15 @@ impl MpdClient for TestMpdClient {
                 Ok(p.songs_indices
                     .iter()
                     .map(|idx| Song {
-                        file: self.songs[*idx].file.clone(),
                         id: *idx as u32,
-                        duration: None,
-                        metadata: HashMap::default(),
+                        ..self.songs[*idx].clone()
                     })
                     .collect())
             },
         )
     }
 
-    fn load_playlist(&mut self, _name: &str) -> MpdResult<()> {
+    fn load_playlist(&mut self, _name: &str, _position: Option<QueueMoveTarget>) -> MpdResult<()> {
         todo!("Not yet implemented")
     }
 
@@ -427,32 +575,74 @@ impl MpdClient for TestMpdClient {
         todo!("Not yet implemented")
     }
 
-    fn delete_playlist(&mut self, _name: &str) -> MpdResult<()> {
-        todo!("Not yet implemented")
+    fn delete_playlist(&mut self, name: &str) -> MpdResult<()> {
+        self.calls
+            .entry(format!("delete_playlist {name}"))
+            .or_default()
+            .add_assign(1);
+        Ok(())
     }
 
-    fn delete_from_playlist(&mut self, _playlist_name: &str, _songs: &SingleOrRange) -> MpdResult<()> {
-        todo!("Not yet implemented")
+    fn delete_from_playlist(&mut self, playlist_name: &str, songs: &SingleOrRange) -> MpdResult<()> {
+        let playlist = self
+            .playlists
+            .iter_mut()
+            .find(|p| p.name == playlist_name)
+            .ok_or_else(|| MpdError::Generic("Playlist not found".to_string()))?;
+        playlist
+            .songs_indices
+            .drain(songs.start..songs.end.unwrap_or(songs.start + 1));
+        Ok(())
     }
 
     fn move_in_playlist(
         &mut self,
-        _playlist_name: &str,
-        _range: &SingleOrRange,
-        _target_position: usize,
+        playlist_name: &str,
+        range: &SingleOrRange,
+        target_position: usize,
     ) -> MpdResult<()> {
-        todo!("Not yet implemented")
+        let playlist = self
+            .playlists
+            .iter_mut()
+            .find(|p| p.name == playlist_name)
+            .ok_or_else(|| MpdError::Generic("Playlist not found".to_string()))?;
+        let moved = playlist
+            .songs_indices
+            .drain(range.start..range.end.unwrap_or(range.start + 1))
+            .collect::<Vec<_>>();
+        playlist.songs_indices.splice(target_position..target_position, moved);
+        Ok(())
     }
 
-    fn add_to_playlist(&mut self, _playlist_name: &str, _uri: &str, _target_position: Option<usize>) -> MpdResult<()> {
-        todo!("Not yet implemented")
+    fn add_to_playlist(&mut self, playlist_name: &str, uri: &str, _target_position: Option<usize>) -> MpdResult<()> {
+        let song_idx = self
+            .songs
+            .iter()
+            .position(|song| song.file == uri)
+            .ok_or_else(|| MpdError::Generic("Song not found".to_string()))?;
+        let playlist = self
+            .playlists
+            .iter_mut()
+            .find(|p| p.name == playlist_name)
+            .ok_or_else(|| MpdError::Generic("Playlist not found".to_string()))?;
+        playlist.songs_indices.push(song_idx);
+        Ok(())
     }
 
-    fn save_queue_as_playlist(&mut self, _name: &str, _mode: Option<SaveMode>) -> MpdResult<()> {
-        todo!("Not yet implemented")
+    fn add_songs_to_playlist(&mut self, playlist_name: &str, uris: &[String]) -> MpdResult<()> {
+        uris.iter()
+            .try_for_each(|uri| self.add_to_playlist(playlist_name, uri, None))
+    }
+
+    fn save_queue_as_playlist(&mut self, name: &str, mode: Option<SaveMode>) -> MpdResult<()> {
+        self.calls
+            .entry(format!("save_queue_as_playlist {name} {mode:?}"))
+            .or_default()
+            .add_assign(1);
+        Ok(())
     }
 
-    fn find_album_art(&mut self, _path: &str) -> MpdResult<Option<Vec<u8>>> {
+    fn find_album_art(&mut self, _path: &str, _source: AlbumArtSource) -> MpdResult<Option<Vec<u8>>> {
         self.calls
             .entry("find_album_art".to_string())
             .or_default()
@@ -488,11 +678,35 @@ impl MpdClient for TestMpdClient {
         todo!("Not yet implemented")
     }
 
-    fn version(&mut self) -> crate::mpd::version::Version {
+    fn version(&mut self) -> Version {
+        self.version
+    }
+
+    fn search_add(&mut self, _filter: &[Filter<'_, '_>], _position: Option<QueueMoveTarget>) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn find_expression(&mut self, _expression: &FilterExpression<'_, '_>) -> MpdResult<Vec<Song>> {
         todo!("Not yet implemented")
     }
 
-    fn search_add(&mut self, _filter: &[Filter<'_, '_>]) -> MpdResult<()> {
+    fn search_expression(&mut self, _expression: &FilterExpression<'_, '_>) -> MpdResult<Vec<Song>> {
+        todo!("Not yet implemented")
+    }
+
+    fn find_add_expression(
+        &mut self,
+        _expression: &FilterExpression<'_, '_>,
+        _position: Option<QueueMoveTarget>,
+    ) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn search_add_expression(
+        &mut self,
+        _expression: &FilterExpression<'_, '_>,
+        _position: Option<QueueMoveTarget>,
+    ) -> MpdResult<()> {
         todo!("Not yet implemented")
     }
 
@@ -516,13 +730,86 @@ impl MpdClient for TestMpdClient {
         todo!("Not yet implemented")
     }
 
-    fn move_in_queue(&mut self, _from: SingleOrRange, _to: QueueMoveTarget) -> MpdResult<()> {
+    fn sticker_get(&mut self, uri: &str, name: &str) -> MpdResult<Option<Sticker>> {
+        Ok(self
+            .stickers
+            .get(uri)
+            .and_then(|stickers| stickers.get(name))
+            .map(|value| Sticker {
+                name: name.to_owned(),
+                value: value.clone(),
+            }))
+    }
+
+    fn sticker_set(&mut self, uri: &str, name: &str, value: &str) -> MpdResult<()> {
+        self.calls
+            .entry(format!("sticker_set {uri} {name} {value}"))
+            .or_default()
+            .add_assign(1);
+        self.stickers
+            .entry(uri.to_owned())
+            .or_default()
+            .insert(name.to_owned(), value.to_owned());
+        Ok(())
+    }
+
+    fn sticker_delete(&mut self, uri: &str, name: &str) -> MpdResult<()> {
+        self.calls
+            .entry(format!("sticker_delete {uri} {name}"))
+            .or_default()
+            .add_assign(1);
+        self.stickers.get_mut(uri).and_then(|stickers| stickers.remove(name));
+        Ok(())
+    }
+
+    fn sticker_list(&mut self, _uri: &str) -> MpdResult<crate::mpd::commands::Stickers> {
         todo!("Not yet implemented")
     }
 
-    fn delete_from_queue(&mut self, _songs: SingleOrRange) -> MpdResult<()> {
+    fn sticker_find(&mut self, _uri: &str, _name: &str) -> MpdResult<crate::mpd::commands::FoundStickers> {
         todo!("Not yet implemented")
     }
+
+    fn queue_find(&mut self, filter: &[Filter<'_, '_>]) -> MpdResult<QueuePositions> {
+        let matching = self.find(filter)?;
+        Ok(self.queue_positions_of(&matching))
+    }
+
+    fn queue_search(&mut self, filter: &[Filter<'_, '_>]) -> MpdResult<QueuePositions> {
+        let matching = self.search(filter)?;
+        Ok(self.queue_positions_of(&matching))
+    }
+
+    fn move_in_queue(&mut self, from: SingleOrRange, to: QueueMoveTarget) -> MpdResult<()> {
+        self.calls
+            .entry(format!("move_in_queue {from:?} {to:?}"))
+            .or_default()
+            .add_assign(1);
+        Ok(())
+    }
+
+    fn delete_from_queue(&mut self, songs: SingleOrRange) -> MpdResult<()> {
+        self.calls
+            .entry(format!("delete_from_queue {}", songs.as_mpd_range()))
+            .or_default()
+            .add_assign(1);
+        self.queue.drain(songs.start..songs.end.unwrap_or(songs.start + 1));
+        Ok(())
+    }
+
+    fn delete_ranges_from_queue(&mut self, ranges: &[SingleOrRange]) -> MpdResult<()> {
+        self.calls
+            .entry(format!(
+                "delete_ranges_from_queue {}",
+                ranges.iter().map(SingleOrRange::as_mpd_range).join(" ")
+            ))
+            .or_default()
+            .add_assign(1);
+        for range in ranges {
+            self.queue.drain(range.start..range.end.unwrap_or(range.start + 1));
+        }
+        Ok(())
+    }
 }
 
 impl SocketClient for TestMpdClient {
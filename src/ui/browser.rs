@@ -1,25 +1,38 @@
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    collections::{hash_map::Entry, HashMap},
+    hash::Hash,
+};
+
 use anyhow::{Context, Result};
 use crossterm::event::KeyCode;
 use itertools::Itertools;
+use rand::Rng;
 use ratatui::{prelude::Rect, widgets::ListItem};
 
 use crate::{
     cli::{create_env, run_external},
     config::{
         keys::{CommonAction, GlobalAction},
-        Config,
+        Config, FilterBehavior, SortOrder,
     },
     context::AppContext,
-    mpd::{commands::Song, mpd_client::MpdClient},
+    mpd::{
+        commands::Song,
+        errors::MpdError,
+        mpd_client::{Filter, FilterKind, FindOptions, MpdClient, QueueMoveTarget, SingleOrRange, ValueChange},
+    },
     shared::{
         key_event::KeyEvent,
+        macros::status_warn,
         mouse_event::{MouseEvent, MouseEventKind},
     },
 };
 
 use super::{
     dirstack::{DirStack, DirStackItem},
-    panes::Pane,
+    panes::{browser::DirOrSong, Pane},
 };
 
 pub enum MoveDirection {
@@ -27,6 +40,153 @@ pub enum MoveDirection {
     Down,
 }
 
+/// How a browser matches the tag values it navigates through when listing and adding songs
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MatchMode {
+    /// Values have to be equal, songs are queried with MPD's `find`
+    #[default]
+    Exact,
+    /// Values only have to contain the browsed value ignoring case, songs are queried with MPD's `search`
+    Substring,
+}
+
+impl MatchMode {
+    pub fn toggled(self) -> Self {
+        match self {
+            MatchMode::Exact => MatchMode::Substring,
+            MatchMode::Substring => MatchMode::Exact,
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            MatchMode::Exact => "exact",
+            MatchMode::Substring => "substring",
+        }
+    }
+
+    pub fn kind(self) -> FilterKind {
+        match self {
+            MatchMode::Exact => FilterKind::Exact,
+            MatchMode::Substring => FilterKind::Regex,
+        }
+    }
+
+    /// Value to filter by. Substring matching is done with a case-insensitive regex so that it
+    /// also applies to `list` which, unlike `search`, does not fold case.
+    pub fn value(self, value: &str) -> Cow<'_, str> {
+        match self {
+            MatchMode::Exact => Cow::Borrowed(value),
+            MatchMode::Substring => Cow::Owned(format!("(?i){}", escape_regex(value))),
+        }
+    }
+
+    pub fn find(self, client: &mut impl MpdClient, filter: &[Filter<'_, '_>]) -> Result<Vec<Song>, MpdError> {
+        match self {
+            MatchMode::Exact => client.find(filter),
+            MatchMode::Substring => client.search(filter),
+        }
+    }
+
+    pub fn find_add(
+        self,
+        client: &mut impl MpdClient,
+        filter: &[Filter<'_, '_>],
+        position: Option<QueueMoveTarget>,
+    ) -> Result<(), MpdError> {
+        match self {
+            MatchMode::Exact => client.find_add(filter, position),
+            MatchMode::Substring => client.search_add(filter, position),
+        }
+    }
+}
+
+/// Orders the values of `(date, value)` pairs by the earliest date they appear with. Values
+/// without any date come last, ties are ordered by the value.
+pub fn sort_by_earliest_date<'a, K: Ord + Hash>(pairs: impl IntoIterator<Item = (&'a str, K)>) -> Vec<K> {
+    let mut earliest: HashMap<K, Option<&str>> = HashMap::new();
+    for (date, value) in pairs {
+        let entry = earliest.entry(value).or_default();
+        match entry {
+            _ if date.is_empty() => {}
+            Some(current) if *current <= date => {}
+            _ => *entry = Some(date),
+        }
+    }
+
+    earliest
+        .into_iter()
+        .sorted_by(|(a, a_date), (b, b_date)| match (a_date, b_date) {
+            (Some(a_date), Some(b_date)) => a_date.cmp(b_date).then_with(|| a.cmp(b)),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => a.cmp(b),
+        })
+        .map(|(value, _)| value)
+        .collect()
+}
+
+/// Year of every value of `(date, value)` pairs whose dates all fall into the same year. Values
+/// without a date or with dates from different years have none.
+pub fn consistent_years<'a, K: Eq + Hash>(pairs: impl IntoIterator<Item = (&'a str, K)>) -> HashMap<K, &'a str> {
+    let mut years: HashMap<K, Option<&str>> = HashMap::new();
+    for (date, value) in pairs {
+        let year = &date[..date.find(|c: char| !c.is_ascii_digit()).unwrap_or(date.len())];
+        match years.entry(value) {
+            Entry::Vacant(entry) => {
+                entry.insert(Some(year).filter(|year| !year.is_empty()));
+            }
+            Entry::Occupied(mut entry) => {
+                if entry.get().is_some_and(|current| current != year) {
+                    entry.insert(None);
+                }
+            }
+        }
+    }
+
+    years
+        .into_iter()
+        .filter_map(|(value, year)| year.map(|year| (value, year)))
+        .collect()
+}
+
+/// Turns listed albums into album dirs ordered by the given sort order. The albums have to be
+/// grouped by `Date` and optionally by `AlbumArtist` as the second group, albums of different
+/// album artists are then listed separately.
+pub fn album_dirs(albums: &[(Vec<String>, String)], sort: SortOrder) -> Vec<DirOrSong> {
+    let keyed = albums
+        .iter()
+        .map(|(groups, album)| {
+            let date = groups.first().map_or("", String::as_str);
+            (date, (groups.get(1).map(String::as_str), album.as_str()))
+        })
+        .collect_vec();
+    let years = consistent_years(keyed.iter().copied());
+    let keys = match sort {
+        SortOrder::Name => keyed.iter().map(|(_, key)| *key).sorted().dedup().collect_vec(),
+        SortOrder::Date => sort_by_earliest_date(keyed.iter().copied()),
+    };
+
+    keys.into_iter()
+        .map(|key @ (album_artist, name)| DirOrSong::Dir {
+            name: name.to_owned(),
+            full_path: String::new(),
+            year: years.get(&key).map(|year| (*year).to_owned()),
+            album_artist: album_artist.map(ToOwned::to_owned),
+        })
+        .collect_vec()
+}
+
+pub fn escape_regex(value: &str) -> String {
+    value.chars().fold(String::with_capacity(value.len()), |mut acc, c| {
+        if "\\^$.|?*+()[]{}".contains(c) {
+            acc.push('\\');
+        }
+        acc.push(c);
+        acc
+    })
+}
+
 #[allow(unused)]
 pub(in crate::ui) trait BrowserPane<T: DirStackItem + std::fmt::Debug>: Pane {
     fn stack(&self) -> &DirStack<T>;
@@ -42,10 +202,21 @@ pub(in crate::ui) trait BrowserPane<T: DirStackItem + std::fmt::Debug>: Pane {
     fn prepare_preview(
         &mut self,
         client: &mut impl MpdClient,
-        config: &Config,
+        context: &AppContext,
     ) -> Result<Option<Vec<ListItem<'static>>>>;
     fn add(&self, item: &T, client: &mut impl MpdClient, context: &AppContext) -> Result<()>;
     fn add_all(&self, client: &mut impl MpdClient, context: &AppContext) -> Result<()>;
+    /// Adds only the songs of the item within the window of the options, sorted by their sort tag
+    fn add_first(
+        &self,
+        _item: &T,
+        _options: FindOptions<'_>,
+        _client: &mut impl MpdClient,
+        _context: &AppContext,
+    ) -> Result<()> {
+        status_warn!("Adding only the first songs is supported in the Artists and Albums panes");
+        Ok(())
+    }
     fn open(&mut self, client: &mut impl MpdClient, context: &AppContext) -> Result<()>;
     fn delete(&self, item: &T, index: usize, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
         Ok(())
@@ -53,6 +224,27 @@ pub(in crate::ui) trait BrowserPane<T: DirStackItem + std::fmt::Debug>: Pane {
     fn rename(&self, item: &T, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
         Ok(())
     }
+    fn toggle_match_mode(&mut self, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
+        Ok(())
+    }
+    /// Goes back to the previous dir, carrying the filter of the current one over if enabled in the config
+    fn go_back(&mut self, config: &Config) {
+        let Some(popped) = self.stack_mut().pop() else {
+            return;
+        };
+
+        if !config.retain_filter_on_back {
+            return;
+        }
+
+        if let Some(filter) = popped.filter().filter(|filter| !filter.is_empty()) {
+            let current = self.stack_mut().current_mut();
+            current.set_filter(Some(filter.to_owned()), config);
+            if config.filter_behavior == FilterBehavior::Reduce {
+                current.reduce_to_matching(config);
+            }
+        }
+    }
     fn handle_filter_input(
         &mut self,
         event: &mut KeyEvent,
@@ -68,12 +260,23 @@ pub(in crate::ui) trait BrowserPane<T: DirStackItem + std::fmt::Debug>: Pane {
             Some(CommonAction::Close) => {
                 self.set_filter_input_mode_active(false);
                 self.stack_mut().current_mut().set_filter(None, config);
-                let preview = self.prepare_preview(client, config)?;
+                let preview = self.prepare_preview(client, context)?;
                 self.stack_mut().set_preview(preview);
                 context.render()?;
             }
+            Some(CommonAction::Confirm) if self.stack().current().filter().is_none_or(str::is_empty) => {
+                // Nothing to match against, so just leave the filter input
+                self.set_filter_input_mode_active(false);
+                self.stack_mut().current_mut().set_filter(None, config);
+                context.render()?;
+            }
             Some(CommonAction::Confirm) => {
                 self.set_filter_input_mode_active(false);
+                if config.filter_behavior == FilterBehavior::Reduce {
+                    self.stack_mut().current_mut().reduce_to_matching(config);
+                    let preview = self.prepare_preview(client, context)?;
+                    self.stack_mut().set_preview(preview);
+                }
                 context.render()?;
             }
             _ => {
@@ -82,7 +285,7 @@ pub(in crate::ui) trait BrowserPane<T: DirStackItem + std::fmt::Debug>: Pane {
                     KeyCode::Char(c) => {
                         self.stack_mut().current_mut().push_filter(c, config);
                         self.stack_mut().current_mut().jump_first_matching(config);
-                        let preview = self.prepare_preview(client, config)?;
+                        let preview = self.prepare_preview(client, context)?;
                         self.stack_mut().set_preview(preview);
                         context.render()?;
                     }
@@ -130,6 +333,28 @@ pub(in crate::ui) trait BrowserPane<T: DirStackItem + std::fmt::Debug>: Pane {
                     run_external(command, create_env(context, songs, client)?);
                 }
             }
+            GlobalAction::AddFirst { count, sort } => {
+                if let Some(selected) = self.stack().current().selected() {
+                    let options = FindOptions {
+                        sort,
+                        window: Some(SingleOrRange::range(0, count)),
+                    };
+                    self.add_first(selected, options, client, context)?;
+                }
+            }
+            GlobalAction::Random => {
+                let len = self.stack().current().items.len();
+                if len > 0 {
+                    let idx = rand::thread_rng().gen_range(0..len);
+                    self.stack_mut().current_mut().select_idx(idx, config.scrolloff);
+                    let preview = self
+                        .prepare_preview(client, context)
+                        .context("Cannot prepare preview")?;
+                    self.stack_mut().set_preview(preview);
+
+                    context.render()?;
+                }
+            }
             _ => {
                 event.abandon();
             }
@@ -154,9 +379,9 @@ pub(in crate::ui) trait BrowserPane<T: DirStackItem + std::fmt::Debug>: Pane {
                 if let Some(idx_to_select) = prev_stack.state.get_at_rendered_row(clicked_row) {
                     prev_stack.select_idx(idx_to_select, context.config.scrolloff);
                 }
-                self.stack_mut().pop();
+                self.go_back(context.config);
                 let preview = self
-                    .prepare_preview(client, context.config)
+                    .prepare_preview(client, context)
                     .context("Cannot prepare preview")?;
                 self.stack_mut().set_preview(preview);
 
@@ -168,7 +393,7 @@ pub(in crate::ui) trait BrowserPane<T: DirStackItem + std::fmt::Debug>: Pane {
                 if let Some(idx_to_select) = self.stack().current().state.get_at_rendered_row(clicked_row) {
                     self.next(client, context)?;
                     let preview = self
-                        .prepare_preview(client, context.config)
+                        .prepare_preview(client, context)
                         .context("Cannot prepare preview")?;
                     self.stack_mut().set_preview(preview);
 
@@ -187,7 +412,7 @@ pub(in crate::ui) trait BrowserPane<T: DirStackItem + std::fmt::Debug>: Pane {
                     }
 
                     let preview = self
-                        .prepare_preview(client, context.config)
+                        .prepare_preview(client, context)
                         .context("Cannot prepare preview")?;
                     self.stack_mut().set_preview(preview);
 
@@ -202,7 +427,7 @@ pub(in crate::ui) trait BrowserPane<T: DirStackItem + std::fmt::Debug>: Pane {
                         .current_mut()
                         .select_idx(idx_to_select, context.config.scrolloff);
                     let preview = self
-                        .prepare_preview(client, context.config)
+                        .prepare_preview(client, context)
                         .context("Cannot prepare preview")?;
                     self.stack_mut().set_preview(preview);
                     context.render()?;
@@ -226,7 +451,7 @@ pub(in crate::ui) trait BrowserPane<T: DirStackItem + std::fmt::Debug>: Pane {
                     .select_idx(idx_to_select.unwrap_or_default(), 0);
 
                 let preview = self
-                    .prepare_preview(client, context.config)
+                    .prepare_preview(client, context)
                     .context("Cannot prepare preview")?;
                 self.stack_mut().set_preview(preview);
 
@@ -235,7 +460,7 @@ pub(in crate::ui) trait BrowserPane<T: DirStackItem + std::fmt::Debug>: Pane {
             MouseEventKind::ScrollUp if current_area.contains(position) => {
                 self.stack_mut().current_mut().prev(context.config.scrolloff, false);
                 let preview = self
-                    .prepare_preview(client, context.config)
+                    .prepare_preview(client, context)
                     .context("Cannot prepare preview")?;
                 self.stack_mut().set_preview(preview);
 
@@ -244,7 +469,7 @@ pub(in crate::ui) trait BrowserPane<T: DirStackItem + std::fmt::Debug>: Pane {
             MouseEventKind::ScrollDown if current_area.contains(position) => {
                 self.stack_mut().current_mut().next(context.config.scrolloff, false);
                 let preview = self
-                    .prepare_preview(client, context.config)
+                    .prepare_preview(client, context)
                     .context("Cannot prepare preview")?;
                 self.stack_mut().set_preview(preview);
 
@@ -272,7 +497,9 @@ pub(in crate::ui) trait BrowserPane<T: DirStackItem + std::fmt::Debug>: Pane {
                 self.stack_mut()
                     .current_mut()
                     .prev(config.scrolloff, config.wrap_navigation);
-                let preview = self.prepare_preview(client, config).context("Cannot prepare preview")?;
+                let preview = self
+                    .prepare_preview(client, context)
+                    .context("Cannot prepare preview")?;
                 self.stack_mut().set_preview(preview);
 
                 context.render()?;
@@ -281,7 +508,9 @@ pub(in crate::ui) trait BrowserPane<T: DirStackItem + std::fmt::Debug>: Pane {
                 self.stack_mut()
                     .current_mut()
                     .next(config.scrolloff, config.wrap_navigation);
-                let preview = self.prepare_preview(client, config).context("Cannot prepare preview")?;
+                let preview = self
+                    .prepare_preview(client, context)
+                    .context("Cannot prepare preview")?;
                 self.stack_mut().set_preview(preview);
 
                 context.render()?;
@@ -296,7 +525,9 @@ pub(in crate::ui) trait BrowserPane<T: DirStackItem + std::fmt::Debug>: Pane {
                 self.stack_mut()
                     .current_mut()
                     .next_half_viewport(context.config.scrolloff);
-                let preview = self.prepare_preview(client, config).context("Cannot prepare preview")?;
+                let preview = self
+                    .prepare_preview(client, context)
+                    .context("Cannot prepare preview")?;
                 self.stack_mut().set_preview(preview);
 
                 context.render()?;
@@ -305,33 +536,43 @@ pub(in crate::ui) trait BrowserPane<T: DirStackItem + std::fmt::Debug>: Pane {
                 self.stack_mut()
                     .current_mut()
                     .prev_half_viewport(context.config.scrolloff);
-                let preview = self.prepare_preview(client, config).context("Cannot prepare preview")?;
+                let preview = self
+                    .prepare_preview(client, context)
+                    .context("Cannot prepare preview")?;
                 self.stack_mut().set_preview(preview);
 
                 context.render()?;
             }
             CommonAction::Bottom => {
                 self.stack_mut().current_mut().last();
-                let preview = self.prepare_preview(client, config).context("Cannot prepare preview")?;
+                let preview = self
+                    .prepare_preview(client, context)
+                    .context("Cannot prepare preview")?;
                 self.stack_mut().set_preview(preview);
 
                 context.render()?;
             }
             CommonAction::Top => {
                 self.stack_mut().current_mut().first();
-                let preview = self.prepare_preview(client, config).context("Cannot prepare preview")?;
+                let preview = self
+                    .prepare_preview(client, context)
+                    .context("Cannot prepare preview")?;
                 self.stack_mut().set_preview(preview);
 
                 context.render()?;
             }
             CommonAction::Right => {
                 self.next(client, context)?;
-                let preview = self.prepare_preview(client, config).context("Cannot prepare preview")?;
+                let preview = self
+                    .prepare_preview(client, context)
+                    .context("Cannot prepare preview")?;
                 self.stack_mut().set_preview(preview);
             }
             CommonAction::Left => {
-                self.stack_mut().pop();
-                let preview = self.prepare_preview(client, config).context("Cannot prepare preview")?;
+                self.go_back(config);
+                let preview = self
+                    .prepare_preview(client, context)
+                    .context("Cannot prepare preview")?;
                 self.stack_mut().set_preview(preview);
 
                 context.render()?;
@@ -344,14 +585,18 @@ pub(in crate::ui) trait BrowserPane<T: DirStackItem + std::fmt::Debug>: Pane {
             }
             CommonAction::NextResult => {
                 self.stack_mut().current_mut().jump_next_matching(config);
-                let preview = self.prepare_preview(client, config).context("Cannot prepare preview")?;
+                let preview = self
+                    .prepare_preview(client, context)
+                    .context("Cannot prepare preview")?;
                 self.stack_mut().set_preview(preview);
 
                 context.render()?;
             }
             CommonAction::PreviousResult => {
                 self.stack_mut().current_mut().jump_previous_matching(config);
-                let preview = self.prepare_preview(client, config).context("Cannot prepare preview")?;
+                let preview = self
+                    .prepare_preview(client, context)
+                    .context("Cannot prepare preview")?;
                 self.stack_mut().set_preview(preview);
 
                 context.render()?;
@@ -366,7 +611,9 @@ pub(in crate::ui) trait BrowserPane<T: DirStackItem + std::fmt::Debug>: Pane {
                 self.stack_mut()
                     .current_mut()
                     .next(context.config.scrolloff, context.config.wrap_navigation);
-                let preview = self.prepare_preview(client, config).context("Cannot prepare preview")?;
+                let preview = self
+                    .prepare_preview(client, context)
+                    .context("Cannot prepare preview")?;
                 self.stack_mut().set_preview(preview);
 
                 context.render()?;
@@ -381,7 +628,16 @@ pub(in crate::ui) trait BrowserPane<T: DirStackItem + std::fmt::Debug>: Pane {
             }
             CommonAction::Add => {
                 if let Some(item) = self.stack().current().selected() {
-                    self.add(item, client, context);
+                    self.add(item, client, context)?;
+                    if context.config.advance_after_add {
+                        self.stack_mut().current_mut().next(context.config.scrolloff, false);
+                        let preview = self
+                            .prepare_preview(client, context)
+                            .context("Cannot prepare preview")?;
+                        self.stack_mut().set_preview(preview);
+
+                        context.render()?;
+                    }
                 }
             }
             CommonAction::AddAll if !self.stack().current().items.is_empty() => {
@@ -410,7 +666,26 @@ pub(in crate::ui) trait BrowserPane<T: DirStackItem + std::fmt::Debug>: Pane {
                     self.rename(item, client, context);
                 }
             }
+            CommonAction::RateUp | CommonAction::RateDown => {
+                let change = if action == CommonAction::RateUp {
+                    ValueChange::Increase(1)
+                } else {
+                    ValueChange::Decrease(1)
+                };
+                if let Some(song) = self.stack().current().selected().and_then(DirStackItem::as_song) {
+                    crate::ui::rate_song(client, context, &song.file, &change)?;
+                }
+            }
             CommonAction::FocusInput => {}
+            CommonAction::Close if self.stack().current().filter().is_some() => {
+                self.stack_mut().current_mut().set_filter(None, config);
+                let preview = self
+                    .prepare_preview(client, context)
+                    .context("Cannot prepare preview")?;
+                self.stack_mut().set_preview(preview);
+
+                context.render()?;
+            }
             CommonAction::Close => {}
             CommonAction::Confirm if self.stack().current().marked().is_empty() => {
                 self.open(client, context)?;
@@ -420,8 +695,78 @@ pub(in crate::ui) trait BrowserPane<T: DirStackItem + std::fmt::Debug>: Pane {
             CommonAction::PaneUp => {}
             CommonAction::PaneRight => {}
             CommonAction::PaneLeft => {}
+            CommonAction::ToggleMatchMode => {
+                self.toggle_match_mode(client, context)?;
+            }
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{album_dirs, consistent_years, sort_by_earliest_date};
+    use crate::{config::SortOrder, ui::panes::browser::DirOrSong};
+
+    #[test]
+    fn sorts_by_earliest_date() {
+        let input = [("2001", "b"), ("1999", "c"), ("2005", "a"), ("1990", "a")];
+
+        assert_eq!(sort_by_earliest_date(input), vec!["a", "c", "b"]);
+    }
+
+    #[test]
+    fn values_without_date_come_last() {
+        let input = [("", "x"), ("2001", "b"), ("", "a"), ("", "b")];
+
+        assert_eq!(sort_by_earliest_date(input), vec!["b", "a", "x"]);
+    }
+
+    #[test]
+    fn year_of_consistent_dates() {
+        let input = [("1973-03-01", "a"), ("1973", "a"), ("1980", "b")];
+
+        let years = consistent_years(input);
+
+        assert_eq!(years.get("a"), Some(&"1973"));
+        assert_eq!(years.get("b"), Some(&"1980"));
+    }
+
+    #[test]
+    fn no_year_for_inconsistent_or_missing_dates() {
+        let input = [("1973", "a"), ("1975", "a"), ("", "b"), ("1990", "c"), ("", "c")];
+
+        let years = consistent_years(input);
+
+        assert!(years.is_empty());
+    }
+
+    #[test]
+    fn albums_of_different_album_artists_are_separate() {
+        let albums = [
+            (vec!["2001".to_owned(), "x".to_owned()], "Greatest Hits".to_owned()),
+            (vec!["1995".to_owned(), "y".to_owned()], "Greatest Hits".to_owned()),
+        ];
+
+        let result = album_dirs(&albums, SortOrder::Date);
+
+        assert_eq!(
+            result,
+            vec![
+                DirOrSong::Dir {
+                    name: "Greatest Hits".to_owned(),
+                    full_path: String::new(),
+                    year: Some("1995".to_owned()),
+                    album_artist: Some("y".to_owned()),
+                },
+                DirOrSong::Dir {
+                    name: "Greatest Hits".to_owned(),
+                    full_path: String::new(),
+                    year: Some("2001".to_owned()),
+                    album_artist: Some("x".to_owned()),
+                },
+            ]
+        );
+    }
+}
@@ -3,7 +3,7 @@ use std::collections::BTreeSet;
 use log::error;
 use ratatui::widgets::ListState;
 
-use crate::{config::Config, shared::macros::status_warn};
+use crate::{config::Config, mpd::commands::Ratings, shared::macros::status_warn};
 
 use super::{state::DirState, DirStackItem};
 
@@ -13,6 +13,8 @@ pub struct Dir<T: std::fmt::Debug + DirStackItem> {
     pub state: DirState<ListState>,
     filter: Option<String>,
     matched_item_count: usize,
+    /// Items hidden by [`Dir::reduce_to_matching`] along with their position in the full list
+    hidden_items: Vec<(usize, T)>,
 }
 
 impl<T: std::fmt::Debug + DirStackItem> Default for Dir<T> {
@@ -22,6 +24,7 @@ impl<T: std::fmt::Debug + DirStackItem> Default for Dir<T> {
             state: DirState::default(),
             filter: None,
             matched_item_count: 0,
+            hidden_items: Vec::new(),
         }
     }
 }
@@ -34,6 +37,7 @@ impl<T: std::fmt::Debug + DirStackItem> Dir<T> {
             state: DirState::default(),
             filter: None,
             matched_item_count: 0,
+            hidden_items: Vec::new(),
         };
 
         if !root.is_empty() {
@@ -50,6 +54,7 @@ impl<T: std::fmt::Debug + DirStackItem> Dir<T> {
             state,
             filter: None,
             matched_item_count: 0,
+            hidden_items: Vec::new(),
         };
     }
 
@@ -58,6 +63,7 @@ impl<T: std::fmt::Debug + DirStackItem> Dir<T> {
     }
 
     pub fn set_filter(&mut self, value: Option<String>, config: &Config) {
+        self.restore_hidden(config.scrolloff);
         self.matched_item_count = if let Some(ref filter) = value {
             self.items.iter().filter(|item| item.matches(config, filter)).count()
         } else {
@@ -66,6 +72,80 @@ impl<T: std::fmt::Debug + DirStackItem> Dir<T> {
         self.filter = value;
     }
 
+    pub fn is_reduced(&self) -> bool {
+        !self.hidden_items.is_empty()
+    }
+
+    /// Hides all items not matching the current filter until [`Dir::restore_hidden`] is called or the filter changes
+    pub fn reduce_to_matching(&mut self, config: &Config) {
+        self.restore_hidden(config.scrolloff);
+        let Some(filter) = self.filter.as_ref() else {
+            status_warn!("No filter set");
+            return;
+        };
+
+        let selected = self.state.get_selected();
+        let mut new_selected = None;
+        let mut kept = Vec::new();
+        for (idx, item) in std::mem::take(&mut self.items).into_iter().enumerate() {
+            if item.matches(config, filter) {
+                if new_selected.is_none() && selected.is_some_and(|sel| sel <= idx) {
+                    new_selected = Some(kept.len());
+                }
+                kept.push(item);
+            } else {
+                self.hidden_items.push((idx, item));
+            }
+        }
+
+        self.items = kept;
+        self.state.unmark_all();
+        self.state.set_content_len(Some(self.items.len()));
+        if self.items.is_empty() {
+            self.state.select(None, config.scrolloff);
+        } else {
+            self.state
+                .select(Some(new_selected.unwrap_or(self.items.len() - 1)), config.scrolloff);
+        }
+    }
+
+    /// Puts the items hidden by [`Dir::reduce_to_matching`] back to their original positions
+    pub fn restore_hidden(&mut self, scrolloff: usize) {
+        if self.hidden_items.is_empty() {
+            return;
+        }
+
+        let selected = self.state.get_selected();
+        let mut new_selected = None;
+        let mut items = Vec::with_capacity(self.items.len() + self.hidden_items.len());
+        let mut hidden = std::mem::take(&mut self.hidden_items).into_iter().peekable();
+        let mut kept = std::mem::take(&mut self.items).into_iter().enumerate().peekable();
+        loop {
+            let take_hidden = match (hidden.peek(), kept.peek()) {
+                (Some((original_idx, _)), Some(_)) => *original_idx <= items.len(),
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => break,
+            };
+
+            if take_hidden {
+                if let Some((_, item)) = hidden.next() {
+                    items.push(item);
+                }
+            } else if let Some((idx, item)) = kept.next() {
+                if selected == Some(idx) {
+                    new_selected = Some(items.len());
+                }
+                items.push(item);
+            }
+        }
+
+        self.items = items;
+        self.state.unmark_all();
+        self.state.set_content_len(Some(self.items.len()));
+        self.state.select(new_selected.or(Some(0)), scrolloff);
+    }
+
     pub fn push_filter(&mut self, char: char, config: &Config) {
         if let Some(ref mut filter) = self.filter {
             filter.push(char);
@@ -80,7 +160,7 @@ impl<T: std::fmt::Debug + DirStackItem> Dir<T> {
         }
     }
 
-    pub fn to_list_items(&self, config: &crate::config::Config) -> Vec<T::Item> {
+    pub fn to_list_items(&self, config: &crate::config::Config, ratings: &Ratings) -> Vec<T::Item> {
         let mut already_matched: u32 = 0;
         let current_item_idx = self.selected_with_idx().map(|(idx, _)| idx);
         self.items
@@ -97,7 +177,7 @@ impl<T: std::fmt::Debug + DirStackItem> Dir<T> {
                 } else {
                     None
                 };
-                item.to_list_item(config, self.marked().contains(&i), matches, content)
+                item.to_list_item(config, ratings, self.marked().contains(&i), matches, content)
             })
             .collect()
     }
@@ -170,13 +250,50 @@ impl<T: std::fmt::Debug + DirStackItem> Dir<T> {
         }
     }
 
+    /// Position of the displayed item at `idx` in the full list, which differs while the list is reduced
+    pub fn original_idx(&self, idx: usize) -> usize {
+        let mut original = idx;
+        for (hidden_idx, _) in &self.hidden_items {
+            if *hidden_idx > original {
+                break;
+            }
+            original += 1;
+        }
+        original
+    }
+
     pub fn remove(&mut self, idx: usize) {
         if idx < self.items.len() {
+            let original = self.original_idx(idx);
+            for (hidden_idx, _) in &mut self.hidden_items {
+                if *hidden_idx > original {
+                    *hidden_idx -= 1;
+                }
+            }
             self.items.remove(idx);
         }
         self.state.remove(idx);
     }
 
+    /// Swaps the displayed item at `idx` with the one at `new_idx`. Hidden items between them are shifted the same
+    /// way as in the full list where the item moves to the position of the other one.
+    pub fn move_item(&mut self, idx: usize, new_idx: usize) {
+        if idx >= self.items.len() || new_idx >= self.items.len() {
+            return;
+        }
+
+        let from = self.original_idx(idx);
+        let to = self.original_idx(new_idx);
+        for (hidden_idx, _) in &mut self.hidden_items {
+            if to < from && (to..from).contains(hidden_idx) {
+                *hidden_idx += 1;
+            } else if from < to && (from + 1..=to).contains(hidden_idx) {
+                *hidden_idx -= 1;
+            }
+        }
+        self.items.swap(idx, new_idx);
+    }
+
     pub fn remove_all_marked(&mut self) {
         for i in 0..self.items.len() {
             if self.state.marked.contains(&i) {
@@ -282,6 +399,7 @@ mod tests {
             state: DirState::default(),
             filter: None,
             matched_item_count: 0,
+            hidden_items: Vec::new(),
         };
         res.state.set_content_len(Some(res.items.len()));
         res.state.set_viewport_len(Some(res.items.len()));
@@ -488,6 +606,76 @@ mod tests {
         }
     }
 
+    mod reduce_to_matching {
+        use crate::{config::Config, ui::dirstack::Dir};
+
+        fn create_subject() -> Dir<String> {
+            let mut val: Dir<String> = Dir {
+                items: vec!["aa", "b", "ab", "c", "ad"]
+                    .into_iter()
+                    .map(ToOwned::to_owned)
+                    .collect(),
+                ..Default::default()
+            };
+            val.state.set_content_len(Some(val.items.len()));
+            val.state.set_viewport_len(Some(val.items.len()));
+            val
+        }
+
+        #[test]
+        fn hides_non_matching_items() {
+            let mut val = create_subject();
+            val.state.select(Some(1), 0);
+            val.set_filter(Some("a".to_string()), &Config::default());
+
+            val.reduce_to_matching(&Config::default());
+
+            assert_eq!(val.items, vec!["aa", "ab", "ad"]);
+            assert_eq!(val.selected().map(String::as_str), Some("ab"));
+            assert!(val.is_reduced());
+        }
+
+        #[test]
+        fn clearing_filter_restores_all_items() {
+            let mut val = create_subject();
+            val.state.select(Some(0), 0);
+            val.set_filter(Some("a".to_string()), &Config::default());
+            val.reduce_to_matching(&Config::default());
+            val.state.select(Some(2), 0);
+
+            val.set_filter(None, &Config::default());
+
+            assert_eq!(val.items, vec!["aa", "b", "ab", "c", "ad"]);
+            assert_eq!(val.selected().map(String::as_str), Some("ad"));
+            assert!(!val.is_reduced());
+        }
+
+        #[test]
+        fn maps_displayed_items_to_their_original_position() {
+            let mut val = create_subject();
+            val.set_filter(Some("a".to_string()), &Config::default());
+            val.reduce_to_matching(&Config::default());
+
+            assert_eq!(
+                (0..3).map(|idx| val.original_idx(idx)).collect::<Vec<_>>(),
+                vec![0, 2, 4]
+            );
+        }
+
+        #[test]
+        fn removing_and_moving_reduced_items_keeps_hidden_positions() {
+            let mut val = create_subject();
+            val.set_filter(Some("a".to_string()), &Config::default());
+            val.reduce_to_matching(&Config::default());
+
+            val.move_item(2, 1);
+            val.remove(0);
+            val.set_filter(None, &Config::default());
+
+            assert_eq!(val.items, vec!["b", "ad", "ab", "c"]);
+        }
+    }
+
     mod matched_item_count {
         use crate::{config::Config, ui::dirstack::Dir};
 
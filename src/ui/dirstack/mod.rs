@@ -11,7 +11,14 @@ pub use dir::Dir;
 pub use stack::DirStack;
 pub use state::DirState;
 
-use crate::{config::Config, mpd::commands::Song, ui::panes::browser::DirOrSong};
+use crate::{
+    config::{
+        theme::properties::{PropertyKindOrText, SongProperty},
+        Config,
+    },
+    mpd::commands::{Ratings, Song},
+    ui::panes::browser::DirOrSong,
+};
 
 pub trait DirStackItem {
     type Item;
@@ -20,12 +27,17 @@ pub trait DirStackItem {
     fn to_list_item(
         &self,
         config: &Config,
+        ratings: &Ratings,
         is_marked: bool,
         matches_filter: bool,
         additional_content: Option<String>,
     ) -> Self::Item;
-    fn to_list_item_simple(&self, config: &Config) -> Self::Item {
-        self.to_list_item(config, false, false, None)
+    fn to_list_item_simple(&self, config: &Config, ratings: &Ratings) -> Self::Item {
+        self.to_list_item(config, ratings, false, false, None)
+    }
+    /// The song this item stands for, `None` for directories and other items which are not a single song
+    fn as_song(&self) -> Option<&Song> {
+        None
     }
 }
 
@@ -39,18 +51,36 @@ impl DirStackItem for DirOrSong {
         }
     }
 
+    fn as_song(&self) -> Option<&Song> {
+        match self {
+            DirOrSong::Dir { .. } => None,
+            DirOrSong::Song(s) => Some(s),
+        }
+    }
+
     fn matches(&self, config: &Config, filter: &str) -> bool {
         match self {
-            DirOrSong::Dir { name, .. } => if name.is_empty() { "Untitled" } else { name.as_str() }
-                .to_lowercase()
-                .contains(&filter.to_lowercase()),
-            DirOrSong::Song(s) => s.matches(config.theme.browser_song_format.0, filter),
+            DirOrSong::Dir { name, album_artist, .. } => {
+                let filter = filter.to_lowercase();
+                if name.is_empty() { "Untitled" } else { name.as_str() }
+                    .to_lowercase()
+                    .contains(&filter)
+                    || album_artist
+                        .as_ref()
+                        .is_some_and(|album_artist| album_artist.to_lowercase().contains(&filter))
+            }
+            DirOrSong::Song(s) => s.matches(
+                config.theme.browser_song_format.0,
+                filter,
+                config.theme.format_tag_separator,
+            ),
         }
     }
 
     fn to_list_item(
         &self,
         config: &Config,
+        ratings: &Ratings,
         is_marked: bool,
         matches_filter: bool,
         additional_content: Option<String>,
@@ -63,25 +93,38 @@ impl DirStackItem for DirOrSong {
         };
 
         let mut value = match self {
-            DirOrSong::Dir { name, .. } => Line::from(vec![
+            DirOrSong::Dir {
+                name,
+                year,
+                album_artist,
+                ..
+            } => Line::from(vec![
                 marker_span,
                 Span::from(format!(
-                    "{} {}",
+                    "{} {}{}{}",
                     symbols.dir,
+                    year.as_ref().map(|year| format!("({year}) ")).unwrap_or_default(),
+                    album_artist
+                        .as_ref()
+                        .filter(|album_artist| !album_artist.is_empty())
+                        .map(|album_artist| format!("{album_artist} — "))
+                        .unwrap_or_default(),
                     if name.is_empty() { "Untitled" } else { name.as_str() }
                 )),
             ]),
             DirOrSong::Song(s) => {
                 let spans = [marker_span, Span::from(symbols.song), Span::from(" ")]
                     .into_iter()
-                    .chain(
-                        config
-                            .theme
-                            .browser_song_format
-                            .0
-                            .iter()
-                            .map(|prop| Span::from(prop.as_string(Some(s)).unwrap_or_default())),
-                    );
+                    .chain(config.theme.browser_song_format.0.iter().map(|prop| {
+                        // Ratings are not part of the song so they are looked up here instead of being formatted
+                        if matches!(prop.kind, PropertyKindOrText::Property(SongProperty::Rating)) {
+                            return Span::from(Ratings::stars(ratings.get(&s.file)));
+                        }
+                        Span::from(
+                            prop.as_string(Some(s), config.theme.format_tag_separator)
+                                .unwrap_or_default(),
+                        )
+                    }));
                 Line::from(spans.collect_vec())
             }
         };
@@ -103,13 +146,22 @@ impl DirStackItem for Song {
         &self.file
     }
 
+    fn as_song(&self) -> Option<&Song> {
+        Some(self)
+    }
+
     fn matches(&self, config: &Config, filter: &str) -> bool {
-        self.matches(config.theme.browser_song_format.0, filter)
+        self.matches(
+            config.theme.browser_song_format.0,
+            filter,
+            config.theme.format_tag_separator,
+        )
     }
 
     fn to_list_item(
         &self,
         config: &Config,
+        _ratings: &Ratings,
         is_marked: bool,
         matches_filter: bool,
         additional_content: Option<String>,
@@ -121,8 +173,8 @@ impl DirStackItem for Song {
             Span::from(" ".repeat(symbols.marker.chars().count()))
         };
 
-        let title = self.title_str().to_owned();
-        let artist = self.artist_str().to_owned();
+        let title = self.title_str(config.theme.format_tag_separator).into_owned();
+        let artist = self.artist_str(config.theme.format_tag_separator).into_owned();
         let separator_span = Span::from(" - ");
         let icon_span = Span::from(format!("{} ", symbols.song));
         let mut result = vec![
@@ -201,6 +253,7 @@ impl DirStackItem for String {
     fn to_list_item(
         &self,
         config: &Config,
+        _ratings: &Ratings,
         is_marked: bool,
         matches_filter: bool,
         _additional_content: Option<String>,
@@ -3,6 +3,7 @@ use std::{collections::BTreeSet, ops::SubAssign};
 use ratatui::widgets::ScrollbarState;
 
 use super::ScrollingState;
+use crate::config::DeleteSelectionBehavior;
 
 #[derive(Debug, Default)]
 pub struct DirState<T: ScrollingState> {
@@ -178,6 +179,11 @@ impl<T: ScrollingState> DirState<T> {
         self.scrollbar_state = self.scrollbar_state.position(idx.unwrap_or(0));
     }
 
+    /// Selects the item and scrolls so that it ends up in the middle of the viewport
+    pub fn select_centered(&mut self, idx: Option<usize>) {
+        self.select(idx, self.viewport_len.unwrap_or_default());
+    }
+
     fn apply_scrolloff(&mut self, scrolloff: usize) {
         if scrolloff == 0 {
             return;
@@ -237,6 +243,21 @@ impl<T: ScrollingState> DirState<T> {
         }
     }
 
+    /// Selects an item according to `behavior` after the item at `removed_idx` was removed.
+    /// Content length has to already reflect the removal.
+    pub fn select_after_removal(&mut self, removed_idx: usize, behavior: DeleteSelectionBehavior, scrolloff: usize) {
+        if self.content_len.is_none_or(|len| len == 0) {
+            self.select(None, scrolloff);
+            return;
+        }
+
+        let idx = match behavior {
+            DeleteSelectionBehavior::KeepIndex => removed_idx,
+            DeleteSelectionBehavior::MoveUp => removed_idx.saturating_sub(1),
+        };
+        self.select(Some(idx), scrolloff);
+    }
+
     pub fn unmark_all(&mut self) {
         self.marked.clear();
     }
@@ -708,6 +729,45 @@ mod tests {
         }
     }
 
+    mod select_after_removal {
+        use ratatui::widgets::ListState;
+        use rstest::rstest;
+
+        use crate::{config::DeleteSelectionBehavior, ui::dirstack::DirState};
+
+        #[rstest]
+        #[case(DeleteSelectionBehavior::KeepIndex, 5, Some(5))]
+        #[case(DeleteSelectionBehavior::MoveUp, 5, Some(4))]
+        #[case(DeleteSelectionBehavior::MoveUp, 0, Some(0))]
+        #[case(DeleteSelectionBehavior::KeepIndex, 9, Some(8))]
+        fn selects_according_to_behavior(
+            #[case] behavior: DeleteSelectionBehavior,
+            #[case] removed_idx: usize,
+            #[case] expected: Option<usize>,
+        ) {
+            let mut subject: DirState<ListState> = DirState::default();
+            subject.set_content_len(Some(10));
+            subject.select(Some(removed_idx), 0);
+
+            subject.remove(removed_idx);
+            subject.select_after_removal(removed_idx, behavior, 0);
+
+            assert_eq!(subject.get_selected(), expected);
+        }
+
+        #[test]
+        fn selects_nothing_when_everything_was_removed() {
+            let mut subject: DirState<ListState> = DirState::default();
+            subject.set_content_len(Some(1));
+            subject.select(Some(0), 0);
+
+            subject.remove(0);
+            subject.select_after_removal(0, DeleteSelectionBehavior::KeepIndex, 0);
+
+            assert_eq!(subject.get_selected(), None);
+        }
+    }
+
     mod marks {
         use std::collections::BTreeSet;
 
@@ -10,7 +10,10 @@ use crossterm::{
 };
 use enum_map::{enum_map, Enum, EnumMap};
 use itertools::Itertools;
-use modals::{decoders::DecodersModal, keybinds::KeybindsModal, outputs::OutputsModal, song_info::SongInfoModal};
+use modals::{
+    decoders::DecodersModal, keybinds::KeybindsModal, outputs::OutputsModal,
+    playlist_membership::PlaylistMembershipModal, song_info::SongInfoModal,
+};
 use panes::{PaneContainer, Panes};
 #[cfg(debug_assertions)]
 use ratatui::style::Stylize;
@@ -20,15 +23,15 @@ use ratatui::{
     prelude::{Backend, Constraint, CrosstermBackend, Layout},
     style::{Color, Style},
     symbols::border,
-    text::Text,
-    widgets::{Block, Borders, Paragraph},
+    text::{Line, Text},
+    widgets::{Block, Borders, Paragraph, Wrap},
     Frame, Terminal,
 };
 use tab_screen::TabScreen;
-use widgets::app_tabs::AppTabs;
+use widgets::{app_tabs::AppTabs, progress_bar::ProgressBar};
 
 use crate::{
-    cli::{create_env, run_external},
+    cli::{create_env, open_song_location, run_external},
     config::{
         cli::Args,
         keys::{CommonAction, GlobalAction},
@@ -37,18 +40,29 @@ use crate::{
     },
     mpd::{
         client::Client,
-        commands::{idle::IdleEvent, volume::Bound, State},
-        mpd_client::{FilterKind, MpdClient, ValueChange},
+        commands::{
+            idle::IdleEvent,
+            sticker::{MAX_RATING, RATING_STICKER},
+            volume::Bound,
+            Ratings, State,
+        },
+        mpd_client::{FilterKind, MpdClient, QueueMoveTarget, ValueChange},
     },
     shared::{
+        ext::mpd_client::{MpdClientExt, SongAdd},
         key_event::KeyEvent,
+        logging::LogEntry,
         macros::{modal, status_error, status_info, status_warn},
         mouse_event::{MouseEvent, MouseEventKind},
     },
 };
-use crate::{context::AppContext, mpd::version::Version};
+use crate::{context::AppContext, mpd::version::Version, AppEvent};
 
-use self::{modals::Modal, panes::Pane, widgets::header::Header};
+use self::{
+    modals::{confirm_modal::ConfirmModal, Modal},
+    panes::Pane,
+    widgets::{compact_bar::CompactBar, header::Header, keybind_hints::KeybindHints},
+};
 
 pub mod browser;
 pub mod dirstack;
@@ -97,12 +111,16 @@ macro_rules! screen_call {
     }
 }
 
+/// Below this width only a message is shown instead of the UI
+const MIN_TERMINAL_WIDTH: u16 = 10;
+
 #[derive(Debug, Enum)]
 enum Areas {
     Header,
     Tabs,
     Content,
     Bar,
+    Hints,
 }
 
 impl<'ui> Ui<'ui> {
@@ -130,17 +148,29 @@ impl<'ui> Ui<'ui> {
     }
 
     fn calc_areas(&mut self, area: Rect, context: &AppContext) -> Result<()> {
+        if context.config.compact {
+            // Only the header area is used so that clicking and scrolling on the line controls playback
+            self.areas = enum_map! {
+                Areas::Header => Rect { height: area.height.min(1), ..area },
+                _ => Rect::default()
+            };
+            return Ok(());
+        }
+
         let tab_area_height = match (context.config.theme.tab_bar.enabled, context.config.theme.draw_borders) {
             (true, true) => 3,
             (true, false) => 1,
             (false, _) => 0,
         };
 
-        let [header_area, tabs_area, content_area, bar_area] = *Layout::vertical([
+        let hints_area_height = u16::from(context.config.keybind_hints);
+
+        let [header_area, tabs_area, content_area, bar_area, hints_area] = *Layout::vertical([
             Constraint::Length(u16::try_from(context.config.theme.header.rows.len())?),
             Constraint::Length(tab_area_height), // Tab bar
             Constraint::Percentage(100),
             Constraint::Min(1),
+            Constraint::Length(hints_area_height),
         ])
         .split(area) else {
             return Ok(());
@@ -149,17 +179,28 @@ impl<'ui> Ui<'ui> {
         self.areas[Areas::Tabs] = tabs_area;
         self.areas[Areas::Content] = content_area;
         self.areas[Areas::Bar] = bar_area;
+        self.areas[Areas::Hints] = hints_area;
 
         Ok(())
     }
+    fn is_too_small(&self, area: Rect) -> bool {
+        self.areas[Areas::Content].height == 0 || area.width < MIN_TERMINAL_WIDTH
+    }
+
     pub fn post_render(&mut self, frame: &mut Frame, context: &mut AppContext) -> Result<()> {
+        if context.config.compact || self.is_too_small(frame.area()) {
+            return Ok(());
+        }
         screen_call!(self, post_render(frame, context))
     }
 
     fn change_tab(&mut self, new_tab: TabName, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
+        if context.config.compact {
+            return Ok(());
+        }
         screen_call!(self, on_hide(client, &context))?;
         self.active_tab = new_tab;
-        screen_call!(self, before_show(client, &context))?;
+        screen_call!(self, before_show(client, &context));
         Ok(())
     }
 
@@ -178,11 +219,43 @@ impl<'ui> Ui<'ui> {
             self.status_message = None;
         }
 
+        if context.config.compact {
+            if let Some(command) = &self.command {
+                let command_line = Paragraph::new(format!(":{command}")).style(context.config.as_text_style());
+                frame.render_widget(command_line, self.areas[Areas::Header]);
+            } else if let Some(StatusMessage { message, level, .. }) = &self.status_message {
+                let status_bar = Paragraph::new(message.to_owned()).style(Style::default().fg(level.into()));
+                frame.render_widget(status_bar, self.areas[Areas::Header]);
+            } else {
+                frame.render_widget(CompactBar::new(context), self.areas[Areas::Header]);
+            }
+
+            for modal in &mut self.modals {
+                modal.render(frame, context)?;
+            }
+
+            return Ok(());
+        }
+
+        if self.is_too_small(frame.area()) {
+            let message = Paragraph::new("Terminal too small")
+                .centered()
+                .wrap(Wrap { trim: true })
+                .style(context.config.as_text_style());
+            // Panes are still rendered into an empty area so that they can hide anything they draw outside of the
+            // buffer, like album art
+            screen_call!(self, render(frame, Rect::default(), context))?;
+            frame.render_widget(message, frame.area());
+            return Ok(());
+        }
+
         let header = Header::new(context);
         frame.render_widget(header, self.areas[Areas::Header]);
 
         if self.areas[Areas::Tabs].height > 0 {
-            self.tab_bar.set_selected(self.active_tab);
+            self.tab_bar
+                .set_selected(self.active_tab)
+                .set_active_style(context.active_tab_style());
             self.tab_bar.render(self.areas[Areas::Tabs], frame.buffer_mut());
         }
 
@@ -214,6 +287,13 @@ impl<'ui> Ui<'ui> {
             frame.render_widget(elapsed_bar, self.areas[Areas::Bar]);
         }
 
+        if context.stop_after_current.is_some() && self.command.is_none() && self.status_message.is_none() {
+            frame.render_widget(
+                Line::styled(" Stop after current ", context.config.theme.highlighted_item_style).right_aligned(),
+                self.areas[Areas::Bar],
+            );
+        }
+
         #[cfg(debug_assertions)]
         #[allow(clippy::cast_possible_truncation)]
         {
@@ -228,6 +308,11 @@ impl<'ui> Ui<'ui> {
             );
         }
 
+        if self.areas[Areas::Hints].height > 0 {
+            let pane = self.tabs.get(&self.active_tab).and_then(TabScreen::focused_pane_type);
+            frame.render_widget(KeybindHints::new(context, pane), self.areas[Areas::Hints]);
+        }
+
         screen_call!(self, render(frame, self.areas[Areas::Content], context))?;
 
         for modal in &mut self.modals {
@@ -254,11 +339,19 @@ impl<'ui> Ui<'ui> {
                 context.render()?;
             }
             MouseEventKind::ScrollUp if self.areas[Areas::Header].contains(event.into()) => {
-                client.set_volume(*context.status.volume.inc_by(context.config.volume_step))?;
+                adjust_volume(
+                    client,
+                    context,
+                    &ValueChange::Increase(context.config.volume_step.into()),
+                )?;
                 context.render()?;
             }
             MouseEventKind::ScrollDown if self.areas[Areas::Header].contains(event.into()) => {
-                client.set_volume(*context.status.volume.dec_by(context.config.volume_step))?;
+                adjust_volume(
+                    client,
+                    context,
+                    &ValueChange::Decrease(context.config.volume_step.into()),
+                )?;
                 context.render()?;
             }
             MouseEventKind::LeftClick if self.areas[Areas::Bar].contains(event.into()) => {
@@ -266,14 +359,17 @@ impl<'ui> Ui<'ui> {
                     return Ok(());
                 }
 
+                // Streams and other sources without a known duration cannot be seeked
+                if context.status.duration == Duration::ZERO {
+                    return Ok(());
+                }
+
                 let second_to_seek_to = context
                     .status
                     .duration
-                    .mul_f32(f32::from(event.x) / f32::from(self.areas[Areas::Bar].width))
+                    .mul_f32(ProgressBar::value_at(self.areas[Areas::Bar], event.x))
                     .as_secs();
-                client.seek_current(ValueChange::Set(u32::try_from(second_to_seek_to)?))?;
-
-                context.render()?;
+                seek(client, context, ValueChange::Set(u32::try_from(second_to_seek_to)?))?;
             }
             MouseEventKind::ScrollDown if self.areas[Areas::Tabs].contains(event.into()) => {
                 self.change_tab(context.config.next_screen(self.active_tab), client, context)?;
@@ -304,6 +400,17 @@ impl<'ui> Ui<'ui> {
         Ok(())
     }
 
+    /// Whether keys can start a multi-key keybind, which is not the case while typing into the command line, a
+    /// modal or the input of the focused pane
+    pub fn accepts_key_sequences(&mut self) -> bool {
+        self.command.is_none()
+            && self.modals.is_empty()
+            && !self
+                .tabs
+                .get(&self.active_tab)
+                .is_some_and(|tab| tab.is_input_active(&mut self.panes))
+    }
+
     pub fn handle_key(
         &mut self,
         key: &mut KeyEvent,
@@ -359,7 +466,23 @@ impl<'ui> Ui<'ui> {
             return Ok(KeyHandleResult::None);
         }
 
-        screen_call!(self, handle_action(key, client, context))?;
+        // Global keybinds take precedence over the pane ones unless the pane takes text input. External
+        // commands, AddFirst, Random and OpenSongLocation are still left to the pane because they act on its items.
+        let global_first = key.peek_global_action(context).is_some_and(|action| {
+            !matches!(
+                action,
+                GlobalAction::ExternalCommand { .. }
+                    | GlobalAction::AddFirst { .. }
+                    | GlobalAction::Random
+                    | GlobalAction::OpenSongLocation
+            )
+        }) && !self
+            .tabs
+            .get(&self.active_tab)
+            .is_some_and(|tab| tab.is_input_active(&mut self.panes));
+        if !context.config.compact && !global_first {
+            screen_call!(self, handle_action(key, client, context))?;
+        }
 
         if let Some(action) = key.as_global_action(context) {
             match action {
@@ -380,36 +503,117 @@ impl<'ui> Ui<'ui> {
                     self.command = Some(String::new());
                     context.render()?;
                 }
-                GlobalAction::NextTrack if context.status.state == State::Play => client.next()?,
-                GlobalAction::PreviousTrack if context.status.state == State::Play => client.prev()?,
+                GlobalAction::NextTrack if context.status.state == State::Play => {
+                    context.stop_after_current = None;
+                    client.next()?;
+                }
+                GlobalAction::PreviousTrack if context.status.state == State::Play => {
+                    context.stop_after_current = None;
+                    client.prev()?;
+                }
                 GlobalAction::Stop if matches!(context.status.state, State::Play | State::Pause) => client.stop()?,
-                GlobalAction::ToggleRepeat => client.repeat(!context.status.repeat)?,
-                GlobalAction::ToggleRandom => client.random(!context.status.random)?,
-                GlobalAction::ToggleSingle if client.version() < Version::new(0, 21, 0) => {
-                    client.single(context.status.single.cycle_pre_mpd_24())?;
+                // The status is updated right away so the header does not wait for the options idle event
+                GlobalAction::ToggleRepeat => {
+                    let repeat = !context.status.repeat;
+                    client.repeat(repeat)?;
+                    context.status.repeat = repeat;
+                    status_info!("Repeat {}", if repeat { "on" } else { "off" });
+                    context.render()?;
                 }
-                GlobalAction::ToggleSingle => client.single(context.status.single.cycle())?,
-                GlobalAction::ToggleConsume if client.version() < Version::new(0, 24, 0) => {
-                    client.consume(context.status.consume.cycle_pre_mpd_24())?;
+                GlobalAction::ToggleRandom => {
+                    let random = !context.status.random;
+                    client.random(random)?;
+                    context.status.random = random;
+                    status_info!("Random {}", if random { "on" } else { "off" });
+                    context.render()?;
+                }
+                GlobalAction::ToggleSingle => {
+                    let single = if client.version() < Version::new(0, 21, 0) {
+                        context.status.single.cycle_pre_mpd_24()
+                    } else {
+                        context.status.single.cycle()
+                    };
+                    client.single(single)?;
+                    context.status.single = single;
+                    status_info!("Single {}", single.label());
+                    context.render()?;
                 }
                 GlobalAction::ToggleConsume => {
-                    client.consume(context.status.consume.cycle())?;
+                    let consume = if client.version() < Version::new(0, 24, 0) {
+                        context.status.consume.cycle_pre_mpd_24()
+                    } else {
+                        context.status.consume.cycle()
+                    };
+                    client.consume(consume)?;
+                    context.status.consume = consume;
+                    status_info!("Consume {}", consume.label());
+                    context.render()?;
+                }
+                GlobalAction::CycleReplayGain => {
+                    let mode = context.status.replay_gain_mode.unwrap_or_default().cycle();
+                    client.set_replay_gain_mode(mode)?;
+                    context.status.replay_gain_mode = Some(mode);
+                    status_info!("ReplayGain {}", mode.to_mpd_value());
+                    context.render()?;
+                }
+                GlobalAction::CrossfadeUp => {
+                    adjust_crossfade(client, context, &ValueChange::Increase(1))?;
+                }
+                GlobalAction::CrossfadeDown => {
+                    adjust_crossfade(client, context, &ValueChange::Decrease(1))?;
                 }
                 GlobalAction::TogglePause if matches!(context.status.state, State::Play | State::Pause) => {
                     client.pause_toggle()?;
                 }
                 GlobalAction::TogglePause => {}
+                GlobalAction::TogglePlayback => match context.status.state {
+                    State::Play => client.pause()?,
+                    State::Pause => client.unpause()?,
+                    State::Stop => client.play()?,
+                },
                 GlobalAction::VolumeUp => {
-                    client.set_volume(*context.status.volume.inc_by(context.config.volume_step))?;
+                    adjust_volume(
+                        client,
+                        context,
+                        &ValueChange::Increase(context.config.volume_step.into()),
+                    )?;
                 }
                 GlobalAction::VolumeDown => {
-                    client.set_volume(*context.status.volume.dec_by(context.config.volume_step))?;
+                    adjust_volume(
+                        client,
+                        context,
+                        &ValueChange::Decrease(context.config.volume_step.into()),
+                    )?;
+                }
+                GlobalAction::VolumeUpFine => {
+                    adjust_volume(
+                        client,
+                        context,
+                        &ValueChange::Increase(context.config.volume_step_fine.into()),
+                    )?;
+                }
+                GlobalAction::VolumeDownFine => {
+                    adjust_volume(
+                        client,
+                        context,
+                        &ValueChange::Decrease(context.config.volume_step_fine.into()),
+                    )?;
+                }
+                GlobalAction::ToggleMute if context.status.volume.is_none() => {
+                    status_warn!("No mixer available");
+                }
+                GlobalAction::ToggleMute => {
+                    if let Some(volume) = context.status.volume.and_then(|volume| context.mute.toggle(volume)) {
+                        client.set_volume(volume)?;
+                    } else {
+                        status_warn!("No volume to restore");
+                    }
                 }
                 GlobalAction::SeekForward if matches!(context.status.state, State::Play | State::Pause) => {
-                    client.seek_current(ValueChange::Increase(5))?;
+                    seek(client, context, ValueChange::Increase(5))?;
                 }
                 GlobalAction::SeekBack if matches!(context.status.state, State::Play | State::Pause) => {
-                    client.seek_current(ValueChange::Decrease(5))?;
+                    seek(client, context, ValueChange::Decrease(5))?;
                 }
                 GlobalAction::NextTab => {
                     self.change_tab(context.config.next_screen(self.active_tab), client, context)?;
@@ -435,6 +639,17 @@ impl<'ui> Ui<'ui> {
                 GlobalAction::ExternalCommand { command, .. } => {
                     run_external(command, create_env(context, std::iter::empty::<&str>(), client)?);
                 }
+                GlobalAction::Quit if context.config.confirm_quit => {
+                    let sender = context.app_event_sender.clone();
+                    modal!(
+                        context,
+                        ConfirmModal::new(context)
+                            .message("Are you sure you want to quit rmpc?")
+                            .on_confirm(move |_| Ok(sender.send(AppEvent::UiAppEvent(UiAppEvent::Quit))?))
+                            .confirm_label("Quit")
+                            .size(45, 6)
+                    );
+                }
                 GlobalAction::Quit => return Ok(KeyHandleResult::Quit),
                 GlobalAction::ShowHelp => {
                     let modal = KeybindsModal::new(context);
@@ -446,6 +661,90 @@ impl<'ui> Ui<'ui> {
                 GlobalAction::ShowDecoders => {
                     modal!(context, DecodersModal::new(client.decoders()?.0));
                 }
+                GlobalAction::ShowCurrentSongPlaylists => {
+                    if let Some((_, song)) = context.find_current_song_in_queue() {
+                        let uri = song.file.clone();
+                        let playlists = client.playlists_containing(&uri, context)?;
+                        modal!(context, PlaylistMembershipModal::new(uri, playlists));
+                    } else {
+                        status_info!("No song is currently playing");
+                    }
+                }
+                GlobalAction::AddCurrentAlbum { skip_queued } => {
+                    if let Some((_, song)) = context.find_current_song_in_queue() {
+                        if let Some(album) = song.album().map(|album| album.first().to_owned()) {
+                            let added = client.add_album_of(song, skip_queued, context)?;
+                            status_info!("{added} songs from album '{album}' added to queue");
+                        } else {
+                            status_info!("Currently playing song has no album");
+                        }
+                    } else {
+                        status_info!("No song is currently playing");
+                    }
+                }
+                GlobalAction::AddSimilar { count } => {
+                    if let Some((_, song)) = context.find_current_song_in_queue() {
+                        let added = client.add_similar(song, count, context)?;
+                        status_info!("{added} similar songs added to queue");
+                    } else {
+                        status_info!("No song is currently playing");
+                    }
+                }
+                GlobalAction::RequeueCurrent => {
+                    if let Some((_, song)) = context.find_current_song_in_queue() {
+                        client.add(&song.file, Some(QueueMoveTarget::RelativeAdd(0)))?;
+                        let name = song.title().map_or(song.file.as_str(), |title| title.first());
+                        status_info!("'{name}' added to queue again");
+                    } else {
+                        status_info!("No song is currently playing");
+                    }
+                }
+                GlobalAction::AddFirst { .. } => {
+                    status_warn!("Adding only the first songs is supported in the Artists and Albums panes");
+                }
+                GlobalAction::Random => match client.add_random_song(context)? {
+                    Some((file, SongAdd::Added)) => status_info!("Random song '{file}' added to queue"),
+                    Some((_, SongAdd::AddedDuplicate | SongAdd::Skipped)) => {}
+                    None => status_info!("There are no songs in the library"),
+                },
+                GlobalAction::StopAfterCurrent => {
+                    if context.stop_after_current.take().is_some() {
+                        status_info!("Playback will continue after the current song");
+                    } else if let Some(id) = context.status.songid.filter(|_| context.status.state == State::Play) {
+                        context.stop_after_current = Some(id);
+                        status_info!("Playback will stop after the current song");
+                    } else {
+                        status_info!("No song is currently playing");
+                    }
+                    context.render()?;
+                }
+                GlobalAction::OpenSongLocation => {
+                    if let Some(song) = context.get_current_song(client)? {
+                        open_song_location(context, &song.file);
+                    } else {
+                        status_info!("No song is currently playing");
+                    }
+                }
+                GlobalAction::Reconnect => {
+                    status_info!("Reconnecting to MPD...");
+                    context.app_event_sender.send(AppEvent::Reconnect)?;
+                }
+                GlobalAction::SetStatusUpdateInterval { interval_ms } => {
+                    let interval = interval_ms
+                        .filter(|ms| *ms > 0)
+                        .map(|ms| Duration::from_millis(ms.max(100)));
+                    context
+                        .app_event_sender
+                        .send(AppEvent::SetStatusUpdateInterval(interval))?;
+                    if let Some(interval) = interval {
+                        status_info!("Refreshing the status every {}ms", interval.as_millis());
+                    } else {
+                        status_info!("Periodic status refresh disabled");
+                    }
+                }
+                GlobalAction::ClearError => {
+                    clear_error(client, context)?;
+                }
                 GlobalAction::ShowCurrentSongInfo => {
                     if let Some(current_song) = context.get_current_song(client)? {
                         modal!(context, SongInfoModal::new(current_song));
@@ -454,13 +753,22 @@ impl<'ui> Ui<'ui> {
                     }
                 }
             }
+        } else if let Some(idx) = key.as_tab_index(context) {
+            if let Some(tab) = context.config.tabs.names.get(idx) {
+                self.change_tab(*tab, client, context)?;
+                context.render()?;
+            }
         };
 
         Ok(KeyHandleResult::None)
     }
 
     pub fn before_show(&mut self, context: &mut AppContext, client: &mut impl MpdClient) -> Result<()> {
-        screen_call!(self, before_show(client, &context))
+        if context.config.compact {
+            return Ok(());
+        }
+        screen_call!(self, before_show(client, &context));
+        Ok(())
     }
 
     pub fn display_message(&mut self, message: String, level: Level) {
@@ -476,7 +784,7 @@ impl<'ui> Ui<'ui> {
         event: UiAppEvent,
         context: &mut AppContext,
         client: &mut impl MpdClient,
-    ) -> Result<()> {
+    ) -> Result<KeyHandleResult> {
         match event {
             UiAppEvent::Modal(modal) => {
                 self.modals.push(modal.0);
@@ -488,8 +796,9 @@ impl<'ui> Ui<'ui> {
                 self.on_event(UiEvent::ModalClosed, context, client)?;
                 context.render()?;
             }
+            UiAppEvent::Quit => return Ok(KeyHandleResult::Quit),
         }
-        Ok(())
+        Ok(KeyHandleResult::None)
     }
 
     pub fn on_event(
@@ -520,9 +829,55 @@ impl<'ui> Ui<'ui> {
             UiEvent::Exit => {}
             UiEvent::LyricsIndexed => {}
             UiEvent::SongChanged => {}
+            // Outputs are only shown in the outputs modal so there is nothing to refresh while no modal is open
+            UiEvent::Output if !self.modals.is_empty() => {
+                let outputs = client.outputs()?.0;
+                for modal in &mut self.modals {
+                    modal.on_outputs_changed(&outputs);
+                }
+                context.render()?;
+            }
+            UiEvent::Output => {}
+            // Ratings were already re-read when the idle event arrived and every idle event is followed by a render
+            UiEvent::Sticker => {}
+        }
+
+        if context.config.compact {
+            return Ok(());
+        }
+
+        // Events from MPD mean that it is reachable again, so give panes which failed to load another chance
+        if matches!(event, UiEvent::Player | UiEvent::Database | UiEvent::StoredPlaylist)
+            && screen_call!(self, retry_failed(client, context))
+        {
+            context.render()?;
         }
 
         for name in context.config.tabs.active_panes {
+            // Panes which are not visible only drop their stale data and fetch it again once they are shown, so
+            // a database update does not make every tab query MPD at once
+            if matches!(event, UiEvent::Database)
+                && !self
+                    .tabs
+                    .get(&self.active_tab)
+                    .is_some_and(|tab| tab.panes.panes_iter().any(|pane| pane.pane == *name))
+            {
+                match self.panes.get_mut(*name) {
+                    #[cfg(debug_assertions)]
+                    Panes::Logs(p) => p.on_database_update(context),
+                    Panes::Queue(p) => p.on_database_update(context),
+                    Panes::Directories(p) => p.on_database_update(context),
+                    Panes::Albums(p) => p.on_database_update(context),
+                    Panes::Artists(p) => p.on_database_update(context),
+                    Panes::Playlists(p) => p.on_database_update(context),
+                    Panes::Search(p) => p.on_database_update(context),
+                    Panes::AlbumArtists(p) => p.on_database_update(context),
+                    Panes::AlbumArt(p) => p.on_database_update(context),
+                    Panes::Lyrics(p) => p.on_database_update(context),
+                }
+                continue;
+            }
+
             match self.panes.get_mut(*name) {
                 #[cfg(debug_assertions)]
                 Panes::Logs(p) => p.on_event(&mut event, client, context),
@@ -549,6 +904,8 @@ pub struct ModalWrapper(Box<dyn Modal + Send + Sync>);
 pub enum UiAppEvent {
     Modal(ModalWrapper),
     PopModal,
+    /// Exit the application, sent once quitting has been confirmed
+    Quit,
 }
 
 #[derive(Debug, Hash, Eq, PartialEq)]
@@ -557,13 +914,15 @@ pub enum UiEvent {
     Player,
     Database,
     StoredPlaylist,
-    LogAdded(Vec<u8>),
+    LogAdded(LogEntry),
     Resized { columns: u16, rows: u16 },
     ModalOpened,
     ModalClosed,
     Exit,
     LyricsIndexed,
     SongChanged,
+    Output,
+    Sticker,
 }
 
 impl TryFrom<IdleEvent> for UiEvent {
@@ -574,11 +933,124 @@ impl TryFrom<IdleEvent> for UiEvent {
             IdleEvent::Player => UiEvent::Player,
             IdleEvent::Database => UiEvent::Database,
             IdleEvent::StoredPlaylist => UiEvent::StoredPlaylist,
+            IdleEvent::Output => UiEvent::Output,
+            IdleEvent::Sticker => UiEvent::Sticker,
             _ => return Err(()),
         })
     }
 }
 
+/// Changes the rating of the song by the given number of stars and writes it to the song's rating sticker. The new
+/// rating is displayed once MPD reports the sticker change.
+fn rate_song(client: &mut impl MpdClient, context: &AppContext, file: &str, change: &ValueChange) -> Result<()> {
+    if !context.supported_commands.contains("sticker") {
+        status_warn!("Ratings are not available because MPD has no sticker database");
+        return Ok(());
+    }
+
+    let current = client
+        .sticker_get(file, RATING_STICKER)?
+        .and_then(|sticker| Ratings::parse(&sticker.value))
+        .unwrap_or_default();
+    let step = |value: u32| u8::try_from(value).unwrap_or(u8::MAX);
+    let rating = match change {
+        ValueChange::Increase(value) => current.saturating_add(step(*value)),
+        ValueChange::Decrease(value) => current.saturating_sub(step(*value)),
+        ValueChange::Set(value) => step(*value),
+    }
+    .min(MAX_RATING);
+    if rating == current {
+        return Ok(());
+    }
+
+    if rating == 0 {
+        client.sticker_delete(file, RATING_STICKER)?;
+    } else {
+        client.sticker_set(file, RATING_STICKER, &rating.to_string())?;
+    }
+    status_info!("Rated {}", Ratings::stars(rating));
+
+    Ok(())
+}
+
+/// Changes the volume by the given step clamped to 0-100, warning instead when MPD has no mixer
+fn adjust_volume(client: &mut impl MpdClient, context: &mut AppContext, change: &ValueChange) -> Result<()> {
+    let Some(volume) = context.status.volume.as_mut() else {
+        status_warn!("No mixer available");
+        return Ok(());
+    };
+
+    let step = |value: u32| u8::try_from(value).unwrap_or(u8::MAX);
+    match change {
+        ValueChange::Increase(value) => volume.inc_by(step(*value)),
+        ValueChange::Decrease(value) => volume.dec_by(step(*value)),
+        ValueChange::Set(value) => volume.set_value(step(*value)),
+    };
+    client.set_volume(*volume)?;
+
+    Ok(())
+}
+
+fn clear_error(client: &mut impl MpdClient, context: &mut AppContext) -> Result<()> {
+    if context.status.error.is_some() {
+        client.clear_error()?;
+        context.status.error = None;
+        status_info!("MPD error cleared");
+    } else {
+        status_info!("MPD has not reported any error");
+    }
+
+    Ok(())
+}
+
+/// Changes the crossfade and updates the status right away. MPD leaves `xfade` out of its status when crossfade is
+/// disabled, so zero is stored as `None`.
+fn adjust_crossfade(client: &mut impl MpdClient, context: &mut AppContext, change: &ValueChange) -> Result<()> {
+    let current = context.status.xfade.unwrap_or_default();
+    let secs = match change {
+        ValueChange::Increase(value) => current.saturating_add(*value),
+        ValueChange::Decrease(value) => current.saturating_sub(*value),
+        ValueChange::Set(value) => *value,
+    };
+    client.set_crossfade(secs)?;
+    context.status.xfade = (secs > 0).then_some(secs);
+    if secs > 0 {
+        status_info!("Crossfade {secs}s");
+    } else {
+        status_info!("Crossfade off");
+    }
+    context.render()?;
+
+    Ok(())
+}
+
+/// Seeks in the current song and updates the elapsed time right away instead of waiting for the next player event.
+/// Seeking forward past the end of the song is clamped to its duration.
+fn seek(client: &mut impl MpdClient, context: &mut AppContext, change: ValueChange) -> Result<()> {
+    let status = &mut context.status;
+    let target = match &change {
+        ValueChange::Increase(value) => status.elapsed + Duration::from_secs((*value).into()),
+        ValueChange::Decrease(value) => status.elapsed.saturating_sub(Duration::from_secs((*value).into())),
+        ValueChange::Set(value) => Duration::from_secs((*value).into()),
+    };
+
+    // Streams have no known duration so the change is left for MPD to handle
+    if status.duration == Duration::ZERO || target < status.duration {
+        client.seek_current(change)?;
+    } else {
+        client.seek_current(ValueChange::Set(u32::try_from(status.duration.as_secs())?))?;
+    }
+
+    status.elapsed = if status.duration == Duration::ZERO {
+        target
+    } else {
+        target.min(status.duration)
+    };
+    context.render()?;
+
+    Ok(())
+}
+
 pub fn restore_terminal<B: Backend + std::io::Write>(terminal: &mut Terminal<B>, enable_mouse: bool) -> Result<()> {
     if enable_mouse {
         execute!(std::io::stdout(), DisableMouseCapture)?;
@@ -735,3 +1207,330 @@ impl Config {
             .thumb_style(self.theme.scrollbar.thumb_style)
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::{collections::HashMap, time::Duration};
+
+    use ratatui::{backend::TestBackend, Terminal};
+    use rstest::rstest;
+
+    use crate::{
+        config::{tabs::TabName, Config, Leak},
+        context::AppContext,
+        mpd::{
+            commands::{
+                volume::{Bound, Volume},
+                Song, State,
+            },
+            mpd_client::ValueChange,
+        },
+        tests::fixtures::{app_context, mpd_client::client, mpd_client::TestMpdClient},
+    };
+
+    use super::{adjust_crossfade, adjust_volume, clear_error, rate_song, seek, Ui, UiEvent};
+
+    #[rstest]
+    #[case(1, 1)]
+    #[case(2, 2)]
+    #[case(5, 3)]
+    #[case(12, 4)]
+    #[case(20, 8)]
+    #[case(30, 10)]
+    #[case(45, 12)]
+    fn renders_all_tabs_in_tiny_terminals(#[case] width: u16, #[case] height: u16, mut app_context: AppContext) {
+        app_context.queue.push(Song {
+            id: 1,
+            file: "some/song.mp3".to_owned(),
+            ..Default::default()
+        });
+        app_context.status.songid = Some(1);
+        app_context.status.state = State::Play;
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        let mut ui = Ui::new(&app_context).unwrap();
+
+        for tab in app_context.config.tabs.names {
+            ui.active_tab = *tab;
+            terminal
+                .draw(|frame| ui.render(frame, &mut app_context).unwrap())
+                .unwrap();
+        }
+    }
+
+    #[rstest]
+    #[case(5, 20)]
+    #[case(40, 3)]
+    fn shows_message_when_terminal_is_too_small(#[case] width: u16, #[case] height: u16, mut app_context: AppContext) {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        let mut ui = Ui::new(&app_context).unwrap();
+
+        terminal
+            .draw(|frame| ui.render(frame, &mut app_context).unwrap())
+            .unwrap();
+
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(content.contains("small"), "{content:?}");
+    }
+
+    #[rstest]
+    fn does_not_show_message_in_normal_terminal(mut app_context: AppContext) {
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        let mut ui = Ui::new(&app_context).unwrap();
+
+        terminal
+            .draw(|frame| ui.render(frame, &mut app_context).unwrap())
+            .unwrap();
+
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(!content.contains("Terminal too small"));
+    }
+
+    #[rstest]
+    fn shows_keybind_hints_on_the_last_line_when_enabled(mut app_context: AppContext) {
+        let config = Config {
+            keybind_hints: true,
+            ..app_context.config.clone()
+        };
+        app_context.config = config.leak();
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        let mut ui = Ui::new(&app_context).unwrap();
+
+        terminal
+            .draw(|frame| ui.render(frame, &mut app_context).unwrap())
+            .unwrap();
+
+        let last_line: String = terminal.backend().buffer().content()[80 * 23..]
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(last_line.contains("q quit"), "{last_line:?}");
+    }
+
+    #[rstest]
+    #[case(Some(1), true)]
+    #[case(None, false)]
+    fn shows_stop_after_current_indicator_while_armed(
+        #[case] stop_after_current: Option<u32>,
+        #[case] expected: bool,
+        mut app_context: AppContext,
+    ) {
+        app_context.stop_after_current = stop_after_current;
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        let mut ui = Ui::new(&app_context).unwrap();
+
+        terminal
+            .draw(|frame| ui.render(frame, &mut app_context).unwrap())
+            .unwrap();
+
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert_eq!(content.contains("Stop after current"), expected);
+    }
+
+    #[rstest]
+    #[case(50, ValueChange::Increase(1), 51)]
+    #[case(50, ValueChange::Decrease(5), 45)]
+    #[case(99, ValueChange::Increase(5), 100)]
+    #[case(2, ValueChange::Decrease(5), 0)]
+    fn adjusts_volume_within_bounds(
+        #[case] initial: u8,
+        #[case] change: ValueChange,
+        #[case] expected: u8,
+        mut app_context: AppContext,
+        mut client: TestMpdClient,
+    ) {
+        app_context.status.volume = Some(Volume::new(initial));
+
+        adjust_volume(&mut client, &mut app_context, &change).unwrap();
+
+        assert_eq!(*client.volume.value(), expected);
+    }
+
+    #[rstest]
+    fn does_not_adjust_volume_without_mixer(mut app_context: AppContext, mut client: TestMpdClient) {
+        app_context.status.volume = None;
+
+        adjust_volume(&mut client, &mut app_context, &ValueChange::Decrease(1)).unwrap();
+
+        assert_eq!(*client.volume.value(), 100);
+    }
+
+    #[rstest]
+    #[case(None, ValueChange::Increase(1), "set_crossfade 1", Some(1))]
+    #[case(Some(3), ValueChange::Increase(1), "set_crossfade 4", Some(4))]
+    #[case(Some(3), ValueChange::Decrease(1), "set_crossfade 2", Some(2))]
+    #[case(Some(1), ValueChange::Decrease(1), "set_crossfade 0", None)]
+    #[case(None, ValueChange::Decrease(1), "set_crossfade 0", None)]
+    fn adjusts_crossfade_and_updates_status(
+        #[case] initial: Option<u32>,
+        #[case] change: ValueChange,
+        #[case] expected_call: &str,
+        #[case] expected: Option<u32>,
+        mut app_context: AppContext,
+        mut client: TestMpdClient,
+    ) {
+        app_context.status.xfade = initial;
+
+        adjust_crossfade(&mut client, &mut app_context, &change).unwrap();
+
+        assert_eq!(client.calls.get(expected_call), Some(&1));
+        assert_eq!(app_context.status.xfade, expected);
+    }
+
+    #[rstest]
+    #[case(Some("Failed to decode".to_owned()), Some(&1))]
+    #[case(None, None)]
+    fn clears_error_only_when_mpd_reported_one(
+        #[case] error: Option<String>,
+        #[case] expected_calls: Option<&u32>,
+        mut app_context: AppContext,
+        mut client: TestMpdClient,
+    ) {
+        app_context.status.error = error;
+
+        clear_error(&mut client, &mut app_context).unwrap();
+
+        assert_eq!(client.calls.get("clear_error"), expected_calls);
+        assert_eq!(app_context.status.error, None);
+    }
+
+    #[rstest]
+    #[case(None, ValueChange::Increase(1), Some("1"))]
+    #[case(Some("3"), ValueChange::Increase(1), Some("4"))]
+    #[case(Some("3"), ValueChange::Decrease(1), Some("2"))]
+    #[case(Some("1"), ValueChange::Decrease(1), None)]
+    #[case(Some("10"), ValueChange::Decrease(1), Some("4"))]
+    #[case(Some("unknown"), ValueChange::Increase(1), Some("1"))]
+    fn rates_song_through_its_sticker(
+        #[case] initial: Option<&str>,
+        #[case] change: ValueChange,
+        #[case] expected: Option<&str>,
+        mut app_context: AppContext,
+        mut client: TestMpdClient,
+    ) {
+        app_context.supported_commands.insert("sticker".to_owned());
+        if let Some(initial) = initial {
+            client.stickers.insert(
+                "song.flac".to_owned(),
+                HashMap::from([("rating".to_owned(), initial.to_owned())]),
+            );
+        }
+
+        rate_song(&mut client, &app_context, "song.flac", &change).unwrap();
+
+        let rating = client
+            .stickers
+            .get("song.flac")
+            .and_then(|stickers| stickers.get("rating"))
+            .map(String::as_str);
+        assert_eq!(rating, expected);
+    }
+
+    #[rstest]
+    #[case(Some("5"), ValueChange::Increase(1))]
+    #[case(None, ValueChange::Decrease(1))]
+    fn does_not_write_rating_beyond_bounds(
+        #[case] initial: Option<&str>,
+        #[case] change: ValueChange,
+        mut app_context: AppContext,
+        mut client: TestMpdClient,
+    ) {
+        app_context.supported_commands.insert("sticker".to_owned());
+        if let Some(initial) = initial {
+            client.stickers.insert(
+                "song.flac".to_owned(),
+                HashMap::from([("rating".to_owned(), initial.to_owned())]),
+            );
+        }
+
+        rate_song(&mut client, &app_context, "song.flac", &change).unwrap();
+
+        assert!(client.calls.is_empty(), "{:?}", client.calls);
+    }
+
+    #[rstest]
+    fn does_not_rate_without_sticker_database(app_context: AppContext, mut client: TestMpdClient) {
+        rate_song(&mut client, &app_context, "song.flac", &ValueChange::Increase(1)).unwrap();
+
+        assert!(client.stickers.is_empty());
+    }
+
+    #[rstest]
+    #[case(ValueChange::Increase(5), "seek_current Increase(5)", 55)]
+    #[case(ValueChange::Decrease(5), "seek_current Decrease(5)", 45)]
+    #[case(ValueChange::Decrease(60), "seek_current Decrease(60)", 0)]
+    #[case(ValueChange::Increase(60), "seek_current Set(100)", 100)]
+    #[case(ValueChange::Set(130), "seek_current Set(100)", 100)]
+    fn seeks_within_the_song_and_updates_elapsed(
+        #[case] change: ValueChange,
+        #[case] expected_call: &str,
+        #[case] expected_elapsed: u64,
+        mut app_context: AppContext,
+        mut client: TestMpdClient,
+    ) {
+        app_context.status.elapsed = Duration::from_secs(50);
+        app_context.status.duration = Duration::from_secs(100);
+
+        seek(&mut client, &mut app_context, change).unwrap();
+
+        assert_eq!(client.calls.get(expected_call), Some(&1));
+        assert_eq!(app_context.status.elapsed, Duration::from_secs(expected_elapsed));
+    }
+
+    #[rstest]
+    fn seeks_streams_without_clamping(mut app_context: AppContext, mut client: TestMpdClient) {
+        app_context.status.elapsed = Duration::from_secs(50);
+        app_context.status.duration = Duration::ZERO;
+
+        seek(&mut client, &mut app_context, ValueChange::Increase(100)).unwrap();
+
+        assert_eq!(client.calls.get("seek_current Increase(100)"), Some(&1));
+        assert_eq!(app_context.status.elapsed, Duration::from_secs(150));
+    }
+
+    #[rstest]
+    fn refreshes_only_the_visible_panes_on_database_update(mut app_context: AppContext, mut client: TestMpdClient) {
+        let mut ui = Ui::new(&app_context).unwrap();
+        ui.change_tab(TabName("Directories"), &mut client, &app_context)
+            .unwrap();
+        client.calls.clear();
+
+        ui.on_event(UiEvent::Database, &mut app_context, &mut client).unwrap();
+
+        assert_eq!(client.calls.get("lsinfo None"), Some(&1));
+    }
+
+    #[rstest]
+    fn refreshes_hidden_panes_once_shown_after_database_update(mut app_context: AppContext, mut client: TestMpdClient) {
+        let mut ui = Ui::new(&app_context).unwrap();
+        ui.change_tab(TabName("Directories"), &mut client, &app_context)
+            .unwrap();
+        ui.change_tab(TabName("Queue"), &mut client, &app_context).unwrap();
+        client.calls.clear();
+
+        ui.on_event(UiEvent::Database, &mut app_context, &mut client).unwrap();
+        assert_eq!(client.calls.get("lsinfo None"), None);
+
+        ui.change_tab(TabName("Directories"), &mut client, &app_context)
+            .unwrap();
+        assert_eq!(client.calls.get("lsinfo None"), Some(&1));
+    }
+}
@@ -8,7 +8,10 @@ use ratatui::{
 };
 
 use crate::{
-    config::keys::{CommonAction, GlobalAction},
+    config::{
+        keys::{CommonAction, GlobalAction},
+        Config,
+    },
     context::AppContext,
     mpd::client::Client,
     shared::{
@@ -50,18 +53,23 @@ impl<'a, Callback: FnMut(&mut Client<'_>) -> Result<()> + 'a> std::fmt::Debug fo
 #[allow(dead_code)]
 impl<'a, Callback: FnMut(&mut Client<'_>) -> Result<()> + 'a> ConfirmModal<'a, Callback> {
     pub fn new(context: &AppContext) -> Self {
+        Self::with_config(context.config)
+    }
+
+    /// Same as [`Self::new`] for callbacks which open the modal without access to the context
+    pub fn with_config(config: &Config) -> Self {
         let mut button_group_state = ButtonGroupState::default();
         let buttons = vec![Button::default().label("Confirm"), Button::default().label("Cancel")];
         button_group_state.set_button_count(buttons.len());
         let button_group = ButtonGroup::default()
-            .active_style(context.config.theme.current_item_style)
-            .inactive_style(context.config.as_text_style())
+            .active_style(config.theme.current_item_style)
+            .inactive_style(config.as_text_style())
             .buttons(buttons)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
                     .border_set(BUTTON_GROUP_SYMBOLS)
-                    .border_style(context.config.as_border_style()),
+                    .border_style(config.as_border_style()),
             );
 
         Self {
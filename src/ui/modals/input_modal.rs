@@ -161,12 +161,13 @@ impl<'a, C: FnMut(&mut Client<'_>, &str) -> Result<()> + 'a> Modal for InputModa
                 context.render()?;
                 return Ok(());
             } else if let Some(CommonAction::Confirm) = action {
+                // Closed before the callback runs so that the callback can open another modal
+                pop_modal!(context);
                 if self.button_group_state.selected == 0 {
                     if let Some(ref mut callback) = self.callback {
                         (callback)(client, &self.value)?;
                     }
                 }
-                pop_modal!(context);
                 return Ok(());
             }
 
@@ -199,12 +200,12 @@ impl<'a, C: FnMut(&mut Client<'_>, &str) -> Result<()> + 'a> Modal for InputModa
                     pop_modal!(context);
                 }
                 CommonAction::Confirm => {
+                    pop_modal!(context);
                     if self.button_group_state.selected == 0 {
                         if let Some(ref mut callback) = self.callback {
                             (callback)(client, &self.value)?;
                         }
                     }
-                    pop_modal!(context);
                 }
                 CommonAction::FocusInput => {
                     self.input_focused = true;
@@ -235,10 +236,10 @@ impl<'a, C: FnMut(&mut Client<'_>, &str) -> Result<()> + 'a> Modal for InputModa
             MouseEventKind::DoubleClick => {
                 match self.button_group.get_button_idx_at(event.into()) {
                     Some(0) => {
+                        pop_modal!(context);
                         if let Some(ref mut callback) = self.callback {
                             (callback)(client, &self.value)?;
                         }
-                        pop_modal!(context);
                     }
                     Some(_) => {
                         pop_modal!(context);
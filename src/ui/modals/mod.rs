@@ -6,7 +6,7 @@ use ratatui::{
 
 use crate::{
     context::AppContext,
-    mpd::client::Client,
+    mpd::{client::Client, commands::Output},
     shared::{key_event::KeyEvent, mouse_event::MouseEvent},
 };
 
@@ -15,6 +15,7 @@ pub mod decoders;
 pub mod input_modal;
 pub mod keybinds;
 pub mod outputs;
+pub mod playlist_membership;
 pub mod select_modal;
 pub mod song_info;
 
@@ -29,6 +30,9 @@ pub(super) trait Modal: std::fmt::Debug {
         client: &mut Client<'_>,
         context: &mut AppContext,
     ) -> Result<()>;
+
+    /// Called with the new list of outputs when they were changed, ie. by another client
+    fn on_outputs_changed(&mut self, _outputs: &[Output]) {}
 }
 
 #[allow(dead_code)]
@@ -49,13 +49,22 @@ impl OutputsModal {
         };
 
         client.toggle_output(output.id)?;
-        self.outputs = client.outputs()?.0;
+        self.set_outputs(client.outputs()?.0);
 
-        if idx >= self.outputs.len() {
+        Ok(())
+    }
+
+    fn set_outputs(&mut self, outputs: Vec<Output>) {
+        self.outputs = outputs;
+        self.scrolling_state.set_content_len(Some(self.outputs.len()));
+
+        if self
+            .scrolling_state
+            .get_selected()
+            .is_some_and(|idx| idx >= self.outputs.len())
+        {
             self.scrolling_state.last();
         }
-
-        Ok(())
     }
 }
 
@@ -169,6 +178,10 @@ impl Modal for OutputsModal {
         Ok(())
     }
 
+    fn on_outputs_changed(&mut self, outputs: &[Output]) {
+        self.set_outputs(outputs.to_vec());
+    }
+
     fn handle_mouse_event(
         &mut self,
         event: MouseEvent,
@@ -207,3 +220,43 @@ impl Modal for OutputsModal {
         Ok(())
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::{mpd::commands::Output, ui::modals::Modal};
+
+    use super::OutputsModal;
+
+    fn outputs(count: u32) -> Vec<Output> {
+        (0..count)
+            .map(|id| Output {
+                id,
+                name: format!("output_{id}"),
+                enabled: true,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn refreshes_outputs_changed_by_another_client() {
+        let mut modal = OutputsModal::new(outputs(2));
+        let mut changed = outputs(2);
+        changed[1].enabled = false;
+
+        modal.on_outputs_changed(&changed);
+
+        assert_eq!(modal.outputs, changed);
+        assert_eq!(modal.scrolling_state.get_selected(), Some(0));
+    }
+
+    #[test]
+    fn keeps_selection_within_removed_outputs() {
+        let mut modal = OutputsModal::new(outputs(3));
+        modal.scrolling_state.last();
+
+        modal.on_outputs_changed(&outputs(1));
+
+        assert_eq!(modal.scrolling_state.get_selected(), Some(0));
+    }
+}
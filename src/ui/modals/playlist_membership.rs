@@ -0,0 +1,197 @@
+use anyhow::Result;
+use ratatui::{
+    layout::{Constraint, Margin, Rect},
+    style::Style,
+    symbols::border,
+    widgets::{Block, Borders, Cell, Clear, Row, Table, TableState},
+};
+
+use crate::{
+    config::keys::CommonAction,
+    context::AppContext,
+    mpd::client::Client,
+    shared::{
+        ext::mpd_client::MpdClientExt,
+        key_event::KeyEvent,
+        macros::pop_modal,
+        mouse_event::{MouseEvent, MouseEventKind},
+    },
+    ui::dirstack::DirState,
+};
+
+use super::{Modal, RectExt};
+
+#[derive(Debug)]
+pub struct PlaylistMembershipModal {
+    scrolling_state: DirState<TableState>,
+    table_area: Rect,
+    uri: String,
+    playlists: Vec<(String, bool)>,
+}
+
+impl PlaylistMembershipModal {
+    pub fn new(uri: String, playlists: Vec<(String, bool)>) -> Self {
+        let mut result = Self {
+            uri,
+            playlists,
+            scrolling_state: DirState::default(),
+            table_area: Rect::default(),
+        };
+        result.scrolling_state.set_content_len(Some(result.playlists.len()));
+        result.scrolling_state.first();
+
+        result
+    }
+
+    pub fn toggle_selected_playlist(&mut self, client: &mut Client<'_>, context: &mut AppContext) -> Result<()> {
+        let Some(idx) = self.scrolling_state.get_selected() else {
+            return Ok(());
+        };
+        let Some((name, contains)) = self.playlists.get_mut(idx) else {
+            return Ok(());
+        };
+
+        *contains = client.toggle_in_playlist(name, &self.uri, context)?;
+        context.render()?;
+
+        Ok(())
+    }
+}
+
+impl Modal for PlaylistMembershipModal {
+    fn render(&mut self, frame: &mut ratatui::Frame, app: &mut AppContext) -> anyhow::Result<()> {
+        let popup_area = frame.area().centered_exact(60, 15);
+        frame.render_widget(Clear, popup_area);
+        if let Some(bg_color) = app.config.theme.modal_background_color {
+            frame.render_widget(Block::default().style(Style::default().bg(bg_color)), popup_area);
+        }
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED)
+            .border_style(app.config.as_border_style())
+            .title_alignment(ratatui::prelude::Alignment::Center)
+            .title("Playlists of the current song");
+
+        let table_area = popup_area.inner(Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+
+        let rows = self.playlists.iter().map(|(name, contains)| {
+            Row::new([
+                Cell::from(if *contains { "[x]" } else { "[ ]" }),
+                Cell::from(name.clone()),
+            ])
+        });
+
+        self.scrolling_state.set_viewport_len(Some(table_area.height.into()));
+
+        let table = Table::new(rows, [Constraint::Length(4), Constraint::Percentage(100)])
+            .column_spacing(0)
+            .style(app.config.as_text_style())
+            .row_highlight_style(app.config.theme.current_item_style);
+
+        let table_area = table_area.inner(Margin {
+            horizontal: 1,
+            vertical: 0,
+        });
+        self.table_area = table_area;
+
+        frame.render_widget(block, popup_area);
+        frame.render_stateful_widget(table, table_area, self.scrolling_state.as_render_state_ref());
+        frame.render_stateful_widget(
+            app.config.as_styled_scrollbar(),
+            popup_area.inner(Margin {
+                horizontal: 0,
+                vertical: 1,
+            }),
+            self.scrolling_state.as_scrollbar_state_ref(),
+        );
+
+        Ok(())
+    }
+
+    fn handle_key(&mut self, key: &mut KeyEvent, client: &mut Client<'_>, context: &mut AppContext) -> Result<()> {
+        if let Some(action) = key.as_common_action(context) {
+            match action {
+                CommonAction::DownHalf => {
+                    self.scrolling_state.next_half_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
+                CommonAction::UpHalf => {
+                    self.scrolling_state.prev_half_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
+                CommonAction::Up => {
+                    self.scrolling_state
+                        .prev(context.config.scrolloff, context.config.wrap_navigation);
+
+                    context.render()?;
+                }
+                CommonAction::Down => {
+                    self.scrolling_state
+                        .next(context.config.scrolloff, context.config.wrap_navigation);
+
+                    context.render()?;
+                }
+                CommonAction::Bottom => {
+                    self.scrolling_state.last();
+
+                    context.render()?;
+                }
+                CommonAction::Top => {
+                    self.scrolling_state.first();
+
+                    context.render()?;
+                }
+                CommonAction::Confirm | CommonAction::Select => {
+                    self.toggle_selected_playlist(client, context)?;
+                }
+                CommonAction::Close => {
+                    pop_modal!(context);
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_mouse_event(
+        &mut self,
+        event: MouseEvent,
+        client: &mut Client<'_>,
+        context: &mut AppContext,
+    ) -> Result<()> {
+        match event.kind {
+            MouseEventKind::LeftClick if self.table_area.contains(event.into()) => {
+                let y: usize = event.y.saturating_sub(self.table_area.y).into();
+                if let Some(idx) = self.scrolling_state.get_at_rendered_row(y) {
+                    self.scrolling_state.select(Some(idx), context.config.scrolloff);
+                    context.render()?;
+                }
+            }
+            MouseEventKind::DoubleClick if self.table_area.contains(event.into()) => {
+                self.toggle_selected_playlist(client, context)?;
+            }
+            MouseEventKind::MiddleClick => {}
+            MouseEventKind::RightClick => {}
+            MouseEventKind::ScrollDown if self.table_area.contains(event.into()) => {
+                self.scrolling_state.next(context.config.scrolloff, false);
+                context.render()?;
+            }
+            MouseEventKind::ScrollUp if self.table_area.contains(event.into()) => {
+                self.scrolling_state.prev(context.config.scrolloff, false);
+                context.render()?;
+            }
+            MouseEventKind::LeftClick => {}
+            MouseEventKind::DoubleClick => {}
+            MouseEventKind::ScrollDown => {}
+            MouseEventKind::ScrollUp => {}
+        }
+
+        Ok(())
+    }
+}
@@ -93,6 +93,16 @@ impl Modal for SongInfoModal {
         value_area.width = value_area.width.saturating_sub(1); // account for the column spacing
 
         let Self { song, .. } = self;
+        let separator = app.config.theme.format_tag_separator;
+        let title = song.title().map(|v| v.join(separator));
+        let artist = song.artist().map(|v| v.join(separator));
+        let album = song.album().map(|v| v.join(separator));
+        let other_tags = song
+            .metadata
+            .iter()
+            .filter(|(key, _)| !["title", "album", "artist", "duration"].contains(&(*key).as_str()))
+            .map(|(k, v)| (k, v.join(separator)))
+            .collect_vec();
         let mut rows = Vec::new();
 
         rows.extend(SongInfoModal::row("File", tag_area.width, &song.file, value_area.width));
@@ -105,13 +115,13 @@ impl Modal for SongInfoModal {
                 value_area.width,
             ));
         };
-        if let Some(title) = song.title() {
+        if let Some(title) = &title {
             rows.extend(SongInfoModal::row("Title", tag_area.width, title, value_area.width));
         }
-        if let Some(artist) = song.artist() {
+        if let Some(artist) = &artist {
             rows.extend(SongInfoModal::row("Artist", tag_area.width, artist, value_area.width));
         }
-        if let Some(album) = song.album() {
+        if let Some(album) = &album {
             rows.extend(SongInfoModal::row("Album", tag_area.width, album, value_area.width));
         }
         let duration = song
@@ -129,9 +139,8 @@ impl Modal for SongInfoModal {
         }
 
         rows.extend(
-            song.metadata
+            other_tags
                 .iter()
-                .filter(|(key, _)| !["title", "album", "artist", "duration"].contains(&(*key).as_str()))
                 .flat_map(|(k, v)| SongInfoModal::row(k, tag_area.width, v, value_area.width)),
         );
 
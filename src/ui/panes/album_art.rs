@@ -14,14 +14,19 @@ use super::Pane;
 pub struct AlbumArtPane {
     album_art: AlbumArtFacade,
     image_data: Option<Vec<u8>>,
+    /// The pane was too small to display the image during the last render
+    hidden_by_size: bool,
 }
 
+const MIN_SIZE: u16 = 2;
+
 impl AlbumArtPane {
     pub fn new(context: &AppContext) -> Self {
         let sender = context.app_event_sender.clone();
         let config = context.config;
         Self {
             image_data: None,
+            hidden_by_size: false,
             album_art: AlbumArtFacade::new(
                 config.album_art.method.into(),
                 config.theme.default_album_art,
@@ -36,7 +41,7 @@ impl AlbumArtPane {
         }
     }
 
-    fn fetch_album_art(client: &mut impl MpdClient, context: &AppContext) -> Result<Option<Vec<u8>>> {
+    fn fetch_album_art(&mut self, client: &mut impl MpdClient, context: &AppContext) -> Result<Option<Vec<u8>>> {
         if matches!(context.config.album_art.method.into(), ImageProtocol::None) {
             return Ok(None);
         };
@@ -54,15 +59,25 @@ impl AlbumArtPane {
 
         let start = std::time::Instant::now();
         log::debug!(file = song_uri; "Searching for album art");
-        let result = client.find_album_art(song_uri)?;
+        let result = context.find_album_art(client, song_uri)?;
         log::debug!(elapsed:? = start.elapsed(), size = result.as_ref().map(|v|v.len()); "Found album art");
-
         Ok(result)
     }
 }
 
 impl Pane for AlbumArtPane {
     fn render(&mut self, frame: &mut Frame, area: Rect, context: &AppContext) -> Result<()> {
+        if area.width < MIN_SIZE || area.height < MIN_SIZE {
+            if !self.hidden_by_size {
+                self.album_art.hide(context.config.theme.background_color)?;
+                self.hidden_by_size = true;
+            }
+            return Ok(());
+        } else if self.hidden_by_size {
+            self.hidden_by_size = false;
+            self.album_art.show();
+        }
+
         if let Some(data) = self.image_data.take() {
             self.album_art.set_size(area);
             self.album_art.set_image(Some(data))?;
@@ -76,6 +91,9 @@ impl Pane for AlbumArtPane {
     }
 
     fn post_render(&mut self, frame: &mut ratatui::Frame, context: &AppContext) -> Result<()> {
+        if self.hidden_by_size {
+            return Ok(());
+        }
         self.album_art.post_render(frame, context.config)?;
         Ok(())
     }
@@ -84,7 +102,7 @@ impl Pane for AlbumArtPane {
         &mut self,
         _event: &mut KeyEvent,
         _client: &mut impl MpdClient,
-        _context: &AppContext,
+        _context: &mut AppContext,
     ) -> Result<()> {
         Ok(())
     }
@@ -95,15 +113,15 @@ impl Pane for AlbumArtPane {
     }
 
     fn before_show(&mut self, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
-        self.image_data = AlbumArtPane::fetch_album_art(client, context)?;
+        self.image_data = self.fetch_album_art(client, context)?;
         Ok(())
     }
 
     fn on_event(&mut self, event: &mut UiEvent, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
         match event {
             UiEvent::SongChanged => {
-                self.album_art
-                    .set_image(AlbumArtPane::fetch_album_art(client, context)?)?;
+                let data = self.fetch_album_art(client, context)?;
+                self.album_art.set_image(data)?;
                 context.render()?;
             }
             UiEvent::Resized { columns, rows } => {
@@ -117,7 +135,9 @@ impl Pane for AlbumArtPane {
                 context.render()?;
             }
             UiEvent::ModalClosed => {
-                self.album_art.show();
+                if !self.hidden_by_size {
+                    self.album_art.show();
+                }
 
                 context.render()?;
             }
@@ -134,12 +154,15 @@ impl Pane for AlbumArtPane {
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
+    use std::cell::RefCell;
+
     use rstest::rstest;
 
     use crate::config::Config;
     use crate::config::Leak;
     use crate::mpd::commands::Song;
     use crate::mpd::commands::State;
+    use crate::shared::album_art_cache::AlbumArtCache;
     use crate::tests::fixtures::app_context;
     use crate::tests::fixtures::mpd_client::client;
     use crate::tests::fixtures::mpd_client::TestMpdClient;
@@ -218,4 +241,91 @@ mod tests {
             u32::from(should_search)
         );
     }
+
+    #[rstest]
+    #[case("album/song_1.flac", 1)]
+    #[case("album/song_2.flac", 2)]
+    #[case("other_album/song_1.flac", 2)]
+    fn reuses_cached_album_art_only_for_the_same_song_file(
+        #[case] next_file: &str,
+        #[case] expected_searches: u32,
+        mut app_context: AppContext,
+        mut client: TestMpdClient,
+    ) {
+        let mut config = Config::default();
+        config.album_art.method = ImageMethod::Kitty;
+        app_context.config = config.leak();
+        app_context.queue.push(Song {
+            id: 1,
+            file: "album/song_1.flac".to_owned(),
+            ..Default::default()
+        });
+        app_context.queue.push(Song {
+            id: 2,
+            file: next_file.to_owned(),
+            ..Default::default()
+        });
+        app_context.status.songid = Some(1);
+        app_context.status.state = State::Play;
+        let mut screen = AlbumArtPane::new(&app_context);
+
+        screen.before_show(&mut client, &app_context).unwrap();
+        // A full state refresh emits the song change again even though the song stayed the same
+        screen
+            .on_event(&mut UiEvent::SongChanged, &mut client, &app_context)
+            .unwrap();
+        app_context.status.songid = Some(2);
+        screen
+            .on_event(&mut UiEvent::SongChanged, &mut client, &app_context)
+            .unwrap();
+
+        assert_eq!(client.calls.get("find_album_art"), Some(&expected_searches));
+    }
+
+    #[rstest]
+    #[case(16, 2)]
+    #[case(1, 3)]
+    #[case(0, 3)]
+    fn keeps_album_art_of_multiple_songs_cached(
+        #[case] cache_size: usize,
+        #[case] expected_searches: u32,
+        mut app_context: AppContext,
+        mut client: TestMpdClient,
+    ) {
+        let mut config = Config::default();
+        config.album_art.method = ImageMethod::Kitty;
+        config.album_art_cache_size = cache_size;
+        app_context.config = config.leak();
+        app_context.album_art_cache = RefCell::new(AlbumArtCache::new(cache_size));
+        app_context.queue.push(Song {
+            id: 1,
+            file: "album/song_1.flac".to_owned(),
+            ..Default::default()
+        });
+        app_context.queue.push(Song {
+            id: 2,
+            file: "other_album/song_1.flac".to_owned(),
+            ..Default::default()
+        });
+        app_context.status.state = State::Play;
+        let mut screen = AlbumArtPane::new(&app_context);
+
+        for id in [1, 2, 1] {
+            app_context.status.songid = Some(id);
+            screen
+                .on_event(&mut UiEvent::SongChanged, &mut client, &app_context)
+                .unwrap();
+        }
+
+        assert_eq!(client.calls.get("find_album_art"), Some(&expected_searches));
+    }
+
+    #[rstest]
+    fn second_lookup_of_the_same_file_does_not_call_the_client(app_context: AppContext, mut client: TestMpdClient) {
+        let first = app_context.find_album_art(&mut client, "album/song_1.flac").unwrap();
+        let second = app_context.find_album_art(&mut client, "album/song_1.flac").unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(client.calls.get("find_album_art"), Some(&1));
+    }
 }
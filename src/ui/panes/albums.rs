@@ -1,16 +1,16 @@
 use crate::{
-    config::Config,
+    config::{tabs::PaneType, RightOnSong, SortOrder},
     context::AppContext,
     mpd::{
         commands::Song as MpdSong,
         errors::MpdError,
-        mpd_client::{Filter, MpdClient, Tag},
+        mpd_client::{Filter, FindOptions, MpdClient, Tag},
     },
-    shared::ext::mpd_client::MpdClientExt,
+    shared::ext::mpd_client::{MpdClientExt, SongAdd},
     shared::{key_event::KeyEvent, macros::status_info, mouse_event::MouseEvent},
     ui::{
-        browser::BrowserPane,
-        dirstack::{DirStack, DirStackItem},
+        browser::{album_dirs, BrowserPane, MatchMode},
+        dirstack::{Dir, DirStack, DirStackItem},
         widgets::browser::Browser,
         UiEvent,
     },
@@ -31,6 +31,8 @@ pub struct AlbumsPane {
     filter_input_mode: bool,
     browser: Browser<DirOrSong>,
     initialized: bool,
+    sort: SortOrder,
+    match_mode: MatchMode,
 }
 
 impl AlbumsPane {
@@ -38,8 +40,10 @@ impl AlbumsPane {
         Self {
             stack: DirStack::default(),
             filter_input_mode: false,
-            browser: Browser::new(context.config),
+            browser: Browser::new(context.config, PaneType::Albums),
             initialized: false,
+            sort: context.config.browser_sort.albums,
+            match_mode: MatchMode::default(),
         }
     }
 
@@ -50,14 +54,14 @@ impl AlbumsPane {
         };
 
         match self.stack.path() {
-            [_album] => {
+            [_] => {
                 self.add(current, client, context)?;
                 if autoplay {
                     client.play_last(context)?;
                 }
             }
             [] => {
-                let res = list_titles(client, current.as_path())?;
+                let res = list_titles(client, self.match_mode, current.as_path(), album_artist_of(current))?;
                 self.stack.push(res.collect());
                 context.render()?;
             }
@@ -72,9 +76,11 @@ impl AlbumsPane {
 }
 
 impl Pane for AlbumsPane {
-    fn render(&mut self, frame: &mut Frame, area: Rect, _context: &AppContext) -> Result<()> {
+    fn render(&mut self, frame: &mut Frame, area: Rect, context: &AppContext) -> Result<()> {
         self.browser
             .set_filter_input_active(self.filter_input_mode)
+            .set_current_item_style(context.current_item_style())
+            .set_ratings(&context.ratings)
             .render(area, frame.buffer_mut(), &mut self.stack);
 
         Ok(())
@@ -82,18 +88,9 @@ impl Pane for AlbumsPane {
 
     fn before_show(&mut self, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
         if !self.initialized {
-            let result = client.list_tag(Tag::Album, None).context("Cannot list tags")?;
-            self.stack = DirStack::new(
-                result
-                    .into_iter()
-                    .map(|v| DirOrSong::Dir {
-                        full_path: String::new(),
-                        name: v,
-                    })
-                    .collect::<Vec<_>>(),
-            );
+            self.stack = DirStack::new(list_albums(client, self.sort).context("Cannot list tags")?);
             let preview = self
-                .prepare_preview(client, context.config)
+                .prepare_preview(client, context)
                 .context("Cannot prepare preview")?;
             self.stack.set_preview(preview);
             self.initialized = true;
@@ -104,18 +101,9 @@ impl Pane for AlbumsPane {
 
     fn on_event(&mut self, event: &mut UiEvent, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
         if let crate::ui::UiEvent::Database = event {
-            let result = client.list_tag(Tag::Album, None).context("Cannot list tags")?;
-            self.stack = DirStack::new(
-                result
-                    .into_iter()
-                    .map(|v| DirOrSong::Dir {
-                        full_path: String::new(),
-                        name: v,
-                    })
-                    .collect::<Vec<_>>(),
-            );
+            self.stack = DirStack::new(list_albums(client, self.sort).context("Cannot list tags")?);
             let preview = self
-                .prepare_preview(client, context.config)
+                .prepare_preview(client, context)
                 .context("Cannot prepare preview")?;
             self.stack.set_preview(preview);
 
@@ -124,6 +112,10 @@ impl Pane for AlbumsPane {
         Ok(())
     }
 
+    fn on_database_update(&mut self, _context: &AppContext) {
+        self.initialized = false;
+    }
+
     fn handle_mouse_event(
         &mut self,
         event: MouseEvent,
@@ -133,7 +125,16 @@ impl Pane for AlbumsPane {
         self.handle_mouse_action(event, client, context)
     }
 
-    fn handle_action(&mut self, event: &mut KeyEvent, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
+    fn is_input_active(&self) -> bool {
+        self.filter_input_mode
+    }
+
+    fn handle_action(
+        &mut self,
+        event: &mut KeyEvent,
+        client: &mut impl MpdClient,
+        context: &mut AppContext,
+    ) -> Result<()> {
         self.handle_filter_input(event, client, context)?;
         self.handle_common_action(event, client, context)?;
         self.handle_global_action(event, client, context)?;
@@ -141,21 +142,61 @@ impl Pane for AlbumsPane {
     }
 }
 
-fn list_titles(client: &mut impl MpdClient, album: &str) -> Result<impl Iterator<Item = DirOrSong>, MpdError> {
-    Ok(client
-        .find(&[Filter::new(Tag::Album, album)])?
+/// Filters matching the songs of the album. Albums are listed separately for each of their album
+/// artists so the album artist is matched as well.
+fn album_filters<'value>(
+    mode: MatchMode,
+    album: &'value str,
+    album_artist: Option<&'value str>,
+) -> Vec<Filter<'static, 'value>> {
+    let mut filters = vec![Filter::new_with_kind(Tag::Album, album, mode.kind())];
+    if let Some(album_artist) = album_artist {
+        filters.push(Filter::new(Tag::AlbumArtist, album_artist));
+    }
+    filters
+}
+
+fn album_artist_of(item: &DirOrSong) -> Option<&str> {
+    match item {
+        DirOrSong::Dir { album_artist, .. } => album_artist.as_deref(),
+        DirOrSong::Song(_) => None,
+    }
+}
+
+fn list_albums(client: &mut impl MpdClient, sort: SortOrder) -> Result<Vec<DirOrSong>, MpdError> {
+    Ok(album_dirs(
+        &client.list_tag_grouped(Tag::Album, &[Tag::Date, Tag::AlbumArtist], None)?,
+        sort,
+    ))
+}
+
+fn list_titles(
+    client: &mut impl MpdClient,
+    mode: MatchMode,
+    album: &str,
+    album_artist: Option<&str>,
+) -> Result<impl Iterator<Item = DirOrSong>, MpdError> {
+    Ok(mode
+        .find(client, &album_filters(mode, &mode.value(album), album_artist))?
         .into_iter()
         .map(DirOrSong::Song)
         .sorted())
 }
 
-fn find_songs(client: &mut impl MpdClient, album: &str, file: &str) -> Result<Vec<MpdSong>, MpdError> {
-    client
-        .find(&[Filter::new(Tag::File, file), Filter::new(Tag::Album, album)])
-        .map(|mut v| {
-            v.sort();
-            v
-        })
+fn find_songs(
+    client: &mut impl MpdClient,
+    mode: MatchMode,
+    album: &str,
+    album_artist: Option<&str>,
+    file: &str,
+) -> Result<Vec<MpdSong>, MpdError> {
+    let album = mode.value(album);
+    let mut filters = album_filters(mode, &album, album_artist);
+    filters.push(Filter::new(Tag::File, file));
+    mode.find(client, &filters).map(|mut v| {
+        v.sort();
+        v
+    })
 }
 
 impl BrowserPane<DirOrSong> for AlbumsPane {
@@ -177,7 +218,10 @@ impl BrowserPane<DirOrSong> for AlbumsPane {
 
     fn list_songs_in_item(&self, client: &mut impl MpdClient, item: &DirOrSong) -> Result<Vec<MpdSong>> {
         match item {
-            DirOrSong::Dir { name, full_path: _ } => Ok(client.find(&[Filter::new(Tag::Album, name)])?),
+            DirOrSong::Dir { name, album_artist, .. } => Ok(self.match_mode.find(
+                client,
+                &album_filters(self.match_mode, &self.match_mode.value(name), album_artist.as_deref()),
+            )?),
             DirOrSong::Song(song) => Ok(vec![song.clone()]),
         }
     }
@@ -187,22 +231,49 @@ impl BrowserPane<DirOrSong> for AlbumsPane {
     }
 
     fn next(&mut self, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
-        self.open_or_play(false, client, context)
+        self.open_or_play(context.config.right_on_song == RightOnSong::AddAndPlay, client, context)
+    }
+
+    fn toggle_match_mode(&mut self, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
+        self.match_mode = self.match_mode.toggled();
+
+        if let [album] = self.stack.path() {
+            let album_artist = self.stack.previous().selected().and_then(album_artist_of);
+            let items = list_titles(client, self.match_mode, album, album_artist)?.collect();
+            let filter = self.stack.current().filter().map(ToOwned::to_owned);
+            let mut dir = Dir::new(items);
+            dir.set_filter(filter, context.config);
+            *self.stack.current_mut() = dir;
+        }
+        let preview = self
+            .prepare_preview(client, context)
+            .context("Cannot prepare preview")?;
+        self.stack.set_preview(preview);
+
+        status_info!("Using {} matching", self.match_mode.description());
+        context.render()?;
+
+        Ok(())
     }
 
     fn add(&self, item: &DirOrSong, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
         match self.stack.path() {
-            [album] => {
-                client.find_add(&[
-                    Filter::new(Tag::File, &item.dir_name_or_file_name()),
-                    Filter::new(Tag::Album, album.as_str()),
-                ])?;
-
-                status_info!("'{}' added to queue", item.dir_name_or_file_name());
+            [_] => {
+                if client.add_song(&item.dir_name_or_file_name(), context)? == SongAdd::Added {
+                    status_info!("'{}' added to queue", item.dir_name_or_file_name());
+                }
                 context.render()?;
             }
             [] => {
-                client.find_add(&[Filter::new(Tag::Album, &item.dir_name_or_file_name())])?;
+                self.match_mode.find_add(
+                    client,
+                    &album_filters(
+                        self.match_mode,
+                        &self.match_mode.value(&item.dir_name_or_file_name()),
+                        album_artist_of(item),
+                    ),
+                    context.add_position(),
+                )?;
 
                 status_info!("Album '{}' added to queue", &item.dir_name_or_file_name());
                 context.render()?;
@@ -213,16 +284,46 @@ impl BrowserPane<DirOrSong> for AlbumsPane {
         Ok(())
     }
 
+    fn add_first(
+        &self,
+        item: &DirOrSong,
+        options: FindOptions<'_>,
+        client: &mut impl MpdClient,
+        context: &AppContext,
+    ) -> Result<()> {
+        match self.stack.path() {
+            [] => {
+                let name = item.dir_name_or_file_name();
+                client.find_add_with(
+                    &album_filters(self.match_mode, &self.match_mode.value(&name), album_artist_of(item)),
+                    options,
+                    context.add_position(),
+                )?;
+
+                status_info!("First songs of album '{name}' added to queue");
+                context.render()?;
+            }
+            _ => self.add(item, client, context)?,
+        }
+
+        Ok(())
+    }
+
     fn add_all(&self, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
         match self.stack.path() {
             [album] => {
-                client.find_add(&[Filter::new(Tag::Album, album.as_str())])?;
+                let album_artist = self.stack.previous().selected().and_then(album_artist_of);
+                self.match_mode.find_add(
+                    client,
+                    &album_filters(self.match_mode, &self.match_mode.value(album), album_artist),
+                    context.add_position(),
+                )?;
                 status_info!("Album '{}' added to queue", album);
 
                 context.render()?;
             }
             [] => {
-                client.add("/")?; // add the whole library
+                client.add("/", context.add_position())?; // add the whole library
                 status_info!("All albums added to queue");
 
                 context.render()?;
@@ -235,33 +336,32 @@ impl BrowserPane<DirOrSong> for AlbumsPane {
     fn prepare_preview(
         &mut self,
         client: &mut impl MpdClient,
-        config: &Config,
+        context: &AppContext,
     ) -> Result<Option<Vec<ListItem<'static>>>> {
-        self.stack()
-            .current()
-            .selected()
-            .map(DirStackItem::as_path)
-            .map_or(Ok(None), |current| -> Result<_> {
-                Ok(match self.stack.path() {
-                    [album] => Some(
-                        find_songs(client, album, current)?
-                            .first()
-                            .context(anyhow!(
-                                "Expected to find exactly one song: album: '{}', current: '{}'",
-                                album,
-                                current
-                            ))?
-                            .to_preview(&config.theme.symbols)
-                            .collect_vec(),
-                    ),
-                    [] => Some(
-                        list_titles(client, current)?
-                            .map(|v| v.to_list_item_simple(config))
-                            .collect_vec(),
-                    ),
-                    _ => None,
-                })
+        let config = context.config;
+        let parent_album_artist = self.stack.previous().selected().and_then(album_artist_of);
+        self.stack().current().selected().map_or(Ok(None), |item| -> Result<_> {
+            let current = item.as_path();
+            Ok(match self.stack.path() {
+                [album] => Some(
+                    find_songs(client, self.match_mode, album, parent_album_artist, current)?
+                        .first()
+                        .context(anyhow!(
+                            "Expected to find exactly one song: album: '{}', current: '{}'",
+                            album,
+                            current
+                        ))?
+                        .to_preview(&config.theme.symbols, config.theme.format_tag_separator)
+                        .collect_vec(),
+                ),
+                [] => Some(
+                    list_titles(client, self.match_mode, current, album_artist_of(item))?
+                        .map(|v| v.to_list_item_simple(config, &context.ratings))
+                        .collect_vec(),
+                ),
+                _ => None,
             })
+        })
     }
 
     fn browser_areas(&self) -> [Rect; 3] {
@@ -1,20 +1,27 @@
 use crate::{
-    config::Config,
+    config::{tabs::PaneType, BrowserSort, RightOnSong, SortOrder},
     context::AppContext,
     mpd::{
         commands::Song,
         errors::MpdError,
-        mpd_client::{Filter, MpdClient, Tag},
+        mpd_client::{Filter, FilterKind, FindOptions, MpdClient, Tag},
+    },
+    shared::{
+        ext::mpd_client::{MpdClientExt, SongAdd},
+        key_event::KeyEvent,
+        macros::status_info,
+        mouse_event::MouseEvent,
     },
-    shared::{ext::mpd_client::MpdClientExt, key_event::KeyEvent, macros::status_info, mouse_event::MouseEvent},
     ui::{
-        browser::BrowserPane,
-        dirstack::{DirStack, DirStackItem},
+        browser::{album_dirs, escape_regex, sort_by_earliest_date, BrowserPane, MatchMode},
+        dirstack::{Dir, DirStack, DirStackItem},
         widgets::browser::Browser,
         UiEvent,
     },
 };
 
+use std::borrow::Cow;
+
 use super::{browser::DirOrSong, Pane};
 use anyhow::{anyhow, Context, Result};
 use itertools::Itertools;
@@ -36,16 +43,26 @@ pub struct ArtistsPane {
     mode: ArtistsPaneMode,
     browser: Browser<DirOrSong>,
     initialized: bool,
+    split_separator: Option<&'static str>,
+    sort: BrowserSort,
+    match_mode: MatchMode,
 }
 
 impl ArtistsPane {
     pub fn new(mode: ArtistsPaneMode, context: &AppContext) -> Self {
+        let pane = match mode {
+            ArtistsPaneMode::AlbumArtist => PaneType::AlbumArtists,
+            ArtistsPaneMode::Artist => PaneType::Artists,
+        };
         Self {
             mode,
             stack: DirStack::default(),
             filter_input_mode: false,
-            browser: Browser::new(context.config),
+            browser: Browser::new(context.config, pane),
             initialized: false,
+            split_separator: context.config.artist_split_separator,
+            sort: context.config.browser_sort,
+            match_mode: MatchMode::default(),
         }
     }
 
@@ -56,14 +73,103 @@ impl ArtistsPane {
         }
     }
 
+    fn list_artists(&self, client: &mut impl MpdClient) -> Result<Vec<DirOrSong>, MpdError> {
+        Ok(match self.sort.artists {
+            SortOrder::Name => self.artist_dirs(client.list_tag(self.artist_tag(), None)?.0),
+            SortOrder::Date => {
+                let artists = client.list_tag_grouped(self.artist_tag(), &[Tag::Date], None)?;
+                let pairs = artists.iter().flat_map(|(groups, value)| {
+                    let date = groups.first().map_or("", String::as_str);
+                    match self.split_separator {
+                        Some(separator) => value
+                            .split(separator)
+                            .map(str::trim)
+                            .filter(|v| !v.is_empty())
+                            .map(|v| (date, v))
+                            .collect_vec(),
+                        None => vec![(date, value.as_str())],
+                    }
+                });
+                sort_by_earliest_date(pairs)
+                    .into_iter()
+                    .map(|v| DirOrSong::Dir {
+                        full_path: String::new(),
+                        name: v.to_owned(),
+                        year: None,
+                        album_artist: None,
+                    })
+                    .collect_vec()
+            }
+        })
+    }
+
+    /// Turns the listed artist tag values into dirs, splitting each of them into separate
+    /// artists if configured to do so.
+    fn artist_dirs(&self, values: Vec<String>) -> Vec<DirOrSong> {
+        let names = match self.split_separator {
+            Some(separator) => values
+                .iter()
+                .flat_map(|v| v.split(separator))
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .sorted()
+                .dedup()
+                .map(str::to_owned)
+                .collect_vec(),
+            None => values,
+        };
+
+        names
+            .into_iter()
+            .map(|v| DirOrSong::Dir {
+                full_path: String::new(),
+                name: v,
+                year: None,
+                album_artist: None,
+            })
+            .collect_vec()
+    }
+
+    /// Value to filter the artist tag by. A split artist can be just a part of the tag's value
+    /// so it is matched as a regex between the separators.
+    fn artist_filter_value<'a>(&self, artist: &'a str) -> Cow<'a, str> {
+        match (self.match_mode, self.split_separator) {
+            (MatchMode::Substring, _) => self.match_mode.value(artist),
+            (MatchMode::Exact, Some(separator)) => {
+                let separator = escape_regex(separator);
+                Cow::Owned(format!(r"(^|{separator})\s*{}\s*($|{separator})", escape_regex(artist)))
+            }
+            (MatchMode::Exact, None) => Cow::Borrowed(artist),
+        }
+    }
+
+    fn artist_filter<'value>(&self, value: &'value str) -> Filter<'_, 'value> {
+        Filter::new_with_kind(
+            self.artist_tag(),
+            value,
+            if self.split_separator.is_some() {
+                FilterKind::Regex
+            } else {
+                self.match_mode.kind()
+            },
+        )
+    }
+
+    fn album_filter<'value>(&self, value: &'value str) -> Filter<'static, 'value> {
+        Filter::new_with_kind(Tag::Album, value, self.match_mode.kind())
+    }
+
     fn list_titles(
         &self,
         client: &mut impl MpdClient,
         artist: &str,
         album: &str,
     ) -> Result<impl Iterator<Item = DirOrSong>, MpdError> {
-        Ok(client
-            .find(&[Filter::new(self.artist_tag(), artist), Filter::new(Tag::Album, album)])?
+        let artist = self.artist_filter_value(artist);
+        let album = self.match_mode.value(album);
+        Ok(self
+            .match_mode
+            .find(client, &[self.artist_filter(&artist), self.album_filter(&album)])?
             .into_iter()
             .map(DirOrSong::Song)
             .sorted())
@@ -74,14 +180,9 @@ impl ArtistsPane {
         client: &mut impl MpdClient,
         artist: &str,
     ) -> Result<impl Iterator<Item = DirOrSong>, MpdError> {
-        Ok(client
-            .list_tag(Tag::Album, Some(&[Filter::new(self.artist_tag(), artist)]))?
-            .into_iter()
-            .map(|v| DirOrSong::Dir {
-                full_path: String::new(),
-                name: v,
-            })
-            .sorted())
+        let artist = self.artist_filter_value(artist);
+        let pairs = client.list_tag_grouped(Tag::Album, &[Tag::Date], Some(&[self.artist_filter(&artist)]))?;
+        Ok(album_dirs(&pairs, self.sort.albums).into_iter())
     }
 
     fn find_songs(
@@ -91,12 +192,17 @@ impl ArtistsPane {
         album: &str,
         file: &str,
     ) -> Result<Vec<Song>, MpdError> {
-        client
-            .find(&[
-                Filter::new(Tag::File, file),
-                Filter::new(self.artist_tag(), artist),
-                Filter::new(Tag::Album, album),
-            ])
+        let artist = self.artist_filter_value(artist);
+        let album = self.match_mode.value(album);
+        self.match_mode
+            .find(
+                client,
+                &[
+                    Filter::new(Tag::File, file),
+                    self.artist_filter(&artist),
+                    self.album_filter(&album),
+                ],
+            )
             .map(|mut v| {
                 v.sort();
                 v
@@ -110,7 +216,7 @@ impl ArtistsPane {
         };
 
         match self.stack.path() {
-            [_artist, _album] => {
+            [_, _] => {
                 self.add(current, client, context)?;
                 if autoplay {
                     client.play_last(context)?;
@@ -137,9 +243,11 @@ impl ArtistsPane {
 }
 
 impl Pane for ArtistsPane {
-    fn render(&mut self, frame: &mut Frame, area: Rect, _context: &AppContext) -> Result<()> {
+    fn render(&mut self, frame: &mut Frame, area: Rect, context: &AppContext) -> Result<()> {
         self.browser
             .set_filter_input_active(self.filter_input_mode)
+            .set_current_item_style(context.current_item_style())
+            .set_ratings(&context.ratings)
             .render(area, frame.buffer_mut(), &mut self.stack);
 
         Ok(())
@@ -147,20 +255,9 @@ impl Pane for ArtistsPane {
 
     fn before_show(&mut self, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
         if !self.initialized {
-            let result = client
-                .list_tag(self.artist_tag(), None)
-                .context("Cannot list artists")?;
-            self.stack = DirStack::new(
-                result
-                    .into_iter()
-                    .map(|v| DirOrSong::Dir {
-                        full_path: String::new(),
-                        name: v,
-                    })
-                    .collect::<Vec<_>>(),
-            );
+            self.stack = DirStack::new(self.list_artists(client).context("Cannot list artists")?);
             let preview = self
-                .prepare_preview(client, context.config)
+                .prepare_preview(client, context)
                 .context("Cannot prepare preview")?;
             self.stack.set_preview(preview);
             self.initialized = true;
@@ -171,20 +268,9 @@ impl Pane for ArtistsPane {
 
     fn on_event(&mut self, event: &mut UiEvent, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
         if let crate::ui::UiEvent::Database = event {
-            let result = client
-                .list_tag(self.artist_tag(), None)
-                .context("Cannot list artists")?;
-            self.stack = DirStack::new(
-                result
-                    .into_iter()
-                    .map(|v| DirOrSong::Dir {
-                        full_path: String::new(),
-                        name: v,
-                    })
-                    .collect::<Vec<_>>(),
-            );
+            self.stack = DirStack::new(self.list_artists(client).context("Cannot list artists")?);
             let preview = self
-                .prepare_preview(client, context.config)
+                .prepare_preview(client, context)
                 .context("Cannot prepare preview")?;
             self.stack.set_preview(preview);
 
@@ -193,6 +279,10 @@ impl Pane for ArtistsPane {
         Ok(())
     }
 
+    fn on_database_update(&mut self, _context: &AppContext) {
+        self.initialized = false;
+    }
+
     fn handle_mouse_event(
         &mut self,
         event: MouseEvent,
@@ -202,7 +292,16 @@ impl Pane for ArtistsPane {
         self.handle_mouse_action(event, client, context)
     }
 
-    fn handle_action(&mut self, event: &mut KeyEvent, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
+    fn is_input_active(&self) -> bool {
+        self.filter_input_mode
+    }
+
+    fn handle_action(
+        &mut self,
+        event: &mut KeyEvent,
+        client: &mut impl MpdClient,
+        context: &mut AppContext,
+    ) -> Result<()> {
         self.handle_filter_input(event, client, context)?;
         self.handle_common_action(event, client, context)?;
         self.handle_global_action(event, client, context)?;
@@ -229,9 +328,17 @@ impl BrowserPane<DirOrSong> for ArtistsPane {
 
     fn list_songs_in_item(&self, client: &mut impl MpdClient, item: &DirOrSong) -> Result<Vec<Song>> {
         Ok(match item {
-            DirOrSong::Dir { name, full_path: _ } => match self.stack().path() {
-                [artist] => client.find(&[Filter::new(Tag::Album, name), Filter::new(self.artist_tag(), artist)])?,
-                [] => client.find(&[Filter::new(self.artist_tag(), name)])?,
+            DirOrSong::Dir { name, .. } => match self.stack().path() {
+                [artist] => self.match_mode.find(
+                    client,
+                    &[
+                        self.album_filter(&self.match_mode.value(name)),
+                        self.artist_filter(&self.artist_filter_value(artist)),
+                    ],
+                )?,
+                [] => self
+                    .match_mode
+                    .find(client, &[self.artist_filter(&self.artist_filter_value(name))])?,
                 _ => Vec::new(),
             },
             DirOrSong::Song(song) => vec![song.clone()],
@@ -240,29 +347,33 @@ impl BrowserPane<DirOrSong> for ArtistsPane {
 
     fn add(&self, item: &DirOrSong, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
         match self.stack.path() {
-            [artist, album] => {
-                client.find_add(&[
-                    Filter::new(self.artist_tag(), artist.as_str()),
-                    Filter::new(Tag::Album, album.as_str()),
-                    Filter::new(Tag::File, &item.dir_name_or_file_name()),
-                ])?;
-
-                status_info!("'{}' added to queue", item.dir_name_or_file_name());
+            [_, _] => {
+                if client.add_song(&item.dir_name_or_file_name(), context)? == SongAdd::Added {
+                    status_info!("'{}' added to queue", item.dir_name_or_file_name());
+                }
 
                 context.render()?;
             }
             [artist] => {
-                client.find_add(&[
-                    Filter::new(self.artist_tag(), artist.as_str()),
-                    Filter::new(Tag::Album, &item.dir_name_or_file_name()),
-                ])?;
+                self.match_mode.find_add(
+                    client,
+                    &[
+                        self.artist_filter(&self.artist_filter_value(artist)),
+                        self.album_filter(&self.match_mode.value(&item.dir_name_or_file_name())),
+                    ],
+                    context.add_position(),
+                )?;
 
                 status_info!("Album '{}' by '{artist}' added to queue", item.dir_name_or_file_name());
 
                 context.render()?;
             }
             [] => {
-                client.find_add(&[Filter::new(self.artist_tag(), &item.dir_name_or_file_name())])?;
+                self.match_mode.find_add(
+                    client,
+                    &[self.artist_filter(&self.artist_filter_value(&item.dir_name_or_file_name()))],
+                    context.add_position(),
+                )?;
 
                 status_info!("All songs by '{}' added to queue", item.dir_name_or_file_name());
             }
@@ -272,27 +383,73 @@ impl BrowserPane<DirOrSong> for ArtistsPane {
         Ok(())
     }
 
+    fn add_first(
+        &self,
+        item: &DirOrSong,
+        options: FindOptions<'_>,
+        client: &mut impl MpdClient,
+        context: &AppContext,
+    ) -> Result<()> {
+        let name = item.dir_name_or_file_name();
+        match self.stack.path() {
+            [artist] => {
+                client.find_add_with(
+                    &[
+                        self.artist_filter(&self.artist_filter_value(artist)),
+                        self.album_filter(&self.match_mode.value(&name)),
+                    ],
+                    options,
+                    context.add_position(),
+                )?;
+
+                status_info!("First songs of album '{name}' by '{artist}' added to queue");
+                context.render()?;
+            }
+            [] => {
+                client.find_add_with(
+                    &[self.artist_filter(&self.artist_filter_value(&name))],
+                    options,
+                    context.add_position(),
+                )?;
+
+                status_info!("First songs by '{name}' added to queue");
+                context.render()?;
+            }
+            _ => self.add(item, client, context)?,
+        }
+
+        Ok(())
+    }
+
     fn add_all(&self, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
         match self.stack.path() {
             [artist, album] => {
-                client.find_add(&[
-                    Filter::new(self.artist_tag(), artist.as_str()),
-                    Filter::new(Tag::Album, album.as_str()),
-                ])?;
+                self.match_mode.find_add(
+                    client,
+                    &[
+                        self.artist_filter(&self.artist_filter_value(artist)),
+                        self.album_filter(&self.match_mode.value(album)),
+                    ],
+                    context.add_position(),
+                )?;
 
                 status_info!("Album '{album}' by '{artist}' added to queue");
 
                 context.render()?;
             }
             [artist] => {
-                client.find_add(&[Filter::new(self.artist_tag(), artist.as_str())])?;
+                self.match_mode.find_add(
+                    client,
+                    &[self.artist_filter(&self.artist_filter_value(artist))],
+                    context.add_position(),
+                )?;
 
                 status_info!("All albums by '{artist}' added to queue");
 
                 context.render()?;
             }
             [] => {
-                client.add("/")?; // add the whole library
+                client.add("/", context.add_position())?; // add the whole library
                 status_info!("All songs added to queue");
             }
             _ => {}
@@ -304,15 +461,41 @@ impl BrowserPane<DirOrSong> for ArtistsPane {
         self.open_or_play(true, client, context)
     }
 
+    fn toggle_match_mode(&mut self, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
+        self.match_mode = self.match_mode.toggled();
+
+        let items: Option<Vec<_>> = match self.stack.path() {
+            [artist, album] => Some(self.list_titles(client, artist, album)?.collect()),
+            [artist] => Some(self.list_albums(client, artist)?.collect()),
+            _ => None,
+        };
+        if let Some(items) = items {
+            let filter = self.stack.current().filter().map(ToOwned::to_owned);
+            let mut dir = Dir::new(items);
+            dir.set_filter(filter, context.config);
+            *self.stack.current_mut() = dir;
+        }
+        let preview = self
+            .prepare_preview(client, context)
+            .context("Cannot prepare preview")?;
+        self.stack.set_preview(preview);
+
+        status_info!("Using {} matching", self.match_mode.description());
+        context.render()?;
+
+        Ok(())
+    }
+
     fn next(&mut self, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
-        self.open_or_play(false, client, context)
+        self.open_or_play(context.config.right_on_song == RightOnSong::AddAndPlay, client, context)
     }
 
     fn prepare_preview(
         &mut self,
         client: &mut impl MpdClient,
-        config: &Config,
+        context: &AppContext,
     ) -> Result<Option<Vec<ListItem<'static>>>> {
+        let config = context.config;
         self.stack
             .current()
             .selected()
@@ -328,17 +511,17 @@ impl BrowserPane<DirOrSong> for ArtistsPane {
                                 album,
                                 current
                             ))?
-                            .to_preview(&config.theme.symbols)
+                            .to_preview(&config.theme.symbols, config.theme.format_tag_separator)
                             .collect_vec(),
                     ),
                     [artist] => Some(
                         self.list_titles(client, artist, current)?
-                            .map(|s| s.to_list_item_simple(config))
+                            .map(|s| s.to_list_item_simple(config, &context.ratings))
                             .collect_vec(),
                     ),
                     [] => Some(
                         self.list_albums(client, current)?
-                            .map(|s| s.to_list_item_simple(config))
+                            .map(|s| s.to_list_item_simple(config, &context.ratings))
                             .collect_vec(),
                     ),
                     _ => None,
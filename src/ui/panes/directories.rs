@@ -7,13 +7,18 @@ use ratatui::{
 };
 
 use crate::{
-    config::Config,
+    config::{tabs::PaneType, RightOnSong},
     context::AppContext,
     mpd::{
-        commands::{lsinfo::FileOrDir, Song},
+        commands::Song,
         mpd_client::{Filter, FilterKind, MpdClient, Tag},
     },
-    shared::{ext::mpd_client::MpdClientExt, key_event::KeyEvent, macros::status_info, mouse_event::MouseEvent},
+    shared::{
+        ext::mpd_client::{MpdClientExt, SongAdd},
+        key_event::KeyEvent,
+        macros::status_info,
+        mouse_event::MouseEvent,
+    },
     ui::{
         browser::BrowserPane,
         dirstack::{DirStack, DirStackItem},
@@ -37,7 +42,7 @@ impl DirectoriesPane {
         Self {
             stack: DirStack::default(),
             filter_input_mode: false,
-            browser: Browser::new(context.config),
+            browser: Browser::new(context.config, PaneType::Directories),
             initialized: false,
         }
     }
@@ -57,13 +62,7 @@ impl DirectoriesPane {
                 let new_current = client.lsinfo(Some(next_path.join("/").to_string().as_str()))?;
                 let res = new_current
                     .into_iter()
-                    .map(|v| match v {
-                        FileOrDir::Dir(d) => DirOrSong::Dir {
-                            name: d.path,
-                            full_path: d.full_path,
-                        },
-                        FileOrDir::File(s) => DirOrSong::Song(s),
-                    })
+                    .filter_map(DirOrSong::from_lsinfo)
                     .sorted()
                     .collect();
                 self.stack.push(res);
@@ -83,9 +82,11 @@ impl DirectoriesPane {
 }
 
 impl Pane for DirectoriesPane {
-    fn render(&mut self, frame: &mut Frame, area: Rect, _context: &AppContext) -> anyhow::Result<()> {
+    fn render(&mut self, frame: &mut Frame, area: Rect, context: &AppContext) -> anyhow::Result<()> {
         self.browser
             .set_filter_input_active(self.filter_input_mode)
+            .set_current_item_style(context.current_item_style())
+            .set_ratings(&context.ratings)
             .render(area, frame.buffer_mut(), &mut self.stack);
 
         Ok(())
@@ -97,11 +98,11 @@ impl Pane for DirectoriesPane {
                 client
                     .lsinfo(None)?
                     .into_iter()
-                    .map(Into::<DirOrSong>::into)
+                    .filter_map(DirOrSong::from_lsinfo)
                     .sorted()
                     .collect::<Vec<_>>(),
             );
-            let preview = self.prepare_preview(client, context.config)?;
+            let preview = self.prepare_preview(client, context)?;
             self.stack.set_preview(preview);
             self.initialized = true;
         }
@@ -115,10 +116,10 @@ impl Pane for DirectoriesPane {
                 client
                     .lsinfo(None)?
                     .into_iter()
-                    .map(Into::<DirOrSong>::into)
+                    .filter_map(DirOrSong::from_lsinfo)
                     .collect::<Vec<_>>(),
             );
-            let preview = self.prepare_preview(client, context.config)?;
+            let preview = self.prepare_preview(client, context)?;
             self.stack.set_preview(preview);
 
             context.render()?;
@@ -126,6 +127,10 @@ impl Pane for DirectoriesPane {
         Ok(())
     }
 
+    fn on_database_update(&mut self, _context: &AppContext) {
+        self.initialized = false;
+    }
+
     fn handle_mouse_event(
         &mut self,
         event: MouseEvent,
@@ -135,7 +140,16 @@ impl Pane for DirectoriesPane {
         self.handle_mouse_action(event, client, context)
     }
 
-    fn handle_action(&mut self, event: &mut KeyEvent, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
+    fn is_input_active(&self) -> bool {
+        self.filter_input_mode
+    }
+
+    fn handle_action(
+        &mut self,
+        event: &mut KeyEvent,
+        client: &mut impl MpdClient,
+        context: &mut AppContext,
+    ) -> Result<()> {
         self.handle_filter_input(event, client, context)?;
         self.handle_common_action(event, client, context)?;
         self.handle_global_action(event, client, context)?;
@@ -171,21 +185,25 @@ impl BrowserPane<DirOrSong> for DirectoriesPane {
 
     fn add(&self, item: &DirOrSong, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
         match item {
-            DirOrSong::Dir {
-                name: dirname,
-                full_path: _,
-            } => {
+            DirOrSong::Dir { name: dirname, .. } => {
                 let mut next_path = self.stack.path().to_vec();
                 next_path.push(dirname.clone());
                 let next_path = next_path.join(std::path::MAIN_SEPARATOR_STR).to_string();
 
-                client.add(&next_path)?;
+                client.add(&next_path, context.add_position())?;
                 status_info!("Directory '{next_path}' added to queue");
             }
             DirOrSong::Song(song) => {
-                client.add(&song.file)?;
+                if client.add_song(&song.file, context)? != SongAdd::Added {
+                    return Ok(());
+                }
                 if let Ok(Some(song)) = client.find_one(&[Filter::new(Tag::File, &song.file)]) {
-                    status_info!("'{}' by '{}' added to queue", song.title_str(), song.artist_str());
+                    let separator = context.config.theme.format_tag_separator;
+                    status_info!(
+                        "'{}' by '{}' added to queue",
+                        song.title_str(separator),
+                        song.artist_str(separator)
+                    );
                 }
             }
         };
@@ -197,7 +215,7 @@ impl BrowserPane<DirOrSong> for DirectoriesPane {
 
     fn add_all(&self, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
         let path = self.stack().path().join(std::path::MAIN_SEPARATOR_STR);
-        client.add(&path)?;
+        client.add(&path, context.add_position())?;
         status_info!("Directory '{path}' added to queue");
 
         context.render()?;
@@ -210,14 +228,15 @@ impl BrowserPane<DirOrSong> for DirectoriesPane {
     }
 
     fn next(&mut self, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
-        self.open_or_play(false, client, context)
+        self.open_or_play(context.config.right_on_song == RightOnSong::AddAndPlay, client, context)
     }
 
     fn prepare_preview(
         &mut self,
         client: &mut impl MpdClient,
-        config: &Config,
+        context: &AppContext,
     ) -> Result<Option<Vec<ListItem<'static>>>> {
+        let config = context.config;
         match &self.stack.current().selected() {
             Some(DirOrSong::Dir { .. }) => {
                 let Some(next_path) = self.stack.next_path() else {
@@ -233,21 +252,16 @@ impl BrowserPane<DirOrSong> for DirectoriesPane {
                 }
                 .0
                 .into_iter()
-                .map(|v| match v {
-                    FileOrDir::Dir(dir) => DirOrSong::Dir {
-                        name: dir.path,
-                        full_path: dir.full_path,
-                    },
-                    FileOrDir::File(song) => DirOrSong::Song(song),
-                })
+                .filter_map(DirOrSong::from_lsinfo)
                 .sorted()
-                .map(|v| v.to_list_item_simple(config))
+                .map(|v| v.to_list_item_simple(config, &context.ratings))
                 .collect();
                 Ok(Some(res))
             }
-            Some(DirOrSong::Song(song)) => Ok(client
-                .find_one(&[Filter::new(Tag::File, &song.file)])?
-                .map(|v| v.to_preview(&config.theme.symbols).collect())),
+            Some(DirOrSong::Song(song)) => Ok(client.find_one(&[Filter::new(Tag::File, &song.file)])?.map(|v| {
+                v.to_preview(&config.theme.symbols, config.theme.format_tag_separator)
+                    .collect()
+            })),
             None => Ok(None),
         }
     }
@@ -255,3 +269,142 @@ impl BrowserPane<DirOrSong> for DirectoriesPane {
         self.browser.areas
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use itertools::Itertools;
+    use rstest::rstest;
+
+    use super::DirectoriesPane;
+    use crate::{
+        config::{Config, Leak, RightOnSong},
+        context::AppContext,
+        mpd::commands::{lsinfo::LsInfo, State},
+        tests::fixtures::{
+            app_context,
+            mpd_client::{client, TestMpdClient},
+        },
+        ui::{
+            dirstack::{DirStack, DirStackItem},
+            panes::{browser::DirOrSong, Pane},
+        },
+    };
+
+    fn screen_with_lsinfo(lines: &[&str], app_context: &AppContext) -> DirectoriesPane {
+        let lsinfo = LsInfo::from_lines(lines).unwrap();
+        let mut screen = DirectoriesPane::new(app_context);
+        screen.stack = DirStack::new(lsinfo.into_iter().filter_map(DirOrSong::from_lsinfo).collect());
+        screen.initialized = true;
+        screen
+    }
+
+    fn right() -> crate::shared::key_event::KeyEvent {
+        KeyEvent::new(KeyCode::Char('l'), KeyModifiers::NONE).into()
+    }
+
+    #[rstest]
+    #[case(RightOnSong::Add)]
+    #[case(RightOnSong::AddAndPlay)]
+    fn right_on_directory_always_descends(
+        #[case] right_on_song: RightOnSong,
+        mut client: TestMpdClient,
+        mut app_context: AppContext,
+    ) {
+        app_context.config = Config {
+            right_on_song,
+            ..app_context.config.clone()
+        }
+        .leak();
+        let mut screen = screen_with_lsinfo(
+            &["directory: rock", "Last-Modified: 2024-01-01T00:00:00Z"],
+            &app_context,
+        );
+
+        screen
+            .handle_action(&mut right(), &mut client, &mut app_context)
+            .unwrap();
+
+        assert_eq!(screen.stack.path(), ["rock"]);
+        assert_eq!(client.calls.get("lsinfo Some(\"rock\")"), Some(&1));
+        assert_eq!(client.calls.get("add"), None);
+    }
+
+    #[rstest]
+    fn skips_playlist_files(app_context: AppContext) {
+        let screen = screen_with_lsinfo(
+            &[
+                "directory: rock",
+                "Last-Modified: 2024-01-01T00:00:00Z",
+                "playlist: favourites.m3u",
+                "Last-Modified: 2024-01-01T00:00:00Z",
+            ],
+            &app_context,
+        );
+
+        let items = screen
+            .stack
+            .current()
+            .items
+            .iter()
+            .map(DirStackItem::as_path)
+            .collect_vec();
+        assert_eq!(items, ["rock"]);
+    }
+
+    #[rstest]
+    #[case(RightOnSong::Add, State::Stop)]
+    #[case(RightOnSong::AddAndPlay, State::Play)]
+    fn right_on_file_performs_configured_add(
+        #[case] right_on_song: RightOnSong,
+        #[case] expected_state: State,
+        mut client: TestMpdClient,
+        mut app_context: AppContext,
+    ) {
+        app_context.config = Config {
+            right_on_song,
+            ..app_context.config.clone()
+        }
+        .leak();
+        client.queue = vec![0];
+        let mut screen = screen_with_lsinfo(&["file: artist_1_album_2_file_3", "Title: Three"], &app_context);
+
+        screen
+            .handle_action(&mut right(), &mut client, &mut app_context)
+            .unwrap();
+
+        assert!(screen.stack.path().is_empty());
+        assert_eq!(client.calls.get("add"), Some(&1));
+        assert_eq!(client.status.state, expected_state);
+    }
+
+    #[rstest]
+    #[case(false, [0, 0])]
+    #[case(true, [1, 1])]
+    fn add_advances_to_next_item_when_enabled(
+        #[case] advance_after_add: bool,
+        #[case] expected_selected: [usize; 2],
+        mut client: TestMpdClient,
+        mut app_context: AppContext,
+    ) {
+        app_context.config = Config {
+            advance_after_add,
+            ..app_context.config.clone()
+        }
+        .leak();
+        let mut screen = screen_with_lsinfo(
+            &["file: artist_1_album_2_file_3", "file: artist_1_album_2_file_4"],
+            &app_context,
+        );
+        let add = || KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE).into();
+
+        screen.handle_action(&mut add(), &mut client, &mut app_context).unwrap();
+        let first = screen.stack.current().selected_with_idx().map(|(idx, _)| idx);
+        screen.handle_action(&mut add(), &mut client, &mut app_context).unwrap();
+        let second = screen.stack.current().selected_with_idx().map(|(idx, _)| idx);
+
+        assert_eq!([first, second], expected_selected.map(Some));
+        assert_eq!(client.calls.get("add"), Some(&2));
+    }
+}
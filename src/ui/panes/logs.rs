@@ -4,6 +4,8 @@ use anyhow::Result;
 use itertools::Itertools;
 use ratatui::{
     prelude::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
     widgets::{List, ListState},
     Frame,
 };
@@ -14,16 +16,17 @@ use crate::{
     mpd::mpd_client::MpdClient,
     shared::{
         key_event::KeyEvent,
+        logging::LogEntry,
         mouse_event::{MouseEvent, MouseEventKind},
     },
-    ui::{dirstack::DirState, UiEvent},
+    ui::{dirstack::DirState, Level, UiEvent},
 };
 
 use super::Pane;
 
 #[derive(Debug)]
 pub struct LogsPane {
-    logs: VecDeque<Vec<u8>>,
+    logs: VecDeque<LogEntry>,
     scrolling_state: DirState<ListState>,
     logs_area: Rect,
     should_scroll_to_last: bool,
@@ -45,19 +48,49 @@ impl LogsPane {
 const INDENT_LEN: usize = 4;
 const INDENT: &str = "    ";
 
+/// Splits the first wrapped line of an entry into the timestamp, the level and the rest so that the level can be
+/// colored. Falls back to a plain line if the header did not fit on it.
+fn header_line(line: &str, timestamp: &str, level: &str, level_style: Style) -> Line<'static> {
+    let rest = line
+        .strip_prefix(timestamp)
+        .and_then(|rest| rest.strip_prefix(' '))
+        .and_then(|rest| rest.strip_prefix(level));
+
+    match rest {
+        Some(rest) => Line::from(vec![
+            Span::from(timestamp.to_owned()).dim(),
+            Span::from(" "),
+            Span::styled(level.to_owned(), level_style),
+            Span::from(rest.to_owned()),
+        ]),
+        None => Line::from(line.to_owned()),
+    }
+}
+
 impl Pane for LogsPane {
-    fn render(&mut self, frame: &mut Frame, area: Rect, AppContext { config, .. }: &AppContext) -> anyhow::Result<()> {
+    fn render(&mut self, frame: &mut Frame, area: Rect, context: &AppContext) -> anyhow::Result<()> {
+        let config = context.config;
         let max_line_width = (area.width as usize).saturating_sub(INDENT_LEN + 3);
-        let lines: Vec<_> = self.logs.iter().map(|l| String::from_utf8_lossy(l)).collect_vec();
-        let lines: Vec<_> = lines
+        let lines: Vec<_> = self
+            .logs
             .iter()
-            .flat_map(|l| {
-                let mut lines = textwrap::wrap(l, textwrap::Options::new(max_line_width));
-                lines
-                    .iter_mut()
-                    .skip(1)
-                    .for_each(|v| *v = std::borrow::Cow::Owned(textwrap::indent(v, INDENT)));
-                lines
+            .flat_map(|entry| {
+                let timestamp = entry.timestamp.format(config.log_timestamp_format).to_string();
+                let level = format!("{:<5}", entry.level);
+                let level_style = Style::default().fg((&Level::from(entry.level)).into());
+                let text = format!("{timestamp} {level} {} {}", entry.target, entry.message);
+
+                textwrap::wrap(&text, textwrap::Options::new(max_line_width))
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, line)| {
+                        if idx == 0 {
+                            header_line(line, &timestamp, &level, level_style)
+                        } else {
+                            Line::from(textwrap::indent(line, INDENT))
+                        }
+                    })
+                    .collect_vec()
             })
             .collect();
 
@@ -71,7 +104,7 @@ impl Pane for LogsPane {
 
         let logs_wg = List::new(lines)
             .style(config.as_text_style())
-            .highlight_style(config.theme.current_item_style);
+            .highlight_style(context.current_item_style());
         frame.render_stateful_widget(
             config.as_styled_scrollbar(),
             area,
@@ -92,7 +125,7 @@ impl Pane for LogsPane {
 
     fn on_event(&mut self, event: &mut UiEvent, _client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
         if let UiEvent::LogAdded(msg) = event {
-            self.logs.push_back(std::mem::take(msg));
+            self.logs.push_back(msg.clone());
             if self.logs.len() > 1000 {
                 self.logs.pop_front();
             }
@@ -134,7 +167,7 @@ impl Pane for LogsPane {
         &mut self,
         event: &mut KeyEvent,
         _client: &mut impl MpdClient,
-        context: &AppContext,
+        context: &mut AppContext,
     ) -> Result<()> {
         let config = context.config;
         if let Some(action) = event.as_logs_action(context) {
@@ -202,6 +235,9 @@ impl Pane for LogsPane {
                 CommonAction::PaneUp => {}
                 CommonAction::PaneRight => {}
                 CommonAction::PaneLeft => {}
+                CommonAction::ToggleMatchMode => {}
+                CommonAction::RateUp => {}
+                CommonAction::RateDown => {}
             }
         }
 
@@ -1,7 +1,7 @@
 use anyhow::Result;
 use ratatui::{
-    layout::{Constraint, Layout, Rect},
-    style::Style,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Modifier, Style},
     text::Text,
     Frame,
 };
@@ -17,10 +17,26 @@ use super::Pane;
 
 #[derive(Debug)]
 pub struct LyricsPane {
-    current_lyrics: Option<Lrc>,
+    current_lyrics: Option<Lyrics>,
     initialized: bool,
 }
 
+#[derive(Debug)]
+enum Lyrics {
+    Synced(Lrc),
+    /// Embedded lyrics without timestamps, shown as they are
+    Plain(String),
+}
+
+impl From<&str> for Lyrics {
+    fn from(value: &str) -> Self {
+        match value.parse::<Lrc>() {
+            Ok(lrc) if !lrc.lines.is_empty() => Lyrics::Synced(lrc),
+            _ => Lyrics::Plain(value.to_owned()),
+        }
+    }
+}
+
 impl LyricsPane {
     pub fn new(_context: &AppContext) -> Self {
         Self {
@@ -28,23 +44,57 @@ impl LyricsPane {
             initialized: false,
         }
     }
+
+    /// Looks for a sidecar lrc file first and falls back to the lyrics embedded in the song's tags. Failing to read
+    /// the tags is treated as the song having no embedded lyrics.
+    fn load_lyrics(client: &mut impl MpdClient, context: &AppContext) -> Result<Option<Lyrics>> {
+        if let Some(lrc) = context.find_lrc()? {
+            return Ok(Some(Lyrics::Synced(lrc)));
+        }
+
+        let Some((_, song)) = context.find_current_song_in_queue() else {
+            return Ok(None);
+        };
+
+        match client.read_comments(&song.file) {
+            Ok(comments) => Ok(comments.lyrics().map(Lyrics::from)),
+            Err(err) => {
+                log::warn!(err:?, file = song.file.as_str(); "Failed to read embedded lyrics");
+                Ok(None)
+            }
+        }
+    }
 }
 
 impl Pane for LyricsPane {
     fn render(&mut self, frame: &mut Frame, area: Rect, context: &AppContext) -> Result<()> {
-        let Some(lrc) = &self.current_lyrics else { return Ok(()) };
-
-        let elapsed = context.status.elapsed;
-        let Some((current_line_idx, _)) = lrc
-            .lines
-            .iter()
-            .enumerate()
-            .filter(|line| line.1.time > elapsed)
-            .min_by(|a, b| a.1.time.abs_diff(elapsed).cmp(&b.1.time.abs_diff(elapsed)))
-        else {
-            return Ok(());
+        let lrc = match &self.current_lyrics {
+            Some(Lyrics::Synced(lrc)) if !lrc.lines.is_empty() => lrc,
+            Some(Lyrics::Plain(lyrics)) => {
+                frame.render_widget(
+                    Text::from(lyrics.as_str())
+                        .centered()
+                        .style(Style::default().fg(context.config.theme.text_color.unwrap_or_default())),
+                    area,
+                );
+                return Ok(());
+            }
+            _ => {
+                let [placeholder_area] = Layout::vertical([Constraint::Length(1)]).flex(Flex::Center).areas(area);
+                frame.render_widget(
+                    Text::from("No lyrics found for the current song")
+                        .centered()
+                        .style(Style::default().fg(context.config.theme.text_color.unwrap_or_default())),
+                    placeholder_area,
+                );
+                return Ok(());
+            }
         };
 
+        // Before the first line starts it is shown in the middle without being highlighted
+        let active_line_idx = lrc.active_line_idx(context.status.elapsed);
+        let current_line_idx = active_line_idx.unwrap_or_default();
+
         let rows = area.height;
         let areas = Layout::vertical((0..rows).map(|_| Constraint::Length(1))).split(area);
         let middle_row = rows / 2;
@@ -58,25 +108,25 @@ impl Pane for LyricsPane {
                 continue;
             };
 
-            let darken = (middle_row as usize).abs_diff(i) > 0;
-
-            let p = Text::from(line.content.clone()).centered().style(if darken {
-                Style::default().fg(context.config.theme.text_color.unwrap_or_default())
-            } else {
+            let style = if active_line_idx == Some(idx) {
                 context.config.theme.highlighted_item_style
-            });
+            } else {
+                Style::default()
+                    .fg(context.config.theme.text_color.unwrap_or_default())
+                    .add_modifier(Modifier::DIM)
+            };
 
-            frame.render_widget(p, areas[i]);
+            frame.render_widget(Text::from(line.content.clone()).centered().style(style), areas[i]);
         }
 
         Ok(())
     }
 
-    fn before_show(&mut self, _client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
+    fn before_show(&mut self, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
         if !self.initialized {
-            match context.find_lrc() {
-                Ok(lrc) => {
-                    self.current_lyrics = lrc;
+            match Self::load_lyrics(client, context) {
+                Ok(lyrics) => {
+                    self.current_lyrics = lyrics;
                 }
                 Err(err) => {
                     status_error!("Failed to load lyrics file: '{err}'");
@@ -88,26 +138,29 @@ impl Pane for LyricsPane {
         Ok(())
     }
 
-    fn on_event(&mut self, event: &mut UiEvent, _client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
+    fn on_event(&mut self, event: &mut UiEvent, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
         match event {
-            UiEvent::SongChanged => match context.find_lrc() {
-                Ok(lrc) => {
-                    self.current_lyrics = lrc;
+            UiEvent::SongChanged => match Self::load_lyrics(client, context) {
+                Ok(lyrics) => {
+                    self.current_lyrics = lyrics;
                     context.render()?;
                 }
                 Err(err) => {
                     status_error!("Failed to load lyrics file: '{err}'");
                 }
             },
-            UiEvent::LyricsIndexed if self.current_lyrics.is_none() => match context.find_lrc() {
-                Ok(lrc) => {
-                    self.current_lyrics = lrc;
-                    context.render()?;
-                }
-                Err(err) => {
-                    status_error!("Failed to load lyrics file: '{err}'");
+            UiEvent::LyricsIndexed if !matches!(self.current_lyrics, Some(Lyrics::Synced(_))) => {
+                match context.find_lrc() {
+                    Ok(Some(lrc)) => {
+                        self.current_lyrics = Some(Lyrics::Synced(lrc));
+                        context.render()?;
+                    }
+                    Ok(None) => {}
+                    Err(err) => {
+                        status_error!("Failed to load lyrics file: '{err}'");
+                    }
                 }
-            },
+            }
             _ => {}
         }
         Ok(())
@@ -117,8 +170,103 @@ impl Pane for LyricsPane {
         &mut self,
         _event: &mut KeyEvent,
         _client: &mut impl MpdClient,
-        _context: &AppContext,
+        _context: &mut AppContext,
     ) -> Result<()> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::{collections::HashMap, path::PathBuf};
+
+    use rstest::rstest;
+
+    use super::{Lyrics, LyricsPane};
+    use crate::{
+        config::{Config, Leak},
+        context::AppContext,
+        mpd::commands::State,
+        tests::fixtures::{
+            app_context,
+            mpd_client::{client, TestMpdClient},
+        },
+    };
+
+    const FILE: &str = "artist_1_album_1_file_1";
+
+    struct TempDir(PathBuf);
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    /// Plays the song with the given file and looks for sidecar lyrics in a fresh music directory
+    fn playing(
+        mut app_context: AppContext,
+        client: &mut TestMpdClient,
+        file: &str,
+        name: &str,
+    ) -> (AppContext, TempDir) {
+        let dir = TempDir(std::env::temp_dir().join(format!("rmpc_lyrics_{}_{name}", std::process::id())));
+        std::fs::create_dir_all(&dir.0).unwrap();
+        app_context.config = Config {
+            music_directory: Some(dir.0.to_string_lossy().into_owned().leak()),
+            ..app_context.config.clone()
+        }
+        .leak();
+        let mut song = client.songs[1].clone();
+        song.file = file.to_owned();
+        app_context.queue = vec![song];
+        app_context.status.songid = Some(app_context.queue[0].id);
+        app_context.status.state = State::Play;
+        (app_context, dir)
+    }
+
+    fn embed(client: &mut TestMpdClient, lyrics: &str) {
+        client.comments.insert(
+            FILE.to_owned(),
+            HashMap::from([("lyrics".to_owned(), lyrics.to_owned())]),
+        );
+    }
+
+    #[rstest]
+    fn prefers_sidecar_lrc_over_embedded_lyrics(app_context: AppContext, mut client: TestMpdClient) {
+        let (app_context, dir) = playing(app_context, &mut client, FILE, "sidecar");
+        std::fs::write(dir.0.join(format!("{FILE}.lrc")), "[00:01.00]sidecar").unwrap();
+        embed(&mut client, "embedded");
+
+        let lyrics = LyricsPane::load_lyrics(&mut client, &app_context).unwrap();
+
+        assert!(matches!(lyrics, Some(Lyrics::Synced(lrc)) if lrc.lines[0].content == "sidecar"));
+    }
+
+    #[rstest]
+    fn falls_back_to_embedded_lyrics(app_context: AppContext, mut client: TestMpdClient) {
+        let (app_context, _dir) = playing(app_context, &mut client, FILE, "embedded");
+        embed(&mut client, "embedded");
+
+        let lyrics = LyricsPane::load_lyrics(&mut client, &app_context).unwrap();
+
+        assert!(matches!(lyrics, Some(Lyrics::Plain(text)) if text == "embedded"));
+    }
+
+    #[rstest]
+    #[case(FILE, "none")]
+    #[case("not_in_the_database", "unreadable")]
+    fn shows_placeholder_without_any_lyrics(
+        #[case] file: &str,
+        #[case] name: &str,
+        app_context: AppContext,
+        mut client: TestMpdClient,
+    ) {
+        let (app_context, _dir) = playing(app_context, &mut client, file, name);
+
+        let lyrics = LyricsPane::load_lyrics(&mut client, &app_context).unwrap();
+
+        assert!(lyrics.is_none());
+    }
+}
@@ -136,7 +136,21 @@ pub(super) trait Pane {
         Ok(())
     }
 
-    fn handle_action(&mut self, event: &mut KeyEvent, client: &mut impl MpdClient, context: &AppContext) -> Result<()>;
+    /// Ran instead of [`Pane::on_event`] when the database changes while the pane is not visible. Should discard
+    /// data loaded from the database so that it is fetched again in [`Pane::before_show`].
+    fn on_database_update(&mut self, context: &AppContext) {}
+
+    fn handle_action(
+        &mut self,
+        event: &mut KeyEvent,
+        client: &mut impl MpdClient,
+        context: &mut AppContext,
+    ) -> Result<()>;
+
+    /// Whether the pane currently takes text input and thus needs to receive keys bound to global actions
+    fn is_input_active(&self) -> bool {
+        false
+    }
 
     fn handle_mouse_event(
         &mut self,
@@ -161,11 +175,15 @@ pub(crate) mod browser {
 
     use crate::{
         config::theme::SymbolsConfig,
-        mpd::commands::{lsinfo::FileOrDir, Song},
+        mpd::commands::{current_song::MetadataTag, lsinfo::FileOrDir, Song},
     };
 
     impl Song {
-        pub(crate) fn to_preview(&self, _symbols: &SymbolsConfig) -> impl Iterator<Item = ListItem<'static>> {
+        pub(crate) fn to_preview(
+            &self,
+            _symbols: &SymbolsConfig,
+            tag_separator: &str,
+        ) -> impl Iterator<Item = ListItem<'static>> {
             let key_style = Style::default().fg(Color::Yellow);
             let separator = Span::from(": ");
             let start_of_line_spacer = Span::from(" ");
@@ -192,7 +210,7 @@ pub(crate) mod browser {
                     start_of_line_spacer.clone(),
                     Span::styled("Title", key_style),
                     separator.clone(),
-                    Span::from(title.clone()),
+                    Span::from(title.join(tag_separator).into_owned()),
                 ]));
             }
             if let Some(artist) = self.artist() {
@@ -200,7 +218,7 @@ pub(crate) mod browser {
                     start_of_line_spacer.clone(),
                     Span::styled("Artist", key_style),
                     separator.clone(),
-                    Span::from(artist.clone()),
+                    Span::from(artist.join(tag_separator).into_owned()),
                 ]));
             }
 
@@ -209,7 +227,7 @@ pub(crate) mod browser {
                     start_of_line_spacer.clone(),
                     Span::styled("Album", key_style),
                     separator.clone(),
-                    Span::from(album.clone()),
+                    Span::from(album.join(tag_separator).into_owned()),
                 ]));
             }
 
@@ -231,7 +249,7 @@ pub(crate) mod browser {
                     start_of_line_spacer.clone(),
                     Span::styled(k.clone(), key_style),
                     separator.clone(),
-                    Span::from(v.clone()),
+                    Span::from(v.join(tag_separator).into_owned()),
                 ]));
             }
 
@@ -241,14 +259,22 @@ pub(crate) mod browser {
 
     #[derive(Debug, Clone, PartialEq, Eq)]
     pub(crate) enum DirOrSong {
-        Dir { name: String, full_path: String },
+        Dir {
+            name: String,
+            full_path: String,
+            /// Release year shown before the name of album dirs
+            year: Option<String>,
+            /// Album artist of album dirs which are listed separately for each of their album
+            /// artists, an empty value stands for songs without the tag
+            album_artist: Option<String>,
+        },
         Song(Song),
     }
 
     impl DirOrSong {
         pub fn dir_name_or_file_name(&self) -> Cow<str> {
             match self {
-                DirOrSong::Dir { name, full_path: _ } => Cow::Borrowed(name),
+                DirOrSong::Dir { name, .. } => Cow::Borrowed(name),
                 DirOrSong::Song(song) => Cow::Borrowed(&song.file),
             }
         }
@@ -273,13 +299,16 @@ pub(crate) mod browser {
 
     impl std::cmp::Ord for Song {
         fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-            let a_track = self.metadata.get("track").map(|v| v.parse::<u32>());
-            let b_track = other.metadata.get("track").map(|v| v.parse::<u32>());
+            let a_track = self.metadata.get("track").map(|v| v.first().parse::<u32>());
+            let b_track = other.metadata.get("track").map(|v| v.first().parse::<u32>());
             match (a_track, b_track) {
                 (Some(Ok(a)), Some(Ok(b))) => a.cmp(&b),
                 (_, Some(Ok(_))) => Ordering::Greater,
                 (Some(Ok(_)), _) => Ordering::Less,
-                _ => self.title().cmp(&other.title()),
+                _ => self
+                    .title()
+                    .map(MetadataTag::first)
+                    .cmp(&other.title().map(MetadataTag::first)),
             }
         }
     }
@@ -290,13 +319,18 @@ pub(crate) mod browser {
         }
     }
 
-    impl From<FileOrDir> for DirOrSong {
-        fn from(value: FileOrDir) -> Self {
+    impl DirOrSong {
+        /// Converts an `lsinfo` entry, playlist files are skipped because they cannot be browsed like directories
+        pub(crate) fn from_lsinfo(value: FileOrDir) -> Option<Self> {
             match value {
-                FileOrDir::Dir(crate::mpd::commands::lsinfo::Dir { path, full_path, .. }) => {
-                    DirOrSong::Dir { name: path, full_path }
-                }
-                FileOrDir::File(song) => DirOrSong::Song(song),
+                FileOrDir::Dir(crate::mpd::commands::lsinfo::Dir { path, full_path, .. }) => Some(DirOrSong::Dir {
+                    year: None,
+                    album_artist: None,
+                    name: path,
+                    full_path,
+                }),
+                FileOrDir::File(song) => Some(DirOrSong::Song(song)),
+                FileOrDir::Playlist(_) => None,
             }
         }
     }
@@ -312,8 +346,11 @@ pub(crate) mod browser {
         fn song(title: &str, track: Option<&str>) -> Song {
             Song {
                 metadata: HashMap::from([
-                    ("title".to_owned(), title.to_owned()),
-                    track.map(|v| ("track".to_owned(), v.to_owned())).into_iter().collect(),
+                    ("title".to_owned(), title.to_owned().into()),
+                    track.map_or_else(
+                        || (String::new(), String::new().into()),
+                        |v| ("track".to_owned(), v.to_owned().into()),
+                    ),
                 ]),
                 ..Default::default()
             }
@@ -326,11 +363,15 @@ pub(crate) mod browser {
                 DirOrSong::Dir {
                     name: "a".to_owned(),
                     full_path: String::new(),
+                    year: None,
+                    album_artist: None,
                 },
                 DirOrSong::Song(Song::default()),
                 DirOrSong::Dir {
                     name: "z".to_owned(),
                     full_path: String::new(),
+                    year: None,
+                    album_artist: None,
                 },
                 DirOrSong::Song(Song::default()),
             ];
@@ -342,11 +383,15 @@ pub(crate) mod browser {
                 vec![
                     DirOrSong::Dir {
                         name: "a".to_owned(),
-                        full_path: String::new()
+                        full_path: String::new(),
+                        year: None,
+                        album_artist: None,
                     },
                     DirOrSong::Dir {
                         name: "z".to_owned(),
-                        full_path: String::new()
+                        full_path: String::new(),
+                        year: None,
+                        album_artist: None,
                     },
                     DirOrSong::Song(Song::default()),
                     DirOrSong::Song(Song::default()),
@@ -362,11 +407,15 @@ pub(crate) mod browser {
                 DirOrSong::Dir {
                     name: "a".to_owned(),
                     full_path: String::new(),
+                    year: None,
+                    album_artist: None,
                 },
                 DirOrSong::Song(song("b", Some("3"))),
                 DirOrSong::Dir {
                     name: "z".to_owned(),
                     full_path: String::new(),
+                    year: None,
+                    album_artist: None,
                 },
                 DirOrSong::Song(song("c", Some("5"))),
             ];
@@ -378,11 +427,15 @@ pub(crate) mod browser {
                 vec![
                     DirOrSong::Dir {
                         name: "a".to_owned(),
-                        full_path: String::new()
+                        full_path: String::new(),
+                        year: None,
+                        album_artist: None,
                     },
                     DirOrSong::Dir {
                         name: "z".to_owned(),
-                        full_path: String::new()
+                        full_path: String::new(),
+                        year: None,
+                        album_artist: None,
                     },
                     DirOrSong::Song(song("b", Some("3"))),
                     DirOrSong::Song(song("c", Some("5"))),
@@ -399,11 +452,15 @@ pub(crate) mod browser {
                 DirOrSong::Dir {
                     name: "a".to_owned(),
                     full_path: String::new(),
+                    year: None,
+                    album_artist: None,
                 },
                 DirOrSong::Song(song("b", Some("3"))),
                 DirOrSong::Dir {
                     name: "z".to_owned(),
                     full_path: String::new(),
+                    year: None,
+                    album_artist: None,
                 },
                 DirOrSong::Song(song("c", None)),
             ];
@@ -415,11 +472,15 @@ pub(crate) mod browser {
                 vec![
                     DirOrSong::Dir {
                         name: "a".to_owned(),
-                        full_path: String::new()
+                        full_path: String::new(),
+                        year: None,
+                        album_artist: None,
                     },
                     DirOrSong::Dir {
                         name: "z".to_owned(),
-                        full_path: String::new()
+                        full_path: String::new(),
+                        year: None,
+                        album_artist: None,
                     },
                     DirOrSong::Song(song("b", Some("3"))),
                     DirOrSong::Song(song("d", Some("10"))),
@@ -437,11 +498,15 @@ pub(crate) mod browser {
                 DirOrSong::Dir {
                     name: "a".to_owned(),
                     full_path: String::new(),
+                    year: None,
+                    album_artist: None,
                 },
                 DirOrSong::Song(song("b", Some("3"))),
                 DirOrSong::Dir {
                     name: "z".to_owned(),
                     full_path: String::new(),
+                    year: None,
+                    album_artist: None,
                 },
                 DirOrSong::Song(song("c", None)),
             ];
@@ -453,11 +518,15 @@ pub(crate) mod browser {
                 vec![
                     DirOrSong::Dir {
                         name: "a".to_owned(),
-                        full_path: String::new()
+                        full_path: String::new(),
+                        year: None,
+                        album_artist: None,
                     },
                     DirOrSong::Dir {
                         name: "z".to_owned(),
-                        full_path: String::new()
+                        full_path: String::new(),
+                        year: None,
+                        album_artist: None,
                     },
                     DirOrSong::Song(song("b", Some("3"))),
                     DirOrSong::Song(song("d", Some("10"))),
@@ -470,12 +539,12 @@ pub(crate) mod browser {
 }
 
 impl Song {
-    pub fn title_str(&self) -> &str {
-        self.title().map_or("Untitled", |v| v.as_str())
+    pub fn title_str(&self, separator: &str) -> Cow<'_, str> {
+        self.title().map_or(Cow::Borrowed("Untitled"), |v| v.join(separator))
     }
 
-    pub fn artist_str(&self) -> &str {
-        self.artist().map_or("Untitled", |v| v.as_str())
+    pub fn artist_str(&self, separator: &str) -> Cow<'_, str> {
+        self.artist().map_or(Cow::Borrowed("Untitled"), |v| v.join(separator))
     }
 
     pub fn file_name(&self) -> Option<Cow<str>> {
@@ -484,32 +553,37 @@ impl Song {
             .map(|file_name| file_name.to_string_lossy())
     }
 
-    fn format<'song>(&'song self, property: &SongProperty) -> Option<Cow<'song, str>> {
+    fn format<'song>(&'song self, property: &SongProperty, tag_separator: &str) -> Option<Cow<'song, str>> {
         match property {
             SongProperty::Filename => self.file_name(),
             SongProperty::File => Some(Cow::Borrowed(self.file.as_str())),
-            SongProperty::Title => self.title().map(|v| Cow::Borrowed(v.as_ref())),
-            SongProperty::Artist => self.artist().map(|v| Cow::Borrowed(v.as_ref())),
-            SongProperty::Album => self.album().map(|v| Cow::Borrowed(v.as_ref())),
-            SongProperty::Track => self
-                .metadata
-                .get("track")
-                .map(|v| Cow::Owned(v.parse::<u32>().map_or_else(|_| v.clone(), |v| format!("{v:0>2}")))),
+            SongProperty::Title => self.title().map(|v| v.join(tag_separator)),
+            SongProperty::Artist => self.artist().map(|v| v.join(tag_separator)),
+            SongProperty::Album => self.album().map(|v| v.join(tag_separator)),
+            SongProperty::Track => self.metadata.get("track").map(|v| {
+                Cow::Owned(
+                    v.first()
+                        .parse::<u32>()
+                        .map_or_else(|_| v.first().to_owned(), |v| format!("{v:0>2}")),
+                )
+            }),
             SongProperty::Duration => self.duration.map(|d| Cow::Owned(d.to_string())),
-            SongProperty::Other(name) => self.metadata.get(*name).map(|v| Cow::Borrowed(v.as_str())),
+            // Ratings are kept in a sticker rather than in the song, only the queue and browsers look them up
+            SongProperty::Rating => None,
+            SongProperty::Other(name) => self.metadata.get(*name).map(|v| v.join(tag_separator)),
         }
     }
 
-    pub fn matches(&self, formats: &[&Property<'static, SongProperty>], filter: &str) -> bool {
+    pub fn matches(&self, formats: &[&Property<'static, SongProperty>], filter: &str, tag_separator: &str) -> bool {
         for format in formats {
             let match_found = match &format.kind {
                 PropertyKindOrText::Text(value) => Some(value.to_lowercase().contains(&filter.to_lowercase())),
-                PropertyKindOrText::Property(property) => self.format(property).map_or_else(
-                    || format.default.map(|f| self.matches(&[f], filter)),
+                PropertyKindOrText::Property(property) => self.format(property, tag_separator).map_or_else(
+                    || format.default.map(|f| self.matches(&[f], filter, tag_separator)),
                     |p| Some(p.to_lowercase().contains(filter)),
                 ),
                 PropertyKindOrText::Group(_) => format
-                    .as_string(Some(self))
+                    .as_string(Some(self), tag_separator)
                     .map(|v| v.to_lowercase().contains(&filter.to_lowercase())),
             };
             if match_found.is_some_and(|v| v) {
@@ -524,10 +598,11 @@ impl Song {
         format: &'static Property<'static, SongProperty>,
         max_len: usize,
         symbols: &SymbolsConfig,
+        tag_separator: &str,
     ) -> Option<Line<'song>> {
         format
             .default
-            .and_then(|f| self.as_line_ellipsized(f, max_len, symbols))
+            .and_then(|f| self.as_line_ellipsized(f, max_len, symbols, tag_separator))
     }
 
     pub fn as_line_ellipsized<'song>(
@@ -535,27 +610,28 @@ impl Song {
         format: &'static Property<'static, SongProperty>,
         max_len: usize,
         symbols: &SymbolsConfig,
+        tag_separator: &str,
     ) -> Option<Line<'song>> {
         let style = format.style.unwrap_or_default();
         match &format.kind {
             PropertyKindOrText::Text(value) => {
                 Some(Line::styled((*value).ellipsize(max_len, symbols).to_string(), style))
             }
-            PropertyKindOrText::Property(property) => self.format(property).map_or_else(
-                || self.default_as_line_ellipsized(format, max_len, symbols),
+            PropertyKindOrText::Property(property) => self.format(property, tag_separator).map_or_else(
+                || self.default_as_line_ellipsized(format, max_len, symbols, tag_separator),
                 |v| Some(Line::styled(v.ellipsize(max_len, symbols).into_owned(), style)),
             ),
             PropertyKindOrText::Group(group) => {
                 let mut buf = Line::default();
                 for grformat in *group {
-                    if let Some(res) = self.as_line_ellipsized(grformat, max_len, symbols) {
+                    if let Some(res) = self.as_line_ellipsized(grformat, max_len, symbols, tag_separator) {
                         for span in res.spans {
                             buf.push_span(span);
                         }
                     } else {
                         return format
                             .default
-                            .and_then(|format| self.as_line_ellipsized(format, max_len, symbols));
+                            .and_then(|format| self.as_line_ellipsized(format, max_len, symbols, tag_separator));
                     }
                 }
                 return Some(buf);
@@ -565,28 +641,28 @@ impl Song {
 }
 
 impl Property<'static, SongProperty> {
-    fn default(&self, song: Option<&Song>) -> Option<String> {
-        self.default.and_then(|p| p.as_string(song))
+    fn default(&self, song: Option<&Song>, tag_separator: &str) -> Option<String> {
+        self.default.and_then(|p| p.as_string(song, tag_separator))
     }
 
-    pub fn as_string(&self, song: Option<&Song>) -> Option<String> {
+    pub fn as_string(&self, song: Option<&Song>, tag_separator: &str) -> Option<String> {
         match &self.kind {
             PropertyKindOrText::Text(value) => Some((*value).to_string()),
             PropertyKindOrText::Property(property) => {
                 if let Some(song) = song {
-                    song.format(property)
-                        .map_or_else(|| self.default(Some(song)), |v| Some(v.into_owned()))
+                    song.format(property, tag_separator)
+                        .map_or_else(|| self.default(Some(song), tag_separator), |v| Some(v.into_owned()))
                 } else {
-                    self.default(song)
+                    self.default(song, tag_separator)
                 }
             }
             PropertyKindOrText::Group(group) => {
                 let mut buf = String::new();
                 for format in *group {
-                    if let Some(res) = format.as_string(song) {
+                    if let Some(res) = format.as_string(song, tag_separator) {
                         buf.push_str(&res);
                     } else {
-                        return self.default.and_then(|d| d.as_string(song));
+                        return self.default.and_then(|d| d.as_string(song, tag_separator));
                     }
                 }
                 return Some(buf);
@@ -600,33 +676,38 @@ impl Property<'static, PropertyKind> {
         &self,
         song: Option<&'song Song>,
         status: &'song Status,
+        tag_separator: &str,
     ) -> Option<Either<Span<'s>, Vec<Span<'s>>>> {
-        self.default.and_then(|p| p.as_span(song, status))
+        self.default.and_then(|p| p.as_span(song, status, tag_separator))
     }
 
     pub fn as_span<'song: 's, 's>(
         &'s self,
         song: Option<&'song Song>,
         status: &'song Status,
+        tag_separator: &str,
     ) -> Option<Either<Span<'s>, Vec<Span<'s>>>> {
         let style = self.style.unwrap_or_default();
         match &self.kind {
             PropertyKindOrText::Text(value) => Some(Either::Left(Span::styled(*value, style))),
             PropertyKindOrText::Property(PropertyKind::Song(property)) => {
                 if let Some(song) = song {
-                    song.format(property).map_or_else(
-                        || self.default_as_span(Some(song), status),
+                    song.format(property, tag_separator).map_or_else(
+                        || self.default_as_span(Some(song), status, tag_separator),
                         |s| Some(Either::Left(Span::styled(s, style))),
                     )
                 } else {
-                    self.default_as_span(song, status)
+                    self.default_as_span(song, status, tag_separator)
                 }
             }
             PropertyKindOrText::Property(PropertyKind::Status(s)) => match s {
                 StatusProperty::State => Some(Either::Left(Span::styled(status.state.as_ref(), style))),
                 StatusProperty::Duration => Some(Either::Left(Span::styled(status.duration.to_string(), style))),
                 StatusProperty::Elapsed => Some(Either::Left(Span::styled(status.elapsed.to_string(), style))),
-                StatusProperty::Volume => Some(Either::Left(Span::styled(status.volume.value().to_string(), style))),
+                StatusProperty::Volume => status.volume.as_ref().map_or_else(
+                    || self.default_as_span(song, status, tag_separator),
+                    |v| Some(Either::Left(Span::styled(v.value().to_string(), style))),
+                ),
                 StatusProperty::Repeat => Some(Either::Left(Span::styled(
                     if status.repeat { "On" } else { "Off" },
                     style,
@@ -638,19 +719,23 @@ impl Property<'static, PropertyKind> {
                 StatusProperty::Consume => Some(Either::Left(Span::styled(status.consume.to_string(), style))),
                 StatusProperty::Single => Some(Either::Left(Span::styled(status.single.to_string(), style))),
                 StatusProperty::Bitrate => status.bitrate.as_ref().map_or_else(
-                    || self.default_as_span(song, status),
+                    || self.default_as_span(song, status, tag_separator),
                     |v| Some(Either::Left(Span::styled(v.to_string(), Style::default()))),
                 ),
                 StatusProperty::Crossfade => status.xfade.as_ref().map_or_else(
-                    || self.default_as_span(song, status),
+                    || self.default_as_span(song, status, tag_separator),
                     |v| Some(Either::Left(Span::styled(v.to_string(), Style::default()))),
                 ),
+                StatusProperty::ReplayGain => status.replay_gain_mode.as_ref().map_or_else(
+                    || self.default_as_span(song, status, tag_separator),
+                    |v| Some(Either::Left(Span::styled(v.as_ref(), style))),
+                ),
             },
             PropertyKindOrText::Property(PropertyKind::Widget(w)) => match w {
-                WidgetProperty::Volume => Some(Either::Left(Span::styled(
-                    Volume::get_str(*status.volume.value()),
-                    style,
-                ))),
+                WidgetProperty::Volume => status.volume.as_ref().map_or_else(
+                    || self.default_as_span(song, status, tag_separator),
+                    |v| Some(Either::Left(Span::styled(Volume::get_str(*v.value()), style))),
+                ),
                 WidgetProperty::States {
                     active_style,
                     separator_style,
@@ -678,7 +763,7 @@ impl Property<'static, PropertyKind> {
             PropertyKindOrText::Group(group) => {
                 let mut buf = Vec::new();
                 for format in *group {
-                    match format.as_span(song, status) {
+                    match format.as_span(song, status, tag_separator) {
                         Some(Either::Left(span)) => buf.push(span),
                         Some(Either::Right(spans)) => buf.extend(spans),
                         None => return None,
@@ -759,8 +844,8 @@ mod format_tests {
         use test_case::test_case;
 
         use crate::{
-            config::theme::properties::{PropertyKind, StatusProperty},
-            mpd::commands::{status::OnOffOneshot, State, Status, Volume},
+            config::theme::properties::{PropertyKind, StatusProperty, WidgetProperty},
+            mpd::commands::{status::OnOffOneshot, ReplayGainMode, State, Status, Volume},
         };
 
         use super::*;
@@ -783,14 +868,14 @@ mod format_tests {
                 file: "file".to_owned(),
                 duration: Some(Duration::from_secs(123)),
                 metadata: HashMap::from([
-                    ("title".to_string(), "title".to_owned()),
-                    ("album".to_string(), "album".to_owned()),
-                    ("track".to_string(), "123".to_string()),
-                    ("artist".to_string(), "artist".to_string()),
+                    ("title".to_string(), "title".to_owned().into()),
+                    ("album".to_string(), "album".to_owned().into()),
+                    ("track".to_string(), "123".to_string().into()),
+                    ("artist".to_string(), "artist".to_string().into()),
                 ]),
             };
 
-            let result = format.as_string(Some(&song));
+            let result = format.as_string(Some(&song), "");
 
             assert_eq!(result, Some(expected.to_string()));
         }
@@ -804,6 +889,7 @@ mod format_tests {
         #[test_case(StatusProperty::Duration, "2:03")]
         #[test_case(StatusProperty::Crossfade, "3")]
         #[test_case(StatusProperty::Bitrate, "123")]
+        #[test_case(StatusProperty::ReplayGain, "Album")]
         fn status_property_resolves_correctly(prop: StatusProperty, expected: &str) {
             let format = Property::<'static, PropertyKind> {
                 kind: PropertyKindOrText::Property(PropertyKind::Status(prop)),
@@ -816,14 +902,14 @@ mod format_tests {
                 file: "file".to_owned(),
                 duration: Some(Duration::from_secs(123)),
                 metadata: HashMap::from([
-                    ("artist".to_string(), "artist".to_string()),
-                    ("album".to_string(), "album".to_owned()),
-                    ("title".to_string(), "title".to_owned()),
-                    ("track".to_string(), "123".to_string()),
+                    ("artist".to_string(), "artist".to_string().into()),
+                    ("album".to_string(), "album".to_owned().into()),
+                    ("title".to_string(), "title".to_owned().into()),
+                    ("track".to_string(), "123".to_string().into()),
                 ]),
             };
             let status = Status {
-                volume: Volume::new(123),
+                volume: Some(Volume::new(123)),
                 repeat: true,
                 random: true,
                 single: OnOffOneshot::On,
@@ -833,16 +919,45 @@ mod format_tests {
                 duration: Duration::from_secs(123),
                 xfade: Some(3),
                 state: State::Play,
+                replay_gain_mode: Some(ReplayGainMode::Album),
                 ..Default::default()
             };
 
-            let result = format.as_span(Some(&song), &status);
+            let result = format.as_span(Some(&song), &status, "");
 
             assert_eq!(
                 result,
                 Some(either::Either::<Span<'_>, Vec<Span<'_>>>::Left(Span::raw(expected)))
             );
         }
+
+        #[test_case(PropertyKind::Status(StatusProperty::Volume); "volume")]
+        #[test_case(PropertyKind::Widget(WidgetProperty::Volume); "volume widget")]
+        fn volume_uses_default_without_mixer(kind: PropertyKind) {
+            let format = Property::<'static, PropertyKind> {
+                kind: PropertyKindOrText::Property(kind),
+                style: None,
+                default: Some(
+                    Property {
+                        kind: PropertyKindOrText::Text("no mixer"),
+                        style: None,
+                        default: None,
+                    }
+                    .leak(),
+                ),
+            };
+            let status = Status {
+                volume: None,
+                ..Default::default()
+            };
+
+            let result = format.as_span(None, &status, "");
+
+            assert_eq!(
+                result,
+                Some(either::Either::<Span<'_>, Vec<Span<'_>>>::Left(Span::raw("no mixer")))
+            );
+        }
     }
 
     mod property {
@@ -860,13 +975,13 @@ mod format_tests {
 
             let song = Song {
                 metadata: HashMap::from([
-                    ("artist".to_string(), "artist".to_string()),
-                    ("title".to_string(), "title".to_owned()),
+                    ("artist".to_string(), "artist".to_string().into()),
+                    ("title".to_string(), "title".to_owned().into()),
                 ]),
                 ..Default::default()
             };
 
-            let result = format.as_string(Some(&song));
+            let result = format.as_string(Some(&song), "");
 
             assert_eq!(result, Some("title".to_owned()));
         }
@@ -888,13 +1003,13 @@ mod format_tests {
 
             let song = Song {
                 metadata: HashMap::from([
-                    ("artist".to_string(), "artist".to_string()),
-                    ("title".to_string(), "title".to_owned()),
+                    ("artist".to_string(), "artist".to_string().into()),
+                    ("title".to_string(), "title".to_owned().into()),
                 ]),
                 ..Default::default()
             };
 
-            let result = format.as_string(Some(&song));
+            let result = format.as_string(Some(&song), "");
 
             assert_eq!(result, Some("fallback".to_owned()));
         }
@@ -909,13 +1024,13 @@ mod format_tests {
 
             let song = Song {
                 metadata: HashMap::from([
-                    ("artist".to_string(), "artist".to_string()),
-                    ("title".to_string(), "title".to_owned()),
+                    ("artist".to_string(), "artist".to_string().into()),
+                    ("title".to_string(), "title".to_owned().into()),
                 ]),
                 ..Default::default()
             };
 
-            let result = format.as_string(Some(&song));
+            let result = format.as_string(Some(&song), "");
 
             assert_eq!(result, None);
         }
@@ -936,13 +1051,13 @@ mod format_tests {
 
             let song = Song {
                 metadata: HashMap::from([
-                    ("artist".to_string(), "artist".to_string()),
-                    ("title".to_string(), "title".to_owned()),
+                    ("artist".to_string(), "artist".to_string().into()),
+                    ("title".to_string(), "title".to_owned().into()),
                 ]),
                 ..Default::default()
             };
 
-            let result = format.as_string(Some(&song));
+            let result = format.as_string(Some(&song), "");
 
             assert_eq!(result, Some("test".to_owned()));
         }
@@ -964,13 +1079,13 @@ mod format_tests {
 
             let song = Song {
                 metadata: HashMap::from([
-                    ("artist".to_string(), "artist".to_string()),
-                    ("title".to_string(), "title".to_owned()),
+                    ("artist".to_string(), "artist".to_string().into()),
+                    ("title".to_string(), "title".to_owned().into()),
                 ]),
                 ..Default::default()
             };
 
-            let result = format.as_string(Some(&song));
+            let result = format.as_string(Some(&song), "");
 
             assert_eq!(result, Some("test".to_owned()));
         }
@@ -1002,13 +1117,13 @@ mod format_tests {
 
             let song = Song {
                 metadata: HashMap::from([
-                    ("artist".to_string(), "artist".to_string()),
-                    ("title".to_string(), "title".to_owned()),
+                    ("artist".to_string(), "artist".to_string().into()),
+                    ("title".to_string(), "title".to_owned().into()),
                 ]),
                 ..Default::default()
             };
 
-            let result = format.as_string(Some(&song));
+            let result = format.as_string(Some(&song), "");
 
             assert_eq!(result, None);
         }
@@ -1041,13 +1156,13 @@ mod format_tests {
 
             let song = Song {
                 metadata: HashMap::from([
-                    ("artist".to_string(), "artist".to_string()),
-                    ("title".to_string(), "title".to_owned()),
+                    ("artist".to_string(), "artist".to_string().into()),
+                    ("title".to_string(), "title".to_owned().into()),
                 ]),
                 ..Default::default()
             };
 
-            let result = format.as_string(Some(&song));
+            let result = format.as_string(Some(&song), "");
 
             assert_eq!(result, Some("fallback".to_owned()));
         }
@@ -1080,13 +1195,13 @@ mod format_tests {
 
             let song = Song {
                 metadata: HashMap::from([
-                    ("artist".to_string(), "artist".to_string()),
-                    ("title".to_string(), "title".to_owned()),
+                    ("artist".to_string(), "artist".to_string().into()),
+                    ("title".to_string(), "title".to_owned().into()),
                 ]),
                 ..Default::default()
             };
 
-            let result = format.as_string(Some(&song));
+            let result = format.as_string(Some(&song), "");
 
             assert_eq!(result, Some("titletext".to_owned()));
         }
@@ -1116,13 +1231,13 @@ mod format_tests {
 
             let song = Song {
                 metadata: HashMap::from([
-                    ("artist".to_string(), "artist".to_string()),
-                    ("title".to_string(), "title".to_owned()),
+                    ("artist".to_string(), "artist".to_string().into()),
+                    ("title".to_string(), "title".to_owned().into()),
                 ]),
                 ..Default::default()
             };
 
-            let result = format.as_string(Some(&song));
+            let result = format.as_string(Some(&song), "");
 
             assert_eq!(result, Some("fallbacktext".to_owned()));
         }
@@ -1162,11 +1277,11 @@ mod format_tests {
             };
 
             let song = Song {
-                metadata: HashMap::from([("title".to_string(), "title".to_owned())]),
+                metadata: HashMap::from([("title".to_string(), "title".to_owned().into())]),
                 ..Default::default()
             };
 
-            let result = format.as_string(Some(&song));
+            let result = format.as_string(Some(&song), "");
 
             assert_eq!(result, Some("innerfallbackouter".to_owned()));
         }
@@ -7,14 +7,15 @@ use ratatui::{
 };
 
 use crate::{
-    config::Config,
+    config::{keys::CommonAction, tabs::PaneType, RightOnSong},
     context::AppContext,
     mpd::{
         commands::Song,
+        errors::{ErrorCode, MpdError, MpdFailureResponse},
         mpd_client::{Filter, MpdClient, SingleOrRange, Tag},
     },
     shared::{
-        ext::mpd_client::MpdClientExt,
+        ext::mpd_client::{MpdClientExt, SongAdd},
         key_event::KeyEvent,
         macros::{modal, status_error, status_info},
         mouse_event::MouseEvent,
@@ -46,11 +47,73 @@ impl PlaylistsPane {
         Self {
             stack: DirStack::default(),
             filter_input_mode: false,
-            browser: Browser::new(context.config),
+            browser: Browser::new(context.config, PaneType::Playlists),
             initialized: false,
         }
     }
 
+    /// Removes the selected or marked songs from the opened playlist and drops them from the
+    /// displayed list right away. The list is reconciled once MPD emits the stored playlist event.
+    fn delete_songs(&mut self, event: &mut KeyEvent, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
+        let [playlist] = self.stack.path() else {
+            return Ok(());
+        };
+        let playlist = playlist.clone();
+        match event.as_common_action(context) {
+            Some(CommonAction::Delete) => {}
+            Some(_) => {
+                event.abandon();
+                return Ok(());
+            }
+            None => return Ok(()),
+        }
+
+        let indices = if self.stack.current().marked().is_empty() {
+            self.stack
+                .current()
+                .selected_with_idx()
+                .map(|(idx, _)| vec![idx])
+                .unwrap_or_default()
+        } else {
+            self.stack.current().marked().iter().copied().collect_vec()
+        };
+
+        let first_deleted = indices.first().copied();
+        for idx in indices.into_iter().rev() {
+            if self.delete_song_at(&playlist, idx, client)? {
+                self.stack.current_mut().remove(idx);
+            }
+        }
+        if let Some(idx) = first_deleted {
+            self.stack.current_mut().state.select_after_removal(
+                idx,
+                context.config.delete_selection_behavior,
+                context.config.scrolloff,
+            );
+        }
+
+        let preview = self
+            .prepare_preview(client, context)
+            .context("Cannot prepare preview")?;
+        self.stack.set_preview(preview);
+        context.render()?;
+
+        Ok(())
+    }
+
+    /// Removes the song displayed at `idx` from the playlist. Returns false when there is no song at `idx`.
+    fn delete_song_at(&self, playlist: &str, idx: usize, client: &mut impl MpdClient) -> Result<bool> {
+        let current = self.stack.current();
+        let Some(DirOrSong::Song(song)) = current.items.get(idx) else {
+            return Ok(false);
+        };
+
+        // The displayed index differs from the position in the playlist while the list is reduced
+        client.delete_from_playlist(playlist, &SingleOrRange::single(current.original_idx(idx)))?;
+        status_info!("File '{}' deleted from playlist '{playlist}'", song.file);
+        Ok(true)
+    }
+
     fn open_or_play(&mut self, autoplay: bool, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
         let Some(selected) = self.stack().current().selected() else {
             log::error!("Failed to move deeper inside dir. Current value is None");
@@ -79,9 +142,11 @@ impl PlaylistsPane {
 }
 
 impl Pane for PlaylistsPane {
-    fn render(&mut self, frame: &mut Frame, area: Rect, _context: &AppContext) -> Result<()> {
+    fn render(&mut self, frame: &mut Frame, area: Rect, context: &AppContext) -> Result<()> {
         self.browser
             .set_filter_input_active(self.filter_input_mode)
+            .set_current_item_style(context.current_item_style())
+            .set_ratings(&context.ratings)
             .render(area, frame.buffer_mut(), &mut self.stack);
 
         Ok(())
@@ -96,12 +161,14 @@ impl Pane for PlaylistsPane {
                 .map(|playlist| DirOrSong::Dir {
                     name: playlist.name,
                     full_path: String::new(),
+                    year: None,
+                    album_artist: None,
                 })
                 .sorted()
                 .collect();
             self.stack = DirStack::new(playlists);
             let preview = self
-                .prepare_preview(client, context.config)
+                .prepare_preview(client, context)
                 .context("Cannot prepare preview")?;
             self.stack.set_preview(preview);
             self.initialized = true;
@@ -119,12 +186,14 @@ impl Pane for PlaylistsPane {
                     .map(|playlist| DirOrSong::Dir {
                         name: playlist.name,
                         full_path: String::new(),
+                        year: None,
+                        album_artist: None,
                     })
                     .sorted()
                     .collect();
                 self.stack = DirStack::new(playlists);
                 let preview = self
-                    .prepare_preview(client, context.config)
+                    .prepare_preview(client, context)
                     .context("Cannot prepare preview")?;
                 self.stack.set_preview(preview);
                 context.render()?;
@@ -138,6 +207,8 @@ impl Pane for PlaylistsPane {
                         .map(|playlist| DirOrSong::Dir {
                             name: playlist.name,
                             full_path: String::new(),
+                            year: None,
+                            album_artist: None,
                         })
                         .sorted()
                         .collect_vec(),
@@ -197,7 +268,7 @@ impl Pane for PlaylistsPane {
                 }
 
                 let preview = self
-                    .prepare_preview(client, context.config)
+                    .prepare_preview(client, context)
                     .context("Cannot prepare preview")?;
                 self.stack.set_preview(preview);
 
@@ -209,6 +280,10 @@ impl Pane for PlaylistsPane {
         Ok(())
     }
 
+    fn on_database_update(&mut self, _context: &AppContext) {
+        self.initialized = false;
+    }
+
     fn handle_mouse_event(
         &mut self,
         event: MouseEvent,
@@ -218,8 +293,18 @@ impl Pane for PlaylistsPane {
         self.handle_mouse_action(event, client, context)
     }
 
-    fn handle_action(&mut self, event: &mut KeyEvent, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
+    fn is_input_active(&self) -> bool {
+        self.filter_input_mode
+    }
+
+    fn handle_action(
+        &mut self,
+        event: &mut KeyEvent,
+        client: &mut impl MpdClient,
+        context: &mut AppContext,
+    ) -> Result<()> {
         self.handle_filter_input(event, client, context)?;
+        self.delete_songs(event, client, context)?;
         self.handle_common_action(event, client, context)?;
         self.handle_global_action(event, client, context)?;
         Ok(())
@@ -267,12 +352,11 @@ impl BrowserPane<DirOrSong> for PlaylistsPane {
                         .size(45, 6)
                 );
             }
-            DirOrSong::Song(s) => {
+            DirOrSong::Song(_) => {
                 let Some(DirOrSong::Dir { name: playlist, .. }) = self.stack.previous().selected() else {
                     return Ok(());
                 };
-                client.delete_from_playlist(playlist, &SingleOrRange::single(index))?;
-                status_info!("File '{}' deleted from playlist '{playlist}'", s.file);
+                self.delete_song_at(playlist, index, client)?;
 
                 context.render()?;
             }
@@ -283,14 +367,21 @@ impl BrowserPane<DirOrSong> for PlaylistsPane {
     fn add_all(&self, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
         match self.stack().path() {
             [playlist] => {
-                client.load_playlist(playlist)?;
+                client.load_playlist(playlist, context.add_position())?;
                 status_info!("Playlist '{playlist}' added to queue");
 
                 context.render()?;
             }
             [] => {
-                for playlist in &self.stack().current().items {
-                    self.add(playlist, client, context)?;
+                // Playlists inserted at a fixed position end up in reverse order unless added back to front
+                if context.add_position().is_some() {
+                    for playlist in self.stack().current().items.iter().rev() {
+                        self.add(playlist, client, context)?;
+                    }
+                } else {
+                    for playlist in &self.stack().current().items {
+                        self.add(playlist, client, context)?;
+                    }
                 }
                 status_info!("All playlists added to queue");
 
@@ -305,15 +396,22 @@ impl BrowserPane<DirOrSong> for PlaylistsPane {
     fn add(&self, item: &DirOrSong, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
         match item {
             DirOrSong::Dir { name: d, .. } => {
-                client.load_playlist(d)?;
+                client.load_playlist(d, context.add_position())?;
                 status_info!("Playlist '{d}' added to queue");
 
                 context.render()?;
             }
             DirOrSong::Song(s) => {
-                client.add(&s.file)?;
+                if client.add_song(&s.file, context)? != SongAdd::Added {
+                    return Ok(());
+                }
                 if let Ok(Some(song)) = client.find_one(&[Filter::new(Tag::File, &s.file)]) {
-                    status_info!("'{}' by '{}' added to queue", song.title_str(), song.artist_str());
+                    let separator = context.config.theme.format_tag_separator;
+                    status_info!(
+                        "'{}' by '{}' added to queue",
+                        song.title_str(separator),
+                        song.artist_str(separator)
+                    );
                 }
 
                 context.render()?;
@@ -335,9 +433,23 @@ impl BrowserPane<DirOrSong> for PlaylistsPane {
                         .input_label("New name:")
                         .initial_value(current_name.clone())
                         .on_confirm(move |client, new_value| {
-                            if current_name != new_value {
-                                client.rename_playlist(&current_name, new_value)?;
-                                status_info!("Playlist '{}' renamed to '{}'", current_name, new_value);
+                            if current_name == new_value {
+                                return Ok(());
+                            }
+                            match client.rename_playlist(&current_name, new_value) {
+                                Ok(()) => {
+                                    status_info!("Playlist '{}' renamed to '{}'", current_name, new_value);
+                                }
+                                Err(MpdError::Mpd(MpdFailureResponse {
+                                    code: ErrorCode::Exist, ..
+                                })) => {
+                                    status_error!(
+                                        "Cannot rename playlist '{}', playlist '{}' already exists",
+                                        current_name,
+                                        new_value
+                                    );
+                                }
+                                Err(err) => return Err(err.into()),
                             }
                             Ok(())
                         })
@@ -354,7 +466,7 @@ impl BrowserPane<DirOrSong> for PlaylistsPane {
     }
 
     fn next(&mut self, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
-        self.open_or_play(false, client, context)
+        self.open_or_play(context.config.right_on_song == RightOnSong::AddAndPlay, client, context)
     }
 
     fn move_selected(&mut self, direction: MoveDirection, client: &mut impl MpdClient) -> Result<()> {
@@ -373,7 +485,15 @@ impl BrowserPane<DirOrSong> for PlaylistsPane {
                     MoveDirection::Up => idx.saturating_sub(1),
                     MoveDirection::Down => (idx + 1).min(self.stack().current().items.len() - 1),
                 };
-                client.move_in_playlist(playlist, &SingleOrRange::single(idx), new_idx)?;
+                let current = self.stack.current();
+                client.move_in_playlist(
+                    playlist,
+                    &SingleOrRange::single(current.original_idx(idx)),
+                    current.original_idx(new_idx),
+                )?;
+                let current = self.stack.current_mut();
+                current.move_item(idx, new_idx);
+                current.state.select(Some(new_idx), 0);
             }
         };
 
@@ -383,23 +503,31 @@ impl BrowserPane<DirOrSong> for PlaylistsPane {
     fn prepare_preview(
         &mut self,
         client: &mut impl MpdClient,
-        config: &Config,
+        context: &AppContext,
     ) -> Result<Option<Vec<ListItem<'static>>>> {
+        let config = context.config;
         self.stack()
             .current()
             .selected()
             .map_or(Ok(None), |current| -> Result<_> {
                 Ok(Some(match current {
+                    // Only the paths are fetched for the preview as listing full song info can be slow
+                    // for big playlists. The full info is fetched once the playlist is opened.
                     DirOrSong::Dir { name: d, .. } => client
-                        .list_playlist_info(d, None)?
+                        .list_playlist(d)?
                         .into_iter()
-                        .map(DirOrSong::Song)
-                        .map(|s| s.to_list_item_simple(config))
+                        .map(|file| {
+                            DirOrSong::Song(Song {
+                                file,
+                                ..Default::default()
+                            })
+                        })
+                        .map(|s| s.to_list_item_simple(config, &context.ratings))
                         .collect_vec(),
                     DirOrSong::Song(song) => client
                         .find_one(&[Filter::new(Tag::File, &song.file)])?
                         .context(anyhow!("File '{}' was listed but not found", song.file))?
-                        .to_preview(&config.theme.symbols)
+                        .to_preview(&config.theme.symbols, config.theme.format_tag_separator)
                         .collect_vec(),
                 }))
             })
@@ -1,6 +1,6 @@
 #![allow(clippy::unwrap_used)]
 
-use crossterm::event::{KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use rstest::{fixture, rstest};
 
 use crate::context::AppContext;
@@ -33,7 +33,9 @@ mod on_idle_event {
                 current.selected(),
                 Some(&DirOrSong::Dir {
                     name: playlist_name.clone(),
-                    full_path: String::new()
+                    full_path: String::new(),
+                    year: None,
+                    album_artist: None,
                 })
             );
 
@@ -44,7 +46,9 @@ mod on_idle_event {
                 screen.stack.current().selected(),
                 Some(&DirOrSong::Dir {
                     name: playlist_name,
-                    full_path: String::new()
+                    full_path: String::new(),
+                    year: None,
+                    album_artist: None,
                 })
             );
         }
@@ -124,7 +128,9 @@ mod on_idle_event {
                 screen.stack.previous().selected(),
                 Some(&DirOrSong::Dir {
                     name: playlist_name,
-                    full_path: String::new()
+                    full_path: String::new(),
+                    year: None,
+                    album_artist: None,
                 })
             );
             assert_eq!(screen.stack.current().selected_with_idx().unwrap().0, 4);
@@ -150,7 +156,9 @@ mod on_idle_event {
                 screen.stack.previous().selected(),
                 Some(&DirOrSong::Dir {
                     name: playlist_name,
-                    full_path: String::new()
+                    full_path: String::new(),
+                    year: None,
+                    album_artist: None,
                 })
             );
             assert_eq!(screen.stack.current().selected_with_idx().unwrap().0, last_song_idx - 1);
@@ -176,7 +184,9 @@ mod on_idle_event {
                 screen.stack.previous().selected(),
                 Some(&DirOrSong::Dir {
                     name: playlist_name,
-                    full_path: String::new()
+                    full_path: String::new(),
+                    year: None,
+                    album_artist: None,
                 })
             );
             assert_eq!(screen.stack.current().selected_with_idx().unwrap().0, 0);
@@ -277,6 +287,315 @@ mod on_idle_event {
     }
 }
 
+mod filter {
+    use super::*;
+    use crate::config::{Config, FilterBehavior, Leak};
+
+    fn press(screen: &mut PlaylistsPane, code: KeyCode, client: &mut TestMpdClient, app_context: &mut AppContext) {
+        let key = KeyEvent::new(code, KeyModifiers::NONE);
+        screen.handle_action(&mut key.into(), client, app_context).unwrap();
+    }
+
+    #[rstest]
+    fn confirming_empty_filter_closes_filter_input(
+        mut screen: PlaylistsPane,
+        mut client: TestMpdClient,
+        mut app_context: AppContext,
+    ) {
+        screen.stack.current_mut().select_idx(2, 0);
+
+        press(&mut screen, KeyCode::Char('/'), &mut client, &mut app_context);
+        press(&mut screen, KeyCode::Enter, &mut client, &mut app_context);
+
+        assert!(!screen.filter_input_mode);
+        assert_eq!(screen.stack.current().filter(), None);
+        assert_eq!(screen.stack.current().selected_with_idx().map(|(idx, _)| idx), Some(2));
+    }
+
+    #[rstest]
+    fn closing_reduced_filter_restores_all_items(
+        mut screen: PlaylistsPane,
+        mut client: TestMpdClient,
+        mut app_context: AppContext,
+    ) {
+        app_context.config = Config {
+            filter_behavior: FilterBehavior::Reduce,
+            ..app_context.config.clone()
+        }
+        .leak();
+        let items_before = screen.stack.current().items.len();
+
+        press(&mut screen, KeyCode::Char('/'), &mut client, &mut app_context);
+        for c in "playlist_2".chars() {
+            press(&mut screen, KeyCode::Char(c), &mut client, &mut app_context);
+        }
+        press(&mut screen, KeyCode::Enter, &mut client, &mut app_context);
+        assert!(screen.stack.current().items.len() < items_before);
+        press(&mut screen, KeyCode::Esc, &mut client, &mut app_context);
+
+        assert_eq!(screen.stack.current().filter(), None);
+        assert_eq!(screen.stack.current().items.len(), items_before);
+    }
+}
+
+mod editing {
+    use super::*;
+    use crate::config::{Config, DeleteSelectionBehavior, Leak};
+
+    #[rstest]
+    fn delete_removes_the_song_from_the_displayed_playlist(
+        mut screen_in_playlist_0: PlaylistsPane,
+        mut client: TestMpdClient,
+        mut app_context: AppContext,
+    ) {
+        screen_in_playlist_0.stack.current_mut().select_idx(1, 0);
+        let songs_before = client.playlists[0].songs_indices.clone();
+        let items_before = screen_in_playlist_0.stack.current().items.len();
+        let delete = KeyEvent::new(crossterm::event::KeyCode::Char('D'), KeyModifiers::SHIFT);
+
+        screen_in_playlist_0
+            .handle_action(&mut delete.into(), &mut client, &mut app_context)
+            .unwrap();
+
+        assert_eq!(screen_in_playlist_0.stack.current().items.len(), items_before - 1);
+        assert_eq!(client.playlists[0].songs_indices.len(), songs_before.len() - 1);
+        assert!(!client.playlists[0].songs_indices.contains(&songs_before[1]));
+    }
+
+    #[rstest]
+    #[case(DeleteSelectionBehavior::KeepIndex, 1)]
+    #[case(DeleteSelectionBehavior::MoveUp, 0)]
+    fn delete_selects_according_to_delete_selection_behavior(
+        mut screen_in_playlist_0: PlaylistsPane,
+        mut client: TestMpdClient,
+        mut app_context: AppContext,
+        #[case] delete_selection_behavior: DeleteSelectionBehavior,
+        #[case] expected: usize,
+    ) {
+        app_context.config = Config {
+            delete_selection_behavior,
+            ..app_context.config.clone()
+        }
+        .leak();
+        screen_in_playlist_0.stack.current_mut().select_idx(1, 0);
+        let delete = KeyEvent::new(crossterm::event::KeyCode::Char('D'), KeyModifiers::SHIFT);
+
+        screen_in_playlist_0
+            .handle_action(&mut delete.into(), &mut client, &mut app_context)
+            .unwrap();
+
+        assert_eq!(
+            screen_in_playlist_0
+                .stack
+                .current()
+                .selected_with_idx()
+                .map(|(idx, _)| idx),
+            Some(expected)
+        );
+    }
+
+    #[rstest]
+    fn deleting_marked_songs_keeps_the_index_of_the_first_marked(
+        mut screen_in_playlist_0: PlaylistsPane,
+        mut client: TestMpdClient,
+        mut app_context: AppContext,
+    ) {
+        let current = screen_in_playlist_0.stack.current_mut();
+        let len_before = current.items.len();
+        current.marked_mut().extend([1, 2]);
+        current.select_idx(len_before - 1, 0);
+        let third = current.items[3].clone();
+        let delete = KeyEvent::new(crossterm::event::KeyCode::Char('D'), KeyModifiers::SHIFT);
+
+        screen_in_playlist_0
+            .handle_action(&mut delete.into(), &mut client, &mut app_context)
+            .unwrap();
+
+        let current = screen_in_playlist_0.stack.current();
+        assert_eq!(current.items.len(), len_before - 2);
+        assert!(current.marked().is_empty());
+        assert_eq!(current.selected(), Some(&third));
+    }
+
+    #[rstest]
+    fn deleting_the_last_song_selects_the_new_last_song(
+        mut screen_in_playlist_0: PlaylistsPane,
+        mut client: TestMpdClient,
+        mut app_context: AppContext,
+    ) {
+        let len_before = screen_in_playlist_0.stack.current().items.len();
+        screen_in_playlist_0.stack.current_mut().select_idx(len_before - 1, 0);
+        let delete = KeyEvent::new(crossterm::event::KeyCode::Char('D'), KeyModifiers::SHIFT);
+
+        screen_in_playlist_0
+            .handle_action(&mut delete.into(), &mut client, &mut app_context)
+            .unwrap();
+
+        assert_eq!(
+            screen_in_playlist_0
+                .stack
+                .current()
+                .selected_with_idx()
+                .map(|(idx, _)| idx),
+            Some(len_before - 2)
+        );
+    }
+
+    #[rstest]
+    fn move_down_swaps_the_songs_in_the_displayed_playlist(
+        mut screen_in_playlist_0: PlaylistsPane,
+        mut client: TestMpdClient,
+        mut app_context: AppContext,
+    ) {
+        screen_in_playlist_0.stack.current_mut().select_idx(0, 0);
+        let first = screen_in_playlist_0.stack.current().items[0].clone();
+        let second = screen_in_playlist_0.stack.current().items[1].clone();
+        let songs_before = client.playlists[0].songs_indices.clone();
+        let move_down = KeyEvent::new(crossterm::event::KeyCode::Char('J'), KeyModifiers::SHIFT);
+
+        screen_in_playlist_0
+            .handle_action(&mut move_down.into(), &mut client, &mut app_context)
+            .unwrap();
+
+        let items = &screen_in_playlist_0.stack.current().items;
+        assert_eq!(items[0], second);
+        assert_eq!(items[1], first);
+        assert_eq!(screen_in_playlist_0.stack.current().selected(), Some(&first));
+        assert_eq!(client.playlists[0].songs_indices[0], songs_before[1]);
+        assert_eq!(client.playlists[0].songs_indices[1], songs_before[0]);
+    }
+}
+
+mod editing_reduced {
+    use super::*;
+    use crate::config::{Config, FilterBehavior, Leak};
+
+    fn reduce(screen: &mut PlaylistsPane, filter: &str, client: &mut TestMpdClient, app_context: &mut AppContext) {
+        app_context.config = Config {
+            filter_behavior: FilterBehavior::Reduce,
+            ..app_context.config.clone()
+        }
+        .leak();
+        let mut press = |code| {
+            let key = KeyEvent::new(code, KeyModifiers::NONE);
+            screen.handle_action(&mut key.into(), client, app_context).unwrap();
+        };
+        press(KeyCode::Char('/'));
+        for c in filter.chars() {
+            press(KeyCode::Char(c));
+        }
+        press(KeyCode::Enter);
+    }
+
+    #[rstest]
+    fn delete_removes_the_song_at_its_playlist_position(
+        mut screen_in_playlist_0: PlaylistsPane,
+        mut client: TestMpdClient,
+        mut app_context: AppContext,
+    ) {
+        reduce(&mut screen_in_playlist_0, "file_5", &mut client, &mut app_context);
+        assert_eq!(screen_in_playlist_0.stack.current().items.len(), 2);
+        let item = screen_in_playlist_0.stack.current().items[1].clone();
+
+        screen_in_playlist_0
+            .delete(&item, 1, &mut client, &app_context)
+            .unwrap();
+
+        assert_eq!(client.playlists[0].songs_indices.len(), 19);
+        assert!(!client.playlists[0].songs_indices.contains(&15));
+        assert!(client.playlists[0].songs_indices.contains(&5));
+    }
+
+    #[rstest]
+    fn deleting_marked_songs_removes_them_at_their_playlist_positions(
+        mut screen_in_playlist_0: PlaylistsPane,
+        mut client: TestMpdClient,
+        mut app_context: AppContext,
+    ) {
+        reduce(&mut screen_in_playlist_0, "file_5", &mut client, &mut app_context);
+        screen_in_playlist_0.stack.current_mut().marked_mut().extend([0, 1]);
+        let delete = KeyEvent::new(KeyCode::Char('D'), KeyModifiers::SHIFT);
+
+        screen_in_playlist_0
+            .handle_action(&mut delete.into(), &mut client, &mut app_context)
+            .unwrap();
+
+        let songs = &client.playlists[0].songs_indices;
+        assert_eq!(songs.len(), 18);
+        assert!(!songs.contains(&5) && !songs.contains(&15));
+        assert!(screen_in_playlist_0.stack.current().items.is_empty());
+    }
+
+    #[rstest]
+    fn move_up_moves_the_song_to_the_previous_displayed_song(
+        mut screen_in_playlist_0: PlaylistsPane,
+        mut client: TestMpdClient,
+        mut app_context: AppContext,
+    ) {
+        reduce(&mut screen_in_playlist_0, "file_5", &mut client, &mut app_context);
+        screen_in_playlist_0.stack.current_mut().select_idx(1, 0);
+        let move_up = KeyEvent::new(KeyCode::Char('K'), KeyModifiers::SHIFT);
+
+        screen_in_playlist_0
+            .handle_action(&mut move_up.into(), &mut client, &mut app_context)
+            .unwrap();
+
+        assert_eq!(client.playlists[0].songs_indices[5], 15);
+        assert_eq!(client.playlists[0].songs_indices[6], 5);
+        screen_in_playlist_0
+            .stack
+            .current_mut()
+            .set_filter(None, app_context.config);
+        let files = screen_in_playlist_0
+            .stack
+            .current()
+            .items
+            .iter()
+            .map(|item| match item {
+                DirOrSong::Song(song) => song.file.clone(),
+                DirOrSong::Dir { name, .. } => name.clone(),
+            })
+            .collect::<Vec<_>>();
+        let expected = client.playlists[0]
+            .songs_indices
+            .iter()
+            .map(|idx| client.songs[*idx].file.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(files, expected);
+    }
+}
+
+mod preview {
+    use super::*;
+
+    #[rstest]
+    fn lists_every_song_of_the_selected_playlist(
+        mut screen: PlaylistsPane,
+        mut client: TestMpdClient,
+        app_context: AppContext,
+    ) {
+        screen.stack.current_mut().select_idx(1, 0);
+        let Some(DirOrSong::Dir { name, .. }) = screen.stack.current().selected().cloned() else {
+            panic!("Expected a playlist to be selected");
+        };
+
+        let preview = screen.prepare_preview(&mut client, &app_context).unwrap().unwrap();
+
+        let playlist = client.playlists.iter().find(|p| p.name == name).unwrap();
+        assert_eq!(preview.len(), playlist.songs_indices.len());
+    }
+
+    #[rstest]
+    fn opened_playlist_has_full_song_info(screen_in_playlist_0: PlaylistsPane) {
+        let items = &screen_in_playlist_0.stack.current().items;
+
+        assert!(!items.is_empty());
+        assert!(items
+            .iter()
+            .all(|item| matches!(item, DirOrSong::Song(song) if song.title().is_some())));
+    }
+}
+
 #[fixture]
 fn screen_in_playlist_0(mut client: TestMpdClient, app_context: AppContext) -> PlaylistsPane {
     let mut screen = PlaylistsPane::new(&app_context);
@@ -1,23 +1,27 @@
+use std::{sync::mpsc::Sender, time::Duration};
+
 use anyhow::Result;
 use crossterm::event::KeyCode;
 use itertools::Itertools;
 
 use crate::{
-    cli::{create_env, run_external},
+    cli::{create_env, open_song_location, run_external},
     config::{
         keys::{GlobalAction, QueueActions},
         theme::{
-            properties::{Property, SongProperty},
+            properties::{Property, PropertyKindOrText, SongProperty},
             PercentOrLength,
         },
+        Config,
     },
     context::AppContext,
     mpd::{
-        commands::Song,
-        mpd_client::{MpdClient, QueueMoveTarget},
+        commands::{Ratings, Song},
+        errors::{ErrorCode, MpdError, MpdFailureResponse},
+        mpd_client::{MpdClient, QueueMoveTarget, SingleOrRange, ValueChange},
     },
     shared::{
-        ext::btreeset_ranges::BTreeSetRanges,
+        ext::{btreeset_ranges::BTreeSetRanges, duration::DurationExt, mpd_client::MpdClientExt},
         key_event::KeyEvent,
         macros::{modal, status_error, status_info, status_warn},
         mouse_event::{MouseEvent, MouseEventKind},
@@ -27,8 +31,9 @@ use crate::{
         modals::{
             confirm_modal::ConfirmModal, input_modal::InputModal, select_modal::SelectModal, song_info::SongInfoModal,
         },
-        UiEvent,
+        ModalWrapper, UiAppEvent, UiEvent,
     },
+    AppEvent,
 };
 use log::error;
 use ratatui::{
@@ -51,6 +56,31 @@ pub struct QueuePane {
     column_widths: Vec<Constraint>,
     column_formats: Vec<&'static Property<'static, SongProperty>>,
     table_area: Rect,
+    time_mode: QueueTimeMode,
+    follow_playing: bool,
+    /// Set when the cursor is moved manually while following the playing song. Cleared when the song
+    /// changes or following is toggled.
+    follow_paused: bool,
+}
+
+/// What the duration columns of the queue table display
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum QueueTimeMode {
+    #[default]
+    Duration,
+    /// Time until the song finishes playing, counted from the current song
+    Remaining,
+    Hidden,
+}
+
+impl QueueTimeMode {
+    fn next(self) -> Self {
+        match self {
+            QueueTimeMode::Duration => QueueTimeMode::Remaining,
+            QueueTimeMode::Remaining => QueueTimeMode::Hidden,
+            QueueTimeMode::Hidden => QueueTimeMode::Duration,
+        }
+    }
 }
 
 impl QueuePane {
@@ -72,8 +102,27 @@ impl QueuePane {
                 .collect_vec(),
             column_formats: config.theme.song_table_format.iter().map(|v| v.prop).collect_vec(),
             table_area: Rect::default(),
+            time_mode: QueueTimeMode::default(),
+            follow_playing: config.follow_playing,
+            follow_paused: false,
         }
     }
+
+    /// Selects and centers the currently playing song if the queue is following it
+    fn follow_current_song(&mut self, context: &AppContext) -> Result<()> {
+        if !self.follow_playing || self.follow_paused {
+            return Ok(());
+        }
+
+        if let Some((idx, _)) = context.find_current_song_in_queue() {
+            if self.scrolling_state.get_selected() != Some(idx) {
+                self.scrolling_state.select_centered(Some(idx));
+                context.render()?;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl Pane for QueuePane {
@@ -118,6 +167,10 @@ impl Pane for QueuePane {
         let table_area = table_block.inner(queue_section);
 
         self.scrolling_state.set_content_len(Some(queue_len));
+        // The queue might have shrunk since the last render, for example after it was cropped
+        if self.scrolling_state.get_selected().is_some_and(|idx| idx >= queue_len) {
+            self.scrolling_state.last();
+        }
 
         let widths = Layout::horizontal(self.column_widths.clone())
             .flex(Flex::Start)
@@ -127,14 +180,18 @@ impl Pane for QueuePane {
         let formats = &config.theme.song_table_format;
 
         let marker_symbol_len = config.theme.symbols.marker.chars().count();
+        let current_idx = context.find_current_song_in_queue().map(|(idx, _)| idx);
+        let remaining_times = if self.time_mode == QueueTimeMode::Remaining {
+            remaining_times(queue, current_idx, context.status.elapsed)
+        } else {
+            Vec::new()
+        };
         let table_items = queue
             .iter()
             .enumerate()
             .map(|(idx, song)| {
-                let is_current = context
-                    .find_current_song_in_queue()
-                    .map(|(_, song)| song.id)
-                    .is_some_and(|v| v == song.id);
+                let is_current = current_idx.is_some_and(|v| v == idx);
+                let is_played = current_idx.is_some_and(|v| idx < v);
 
                 let is_marked = self.scrolling_state.get_marked().contains(&idx);
                 let columns = (0..formats.len()).map(|i| {
@@ -146,10 +203,35 @@ impl Pane for QueuePane {
                         max_len = max_len.saturating_sub(marker_symbol_len);
                     }
 
-                    let mut line = song
-                        .as_line_ellipsized(formats[i].prop, max_len, &config.theme.symbols)
-                        .unwrap_or_default()
-                        .alignment(formats[i].alignment.into());
+                    let is_duration = matches!(
+                        formats[i].prop.kind,
+                        PropertyKindOrText::Property(SongProperty::Duration)
+                    );
+                    let is_rating = matches!(formats[i].prop.kind, PropertyKindOrText::Property(SongProperty::Rating));
+                    let mut line = match self.time_mode {
+                        _ if is_rating => Line::styled(
+                            Ratings::stars(context.ratings.get(&song.file)),
+                            formats[i].prop.style.unwrap_or_default(),
+                        ),
+                        QueueTimeMode::Remaining if is_duration => remaining_times
+                            .get(idx)
+                            .copied()
+                            .flatten()
+                            .map(|remaining| {
+                                Line::styled(remaining.to_string(), formats[i].prop.style.unwrap_or_default())
+                            })
+                            .unwrap_or_default(),
+                        QueueTimeMode::Hidden if is_duration => Line::default(),
+                        QueueTimeMode::Duration | QueueTimeMode::Remaining | QueueTimeMode::Hidden => song
+                            .as_line_ellipsized(
+                                formats[i].prop,
+                                max_len,
+                                &config.theme.symbols,
+                                config.theme.format_tag_separator,
+                            )
+                            .unwrap_or_default(),
+                    }
+                    .alignment(formats[i].alignment.into());
 
                     if is_marked && i == 0 {
                         let marker_span =
@@ -160,15 +242,23 @@ impl Pane for QueuePane {
                     line
                 });
 
-                let is_highlighted = is_current
-                    || self
-                        .filter
-                        .as_ref()
-                        .is_some_and(|filter| song.matches(self.column_formats.as_slice(), filter));
+                let is_highlighted = self.filter.as_ref().is_some_and(|filter| {
+                    song.matches(
+                        self.column_formats.as_slice(),
+                        filter,
+                        config.theme.format_tag_separator,
+                    )
+                });
 
-                if is_highlighted {
+                if is_current {
+                    Row::new(columns.map(|column| column.patch_style(config.theme.playing_item_style)))
+                        .style(config.theme.playing_item_style)
+                } else if is_highlighted {
                     Row::new(columns.map(|column| column.patch_style(config.theme.highlighted_item_style)))
                         .style(config.theme.highlighted_item_style)
+                } else if is_played {
+                    Row::new(columns.map(|column| column.patch_style(config.theme.played_item_style)))
+                        .style(config.theme.played_item_style)
                 } else {
                     Row::new(columns)
                 }
@@ -188,7 +278,7 @@ impl Pane for QueuePane {
 
         let table = Table::new(table_items, self.column_widths.clone())
             .style(config.as_text_style())
-            .row_highlight_style(config.theme.current_item_style);
+            .row_highlight_style(context.current_item_style());
 
         self.table_area = table_area;
         frame.render_stateful_widget(table, table_area, self.scrolling_state.as_render_state_ref());
@@ -224,13 +314,19 @@ impl Pane for QueuePane {
     }
 
     fn on_event(&mut self, event: &mut UiEvent, _client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
-        if let UiEvent::SongChanged = event {
-            if let Some((idx, _)) = context.find_current_song_in_queue() {
-                if context.config.select_current_song_on_change {
-                    self.scrolling_state.select(Some(idx), context.config.scrolloff);
-                    context.render()?;
+        match event {
+            UiEvent::SongChanged => {
+                if let Some((idx, _)) = context.find_current_song_in_queue() {
+                    if context.config.select_current_song_on_change {
+                        self.scrolling_state.select(Some(idx), context.config.scrolloff);
+                        context.render()?;
+                    }
                 }
+                self.follow_paused = false;
+                self.follow_current_song(context)?;
             }
+            UiEvent::Player => self.follow_current_song(context)?,
+            _ => {}
         };
 
         Ok(())
@@ -246,6 +342,13 @@ impl Pane for QueuePane {
             return Ok(());
         }
 
+        if matches!(
+            event.kind,
+            MouseEventKind::LeftClick | MouseEventKind::ScrollDown | MouseEventKind::ScrollUp
+        ) {
+            self.follow_paused = true;
+        }
+
         match event.kind {
             MouseEventKind::LeftClick => {
                 let clicked_row: usize = event.y.saturating_sub(self.table_area.y).into();
@@ -293,7 +396,16 @@ impl Pane for QueuePane {
         Ok(())
     }
 
-    fn handle_action(&mut self, event: &mut KeyEvent, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
+    fn is_input_active(&self) -> bool {
+        self.filter_input_mode
+    }
+
+    fn handle_action(
+        &mut self,
+        event: &mut KeyEvent,
+        client: &mut impl MpdClient,
+        context: &mut AppContext,
+    ) -> Result<()> {
         if self.filter_input_mode {
             match event.as_common_action(context) {
                 Some(CommonAction::Confirm) => {
@@ -314,7 +426,7 @@ impl Pane for QueuePane {
                             if let Some(ref mut f) = self.filter {
                                 f.push(c);
                             };
-                            self.jump_first(&context.queue, context.config.scrolloff);
+                            self.jump_first(&context.queue, context.config);
 
                             context.render()?;
                         }
@@ -336,18 +448,33 @@ impl Pane for QueuePane {
                         client.delete_from_queue(range.into())?;
                     }
 
+                    let first_marked = self.scrolling_state.marked.first().copied().unwrap_or_default();
+                    let remaining = context.queue.len().saturating_sub(self.scrolling_state.marked.len());
                     self.scrolling_state.marked.clear();
+                    self.scrolling_state.set_content_len(Some(remaining));
+                    self.scrolling_state.select_after_removal(
+                        first_marked,
+                        context.config.delete_selection_behavior,
+                        context.config.scrolloff,
+                    );
                     status_info!("Marked songs removed from queue");
                     context.render()?;
                 }
                 QueueActions::Delete => {
-                    if let Some(selected_song) = self
+                    if let Some((idx, selected_song)) = self
                         .scrolling_state
                         .get_selected()
-                        .and_then(|idx| context.queue.get(idx))
+                        .and_then(|idx| context.queue.get(idx).map(|song| (idx, song)))
                     {
                         match client.delete_id(selected_song.id) {
-                            Ok(()) => {}
+                            Ok(()) => {
+                                self.scrolling_state.remove(idx);
+                                self.scrolling_state.select_after_removal(
+                                    idx,
+                                    context.config.delete_selection_behavior,
+                                    context.config.scrolloff,
+                                );
+                            }
                             Err(e) => error!("{:?}", e),
                         }
                     } else {
@@ -373,6 +500,51 @@ impl Pane for QueuePane {
                         client.play_id(selected_song.id)?;
                     }
                 }
+                QueueActions::Crop => {
+                    if let Some((idx, _)) = context.find_current_song_in_queue() {
+                        let ranges = crop_ranges(idx, context.queue.len());
+                        if !ranges.is_empty() {
+                            modal!(
+                                context,
+                                ConfirmModal::new(context)
+                                    .message("Are you sure you want to remove all songs except the current one from the queue? This action cannot be undone.")
+                                    .on_confirm(move |client| Ok(client.delete_ranges_from_queue(&ranges)?))
+                                    .confirm_label("Crop")
+                                    .size(45, 7)
+                            );
+                        }
+                    } else {
+                        status_info!("No song is currently playing");
+                    }
+                }
+                QueueActions::RemovePlayed => {
+                    if let Some((idx, _)) = context.find_current_song_in_queue() {
+                        if idx > 0 {
+                            client.delete_ranges_from_queue(&[SingleOrRange::range(0, idx)])?;
+                            self.scrolling_state.select(Some(0), context.config.scrolloff);
+                            status_info!("Removed {idx} played songs from the queue");
+
+                            context.render()?;
+                        }
+                    } else {
+                        status_info!("No song is currently playing");
+                    }
+                }
+                QueueActions::ToggleQueueTime => {
+                    self.time_mode = self.time_mode.next();
+                    context.render()?;
+                }
+                QueueActions::ToggleFollowPlaying => {
+                    self.follow_playing = !self.follow_playing;
+                    self.follow_paused = false;
+                    if self.follow_playing {
+                        status_info!("Following the playing song");
+                    } else {
+                        status_info!("Stopped following the playing song");
+                    }
+                    self.follow_current_song(context)?;
+                    context.render()?;
+                }
                 QueueActions::JumpToCurrent => {
                     if let Some((idx, _)) = context.find_current_song_in_queue() {
                         self.scrolling_state.select(Some(idx), context.config.scrolloff);
@@ -382,6 +554,8 @@ impl Pane for QueuePane {
                     }
                 }
                 QueueActions::Save => {
+                    let config = context.config;
+                    let app_event_sender = context.app_event_sender.clone();
                     modal!(
                         context,
                         InputModal::new(context)
@@ -393,6 +567,11 @@ impl Pane for QueuePane {
                                     Ok(()) => {
                                         status_info!("Playlist '{}' saved", value);
                                     }
+                                    Err(MpdError::Mpd(MpdFailureResponse {
+                                        code: ErrorCode::Exist, ..
+                                    })) => {
+                                        open_overwrite_playlist_modal(config, &app_event_sender, value.to_owned())?;
+                                    }
                                     Err(err) => {
                                         status_error!(err:?; "Failed to save playlist '{}'",value);
                                     }
@@ -402,18 +581,28 @@ impl Pane for QueuePane {
                     );
                 }
                 QueueActions::AddToPlaylist => {
-                    if let Some(selected_song) = self
-                        .scrolling_state
-                        .get_selected()
-                        .and_then(|idx| context.queue.get(idx))
-                    {
+                    let uris = if self.scrolling_state.marked.is_empty() {
+                        self.scrolling_state
+                            .get_selected()
+                            .and_then(|idx| context.queue.get(idx))
+                            .map(|song| song.file.clone())
+                            .into_iter()
+                            .collect_vec()
+                    } else {
+                        self.scrolling_state
+                            .marked
+                            .iter()
+                            .filter_map(|idx| context.queue.get(*idx))
+                            .map(|song| song.file.clone())
+                            .collect_vec()
+                    };
+                    if !uris.is_empty() {
                         let playlists = client
                             .list_playlists()?
                             .into_iter()
                             .map(|v| v.name)
                             .sorted()
                             .collect_vec();
-                        let uri = selected_song.file.clone();
                         modal!(
                             context,
                             SelectModal::new(context)
@@ -421,8 +610,13 @@ impl Pane for QueuePane {
                                 .confirm_label("Add")
                                 .title("Select a playlist")
                                 .on_confirm(move |client, selected: &String, _idx| {
-                                    client.add_to_playlist(selected, &uri, None)?;
-                                    status_info!("Song added to playlist {}", selected);
+                                    if let [uri] = uris.as_slice() {
+                                        client.add_to_playlist(selected, uri, None)?;
+                                        status_info!("Song added to playlist {}", selected);
+                                    } else {
+                                        client.add_songs_to_playlist(selected, &uris)?;
+                                        status_info!("{} songs added to playlist {}", uris.len(), selected);
+                                    }
                                     Ok(())
                                 })
                         );
@@ -441,6 +635,20 @@ impl Pane for QueuePane {
                 }
             }
         } else if let Some(action) = event.as_common_action(context) {
+            if matches!(
+                action,
+                CommonAction::Up
+                    | CommonAction::Down
+                    | CommonAction::UpHalf
+                    | CommonAction::DownHalf
+                    | CommonAction::Top
+                    | CommonAction::Bottom
+                    | CommonAction::NextResult
+                    | CommonAction::PreviousResult
+            ) {
+                self.follow_paused = true;
+            }
+
             match action {
                 CommonAction::Up => {
                     if !context.queue.is_empty() {
@@ -470,11 +678,15 @@ impl Pane for QueuePane {
 
                     for range in marked.ranges() {
                         let new_idx = range.start().saturating_sub(1);
+                        // The song above the block ends up below it, updated locally right away so the
+                        // move is visible before MPD reports the changed queue
+                        context.queue[new_idx..=*range.end()].rotate_left(1);
                         client.move_in_queue(range.into(), QueueMoveTarget::Absolute(new_idx))?;
                     }
 
                     let mut new_marked = marked.iter().map(|i| i.saturating_sub(1)).collect();
                     std::mem::swap(&mut self.scrolling_state.marked, &mut new_marked);
+                    context.render()?;
 
                     return Ok(());
                 }
@@ -492,11 +704,13 @@ impl Pane for QueuePane {
 
                     for range in marked.ranges().rev() {
                         let new_idx = range.start().saturating_add(1);
+                        context.queue[*range.start()..=range.end() + 1].rotate_right(1);
                         client.move_in_queue(range.into(), QueueMoveTarget::Absolute(new_idx))?;
                     }
 
                     let mut new_marked = marked.iter().map(|i| i.saturating_add(1)).collect();
                     std::mem::swap(&mut self.scrolling_state.marked, &mut new_marked);
+                    context.render()?;
 
                     return Ok(());
                 }
@@ -519,7 +733,9 @@ impl Pane for QueuePane {
 
                     let new_idx = idx.saturating_sub(1);
                     client.move_id(selected.id, QueueMoveTarget::Absolute(new_idx))?;
+                    context.queue.swap(idx, new_idx);
                     self.scrolling_state.select(Some(new_idx), context.config.scrolloff);
+                    context.render()?;
                 }
                 CommonAction::MoveDown => {
                     if context.queue.is_empty() {
@@ -539,7 +755,9 @@ impl Pane for QueuePane {
 
                     let new_idx = (idx + 1).min(context.queue.len() - 1);
                     client.move_id(selected.id, QueueMoveTarget::Absolute(new_idx))?;
+                    context.queue.swap(idx, new_idx);
                     self.scrolling_state.select(Some(new_idx), context.config.scrolloff);
+                    context.render()?;
                 }
                 CommonAction::DownHalf => {
                     if !context.queue.is_empty() {
@@ -578,12 +796,12 @@ impl Pane for QueuePane {
                     context.render()?;
                 }
                 CommonAction::NextResult => {
-                    self.jump_forward(&context.queue, context.config.scrolloff);
+                    self.jump_forward(&context.queue, context.config);
 
                     context.render()?;
                 }
                 CommonAction::PreviousResult => {
-                    self.jump_back(&context.queue, context.config.scrolloff);
+                    self.jump_back(&context.queue, context.config);
 
                     context.render()?;
                 }
@@ -612,6 +830,21 @@ impl Pane for QueuePane {
                 CommonAction::PaneUp => {}
                 CommonAction::PaneRight => {}
                 CommonAction::PaneLeft => {}
+                CommonAction::ToggleMatchMode => {}
+                CommonAction::RateUp | CommonAction::RateDown => {
+                    let change = if action == CommonAction::RateUp {
+                        ValueChange::Increase(1)
+                    } else {
+                        ValueChange::Decrease(1)
+                    };
+                    if let Some(song) = self
+                        .scrolling_state
+                        .get_selected()
+                        .and_then(|idx| context.queue.get(idx))
+                    {
+                        crate::ui::rate_song(client, context, &song.file, &change)?;
+                    }
+                }
             }
         } else if let Some(action) = event.as_global_action(context) {
             match action {
@@ -623,6 +856,17 @@ impl Pane for QueuePane {
 
                     run_external(command, create_env(context, song, client)?);
                 }
+                GlobalAction::OpenSongLocation => {
+                    if let Some(song) = self
+                        .scrolling_state
+                        .get_selected()
+                        .and_then(|idx| context.queue.get(idx))
+                    {
+                        open_song_location(context, &song.file);
+                    } else {
+                        event.abandon();
+                    }
+                }
                 _ => {
                     event.abandon();
                 }
@@ -634,47 +878,57 @@ impl Pane for QueuePane {
 }
 
 impl QueuePane {
-    pub fn jump_forward(&mut self, queue: &[Song], scrolloff: usize) {
+    /// Positions of the songs in the queue whose displayed columns match the filter, the same way
+    /// the first match is found while typing the filter.
+    fn matching_positions(&self, queue: &[Song], config: &Config) -> Vec<usize> {
         let Some(filter) = self.filter.as_ref() else {
             status_warn!("No filter set");
-            return;
+            return Vec::new();
         };
+
+        let positions = queue
+            .iter()
+            .positions(|song| {
+                song.matches(
+                    self.column_formats.as_slice(),
+                    filter,
+                    config.theme.format_tag_separator,
+                )
+            })
+            .collect_vec();
+        if positions.is_empty() {
+            status_info!("No song in the queue matches '{filter}'");
+        }
+        positions
+    }
+
+    pub fn jump_forward(&mut self, queue: &[Song], config: &Config) {
         let Some(selected) = self.scrolling_state.get_selected() else {
             error!(state:? = self.scrolling_state; "No song selected");
             return;
         };
 
-        let length = queue.len();
-        for i in selected + 1..length + selected {
-            let i = i % length;
-            if queue[i].matches(self.column_formats.as_slice(), filter) {
-                self.scrolling_state.select(Some(i), scrolloff);
-                break;
-            }
+        let positions = self.matching_positions(queue, config);
+        let next = positions.iter().find(|pos| **pos > selected).or(positions.first());
+        if let Some(next) = next {
+            self.scrolling_state.select(Some(*next), config.scrolloff);
         }
     }
 
-    pub fn jump_back(&mut self, queue: &[Song], scrolloff: usize) {
-        let Some(filter) = self.filter.as_ref() else {
-            status_warn!("No filter set");
-            return;
-        };
+    pub fn jump_back(&mut self, queue: &[Song], config: &Config) {
         let Some(selected) = self.scrolling_state.get_selected() else {
             error!(state:? = self.scrolling_state; "No song selected");
             return;
         };
 
-        let length = queue.len();
-        for i in (0..length).rev() {
-            let i = (i + selected) % length;
-            if queue[i].matches(self.column_formats.as_slice(), filter) {
-                self.scrolling_state.select(Some(i), scrolloff);
-                break;
-            }
+        let positions = self.matching_positions(queue, config);
+        let previous = positions.iter().rev().find(|pos| **pos < selected).or(positions.last());
+        if let Some(previous) = previous {
+            self.scrolling_state.select(Some(*previous), config.scrolloff);
         }
     }
 
-    pub fn jump_first(&mut self, queue: &[Song], scrolloff: usize) {
+    pub fn jump_first(&mut self, queue: &[Song], config: &Config) {
         let Some(filter) = self.filter.as_ref() else {
             status_warn!("No filter set");
             return;
@@ -683,7 +937,457 @@ impl QueuePane {
         queue
             .iter()
             .enumerate()
-            .find(|(_, item)| item.matches(self.column_formats.as_slice(), filter))
-            .inspect(|(idx, _)| self.scrolling_state.select(Some(*idx), scrolloff));
+            .find(|(_, item)| {
+                item.matches(
+                    self.column_formats.as_slice(),
+                    filter,
+                    config.theme.format_tag_separator,
+                )
+            })
+            .inspect(|(idx, _)| self.scrolling_state.select(Some(*idx), config.scrolloff));
+    }
+}
+
+/// Ranges around the current song which have to be deleted to keep only the current song. They are ordered from the
+/// end of the queue so the positions stay valid while deleting.
+fn crop_ranges(current_idx: usize, queue_len: usize) -> Vec<SingleOrRange> {
+    let mut ranges = Vec::new();
+    if current_idx + 1 < queue_len {
+        ranges.push(SingleOrRange::range(current_idx + 1, queue_len));
+    }
+    if current_idx > 0 {
+        ranges.push(SingleOrRange::range(0, current_idx));
+    }
+    ranges
+}
+
+/// Time until each song of the queue finishes playing. Counting starts at the current song, or at the first song when
+/// nothing is playing, and songs before it have no remaining time.
+fn remaining_times(queue: &[Song], current_idx: Option<usize>, elapsed: Duration) -> Vec<Option<Duration>> {
+    let start = current_idx.unwrap_or(0);
+    let elapsed = if current_idx.is_some() { elapsed } else { Duration::ZERO };
+    let mut total = Duration::ZERO;
+    queue
+        .iter()
+        .enumerate()
+        .map(|(idx, song)| {
+            if idx < start {
+                return None;
+            }
+            total += song.duration.unwrap_or_default();
+            Some(total.saturating_sub(elapsed))
+        })
+        .collect()
+}
+
+/// Asks whether to replace the stored playlist of the given name after saving the queue under it failed because it
+/// already exists
+fn open_overwrite_playlist_modal(
+    config: &'static Config,
+    app_event_sender: &Sender<AppEvent>,
+    name: String,
+) -> Result<()> {
+    let modal = ConfirmModal::with_config(config)
+        .message("A playlist with this name already exists. Do you want to overwrite it?")
+        .confirm_label("Overwrite")
+        .size(45, 7)
+        .on_confirm(move |client| {
+            client.replace_playlist_with_queue(&name)?;
+            status_info!("Playlist '{}' overwritten", name);
+            Ok(())
+        });
+    app_event_sender.send(AppEvent::UiAppEvent(UiAppEvent::Modal(ModalWrapper(Box::new(modal)))))?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use itertools::Itertools;
+    use rstest::rstest;
+
+    use std::time::Duration;
+
+    use super::{crop_ranges, remaining_times, QueuePane, QueueTimeMode};
+    use crate::{
+        config::{Config, DeleteSelectionBehavior, Leak},
+        context::AppContext,
+        mpd::{
+            commands::State,
+            mpd_client::{MpdClient, SingleOrRange},
+        },
+        tests::fixtures::{
+            app_context,
+            mpd_client::{client, TestMpdClient},
+        },
+        ui::{panes::Pane, UiAppEvent, UiEvent},
+        AppEvent,
+    };
+
+    fn screen_with_queue(client: &mut TestMpdClient, app_context: &AppContext) -> QueuePane {
+        // Songs 10 to 19 are from 'artist_1_album_2'
+        client.queue = vec![0, 10, 1, 11, 2];
+        let mut screen = QueuePane::new(app_context);
+        screen.scrolling_state.set_content_len(Some(client.queue.len()));
+        screen.filter = Some("artist_1_album_2".to_owned());
+        screen
+    }
+
+    #[rstest]
+    #[case(0, 1)]
+    #[case(1, 3)]
+    #[case(3, 1)]
+    fn jumps_forward_to_next_match(
+        #[case] selected: usize,
+        #[case] expected: usize,
+        mut client: TestMpdClient,
+        app_context: AppContext,
+    ) {
+        let mut screen = screen_with_queue(&mut client, &app_context);
+        let queue = client.playlist_info().unwrap().unwrap();
+        screen.scrolling_state.select(Some(selected), 0);
+
+        screen.jump_forward(&queue, app_context.config);
+
+        assert_eq!(screen.scrolling_state.get_selected(), Some(expected));
+    }
+
+    #[rstest]
+    #[case(4, 3)]
+    #[case(3, 1)]
+    #[case(1, 3)]
+    fn jumps_back_to_previous_match(
+        #[case] selected: usize,
+        #[case] expected: usize,
+        mut client: TestMpdClient,
+        app_context: AppContext,
+    ) {
+        let mut screen = screen_with_queue(&mut client, &app_context);
+        let queue = client.playlist_info().unwrap().unwrap();
+        screen.scrolling_state.select(Some(selected), 0);
+
+        screen.jump_back(&queue, app_context.config);
+
+        assert_eq!(screen.scrolling_state.get_selected(), Some(expected));
+    }
+
+    fn screen_with_selected(
+        selected: usize,
+        client: &mut TestMpdClient,
+        app_context: &mut AppContext,
+        delete_selection_behavior: DeleteSelectionBehavior,
+    ) -> QueuePane {
+        client.queue = (0..10).collect();
+        app_context.queue = client.playlist_info().unwrap().unwrap();
+        app_context.config = Config {
+            delete_selection_behavior,
+            ..app_context.config.clone()
+        }
+        .leak();
+        let mut screen = QueuePane::new(app_context);
+        screen.scrolling_state.set_content_len(Some(client.queue.len()));
+        screen.scrolling_state.select(Some(selected), 0);
+        screen
+    }
+
+    #[rstest]
+    #[case(DeleteSelectionBehavior::KeepIndex, 4, Some(4))]
+    #[case(DeleteSelectionBehavior::MoveUp, 4, Some(3))]
+    #[case(DeleteSelectionBehavior::KeepIndex, 9, Some(8))]
+    fn delete_selects_according_to_delete_selection_behavior(
+        #[case] delete_selection_behavior: DeleteSelectionBehavior,
+        #[case] selected: usize,
+        #[case] expected: Option<usize>,
+        mut client: TestMpdClient,
+        mut app_context: AppContext,
+    ) {
+        let mut screen = screen_with_selected(selected, &mut client, &mut app_context, delete_selection_behavior);
+        let delete = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE);
+
+        screen
+            .handle_action(&mut delete.into(), &mut client, &mut app_context)
+            .unwrap();
+
+        assert_eq!(client.queue.len(), 9);
+        assert_eq!(screen.scrolling_state.get_selected(), expected);
+    }
+
+    #[rstest]
+    fn deleting_marked_songs_uses_the_first_marked_position(mut client: TestMpdClient, mut app_context: AppContext) {
+        let mut screen = screen_with_selected(9, &mut client, &mut app_context, DeleteSelectionBehavior::KeepIndex);
+        screen.scrolling_state.marked.extend([2, 3, 7]);
+        let delete = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE);
+
+        screen
+            .handle_action(&mut delete.into(), &mut client, &mut app_context)
+            .unwrap();
+
+        assert_eq!(client.queue, vec![0, 1, 4, 5, 6, 8, 9]);
+        assert!(screen.scrolling_state.marked.is_empty());
+        assert_eq!(screen.scrolling_state.get_selected(), Some(2));
+    }
+
+    #[rstest]
+    fn deletes_contiguous_marked_songs_as_ranges(mut client: TestMpdClient, mut app_context: AppContext) {
+        let mut screen = screen_with_selected(0, &mut client, &mut app_context, DeleteSelectionBehavior::KeepIndex);
+        screen.scrolling_state.marked.extend([2, 3, 4, 7]);
+        let delete = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE);
+
+        screen
+            .handle_action(&mut delete.into(), &mut client, &mut app_context)
+            .unwrap();
+
+        assert_eq!(client.calls.len(), 2);
+        assert_eq!(client.calls.get(r#"delete_from_queue "2:5""#), Some(&1));
+        assert_eq!(client.calls.get(r#"delete_from_queue "7:8""#), Some(&1));
+        assert_eq!(client.queue, vec![0, 1, 5, 6, 8, 9]);
+    }
+
+    #[rstest]
+    #[case(Some(1), Duration::from_secs(1), vec![None, Some(1), Some(4), Some(8)])]
+    #[case(None, Duration::from_secs(1), vec![Some(1), Some(3), Some(6), Some(10)])]
+    fn remaining_times_are_cumulative_from_the_current_song(
+        #[case] current_idx: Option<usize>,
+        #[case] elapsed: Duration,
+        #[case] expected: Vec<Option<u64>>,
+        mut client: TestMpdClient,
+    ) {
+        // Song 'n' of the first album is 'n' seconds long
+        client.queue = vec![1, 2, 3, 4];
+        let queue = client.playlist_info().unwrap().unwrap();
+
+        let result = remaining_times(&queue, current_idx, elapsed);
+
+        assert_eq!(
+            result,
+            expected
+                .into_iter()
+                .map(|v| v.map(Duration::from_secs))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[rstest]
+    fn toggle_queue_time_cycles_through_modes(mut client: TestMpdClient, mut app_context: AppContext) {
+        let mut screen = QueuePane::new(&app_context);
+        let toggle = KeyEvent::new(KeyCode::Char('T'), KeyModifiers::SHIFT);
+        let mut modes = Vec::new();
+
+        for _ in 0..3 {
+            screen
+                .handle_action(&mut toggle.into(), &mut client, &mut app_context)
+                .unwrap();
+            modes.push(screen.time_mode);
+        }
+
+        assert_eq!(
+            modes,
+            [QueueTimeMode::Remaining, QueueTimeMode::Hidden, QueueTimeMode::Duration]
+        );
+    }
+
+    #[rstest]
+    fn keeps_selection_without_matches(mut client: TestMpdClient, app_context: AppContext) {
+        let mut screen = screen_with_queue(&mut client, &app_context);
+        screen.filter = Some("nothing matches this".to_owned());
+        let queue = client.playlist_info().unwrap().unwrap();
+        screen.scrolling_state.select(Some(2), 0);
+
+        screen.jump_forward(&queue, app_context.config);
+
+        assert_eq!(screen.scrolling_state.get_selected(), Some(2));
+    }
+
+    #[rstest]
+    fn jumps_to_current_song_regardless_of_filter(mut client: TestMpdClient, mut app_context: AppContext) {
+        let mut screen = screen_with_queue(&mut client, &app_context);
+        app_context.queue = client.playlist_info().unwrap().unwrap();
+        app_context.status.state = State::Play;
+        app_context.status.songid = Some(app_context.queue[2].id);
+        screen.filter = Some("nothing matches this".to_owned());
+        screen.scrolling_state.select(Some(4), 0);
+        let jump = KeyEvent::new(KeyCode::Char('C'), KeyModifiers::SHIFT);
+
+        screen
+            .handle_action(&mut jump.into(), &mut client, &mut app_context)
+            .unwrap();
+
+        assert_eq!(screen.scrolling_state.get_selected(), Some(2));
+    }
+
+    #[rstest]
+    fn does_not_jump_when_nothing_is_playing(mut client: TestMpdClient, mut app_context: AppContext) {
+        let mut screen = screen_with_queue(&mut client, &app_context);
+        app_context.queue = client.playlist_info().unwrap().unwrap();
+        app_context.status.state = State::Stop;
+        app_context.status.songid = Some(app_context.queue[2].id);
+        screen.scrolling_state.select(Some(4), 0);
+        let jump = KeyEvent::new(KeyCode::Char('C'), KeyModifiers::SHIFT);
+
+        screen
+            .handle_action(&mut jump.into(), &mut client, &mut app_context)
+            .unwrap();
+
+        assert_eq!(screen.scrolling_state.get_selected(), Some(4));
+    }
+
+    #[rstest]
+    fn follows_playing_song_until_moved_manually(mut client: TestMpdClient, mut app_context: AppContext) {
+        let mut screen = screen_with_queue(&mut client, &app_context);
+        app_context.queue = client.playlist_info().unwrap().unwrap();
+        app_context.status.state = State::Play;
+        app_context.status.songid = Some(app_context.queue[2].id);
+        screen.filter = None;
+        screen.follow_playing = true;
+        screen.scrolling_state.select(Some(0), 0);
+
+        screen
+            .on_event(&mut UiEvent::Player, &mut client, &app_context)
+            .unwrap();
+        assert_eq!(screen.scrolling_state.get_selected(), Some(2));
+
+        let down = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE);
+        screen
+            .handle_action(&mut down.into(), &mut client, &mut app_context)
+            .unwrap();
+        screen
+            .on_event(&mut UiEvent::Player, &mut client, &app_context)
+            .unwrap();
+        assert_eq!(screen.scrolling_state.get_selected(), Some(3));
+
+        app_context.status.songid = Some(app_context.queue[4].id);
+        screen
+            .on_event(&mut UiEvent::SongChanged, &mut client, &app_context)
+            .unwrap();
+        assert_eq!(screen.scrolling_state.get_selected(), Some(4));
+    }
+
+    #[rstest]
+    fn does_not_follow_playing_song_when_disabled(mut client: TestMpdClient, mut app_context: AppContext) {
+        let mut screen = screen_with_queue(&mut client, &app_context);
+        app_context.queue = client.playlist_info().unwrap().unwrap();
+        app_context.status.state = State::Play;
+        app_context.status.songid = Some(app_context.queue[2].id);
+        screen.scrolling_state.select(Some(0), 0);
+
+        screen
+            .on_event(&mut UiEvent::Player, &mut client, &app_context)
+            .unwrap();
+
+        assert_eq!(screen.scrolling_state.get_selected(), Some(0));
+    }
+
+    fn queue_files(app_context: &AppContext) -> Vec<&str> {
+        app_context.queue.iter().map(|song| song.file.as_str()).collect()
+    }
+
+    #[rstest]
+    fn moving_a_song_updates_the_queue_right_away(mut client: TestMpdClient, mut app_context: AppContext) {
+        let mut screen = screen_with_selected(2, &mut client, &mut app_context, DeleteSelectionBehavior::KeepIndex);
+        let moved = app_context.queue[2].file.clone();
+        let move_down = KeyEvent::new(KeyCode::Char('J'), KeyModifiers::SHIFT);
+
+        screen
+            .handle_action(&mut move_down.into(), &mut client, &mut app_context)
+            .unwrap();
+        screen
+            .handle_action(&mut move_down.into(), &mut client, &mut app_context)
+            .unwrap();
+
+        assert_eq!(app_context.queue[4].file, moved);
+        assert_eq!(screen.scrolling_state.get_selected(), Some(4));
+    }
+
+    #[rstest]
+    fn moving_marked_songs_moves_the_whole_block(mut client: TestMpdClient, mut app_context: AppContext) {
+        let mut screen = screen_with_selected(0, &mut client, &mut app_context, DeleteSelectionBehavior::KeepIndex);
+        let before = queue_files(&app_context).into_iter().map(str::to_owned).collect_vec();
+        screen.scrolling_state.mark(3);
+        screen.scrolling_state.mark(4);
+        let move_up = KeyEvent::new(KeyCode::Char('K'), KeyModifiers::SHIFT);
+
+        screen
+            .handle_action(&mut move_up.into(), &mut client, &mut app_context)
+            .unwrap();
+
+        assert_eq!(
+            queue_files(&app_context)[1..6],
+            [&before[1], &before[3], &before[4], &before[2], &before[5]]
+        );
+        assert_eq!(
+            screen.scrolling_state.get_marked().iter().copied().collect_vec(),
+            [2, 3]
+        );
+        assert_eq!(client.calls.len(), 1);
+    }
+
+    fn screen_playing(
+        current: usize,
+        client: &mut TestMpdClient,
+        app_context: &mut AppContext,
+    ) -> (QueuePane, std::sync::mpsc::Receiver<AppEvent>) {
+        let screen = screen_with_selected(5, client, app_context, DeleteSelectionBehavior::KeepIndex);
+        app_context.status.state = State::Play;
+        app_context.status.songid = Some(app_context.queue[current].id);
+        let (tx, rx) = std::sync::mpsc::channel();
+        app_context.app_event_sender = tx;
+        (screen, rx)
+    }
+
+    #[rstest]
+    #[case(0, 10, vec![SingleOrRange::range(1, 10)])]
+    #[case(4, 10, vec![SingleOrRange::range(5, 10), SingleOrRange::range(0, 4)])]
+    #[case(9, 10, vec![SingleOrRange::range(0, 9)])]
+    #[case(0, 1, vec![])]
+    fn crop_deletes_everything_around_the_current_song_from_the_end(
+        #[case] current: usize,
+        #[case] queue_len: usize,
+        #[case] expected: Vec<SingleOrRange>,
+    ) {
+        assert_eq!(crop_ranges(current, queue_len), expected);
+    }
+
+    #[rstest]
+    fn crop_asks_for_confirmation_first(mut client: TestMpdClient, mut app_context: AppContext) {
+        let (mut screen, rx) = screen_playing(4, &mut client, &mut app_context);
+        let crop = KeyEvent::new(KeyCode::Char('X'), KeyModifiers::SHIFT);
+
+        screen
+            .handle_action(&mut crop.into(), &mut client, &mut app_context)
+            .unwrap();
+
+        assert!(matches!(rx.try_recv(), Ok(AppEvent::UiAppEvent(UiAppEvent::Modal(_)))));
+        assert_eq!(client.queue.len(), 10);
+    }
+
+    #[rstest]
+    #[case(0, None, 10)]
+    #[case(4, Some(r#"delete_ranges_from_queue "0:4""#), 6)]
+    #[case(9, Some(r#"delete_ranges_from_queue "0:9""#), 1)]
+    fn remove_played_deletes_the_songs_before_the_current_one(
+        #[case] current: usize,
+        #[case] expected_call: Option<&str>,
+        #[case] expected_len: usize,
+        mut client: TestMpdClient,
+        mut app_context: AppContext,
+    ) {
+        let (mut screen, _rx) = screen_playing(current, &mut client, &mut app_context);
+        let remove_played = KeyEvent::new(KeyCode::Char('R'), KeyModifiers::SHIFT);
+
+        screen
+            .handle_action(&mut remove_played.into(), &mut client, &mut app_context)
+            .unwrap();
+
+        let delete_calls = client
+            .calls
+            .keys()
+            .filter(|call| call.starts_with("delete_ranges_from_queue"))
+            .collect_vec();
+        assert_eq!(delete_calls, expected_call.iter().collect_vec());
+        assert_eq!(client.queue.len(), expected_len);
+        assert_eq!(client.queue[0], current);
+        let expected_selected = if expected_call.is_some() { 0 } else { 5 };
+        assert_eq!(screen.scrolling_state.get_selected(), Some(expected_selected));
     }
 }
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::rc::Rc;
 
 use anyhow::Context;
@@ -10,31 +12,37 @@ use ratatui::style::Styled;
 use ratatui::widgets::Padding;
 use ratatui::{
     layout::{Constraint, Layout},
-    widgets::{Block, Borders, List, ListItem},
+    widgets::{Block, Borders, Clear, List, ListItem},
 };
 
 use crate::cli::create_env;
 use crate::cli::run_external;
 use crate::config::keys::GlobalAction;
+use crate::config::keys::SearchActions;
+use crate::config::tabs::PaneType;
 use crate::config::Config;
 use crate::config::Search;
 use crate::context::AppContext;
 use crate::mpd::commands::Song;
-use crate::shared::ext::mpd_client::MpdClientExt;
+use crate::shared::ext::mpd_client::{MpdClientExt, SongAdd};
 use crate::shared::key_event::KeyEvent;
+use crate::shared::macros::modal;
 use crate::shared::macros::status_info;
 use crate::shared::macros::status_warn;
 use crate::shared::mouse_event::MouseEvent;
 use crate::shared::mouse_event::MouseEventKind;
 use crate::ui::dirstack::Dir;
+use crate::ui::modals::confirm_modal::ConfirmModal;
 use crate::ui::UiEvent;
 use crate::{
-    mpd::mpd_client::{Filter, FilterKind, MpdClient, Tag},
+    mpd::mpd_client::{Filter, FilterExpression, FilterKind, MpdClient, Tag, ValueChange},
     ui::widgets::{button::Button, input::Input},
 };
 
 use super::{CommonAction, Pane};
 
+const MAX_SUGGESTIONS: usize = 5;
+
 #[derive(Debug)]
 pub struct SearchPane {
     inputs: InputGroups<2, 1>,
@@ -43,6 +51,10 @@ pub struct SearchPane {
     songs_dir: Dir<Song>,
     input_areas: Rc<[Rect]>,
     column_areas: [Rect; 3],
+    /// All values of a tag as returned by `list`, fetched lazily the first time
+    /// the tag's textbox is typed into and kept until the database changes
+    tag_values: HashMap<&'static str, Vec<String>>,
+    suggestions: Vec<String>,
 }
 
 impl SearchPane {
@@ -75,21 +87,96 @@ impl SearchPane {
             ),
             input_areas: Rc::default(),
             column_areas: [Rect::default(); 3],
+            tag_values: HashMap::new(),
+            suggestions: Vec::new(),
         }
     }
 
+    fn update_suggestions(&mut self, client: &mut impl MpdClient) {
+        self.suggestions.clear();
+        let FocusedInputGroup::Textboxes(Textbox { value, filter_key, .. }) = self.inputs.focused() else {
+            return;
+        };
+        // These are not real tags and cannot be listed
+        if value.is_empty() || matches!(*filter_key, "any" | "file" | "filename" | "base") {
+            return;
+        }
+
+        let values = self.tag_values.entry(*filter_key).or_insert_with(|| {
+            client.list_tag(Tag::Custom(filter_key), None).map_or_else(
+                |err| {
+                    log::warn!(tag = *filter_key, error:? = err; "Failed to list tag values for autocomplete");
+                    Vec::new()
+                },
+                |list| list.0,
+            )
+        });
+
+        let prefix = value.to_lowercase();
+        self.suggestions = values
+            .iter()
+            .filter(|v| v.len() > prefix.len() && v.to_lowercase().starts_with(&prefix))
+            .take(MAX_SUGGESTIONS)
+            .cloned()
+            .collect_vec();
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn render_suggestions(&self, frame: &mut ratatui::prelude::Frame, area: Rect, config: &Config) {
+        if !matches!(self.phase, Phase::SearchTextboxInput) || self.suggestions.is_empty() {
+            return;
+        }
+        let FocusedInput::Textboxes(idx) = self.inputs.focused_idx else {
+            return;
+        };
+        let Some(input_area) = self.input_areas.get(idx) else {
+            return;
+        };
+        let label_len = self.inputs.textbox_inputs[idx].label.chars().count() as u16 + 1;
+
+        let y = input_area.y + 1;
+        let dropdown_area = Rect {
+            x: input_area.x + label_len.min(input_area.width),
+            y,
+            width: input_area.width.saturating_sub(label_len),
+            height: (self.suggestions.len() as u16 + 2).min(area.bottom().saturating_sub(y)),
+        };
+
+        let list = List::new(self.suggestions.iter().map(String::as_str))
+            .style(config.as_text_style())
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(config.as_border_style()),
+            );
+        frame.render_widget(Clear, dropdown_area);
+        frame.render_widget(list, dropdown_area);
+    }
+
     fn add_current(&mut self, autoplay: bool, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
         if !self.songs_dir.marked().is_empty() {
-            for idx in self.songs_dir.marked() {
+            let position = context.add_position();
+            // Songs inserted at a fixed position end up in reverse order unless added back to front
+            let mut marked = self.songs_dir.marked().iter().collect_vec();
+            if position.is_some() {
+                marked.reverse();
+            }
+            let mut added = 0;
+            for idx in marked {
                 let item = &self.songs_dir.items[*idx];
-                client.add(&item.file)?;
+                if client.add_song(&item.file, context)? != SongAdd::Skipped {
+                    added += 1;
+                }
             }
-            status_info!("Added {} songs to queue", self.songs_dir.marked().len());
+            status_info!("Added {added} songs to queue");
 
             context.render()?;
         } else if let Some(item) = self.songs_dir.selected() {
-            client.add(&item.file)?;
-            status_info!("Added '{}' to queue", item.file);
+            match client.add_song(&item.file, context)? {
+                SongAdd::Added => status_info!("Added '{}' to queue", item.file),
+                SongAdd::AddedDuplicate => {}
+                SongAdd::Skipped => return Ok(()),
+            }
             if autoplay {
                 client.play_last(context)?;
             }
@@ -100,12 +187,47 @@ impl SearchPane {
         Ok(())
     }
 
+    fn play_now(&self, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
+        let songs = if self.songs_dir.marked().is_empty() {
+            self.songs_dir
+                .selected()
+                .map(|song| song.file.clone())
+                .into_iter()
+                .collect_vec()
+        } else {
+            self.songs_dir
+                .marked_items()
+                .map(|song| song.file.clone())
+                .collect_vec()
+        };
+
+        if songs.is_empty() {
+            return Ok(());
+        }
+
+        if context.queue.is_empty() {
+            replace_queue_and_play(client, &songs)?;
+        } else {
+            modal!(
+                context,
+                ConfirmModal::new(context)
+                    .message("This will replace the current queue. Are you sure you want to continue?")
+                    .on_confirm(move |client| replace_queue_and_play(client, &songs))
+                    .confirm_label("Replace")
+                    .size(45, 6)
+            );
+        }
+
+        Ok(())
+    }
+
     fn render_song_column(
         &mut self,
         frame: &mut ratatui::prelude::Frame<'_>,
         area: ratatui::prelude::Rect,
-        config: &Config,
+        context: &AppContext,
     ) {
+        let config = context.config;
         let title = self.songs_dir.filter().as_ref().map(|v| {
             format!(
                 "[FILTER]: {v}{} ",
@@ -124,7 +246,8 @@ impl SearchPane {
             }
             b.padding(Padding::new(0, 2, 0, 0))
         };
-        let current = List::new(self.songs_dir.to_list_items(config)).highlight_style(config.theme.current_item_style);
+        let current = List::new(self.songs_dir.to_list_items(config, &context.ratings))
+            .highlight_style(context.current_item_style());
         let directory = &mut self.songs_dir;
 
         directory.state.set_content_len(Some(directory.items.len()));
@@ -153,11 +276,12 @@ impl SearchPane {
     fn prepare_preview(
         &mut self,
         client: &mut impl MpdClient,
-        config: &Config,
+        context: &AppContext,
     ) -> Result<Option<Vec<ListItem<'static>>>> {
+        let config = context.config;
         match &self.phase {
             Phase::SearchTextboxInput => Ok(None),
-            Phase::Search => Ok(Some(self.songs_dir.to_list_items(config))),
+            Phase::Search => Ok(Some(self.songs_dir.to_list_items(config, &context.ratings))),
             Phase::BrowseResults { .. } => {
                 let Some(current) = self.songs_dir.selected() else {
                     return Ok(None);
@@ -167,7 +291,7 @@ impl SearchPane {
                     .find(&[Filter::new(Tag::File, &current.file)])?
                     .first()
                     .context("Expected to find exactly one song")?
-                    .to_preview(&config.theme.symbols)
+                    .to_preview(&config.theme.symbols, config.theme.format_tag_separator)
                     .collect_vec();
                 Ok(Some(preview))
             }
@@ -288,6 +412,8 @@ impl SearchPane {
             }
             frame.render_widget(button, input_areas[idx]);
         }
+
+        self.render_suggestions(frame, area, config);
     }
 
     fn filter_type(&self) -> (FilterKind, bool) {
@@ -307,49 +433,49 @@ impl SearchPane {
             })
     }
 
-    fn search_add(&mut self, client: &mut impl MpdClient) -> Result<()> {
-        let (filter_kind, case_sensitive) = self.filter_type();
-        let filter = self.inputs.textbox_inputs.iter().filter_map(|input| match &input {
-            Textbox { value, filter_key, .. } if !value.is_empty() => {
-                Some(Filter::new(*filter_key, value).with_type(filter_kind))
-            }
-            _ => None,
-        });
+    /// Combines the values of all non empty textboxes with AND. A value can list alternatives
+    /// separated by `|` and can be negated by prefixing it with `!`.
+    fn filter_expression(&self) -> FilterExpression<'_, '_> {
+        let (filter_kind, _) = self.filter_type();
+        FilterExpression::And(
+            self.inputs
+                .textbox_inputs
+                .iter()
+                .filter(|input| !input.value.is_empty())
+                .map(|Textbox { value, filter_key, .. }| textbox_expression(filter_key, value, filter_kind))
+                .collect_vec(),
+        )
+    }
 
-        let filter = filter.collect_vec();
+    fn search_add(&mut self, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
+        let (_, case_sensitive) = self.filter_type();
+        let expression = self.filter_expression();
 
-        if filter.is_empty() {
+        if expression.is_empty() {
             return Ok(());
         }
 
         if case_sensitive {
-            client.find_add(&filter)?;
+            client.find_add_expression(&expression, context.add_position())?;
         } else {
-            client.search_add(&filter)?;
+            client.search_add_expression(&expression, context.add_position())?;
         }
 
         Ok(())
     }
 
     fn search(&mut self, client: &mut impl MpdClient) -> Result<Vec<Song>> {
-        let (filter_kind, case_sensitive) = self.filter_type();
-        let filter = self.inputs.textbox_inputs.iter().filter_map(|input| match &input {
-            Textbox { value, filter_key, .. } if !value.is_empty() => {
-                Some(Filter::new(*filter_key, value).with_type(filter_kind))
-            }
-            _ => None,
-        });
+        let (_, case_sensitive) = self.filter_type();
+        let expression = self.filter_expression();
 
-        let filter = filter.collect_vec();
-
-        if filter.is_empty() {
+        if expression.is_empty() {
             return Ok(Vec::new());
         }
 
         Ok(if case_sensitive {
-            client.find(&filter)?
+            client.find_expression(&expression)?
         } else {
-            client.search(&filter)?
+            client.search_expression(&expression)?
         })
     }
 
@@ -370,12 +496,15 @@ impl SearchPane {
 
     fn activate_input(&mut self, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
         match self.inputs.focused_mut() {
-            FocusedInputGroup::Textboxes(_) => self.phase = Phase::SearchTextboxInput,
+            FocusedInputGroup::Textboxes(_) => {
+                self.phase = Phase::SearchTextboxInput;
+                self.suggestions.clear();
+            }
             FocusedInputGroup::Buttons(_) => {
                 // Reset is the only button in this group at the moment
                 self.reset(&context.config.search);
                 self.songs_dir = Dir::default();
-                self.preview = self.prepare_preview(client, context.config)?;
+                self.preview = self.prepare_preview(client, context)?;
             }
             FocusedInputGroup::Filters(FilterInput {
                 variant: FilterInputVariant::SelectFilterKind { ref mut value },
@@ -383,7 +512,7 @@ impl SearchPane {
             }) => {
                 value.cycle();
                 self.songs_dir = Dir::new(self.search(client)?);
-                self.preview = self.prepare_preview(client, context.config)?;
+                self.preview = self.prepare_preview(client, context)?;
             }
             FocusedInputGroup::Filters(FilterInput {
                 variant: FilterInputVariant::SelectFilterCaseSensitive { ref mut value },
@@ -391,7 +520,7 @@ impl SearchPane {
             }) => {
                 *value = !*value;
                 self.songs_dir = Dir::new(self.search(client)?);
-                self.preview = self.prepare_preview(client, context.config)?;
+                self.preview = self.prepare_preview(client, context)?;
             }
         };
         Ok(())
@@ -429,9 +558,10 @@ impl Pane for SearchPane {
         &mut self,
         frame: &mut ratatui::prelude::Frame,
         area: ratatui::prelude::Rect,
-        AppContext { config, .. }: &AppContext,
+        context: &AppContext,
     ) -> anyhow::Result<()> {
-        let widths = &config.theme.column_widths;
+        let config = context.config;
+        let widths = config.theme.browser_column_widths(PaneType::Search);
         let [previous_area, current_area_init, preview_area] = *Layout::horizontal([
             Constraint::Percentage(widths[0]),
             Constraint::Percentage(widths[1]),
@@ -484,7 +614,7 @@ impl Pane for SearchPane {
                 }
             }
             Phase::BrowseResults { filter_input_on: _ } => {
-                self.render_song_column(frame, current_area, config);
+                self.render_song_column(frame, current_area, context);
                 self.render_input_column(frame, previous_area, config);
                 if let Some(preview) = &self.preview {
                     let preview = List::new(preview.clone()).highlight_style(config.theme.current_item_style);
@@ -502,7 +632,8 @@ impl Pane for SearchPane {
     fn on_event(&mut self, event: &mut UiEvent, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
         if let crate::ui::UiEvent::Database = event {
             self.songs_dir = Dir::default();
-            self.preview = self.prepare_preview(client, context.config)?;
+            self.tag_values.clear();
+            self.preview = self.prepare_preview(client, context)?;
             self.phase = Phase::Search;
 
             status_warn!("The music database has been updated. The current tab has been reinitialized in the root directory to prevent inconsistent behaviours.");
@@ -511,6 +642,13 @@ impl Pane for SearchPane {
         Ok(())
     }
 
+    fn on_database_update(&mut self, context: &AppContext) {
+        self.songs_dir = Dir::default();
+        self.tag_values.clear();
+        self.preview = Some(self.songs_dir.to_list_items(context.config, &context.ratings));
+        self.phase = Phase::Search;
+    }
+
     fn handle_mouse_event(
         &mut self,
         mut event: MouseEvent,
@@ -527,7 +665,7 @@ impl Pane for SearchPane {
                 if let Some(input) = self.get_clicked_input(event) {
                     self.inputs.focused_idx = input;
                 }
-                self.preview = self.prepare_preview(client, context.config)?;
+                self.preview = self.prepare_preview(client, context)?;
 
                 context.render()?;
             }
@@ -544,7 +682,7 @@ impl Pane for SearchPane {
                             self.songs_dir.select_idx(idx_to_select, context.config.scrolloff);
                         }
 
-                        self.preview = self.prepare_preview(client, context.config)?;
+                        self.preview = self.prepare_preview(client, context)?;
 
                         context.render()?;
                     }
@@ -558,7 +696,7 @@ impl Pane for SearchPane {
                     if matches!(self.phase, Phase::SearchTextboxInput) {
                         self.phase = Phase::Search;
                         self.songs_dir = Dir::new(self.search(client)?);
-                        self.preview = self.prepare_preview(client, context.config)?;
+                        self.preview = self.prepare_preview(client, context)?;
                     }
 
                     if let Some(input) = self.get_clicked_input(event) {
@@ -571,7 +709,7 @@ impl Pane for SearchPane {
                     let clicked_row = event.y.saturating_sub(self.column_areas[1].y).into();
                     if let Some(idx) = self.songs_dir.state.get_at_rendered_row(clicked_row) {
                         self.songs_dir.select_idx(idx, context.config.scrolloff);
-                        self.preview = self.prepare_preview(client, context.config)?;
+                        self.preview = self.prepare_preview(client, context)?;
 
                         context.render()?;
                     }
@@ -594,7 +732,7 @@ impl Pane for SearchPane {
                     if matches!(self.phase, Phase::SearchTextboxInput) {
                         self.phase = Phase::Search;
                         self.songs_dir = Dir::new(self.search(client)?);
-                        self.preview = self.prepare_preview(client, context.config)?;
+                        self.preview = self.prepare_preview(client, context)?;
                     }
                     self.inputs.next_non_wrapping();
 
@@ -611,7 +749,7 @@ impl Pane for SearchPane {
                     if matches!(self.phase, Phase::SearchTextboxInput) {
                         self.phase = Phase::Search;
                         self.songs_dir = Dir::new(self.search(client)?);
-                        self.preview = self.prepare_preview(client, context.config)?;
+                        self.preview = self.prepare_preview(client, context)?;
                     }
 
                     self.inputs.prev_non_wrapping();
@@ -630,21 +768,33 @@ impl Pane for SearchPane {
         Ok(())
     }
 
-    fn handle_action(&mut self, event: &mut KeyEvent, client: &mut impl MpdClient, context: &AppContext) -> Result<()> {
+    fn is_input_active(&self) -> bool {
+        matches!(
+            self.phase,
+            Phase::SearchTextboxInput | Phase::BrowseResults { filter_input_on: true }
+        )
+    }
+
+    fn handle_action(
+        &mut self,
+        event: &mut KeyEvent,
+        client: &mut impl MpdClient,
+        context: &mut AppContext,
+    ) -> Result<()> {
         let config = context.config;
         match &mut self.phase {
             Phase::SearchTextboxInput => match event.as_common_action(context) {
                 Some(CommonAction::Close) => {
                     self.phase = Phase::Search;
                     self.songs_dir = Dir::new(self.search(client)?);
-                    self.preview = self.prepare_preview(client, config)?;
+                    self.preview = self.prepare_preview(client, context)?;
 
                     context.render()?;
                 }
                 Some(CommonAction::Confirm) => {
                     self.phase = Phase::Search;
                     self.songs_dir = Dir::new(self.search(client)?);
-                    self.preview = self.prepare_preview(client, config)?;
+                    self.preview = self.prepare_preview(client, context)?;
 
                     context.render()?;
                 }
@@ -654,6 +804,7 @@ impl Pane for SearchPane {
                         KeyCode::Char(c) => match self.inputs.focused_mut() {
                             FocusedInputGroup::Textboxes(Textbox { value, .. }) => {
                                 value.push(c);
+                                self.update_suggestions(client);
 
                                 context.render()?;
                             }
@@ -662,6 +813,16 @@ impl Pane for SearchPane {
                         KeyCode::Backspace => match self.inputs.focused_mut() {
                             FocusedInputGroup::Textboxes(Textbox { value, .. }) => {
                                 value.pop();
+                                self.update_suggestions(client);
+
+                                context.render()?;
+                            }
+                            FocusedInputGroup::Filters(_) | FocusedInputGroup::Buttons(_) => {}
+                        },
+                        KeyCode::Tab if !self.suggestions.is_empty() => match self.inputs.focused_mut() {
+                            FocusedInputGroup::Textboxes(Textbox { value, .. }) => {
+                                *value = self.suggestions.remove(0);
+                                self.suggestions.clear();
 
                                 context.render()?;
                             }
@@ -705,7 +866,7 @@ impl Pane for SearchPane {
                         CommonAction::UpHalf => {}
                         CommonAction::Right if !self.songs_dir.items.is_empty() => {
                             self.phase = Phase::BrowseResults { filter_input_on: false };
-                            self.preview = self.prepare_preview(client, config)?;
+                            self.preview = self.prepare_preview(client, context)?;
 
                             context.render()?;
                         }
@@ -736,11 +897,12 @@ impl Pane for SearchPane {
                             if matches!(self.inputs.focused(), FocusedInputGroup::Textboxes(_)) =>
                         {
                             self.phase = Phase::SearchTextboxInput;
+                            self.suggestions.clear();
 
                             context.render()?;
                         }
                         CommonAction::AddAll => {
-                            self.search_add(client)?;
+                            self.search_add(client, context)?;
 
                             status_info!("All found songs added to queue");
 
@@ -752,7 +914,7 @@ impl Pane for SearchPane {
                             FocusedInputGroup::Textboxes(textbox) if !textbox.value.is_empty() => {
                                 textbox.value.clear();
                                 self.songs_dir = Dir::new(self.search(client)?);
-                                self.preview = self.prepare_preview(client, config)?;
+                                self.preview = self.prepare_preview(client, context)?;
 
                                 context.render()?;
                             }
@@ -762,6 +924,9 @@ impl Pane for SearchPane {
                         CommonAction::PaneUp => {}
                         CommonAction::PaneRight => {}
                         CommonAction::PaneLeft => {}
+                        CommonAction::ToggleMatchMode => {}
+                        CommonAction::RateUp => {}
+                        CommonAction::RateDown => {}
                     }
                 }
             }
@@ -771,7 +936,7 @@ impl Pane for SearchPane {
                 Some(CommonAction::Close) => {
                     *filter_input_on = false;
                     self.songs_dir.set_filter(None, config);
-                    self.preview = self.prepare_preview(client, config)?;
+                    self.preview = self.prepare_preview(client, context)?;
 
                     context.render()?;
                 }
@@ -786,7 +951,7 @@ impl Pane for SearchPane {
                         KeyCode::Char(c) => {
                             self.songs_dir.push_filter(c, config);
                             self.songs_dir.jump_first_matching(config);
-                            self.preview = self.prepare_preview(client, config)?;
+                            self.preview = self.prepare_preview(client, context)?;
 
                             context.render()?;
                         }
@@ -802,7 +967,15 @@ impl Pane for SearchPane {
             Phase::BrowseResults {
                 filter_input_on: filter_input_modce @ false,
             } => {
-                if let Some(action) = event.as_global_action(context) {
+                if let Some(action) = event.as_search_action(context) {
+                    match action {
+                        SearchActions::PlayNow => {
+                            self.play_now(client, context)?;
+
+                            context.render()?;
+                        }
+                    }
+                } else if let Some(action) = event.as_global_action(context) {
                     match action {
                         GlobalAction::ExternalCommand { command, .. } if !self.songs_dir.marked().is_empty() => {
                             let songs = self.songs_dir.marked_items().map(|song| song.file.as_str());
@@ -821,14 +994,14 @@ impl Pane for SearchPane {
                         CommonAction::Down => {
                             self.songs_dir
                                 .next(context.config.scrolloff, context.config.wrap_navigation);
-                            self.preview = self.prepare_preview(client, config)?;
+                            self.preview = self.prepare_preview(client, context)?;
 
                             context.render()?;
                         }
                         CommonAction::Up => {
                             self.songs_dir
                                 .prev(context.config.scrolloff, context.config.wrap_navigation);
-                            self.preview = self.prepare_preview(client, config)?;
+                            self.preview = self.prepare_preview(client, context)?;
 
                             context.render()?;
                         }
@@ -836,32 +1009,32 @@ impl Pane for SearchPane {
                         CommonAction::MoveUp => {}
                         CommonAction::DownHalf => {
                             self.songs_dir.next_half_viewport(context.config.scrolloff);
-                            self.preview = self.prepare_preview(client, config)?;
+                            self.preview = self.prepare_preview(client, context)?;
 
                             context.render()?;
                         }
                         CommonAction::UpHalf => {
                             self.songs_dir.prev_half_viewport(context.config.scrolloff);
-                            self.preview = self.prepare_preview(client, config)?;
+                            self.preview = self.prepare_preview(client, context)?;
 
                             context.render()?;
                         }
                         CommonAction::Right => self.add_current(false, client, context)?,
                         CommonAction::Left => {
                             self.phase = Phase::Search;
-                            self.preview = self.prepare_preview(client, config)?;
+                            self.preview = self.prepare_preview(client, context)?;
 
                             context.render()?;
                         }
                         CommonAction::Top => {
                             self.songs_dir.first();
-                            self.preview = self.prepare_preview(client, config)?;
+                            self.preview = self.prepare_preview(client, context)?;
 
                             context.render()?;
                         }
                         CommonAction::Bottom => {
                             self.songs_dir.last();
-                            self.preview = self.prepare_preview(client, config)?;
+                            self.preview = self.prepare_preview(client, context)?;
 
                             context.render()?;
                         }
@@ -873,13 +1046,13 @@ impl Pane for SearchPane {
                         }
                         CommonAction::NextResult => {
                             self.songs_dir.jump_next_matching(config);
-                            self.preview = self.prepare_preview(client, config)?;
+                            self.preview = self.prepare_preview(client, context)?;
 
                             context.render()?;
                         }
                         CommonAction::PreviousResult => {
                             self.songs_dir.jump_previous_matching(config);
-                            self.preview = self.prepare_preview(client, config)?;
+                            self.preview = self.prepare_preview(client, context)?;
 
                             context.render()?;
                         }
@@ -903,9 +1076,17 @@ impl Pane for SearchPane {
                             context.render()?;
                         }
                         CommonAction::FocusInput => {}
-                        CommonAction::Add => self.add_current(false, client, context)?,
+                        CommonAction::Add => {
+                            self.add_current(false, client, context)?;
+                            if context.config.advance_after_add {
+                                self.songs_dir.next(context.config.scrolloff, false);
+                                self.preview = self.prepare_preview(client, context)?;
+
+                                context.render()?;
+                            }
+                        }
                         CommonAction::AddAll => {
-                            self.search_add(client)?;
+                            self.search_add(client, context)?;
                             status_info!("All found songs added to queue");
 
                             context.render()?;
@@ -915,6 +1096,17 @@ impl Pane for SearchPane {
                         CommonAction::PaneUp => {}
                         CommonAction::PaneRight => {}
                         CommonAction::PaneLeft => {}
+                        CommonAction::ToggleMatchMode => {}
+                        CommonAction::RateUp | CommonAction::RateDown => {
+                            let change = if action == CommonAction::RateUp {
+                                ValueChange::Increase(1)
+                            } else {
+                                ValueChange::Decrease(1)
+                            };
+                            if let Some(song) = self.songs_dir.selected() {
+                                crate::ui::rate_song(client, context, &song.file, &change)?;
+                            }
+                        }
                     }
                 }
             }
@@ -923,6 +1115,72 @@ impl Pane for SearchPane {
     }
 }
 
+fn replace_queue_and_play(client: &mut impl MpdClient, songs: &[String]) -> Result<()> {
+    client.clear()?;
+    let mut first_id = None;
+    for song in songs {
+        let added = client.add_id(song, None)?;
+        first_id.get_or_insert(added.id);
+    }
+    if let Some(id) = first_id {
+        client.play_id(id)?;
+    }
+    status_info!("Playing {} songs", songs.len());
+
+    Ok(())
+}
+
+fn textbox_expression<'value>(
+    filter_key: &'static str,
+    value: &'value str,
+    kind: FilterKind,
+) -> FilterExpression<'static, 'value> {
+    let (negated, value) = match value.strip_prefix('!') {
+        Some(value) if !value.is_empty() => (true, value),
+        _ => (false, value),
+    };
+    let alternatives = FilterExpression::Or(
+        split_alternatives(value)
+            .into_iter()
+            .filter(|alternative| !alternative.is_empty())
+            .map(|alternative| Filter::new_with_kind(filter_key, alternative, kind).into())
+            .collect_vec(),
+    );
+
+    if negated {
+        FilterExpression::not(alternatives)
+    } else {
+        alternatives
+    }
+}
+
+/// Splits the value on `|`. A backslash makes the following `!`, `|` or `\` literal so values
+/// like `\!!!` or `a\|b` can be searched for as they are.
+fn split_alternatives(value: &str) -> Vec<Cow<'_, str>> {
+    if !value.contains('\\') {
+        return value.split('|').map(Cow::Borrowed).collect_vec();
+    }
+
+    let mut result = Vec::new();
+    let mut current = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.clone().next() {
+                Some(next @ ('!' | '|' | '\\')) => {
+                    current.push(next);
+                    chars.next();
+                }
+                _ => current.push(c),
+            },
+            '|' => result.push(Cow::Owned(std::mem::take(&mut current))),
+            c => current.push(c),
+        }
+    }
+    result.push(Cow::Owned(current));
+    result
+}
+
 enum FocusedInputGroup<T, F, B> {
     Textboxes(T),
     Filters(F),
@@ -1111,3 +1369,72 @@ struct ButtonInput {
 enum ButtonInputVariant {
     Reset,
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use rstest::rstest;
+
+    use super::{textbox_expression, SearchPane};
+    use crate::{
+        context::AppContext,
+        mpd::{mpd_client::FilterKind, version::Version},
+        tests::fixtures::app_context,
+    };
+
+    fn pane_with_values(app_context: &AppContext, values: &[(&str, &str)]) -> SearchPane {
+        let mut pane = SearchPane::new(app_context);
+        for input in &mut pane.inputs.textbox_inputs {
+            if let Some((_, value)) = values.iter().find(|(key, _)| *key == input.filter_key) {
+                input.value = (*value).to_owned();
+            }
+        }
+        pane
+    }
+
+    #[rstest]
+    fn combines_title_artist_and_album_into_one_query(app_context: AppContext) {
+        let pane = pane_with_values(
+            &app_context,
+            &[("title", "song"), ("artist", "band"), ("album", "record")],
+        );
+
+        let query = pane.filter_expression().to_query_str(Version::new(0, 24, 0)).unwrap();
+
+        assert_eq!(
+            query,
+            r#""((artist =~ '.*band.*') AND (album =~ '.*record.*') AND (title =~ '.*song.*'))""#
+        );
+    }
+
+    #[rstest]
+    fn uses_classic_query_for_old_mpd(app_context: AppContext) {
+        let pane = pane_with_values(&app_context, &[("title", "song"), ("artist", "band")]);
+
+        let query = pane.filter_expression().to_query_str(Version::new(0, 20, 0)).unwrap();
+
+        assert_eq!(query, r#"artist "band" title "song""#);
+    }
+
+    #[rstest]
+    fn skips_empty_textboxes(app_context: AppContext) {
+        let pane = pane_with_values(&app_context, &[]);
+
+        assert!(pane.filter_expression().is_empty());
+    }
+
+    #[rstest]
+    #[case("rock", r#""(genre =~ '.*rock.*')""#)]
+    #[case("metal|rock", r#""(!((!(genre =~ '.*metal.*')) AND (!(genre =~ '.*rock.*'))))""#)]
+    #[case("!metal", r#""(!(genre =~ '.*metal.*'))""#)]
+    #[case("!", r#""(genre =~ '.*!.*')""#)]
+    #[case(r"\!!!", r#""(genre =~ '.*!!!.*')""#)]
+    #[case(r"a\|b", r#""(genre =~ '.*a|b.*')""#)]
+    #[case(r"a\\|b", r#""(!((!(genre =~ '.*a\\\\.*')) AND (!(genre =~ '.*b.*'))))""#)]
+    #[case(r"a\b", r#""(genre =~ '.*a\\\\b.*')""#)]
+    fn textbox_expression_handles_alternatives_negation_and_escapes(#[case] value: &str, #[case] expected: &str) {
+        let expression = textbox_expression("genre", value, FilterKind::Contains);
+
+        assert_eq!(expression.to_query_str(Version::new(0, 24, 0)).unwrap(), expected);
+    }
+}
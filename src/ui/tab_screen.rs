@@ -2,33 +2,47 @@ use std::collections::HashMap;
 
 use anyhow::Result;
 use ratatui::{
-    layout::{Constraint, Layout, Rect},
-    widgets::Block,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Color,
+    widgets::{Block, Paragraph, Wrap},
     Frame,
 };
 
 use crate::{
     config::{
         keys::CommonAction,
-        tabs::{Pane, PaneOrSplitWithPosition, SubPaneWithPosition},
+        tabs::{Pane, PaneOrSplitWithPosition, PaneType, SubPaneWithPosition},
     },
     context::AppContext,
-    mpd::mpd_client::MpdClient,
+    mpd::{
+        commands::State,
+        mpd_client::{MpdClient, ValueChange},
+    },
     shared::{
+        ext::error::ErrorExt,
         geometry::Point,
         id::Id,
         key_event::KeyEvent,
+        macros::status_error,
         mouse_event::{MouseEvent, MouseEventKind},
     },
 };
 
 use super::{Pane as _, PaneContainer, Panes};
 
+/// Seconds skipped by left and right on tabs without a focusable pane
+const SEEK_STEP_SECS: u32 = 5;
+/// Splits are abandoned in favor of a single pane when any of their panes would be narrower than this
+const MIN_SPLIT_PANE_WIDTH: u16 = 10;
+/// Splits are abandoned in favor of a single pane when any of their panes would be shorter than this
+const MIN_SPLIT_PANE_HEIGHT: u16 = 3;
+
 #[derive(Debug)]
 pub struct TabScreen {
     focused: Option<Pane>, // can focused ever be none?
     pub panes: &'static crate::config::tabs::PaneOrSplitWithPosition,
     pane_areas: HashMap<Id, Rect>,
+    failed_panes: HashMap<Id, String>,
 }
 
 impl TabScreen {
@@ -46,24 +60,25 @@ impl TabScreen {
             focused,
             panes,
             pane_areas: HashMap::default(),
+            failed_panes: HashMap::default(),
         }
     }
 }
 
 macro_rules! screen_call {
-    ($screen:ident, $fn:ident($($param:expr),+)) => {
+    ($screen:ident, $fn:ident($($param:expr),*)) => {
         match $screen {
-            Panes::Queue(s) => s.$fn($($param),+),
+            Panes::Queue(s) => s.$fn($($param),*),
             #[cfg(debug_assertions)]
-            Panes::Logs(s) => s.$fn($($param),+),
-            Panes::Directories(s) => s.$fn($($param),+),
-            Panes::Artists(s) => s.$fn($($param),+),
-            Panes::AlbumArtists(s) => s.$fn($($param),+),
-            Panes::Albums(s) => s.$fn($($param),+),
-            Panes::Playlists(s) => s.$fn($($param),+),
-            Panes::Search(s) => s.$fn($($param),+),
-            Panes::AlbumArt(s) => s.$fn($($param),+),
-            Panes::Lyrics(s) => s.$fn($($param),+),
+            Panes::Logs(s) => s.$fn($($param),*),
+            Panes::Directories(s) => s.$fn($($param),*),
+            Panes::Artists(s) => s.$fn($($param),*),
+            Panes::AlbumArtists(s) => s.$fn($($param),*),
+            Panes::Albums(s) => s.$fn($($param),*),
+            Panes::Playlists(s) => s.$fn($($param),*),
+            Panes::Search(s) => s.$fn($($param),*),
+            Panes::AlbumArt(s) => s.$fn($($param),*),
+            Panes::Lyrics(s) => s.$fn($($param),*),
         }
     }
 }
@@ -100,7 +115,14 @@ impl TabScreen {
                 let pane = panes.get_mut(*pane);
                 let pane_area = block.inner(area);
                 self.pane_areas.insert(*id, pane_area);
-                screen_call!(pane, render(frame, pane_area, context))?;
+                if let Some(error) = self.failed_panes.get(id) {
+                    let error = Paragraph::new(format!("Failed to load: {error}"))
+                        .wrap(Wrap { trim: true })
+                        .style(context.config.as_text_style().fg(Color::Red));
+                    frame.render_widget(error, pane_area);
+                } else {
+                    screen_call!(pane, render(frame, pane_area, context))?;
+                }
                 frame.render_widget(block, area);
             }
             PaneOrSplitWithPosition::Split {
@@ -112,8 +134,29 @@ impl TabScreen {
                     .map(|SubPaneWithPosition { size, .. }| Constraint::Percentage((*size).into()));
                 let areas = Layout::new(*direction, constraints).split(area);
 
-                for (idx, area) in areas.iter().enumerate() {
-                    self.render_recursive(panes, &sub_panes[idx].pane, frame, *area, context)?;
+                let too_small = areas.iter().any(|area| match direction {
+                    Direction::Horizontal => area.width < MIN_SPLIT_PANE_WIDTH,
+                    Direction::Vertical => area.height < MIN_SPLIT_PANE_HEIGHT,
+                });
+                if too_small {
+                    // Not enough space for all of the panes, give the whole area to the first one which contains
+                    // something focusable and hide the rest, usually album art or lyrics
+                    let essential_idx = sub_panes
+                        .iter()
+                        .position(|sub_pane| sub_pane.pane.panes_iter().any(|pane| pane.focusable))
+                        .unwrap_or(0);
+                    for (idx, sub_pane) in sub_panes.iter().enumerate() {
+                        let sub_area = if idx == essential_idx {
+                            area
+                        } else {
+                            Rect::new(area.x, area.y, 0, 0)
+                        };
+                        self.render_recursive(panes, &sub_pane.pane, frame, sub_area, context)?;
+                    }
+                } else {
+                    for (idx, area) in areas.iter().enumerate() {
+                        self.render_recursive(panes, &sub_panes[idx].pane, frame, *area, context)?;
+                    }
                 }
             }
         };
@@ -126,9 +169,21 @@ impl TabScreen {
         panes: &mut PaneContainer,
         event: &mut KeyEvent,
         client: &mut impl MpdClient,
-        context: &AppContext,
+        context: &mut AppContext,
     ) -> Result<()> {
+        // Tabs without any focusable pane, like album art with lyrics, only show the playing song. There is
+        // nothing to navigate so left and right seek in the song instead.
         let Some(focused) = self.focused else {
+            match event.as_common_action(context) {
+                Some(CommonAction::Left) if matches!(context.status.state, State::Play | State::Pause) => {
+                    super::seek(client, context, ValueChange::Decrease(SEEK_STEP_SECS))?;
+                }
+                Some(CommonAction::Right) if matches!(context.status.state, State::Play | State::Pause) => {
+                    super::seek(client, context, ValueChange::Increase(SEEK_STEP_SECS))?;
+                }
+                Some(_) => event.abandon(),
+                None => {}
+            }
             return Ok(());
         };
 
@@ -227,6 +282,20 @@ impl TabScreen {
         Ok(())
     }
 
+    pub(in crate::ui) fn focused_pane_type(&self) -> Option<PaneType> {
+        self.focused.map(|pane| pane.pane)
+    }
+
+    /// Whether the focused pane currently takes text input
+    pub(in crate::ui) fn is_input_active(&self, panes: &mut PaneContainer) -> bool {
+        let Some(focused) = self.focused else {
+            return false;
+        };
+
+        let pane = panes.get_mut(focused.pane);
+        screen_call!(pane, is_input_active())
+    }
+
     pub(in crate::ui) fn handle_mouse_event(
         &mut self,
         panes: &mut PaneContainer,
@@ -280,16 +349,132 @@ impl TabScreen {
         Ok(())
     }
 
-    pub fn before_show(
+    /// Prepares all panes of the screen to be shown. A pane which fails does not prevent the others from being
+    /// shown, an error is displayed in its place instead until it is loaded successfully by [`Self::retry_failed`]
+    /// or by showing the screen again.
+    pub fn before_show(&mut self, panes: &mut PaneContainer, client: &mut impl MpdClient, context: &AppContext) {
+        self.failed_panes.clear();
+        for pane in self.panes.panes_iter() {
+            let screen = panes.get_mut(pane.pane);
+            if let Err(err) = screen_call!(screen, before_show(client, context)) {
+                status_error!(error:? = err, pane:? = pane.pane; "Failed to load {:?} pane: {}", pane.pane, err.to_status());
+                self.failed_panes.insert(pane.id, err.to_status());
+            }
+        }
+    }
+
+    /// Tries to prepare the panes which failed in [`Self::before_show`] again. Returns true if any of them succeeded.
+    pub fn retry_failed(
         &mut self,
         panes: &mut PaneContainer,
         client: &mut impl MpdClient,
         context: &AppContext,
-    ) -> Result<()> {
+    ) -> bool {
+        if self.failed_panes.is_empty() {
+            return false;
+        }
+
+        let mut recovered = false;
         for pane in self.panes.panes_iter() {
+            if !self.failed_panes.contains_key(&pane.id) {
+                continue;
+            }
+
             let screen = panes.get_mut(pane.pane);
-            screen_call!(screen, before_show(client, context))?;
+            match screen_call!(screen, before_show(client, context)) {
+                Ok(()) => {
+                    self.failed_panes.remove(&pane.id);
+                    recovered = true;
+                }
+                Err(err) => {
+                    log::debug!(error:? = err, pane:? = pane.pane; "Pane failed to load again");
+                }
+            }
         }
-        Ok(())
+        recovered
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::widgets::Borders;
+    use rstest::rstest;
+
+    use super::TabScreen;
+    use crate::{
+        config::{
+            tabs::{Pane, PaneOrSplitWithPosition, PaneType},
+            Leak,
+        },
+        context::AppContext,
+        mpd::commands::State,
+        shared::{geometry::Geometry, id},
+        tests::fixtures::{
+            app_context,
+            mpd_client::{client, TestMpdClient},
+        },
+        ui::panes::PaneContainer,
+    };
+
+    #[rstest]
+    #[case(KeyCode::Right, State::Play, "seek_current Increase(5)")]
+    #[case(KeyCode::Left, State::Pause, "seek_current Decrease(5)")]
+    fn seeks_on_tab_without_focusable_pane(
+        #[case] code: KeyCode,
+        #[case] state: State,
+        #[case] expected_call: &str,
+        mut app_context: AppContext,
+        mut client: TestMpdClient,
+    ) {
+        app_context.status.state = state;
+        let panes = PaneOrSplitWithPosition::Pane(Pane {
+            pane: PaneType::AlbumArt,
+            geometry: Geometry::new(0, 0, 100, 100),
+            border: Borders::NONE,
+            focusable: false,
+            id: id::new(),
+        })
+        .leak();
+        let mut screen = TabScreen::new(panes);
+        let mut pane_container = PaneContainer::new(&app_context);
+
+        screen
+            .handle_action(
+                &mut pane_container,
+                &mut KeyEvent::new(code, KeyModifiers::NONE).into(),
+                &mut client,
+                &mut app_context,
+            )
+            .unwrap();
+
+        assert_eq!(client.calls.get(expected_call), Some(&1));
+    }
+
+    #[rstest]
+    fn does_not_seek_when_stopped(mut app_context: AppContext, mut client: TestMpdClient) {
+        app_context.status.state = State::Stop;
+        let panes = PaneOrSplitWithPosition::Pane(Pane {
+            pane: PaneType::AlbumArt,
+            geometry: Geometry::new(0, 0, 100, 100),
+            border: Borders::NONE,
+            focusable: false,
+            id: id::new(),
+        })
+        .leak();
+        let mut screen = TabScreen::new(panes);
+        let mut pane_container = PaneContainer::new(&app_context);
+
+        screen
+            .handle_action(
+                &mut pane_container,
+                &mut KeyEvent::new(KeyCode::Right, KeyModifiers::NONE).into(),
+                &mut client,
+                &mut app_context,
+            )
+            .unwrap();
+
+        assert!(client.calls.is_empty());
     }
 }
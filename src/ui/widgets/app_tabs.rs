@@ -1,5 +1,6 @@
 use ratatui::{
     layout::{Position, Rect},
+    style::Style,
     widgets::Widget,
 };
 
@@ -37,6 +38,11 @@ impl AppTabs<'_> {
         self
     }
 
+    pub fn set_active_style(&mut self, style: Style) -> &mut Self {
+        self.tabs.set_highlight_style(style);
+        self
+    }
+
     pub fn get_tab_idx_at(&self, position: Position) -> Option<usize> {
         self.tabs
             .areas
@@ -1,8 +1,11 @@
+use std::sync::Arc;
+
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, List, ListItem, Padding, StatefulWidget};
 use style::Styled;
 
-use crate::config::Config;
+use crate::config::{tabs::PaneType, Config};
+use crate::mpd::commands::Ratings;
 use crate::ui::dirstack::{Dir, DirStack, DirStackItem};
 
 #[derive(Debug)]
@@ -10,18 +13,22 @@ pub struct Browser<T: std::fmt::Debug + DirStackItem> {
     state_type_marker: std::marker::PhantomData<T>,
     widths: Vec<u16>,
     config: &'static Config,
+    ratings: Arc<Ratings>,
     border_style: Style,
+    current_item_style: Style,
     pub areas: [Rect; 3],
     filter_input_active: bool,
 }
 
 impl<T: std::fmt::Debug + DirStackItem> Browser<T> {
-    pub fn new(config: &'static Config) -> Self {
+    pub fn new(config: &'static Config, pane: PaneType) -> Self {
         Self {
             state_type_marker: std::marker::PhantomData,
-            widths: config.theme.column_widths.to_vec(),
+            widths: config.theme.browser_column_widths(pane).to_vec(),
             config,
+            ratings: Arc::default(),
             border_style: config.as_border_style(),
+            current_item_style: config.theme.current_item_style,
             areas: [Rect::default(); 3],
             filter_input_active: false,
         }
@@ -31,7 +38,19 @@ impl<T: std::fmt::Debug + DirStackItem> Browser<T> {
         self.filter_input_active = value;
         self
     }
+
+    pub fn set_current_item_style(&mut self, style: Style) -> &mut Self {
+        self.current_item_style = style;
+        self
+    }
+
+    pub fn set_ratings(&mut self, ratings: &Arc<Ratings>) -> &mut Self {
+        self.ratings = Arc::clone(ratings);
+        self
+    }
 }
+const MIN_ALL_COLUMNS_WIDTH: u16 = 40;
+
 const MIDDLE_COLUMN_SYMBOLS: symbols::border::Set = symbols::border::Set {
     top_right: symbols::line::NORMAL.horizontal_down,
     bottom_right: symbols::line::NORMAL.horizontal_up,
@@ -56,28 +75,36 @@ where
             vertical: 0,
             horizontal: 0,
         };
-        let previous = state.previous().to_list_items(self.config);
-        let current = state.current().to_list_items(self.config);
+        let previous = state.previous().to_list_items(self.config, &self.ratings);
+        let current = state.current().to_list_items(self.config, &self.ratings);
         let preview = state.preview().cloned();
 
+        // Only the current column is shown when there is not enough space for the others
+        let widths = if area.width < MIN_ALL_COLUMNS_WIDTH {
+            [0, 100, 0]
+        } else {
+            [self.widths[0], self.widths[1], self.widths[2]]
+        };
+        self.areas = [Rect::default(); 3];
+
         let [previous_area, current_area, preview_area] = *Layout::horizontal([
-            Constraint::Percentage(self.widths[0]),
-            Constraint::Percentage(self.widths[1]),
-            Constraint::Percentage(self.widths[2]),
+            Constraint::Percentage(widths[0]),
+            Constraint::Percentage(widths[1]),
+            Constraint::Percentage(widths[2]),
         ])
         .split(area) else {
             return;
         };
 
-        if self.widths[2] > 0 {
+        if widths[2] > 0 {
             self.areas[2] = preview_area;
             let preview = List::new(preview.unwrap_or_default())
-                .highlight_style(self.config.theme.current_item_style)
+                .highlight_style(self.current_item_style)
                 .style(self.config.as_text_style());
             ratatui::widgets::Widget::render(preview, preview_area, buf);
         }
 
-        if self.widths[0] > 0 {
+        if widths[0] > 0 {
             let title = state.previous().filter().as_ref().map(|v| format!("[FILTER]: {v} "));
             let prev_state = &mut state.previous_mut().state;
             prev_state.set_content_len(Some(previous.len()));
@@ -97,7 +124,7 @@ where
                 block = block.title(title.clone().set_style(self.config.theme.borders_style));
             }
 
-            previous = previous.highlight_style(self.config.theme.current_item_style);
+            previous = previous.highlight_style(self.current_item_style);
 
             let inner_block = block.inner(previous_area);
             self.areas[0] = inner_block;
@@ -110,7 +137,7 @@ where
                 prev_state.as_scrollbar_state_ref(),
             );
         }
-        if self.widths[1] > 0 {
+        if widths[1] > 0 {
             let title = state
                 .current()
                 .filter()
@@ -134,7 +161,7 @@ where
                 b.padding(Padding::new(0, 1, 0, 0))
             };
             let current = List::new(current)
-                .highlight_style(self.config.theme.current_item_style)
+                .highlight_style(self.current_item_style)
                 .style(self.config.as_text_style());
 
             let inner_block = block.inner(current_area);
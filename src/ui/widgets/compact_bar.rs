@@ -0,0 +1,48 @@
+use ratatui::{
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use crate::{context::AppContext, mpd::commands::State, shared::ext::duration::DurationExt};
+
+/// Single line with the playback state, progress and current song used in place of the whole UI in compact mode
+pub struct CompactBar<'a> {
+    context: &'a AppContext,
+}
+
+impl Widget for CompactBar<'_> {
+    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
+        let config = self.context.config;
+        let status = &self.context.status;
+        let separator = config.theme.format_tag_separator;
+
+        let state = match status.state {
+            State::Play => "[playing]",
+            State::Pause => "[paused]",
+            State::Stop => "[stopped]",
+        };
+        let mut spans = vec![Span::styled(state, config.theme.highlighted_item_style)];
+
+        if let Some((_, song)) = self.context.find_current_song_in_queue() {
+            spans.push(Span::from(format!(
+                " {}/{} ",
+                status.elapsed.to_string(),
+                status.duration.to_string()
+            )));
+            spans.push(Span::from(song.artist_str(separator).into_owned()).bold());
+            spans.push(Span::from(" - "));
+            spans.push(Span::from(song.title_str(separator).into_owned()));
+        }
+
+        Paragraph::new(Line::from(spans))
+            .style(config.as_text_style())
+            .render(area, buf);
+    }
+}
+
+impl<'a> CompactBar<'a> {
+    pub fn new(context: &'a AppContext) -> Self {
+        Self { context }
+    }
+}
@@ -1,14 +1,14 @@
 use either::Either;
 use ratatui::{
     prelude::{Constraint, Layout},
-    style::Style,
-    text::Line,
+    style::{Color, Style},
+    text::{Line, Span},
     widgets::{Block, Widget},
 };
 
 use crate::{
     config::theme::properties::{Property, PropertyKind},
-    context::AppContext,
+    context::{AppContext, ConnectionState},
     mpd::commands::{Song, Status},
 };
 
@@ -40,25 +40,50 @@ impl Widget for Header<'_> {
                 return;
             };
             let template = PropertyTemplates(config.theme.header.rows[row].left);
-            let widget = template.format(song, &self.context.status).left_aligned();
+            let widget = template
+                .format(song, &self.context.status, config.theme.format_tag_separator)
+                .left_aligned();
             widget.render(left, buf);
 
             let template = PropertyTemplates(config.theme.header.rows[row].center);
-            let widget = template.format(song, &self.context.status).centered();
+            let widget = template
+                .format(song, &self.context.status, config.theme.format_tag_separator)
+                .centered();
             widget.render(center, buf);
 
             let template = PropertyTemplates(config.theme.header.rows[row].right);
-            let widget = template.format(song, &self.context.status).right_aligned();
+            let widget = template
+                .format(song, &self.context.status, config.theme.format_tag_separator)
+                .right_aligned();
             widget.render(right, buf);
         }
+
+        if let Some(area) = layouts.first() {
+            connection_indicator(self.context.connection).render(*area, buf);
+        }
     }
 }
 
+/// Colored dot with a label shown in the top right corner while the connection to MPD is not working
+fn connection_indicator(state: ConnectionState) -> Line<'static> {
+    let (color, label) = match state {
+        ConnectionState::Connected => return Line::default(),
+        ConnectionState::Reconnecting => (Color::Yellow, "Reconnecting"),
+        ConnectionState::Disconnected => (Color::Red, "Disconnected"),
+    };
+
+    Line::from(vec![
+        Span::styled("● ", Style::default().fg(color)),
+        Span::styled(label, Style::default().fg(color)),
+    ])
+    .right_aligned()
+}
+
 struct PropertyTemplates<'a>(&'a [&'a Property<'static, PropertyKind>]);
 impl<'a> PropertyTemplates<'a> {
-    fn format(&'a self, song: Option<&'a Song>, status: &'a Status) -> Line<'a> {
+    fn format(&'a self, song: Option<&'a Song>, status: &'a Status, tag_separator: &str) -> Line<'a> {
         Line::from(self.0.iter().fold(Vec::new(), |mut acc, val| {
-            match val.as_span(song, status) {
+            match val.as_span(song, status, tag_separator) {
                 Some(Either::Left(span)) => acc.push(span),
                 Some(Either::Right(ref mut spans)) => acc.append(spans),
                 None => {}
@@ -73,3 +98,36 @@ impl<'a> Header<'a> {
         Self { context }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+    use rstest::rstest;
+
+    use super::Header;
+    use crate::{
+        context::{AppContext, ConnectionState},
+        tests::fixtures::app_context,
+    };
+
+    fn render(app_context: &AppContext) -> String {
+        let area = Rect::new(0, 0, 80, 2);
+        let mut buf = Buffer::empty(area);
+        Header::new(app_context).render(area, &mut buf);
+        (0..area.width).map(|x| buf[(x, 0)].symbol()).collect()
+    }
+
+    #[rstest]
+    #[case(ConnectionState::Reconnecting, "● Reconnecting")]
+    #[case(ConnectionState::Disconnected, "● Disconnected")]
+    fn shows_connection_problems(#[case] state: ConnectionState, #[case] expected: &str, mut app_context: AppContext) {
+        app_context.connection = state;
+
+        assert!(render(&app_context).trim_end().ends_with(expected));
+    }
+
+    #[rstest]
+    fn hides_indicator_when_connected(app_context: AppContext) {
+        assert!(!render(&app_context).contains('●'));
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use ratatui::{
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use crate::{
+    config::{
+        keys::{CommonAction, GlobalAction, KeyConfig, KeySequence, QueueActions, SearchActions},
+        tabs::PaneType,
+    },
+    context::AppContext,
+};
+
+const SEPARATOR: &str = "  ";
+
+/// Single line with the keys bound to the actions most relevant to the focused pane
+pub struct KeybindHints<'a> {
+    context: &'a AppContext,
+    pane: Option<PaneType>,
+}
+
+impl Widget for KeybindHints<'_> {
+    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
+        let config = self.context.config;
+        let mut width = 0;
+        let mut spans = Vec::new();
+
+        for (key, label) in hints(self.pane, &config.keybinds) {
+            let separator_width = if spans.is_empty() { 0 } else { SEPARATOR.len() };
+            let hint_width = separator_width + key.chars().count() + 1 + label.chars().count();
+            if width + hint_width > area.width as usize {
+                break;
+            }
+            width += hint_width;
+
+            if separator_width > 0 {
+                spans.push(Span::from(SEPARATOR));
+            }
+            spans.push(Span::styled(key, config.theme.highlighted_item_style));
+            spans.push(Span::from(format!(" {label}")));
+        }
+
+        Paragraph::new(Line::from(spans))
+            .style(config.as_text_style())
+            .render(area, buf);
+    }
+}
+
+impl<'a> KeybindHints<'a> {
+    pub fn new(context: &'a AppContext, pane: Option<PaneType>) -> Self {
+        Self { context, pane }
+    }
+}
+
+/// Pairs of the bound key and a short label, ordered by relevance. Unbound actions are skipped.
+fn hints(pane: Option<PaneType>, keybinds: &KeyConfig) -> Vec<(String, &'static str)> {
+    let common = |actions: &[(CommonAction, &'static str)]| {
+        actions
+            .iter()
+            .filter_map(|(action, label)| key_for(&keybinds.navigation, action).map(|key| (key, *label)))
+            .collect::<Vec<_>>()
+    };
+
+    let mut result = match pane {
+        Some(PaneType::Queue) => [
+            (QueueActions::Play, "play"),
+            (QueueActions::Delete, "delete"),
+            (QueueActions::Save, "save"),
+            (QueueActions::JumpToCurrent, "current"),
+        ]
+        .iter()
+        .filter_map(|(action, label)| key_for(&keybinds.queue, action).map(|key| (key, *label)))
+        .chain(common(&[(CommonAction::EnterSearch, "filter")]))
+        .collect(),
+        Some(PaneType::Playlists) => common(&[
+            (CommonAction::Right, "open"),
+            (CommonAction::Left, "back"),
+            (CommonAction::Add, "add"),
+            (CommonAction::Delete, "delete"),
+            (CommonAction::Rename, "rename"),
+            (CommonAction::EnterSearch, "filter"),
+        ]),
+        Some(PaneType::Directories | PaneType::Artists | PaneType::AlbumArtists | PaneType::Albums) => common(&[
+            (CommonAction::Right, "open"),
+            (CommonAction::Left, "back"),
+            (CommonAction::Add, "add"),
+            (CommonAction::Select, "select"),
+            (CommonAction::EnterSearch, "filter"),
+        ]),
+        Some(PaneType::Search) => {
+            let mut result = common(&[(CommonAction::FocusInput, "input"), (CommonAction::Add, "add")]);
+            result.extend(key_for(&keybinds.search, &SearchActions::PlayNow).map(|key| (key, "play now")));
+            result
+        }
+        _ => Vec::new(),
+    };
+
+    result.extend(
+        [(GlobalAction::ShowHelp, "help"), (GlobalAction::Quit, "quit")]
+            .iter()
+            .filter_map(|(action, label)| key_for(&keybinds.global, action).map(|key| (key, *label))),
+    );
+
+    result
+}
+
+/// Shortest key bound to the given action so that the hint stays compact
+fn key_for<A: PartialEq>(keybinds: &HashMap<KeySequence, A>, action: &A) -> Option<String> {
+    keybinds
+        .iter()
+        .filter(|(_, bound)| *bound == action)
+        .map(|(key, _)| key.to_string())
+        .min_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use rstest::rstest;
+
+    use super::hints;
+    use crate::{
+        config::{keys::KeyConfigFile, tabs::PaneType},
+        context::AppContext,
+        tests::fixtures::app_context,
+    };
+
+    #[rstest]
+    fn lists_pane_actions_before_help_and_quit(app_context: AppContext) {
+        let result = hints(Some(PaneType::Queue), &app_context.config.keybinds);
+
+        let labels = result.iter().map(|(_, label)| *label).collect::<Vec<_>>();
+        assert_eq!(labels.first(), Some(&"play"));
+        assert_eq!(&labels[labels.len() - 2..], ["help", "quit"]);
+        assert!(result.contains(&("q".to_owned(), "quit")));
+    }
+
+    #[test]
+    fn skips_unbound_actions() {
+        let mut keybinds = KeyConfigFile::default();
+        keybinds.global.clear();
+
+        let result = hints(None, &keybinds.into());
+
+        assert!(result.is_empty());
+    }
+}
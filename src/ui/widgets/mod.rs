@@ -9,8 +9,10 @@ use ratatui::prelude::Alignment;
 pub mod app_tabs;
 pub mod browser;
 pub mod button;
+pub mod compact_bar;
 pub mod header;
 pub mod input;
+pub mod keybind_hints;
 pub mod progress_bar;
 pub mod tabs;
 pub mod volume;
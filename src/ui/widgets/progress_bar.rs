@@ -63,6 +63,16 @@ impl<'a> ProgressBar<'a> {
         self.thumb_style = style;
         self
     }
+
+    /// Maps a column inside of the given area to the value the progress bar would display
+    /// if its thumb was at that column. Columns outside of the area are clamped.
+    pub fn value_at(area: Rect, x: u16) -> f32 {
+        if area.width == 0 {
+            return 0.0;
+        }
+
+        (f32::from(x.saturating_sub(area.x)) / f32::from(area.width)).clamp(0.0, 1.0)
+    }
 }
 
 impl<'a> Widget for ProgressBar<'a> {
@@ -184,4 +194,21 @@ mod tests {
         assert_eq!(buf[(1, 0)].symbol(), "T");
         assert_eq!(buf[(2, 0)].symbol(), "B");
     }
+
+    #[test]
+    fn value_at_is_relative_to_area() {
+        let area = Rect::new(10, 0, 20, 1);
+
+        assert!((ProgressBar::value_at(area, 10) - 0.0).abs() < f32::EPSILON);
+        assert!((ProgressBar::value_at(area, 20) - 0.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn value_at_is_clamped() {
+        let area = Rect::new(10, 0, 20, 1);
+
+        assert!((ProgressBar::value_at(area, 0) - 0.0).abs() < f32::EPSILON);
+        assert!((ProgressBar::value_at(area, 100) - 1.0).abs() < f32::EPSILON);
+        assert!((ProgressBar::value_at(Rect::default(), 5) - 0.0).abs() < f32::EPSILON);
+    }
 }
@@ -109,6 +109,11 @@ impl<'a> Tabs<'a> {
         self
     }
 
+    pub fn set_highlight_style(&mut self, style: Style) -> &mut Self {
+        self.highlight_style = style;
+        self
+    }
+
     pub fn divider<T>(mut self, divider: T) -> Tabs<'a>
     where
         T: Into<Span<'a>>,